@@ -18,16 +18,33 @@
 /// # }
 /// ```
 
-use crate::{config::Config, middleware::security::SecurityHeadersLayer};
+use crate::{
+    config::Config,
+    middleware::{
+        authorize::{
+            AuthorizeLayer, API_KEYS_MANAGE, API_KEYS_READ, MEMBERS_MANAGE, MEMBERS_READ,
+            TASKS_READ, TASKS_WRITE, USAGE_READ, WEBHOOKS_MANAGE, WEBHOOKS_READ,
+        },
+        security::SecurityHeadersLayer,
+    },
+};
 use axum::{
     extract::Request,
-    http::{header, HeaderValue, Method},
+    http::{header, HeaderName, HeaderValue, Method},
     middleware::Next,
     response::Response,
-    routing::{get, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
-use axontask_shared::auth::{jwt, middleware::AuthContext};
+use axontask_shared::auth::{
+    api_key::constant_time_compare,
+    api_key_cache::ApiKeyCache,
+    jwt_keys::JwtKeySet,
+    middleware::{authenticate_api_key, extract_credentials, AuthContext, Credentials},
+};
+use axontask_shared::email::Mailer;
+use axontask_shared::quota::QuotaEnforcer;
+use axontask_shared::redis::{ControlPublisher, LoginThrottle, RateLimiter, RedisClient};
 use sqlx::PgPool;
 use std::sync::Arc;
 use tower_http::{
@@ -47,20 +64,105 @@ pub struct AppState {
 
     /// Application configuration
     pub config: Arc<Config>,
+
+    /// Redis client (None if Redis is not configured)
+    pub redis: Option<RedisClient>,
+
+    /// Recently validated API keys
+    pub api_keys: ApiKeyCache,
+
+    /// Sends account emails (invitations, verification, password resets)
+    pub mailer: Arc<dyn Mailer>,
 }
 
 impl AppState {
     /// Creates new application state
     pub fn new(db: PgPool, config: Config) -> Self {
+        let api_keys = ApiKeyCache::new(std::time::Duration::from_secs(
+            config.auth.api_key_cache_ttl_secs,
+        ));
+
+        let mailer = config.mail.mailer();
+
         Self {
             db,
             config: Arc::new(config),
+            redis: None,
+            api_keys,
+            mailer,
         }
     }
 
-    /// Gets JWT secret for token operations
-    pub fn jwt_secret(&self) -> &str {
-        &self.config.jwt.secret
+    /// Attaches a Redis client
+    pub fn with_redis(mut self, redis: RedisClient) -> Self {
+        self.redis = Some(redis);
+        self
+    }
+
+    /// Replaces the configured mailer
+    pub fn with_mailer(mut self, mailer: Arc<dyn Mailer>) -> Self {
+        self.mailer = mailer;
+        self
+    }
+
+    /// Gets the Redis client
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceUnavailable` if Redis is not configured
+    pub fn redis(&self) -> Result<&RedisClient, crate::error::ApiError> {
+        self.redis.as_ref().ok_or_else(|| {
+            crate::error::ApiError::ServiceUnavailable("Redis is not configured".to_string())
+        })
+    }
+
+    /// Creates a publisher for task control messages
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceUnavailable` if Redis is not configured
+    pub fn control_publisher(&self) -> Result<ControlPublisher, crate::error::ApiError> {
+        Ok(ControlPublisher::new(self.redis()?.clone()))
+    }
+
+    /// Creates a Redis-backed rate limiter
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceUnavailable` if Redis is not configured
+    pub fn rate_limiter(&self) -> Result<RateLimiter, crate::error::ApiError> {
+        Ok(RateLimiter::new(self.redis()?.clone()))
+    }
+
+    /// Creates a Redis-backed login throttle with the configured limits
+    ///
+    /// # Errors
+    ///
+    /// Returns `ServiceUnavailable` if Redis is not configured
+    pub fn login_throttle(&self) -> Result<LoginThrottle, crate::error::ApiError> {
+        Ok(LoginThrottle::new(
+            self.redis()?.clone(),
+            self.config.auth.login_throttle.clone(),
+        ))
+    }
+
+    /// Creates a quota enforcer
+    ///
+    /// Counts stream connections when Redis is configured and uses the
+    /// configured soft thresholds.
+    pub fn quota_enforcer(&self) -> QuotaEnforcer {
+        let enforcer = QuotaEnforcer::new(self.db.clone())
+            .with_thresholds(&self.config.quota.soft_thresholds);
+
+        match &self.redis {
+            Some(redis) => enforcer.with_redis(redis.clone()),
+            None => enforcer,
+        }
+    }
+
+    /// Gets the keys that sign and verify JWTs
+    pub fn jwt_keys(&self) -> &JwtKeySet {
+        &self.config.jwt.keys
     }
 }
 
@@ -72,15 +174,58 @@ impl AppState {
 /// ```text
 /// /
 /// ├── /health                   # Health check (public)
+/// ├── /.well-known/jwks.json    # Public token signing keys (public)
 /// ├── /v1/                      # API v1 (versioned)
 /// │   ├── /auth/                # Authentication endpoints
 /// │   │   ├── POST /register
 /// │   │   ├── POST /login
-/// │   │   └── POST /refresh
-/// │   └── /api-keys/            # API key management (authenticated)
-/// │       ├── POST   /          # Create API key
-/// │       ├── GET    /          # List API keys
-/// │       └── DELETE /:id       # Revoke API key
+/// │   │   ├── POST /login/mfa
+/// │   │   ├── POST /refresh
+/// │   │   ├── POST /logout
+/// │   │   ├── POST /logout-all  # (JWT)
+/// │   │   ├── GET  /sessions    # (JWT)
+/// │   │   ├── DELETE /sessions/:id (JWT)
+/// │   │   ├── POST /switch-tenant (JWT)
+/// │   │   ├── GET  /2fa         # (JWT or enrollment token)
+/// │   │   ├── POST /2fa/setup   # (JWT or enrollment token)
+/// │   │   ├── POST /2fa/enable  # (JWT or enrollment token)
+/// │   │   ├── POST /2fa/disable # (JWT)
+/// │   │   ├── POST /2fa/recovery-codes (JWT)
+/// │   │   ├── POST /verify-email/request
+/// │   │   ├── POST /verify-email/confirm
+/// │   │   ├── POST /password-reset/request
+/// │   │   └── POST /password-reset/confirm
+/// │   ├── /api-keys/            # API key management (JWT only)
+/// │   │   ├── POST   /          # Create API key
+/// │   │   ├── GET    /          # List API keys
+/// │   │   └── DELETE /:id       # Revoke API key
+/// │   ├── /members/             # Tenant members (JWT only)
+/// │   │   ├── GET    /          # List members
+/// │   │   ├── PATCH  /:user_id  # Change role
+/// │   │   ├── DELETE /:user_id  # Remove member
+/// │   │   ├── GET    /two-factor # Two-factor requirement
+/// │   │   ├── PUT    /two-factor
+/// │   │   ├── POST   /invitations
+/// │   │   ├── GET    /invitations
+/// │   │   └── DELETE /invitations/:id
+/// │   ├── POST /invitations/accept (JWT)
+/// │   ├── /tasks/               # Tasks (authenticated)
+/// │   │   └── GET    /          # List tasks
+/// │   ├── GET /usage            # Usage report (authenticated, JSON or CSV)
+/// │   └── /webhooks/            # Webhook management (admin)
+/// │       ├── POST   /          # Create webhook
+/// │       ├── GET    /          # List webhooks
+/// │       ├── GET    /:id       # Get webhook
+/// │       ├── PATCH  /:id       # Update webhook
+/// │       ├── DELETE /:id       # Delete webhook
+/// │       ├── POST   /:id/toggle
+/// │       ├── POST   /:id/rotate-secret
+/// │       ├── POST   /:id/test
+/// │       ├── GET    /:id/deliveries
+/// │       └── POST   /:id/deliveries/:delivery_id/redeliver
+/// │   └── /admin/               # Operator API (ADMIN_API_TOKEN)
+/// │       ├── GET    /tenants/:id/quotas
+/// │       └── PUT    /tenants/:id/quotas
 /// ```
 ///
 /// # Middleware Stack
@@ -88,7 +233,14 @@ impl AppState {
 /// Applied in order (bottom to top):
 /// 1. Logging (tower-http TraceLayer)
 /// 2. CORS (tower-http CorsLayer)
-/// 3. Authentication (per-route basis)
+/// 3. Authentication (per-route basis): `auth_layer` accepts a Bearer JWT or
+///    an API key (`Authorization: Bearer axon_...` or `X-Api-Key`);
+///    `jwt_auth_layer` accepts JWTs only; `enrollment_auth_layer` also
+///    accepts the enrollment tokens login issues to users who must enable
+///    two-factor authentication
+/// 4. Rate limiting (MCP, task and usage routes)
+/// 5. Authorization (per route): each route names the `Policy` it needs, see
+///    `middleware::authorize`
 ///
 /// # Example
 ///
@@ -118,43 +270,265 @@ pub fn build_router(state: AppState) -> Router {
     let health_routes = Router::new()
         .route("/health", get(routes::health::health_check));
 
+    // Token signing keys (public, no auth)
+    let jwks_routes = Router::new()
+        .route("/.well-known/jwks.json", get(routes::auth::jwks));
+
+    // Session routes (require JWT authentication)
+    let session_routes = Router::new()
+        .route("/logout-all", post(routes::auth::logout_all))
+        .route("/sessions", get(routes::auth::list_sessions))
+        .route("/sessions/:id", delete(routes::auth::revoke_session))
+        .route("/switch-tenant", post(routes::auth::switch_tenant))
+        .route("/2fa/disable", post(routes::two_factor::disable))
+        .route(
+            "/2fa/recovery-codes",
+            post(routes::two_factor::regenerate_recovery_codes),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            jwt_auth_layer,
+        ));
+
+    // Two-factor enrollment routes (JWT or enrollment token from login)
+    let enrollment_routes = Router::new()
+        .route("/2fa", get(routes::two_factor::status))
+        .route("/2fa/setup", post(routes::two_factor::setup))
+        .route("/2fa/enable", post(routes::two_factor::enable))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            enrollment_auth_layer,
+        ));
+
     // Auth routes (public, no auth required)
     let auth_routes = Router::new()
         .route("/register", post(routes::auth::register))
         .route("/login", post(routes::auth::login))
-        .route("/refresh", post(routes::auth::refresh));
+        .route("/login/mfa", post(routes::auth::login_mfa))
+        .route("/refresh", post(routes::auth::refresh))
+        .route("/logout", post(routes::auth::logout))
+        .route(
+            "/verify-email/request",
+            post(routes::auth::request_email_verification),
+        )
+        .route("/verify-email/confirm", post(routes::auth::verify_email))
+        .route(
+            "/password-reset/request",
+            post(routes::auth::request_password_reset),
+        )
+        .route("/password-reset/confirm", post(routes::auth::reset_password))
+        .merge(session_routes)
+        .merge(enrollment_routes);
+
+    // Per-route authorization (member role or API key scope)
+    let policy = |policy| AuthorizeLayer::new(state.db.clone(), policy);
 
     // API key routes (require JWT authentication)
     let api_key_routes = Router::new()
-        .route("/", post(routes::api_keys::create_api_key))
-        .route("/", get(routes::api_keys::list_api_keys))
-        .route("/:id", post(routes::api_keys::revoke_api_key))
+        .route(
+            "/",
+            post(routes::api_keys::create_api_key).route_layer(policy(API_KEYS_MANAGE)),
+        )
+        .route(
+            "/",
+            get(routes::api_keys::list_api_keys).route_layer(policy(API_KEYS_READ)),
+        )
+        .route(
+            "/:id",
+            post(routes::api_keys::revoke_api_key).route_layer(policy(API_KEYS_MANAGE)),
+        )
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
             jwt_auth_layer,
         ));
 
+    // Member routes (require JWT authentication)
+    let member_routes = Router::new()
+        .route(
+            "/",
+            get(routes::members::list_members).route_layer(policy(MEMBERS_READ)),
+        )
+        .route(
+            "/:user_id",
+            patch(routes::members::update_member)
+                .delete(routes::members::remove_member)
+                .route_layer(policy(MEMBERS_MANAGE)),
+        )
+        .route(
+            "/two-factor",
+            get(routes::members::get_two_factor_policy).route_layer(policy(MEMBERS_READ)),
+        )
+        .route(
+            "/two-factor",
+            put(routes::members::set_two_factor_policy).route_layer(policy(MEMBERS_MANAGE)),
+        )
+        .route(
+            "/invitations",
+            post(routes::members::create_invitation)
+                .get(routes::members::list_invitations)
+                .route_layer(policy(MEMBERS_MANAGE)),
+        )
+        .route(
+            "/invitations/:id",
+            delete(routes::members::revoke_invitation).route_layer(policy(MEMBERS_MANAGE)),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            jwt_auth_layer,
+        ));
+
+    // Invitation acceptance (requires JWT; the user is not a member yet)
+    let invitation_routes = Router::new()
+        .route("/accept", post(routes::members::accept_invitation))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            jwt_auth_layer,
+        ));
+
+    // Task routes (require JWT or API key authentication + rate limiting)
+    let task_routes = Router::new()
+        .route(
+            "/",
+            get(routes::tasks::list_tasks).route_layer(policy(TASKS_READ)),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::middleware::rate_limit::rate_limit_layer,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth_layer,
+        ));
+
+    // Usage routes (require JWT or API key authentication + rate limiting)
+    let usage_routes = Router::new()
+        .route(
+            "/",
+            get(routes::usage::get_usage).route_layer(policy(USAGE_READ)),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::middleware::rate_limit::rate_limit_layer,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth_layer,
+        ));
+
+    // Webhook routes (require JWT or API key authentication, admin only)
+    let webhook_routes = Router::new()
+        .route(
+            "/",
+            post(routes::webhooks::create_webhook).route_layer(policy(WEBHOOKS_MANAGE)),
+        )
+        .route(
+            "/",
+            get(routes::webhooks::list_webhooks).route_layer(policy(WEBHOOKS_READ)),
+        )
+        .route(
+            "/:id",
+            get(routes::webhooks::get_webhook).route_layer(policy(WEBHOOKS_READ)),
+        )
+        .route(
+            "/:id",
+            patch(routes::webhooks::update_webhook)
+                .delete(routes::webhooks::delete_webhook)
+                .route_layer(policy(WEBHOOKS_MANAGE)),
+        )
+        .route(
+            "/:id/toggle",
+            post(routes::webhooks::toggle_webhook).route_layer(policy(WEBHOOKS_MANAGE)),
+        )
+        .route(
+            "/:id/rotate-secret",
+            post(routes::webhooks::rotate_webhook_secret).route_layer(policy(WEBHOOKS_MANAGE)),
+        )
+        .route(
+            "/:id/test",
+            post(routes::webhooks::test_webhook).route_layer(policy(WEBHOOKS_MANAGE)),
+        )
+        .route(
+            "/:id/deliveries",
+            get(routes::webhooks::list_deliveries).route_layer(policy(WEBHOOKS_READ)),
+        )
+        .route(
+            "/:id/deliveries/:delivery_id/redeliver",
+            post(routes::webhooks::redeliver).route_layer(policy(WEBHOOKS_MANAGE)),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth_layer,
+        ));
+
     // MCP tool routes (require JWT or API key authentication + rate limiting)
     let mcp_routes = Router::new()
-        .route("/start_task", post(routes::mcp::start_task))
-        .route("/tasks/:task_id/status", get(routes::mcp::get_task_status))
-        .route("/tasks/:task_id/cancel", post(routes::mcp::cancel_task))
-        .route("/tasks/:task_id/stream", get(routes::mcp::stream_task))
-        .route("/tasks/:task_id/resume", post(routes::mcp::resume_task))
-        .layer(axum::middleware::from_fn_with_state(
+        .route(
+            "/start_task",
+            post(routes::mcp::start_task).route_layer(policy(TASKS_WRITE)),
+        )
+        .route(
+            "/tasks/:task_id/status",
+            get(routes::mcp::get_task_status).route_layer(policy(TASKS_READ)),
+        )
+        .route(
+            "/tasks/:task_id/cancel",
+            post(routes::mcp::cancel_task).route_layer(policy(TASKS_WRITE)),
+        )
+        .route(
+            "/tasks/:task_id/stream",
+            get(routes::mcp::stream_task).route_layer(policy(TASKS_READ)),
+        )
+        .route(
+            "/tasks/:task_id/resume",
+            post(routes::mcp::resume_task).route_layer(policy(TASKS_READ)),
+        )
+        .route(
+            "/tasks/:task_id/pause",
+            post(routes::mcp::pause_task).route_layer(policy(TASKS_WRITE)),
+        )
+        .route(
+            "/tasks/:task_id/unpause",
+            post(routes::mcp::unpause_task).route_layer(policy(TASKS_WRITE)),
+        )
+        .route(
+            "/tasks/:task_id/input",
+            post(routes::mcp::send_input).route_layer(policy(TASKS_WRITE)),
+        )
+        .route(
+            "/cancel_tasks",
+            post(routes::mcp::cancel_tasks).route_layer(policy(TASKS_WRITE)),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
             state.clone(),
             crate::middleware::rate_limit::rate_limit_layer,
         ))
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
-            jwt_auth_layer,
+            auth_layer,
+        ));
+
+    // Admin routes (require the operator admin token)
+    let admin_routes = Router::new()
+        .route(
+            "/tenants/:id/quotas",
+            get(routes::admin::get_tenant_quotas).put(routes::admin::update_tenant_quotas),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            admin_auth_layer,
         ));
 
     // Build complete v1 API
     let v1_routes = Router::new()
         .nest("/auth", auth_routes)
         .nest("/api-keys", api_key_routes)
-        .nest("/mcp", mcp_routes);
+        .nest("/members", member_routes)
+        .nest("/invitations", invitation_routes)
+        .nest("/tasks", task_routes)
+        .nest("/usage", usage_routes)
+        .nest("/webhooks", webhook_routes)
+        .nest("/mcp", mcp_routes)
+        .nest("/admin", admin_routes);
 
     // Configure CORS based on environment
     let cors = if state.config.api.cors_origins.contains(&"*".to_string()) {
@@ -176,10 +550,15 @@ pub fn build_router(state: AppState) -> Router {
                 Method::GET,
                 Method::POST,
                 Method::PUT,
+                Method::PATCH,
                 Method::DELETE,
                 Method::OPTIONS,
             ])
-            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+            .allow_headers([
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                HeaderName::from_static("x-api-key"),
+            ])
             .allow_credentials(true)
             .max_age(std::time::Duration::from_secs(3600))
     };
@@ -187,6 +566,7 @@ pub fn build_router(state: AppState) -> Router {
     // Combine all routes with middleware stack
     Router::new()
         .merge(health_routes)
+        .merge(jwks_routes)
         .nest("/v1", v1_routes)
         .layer(
             TraceLayer::new_for_http()
@@ -220,7 +600,7 @@ async fn jwt_auth_layer(
         .ok_or_else(|| crate::error::ApiError::BadRequest("Expected Bearer token".to_string()))?;
 
     // Validate token
-    let claims = jwt::validate_access_token(token, state.jwt_secret())?;
+    let claims = state.jwt_keys().validate_access(token)?;
 
     // Create auth context
     let auth_context = AuthContext::from_jwt(claims.sub, claims.tenant_id);
@@ -231,6 +611,86 @@ async fn jwt_auth_layer(
     Ok(next.run(req).await)
 }
 
+/// Two-factor enrollment authentication middleware layer
+///
+/// Like `jwt_auth_layer`, but also accepts enrollment tokens, so users
+/// refused a session until they enable two-factor authentication can
+/// enroll.
+async fn enrollment_auth_layer(
+    state: axum::extract::State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, crate::error::ApiError> {
+    let token = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| crate::error::ApiError::Unauthorized("Missing authorization header".to_string()))?
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| crate::error::ApiError::BadRequest("Expected Bearer token".to_string()))?;
+
+    let claims = state.jwt_keys().validate_enrollment(token)?;
+    req.extensions_mut()
+        .insert(AuthContext::from_jwt(claims.sub, claims.tenant_id));
+
+    Ok(next.run(req).await)
+}
+
+/// JWT or API key authentication middleware layer
+///
+/// Accepts a Bearer JWT, or an API key in `Authorization: Bearer axon_...`
+/// or `X-Api-Key`. API keys are validated through the state's key cache,
+/// so most requests skip the database. Injects AuthContext into request
+/// extensions.
+async fn auth_layer(
+    state: axum::extract::State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, crate::error::ApiError> {
+    let auth_context = match extract_credentials(req.headers())? {
+        Credentials::Jwt(token) => {
+            let claims = state.jwt_keys().validate_access(&token)?;
+            AuthContext::from_jwt(claims.sub, claims.tenant_id)
+        }
+        Credentials::ApiKey(key) => {
+            let api_key = authenticate_api_key(&state.db, &state.api_keys, &key).await?;
+            AuthContext::from_api_key(&api_key)
+        }
+    };
+
+    req.extensions_mut().insert(auth_context);
+
+    Ok(next.run(req).await)
+}
+
+/// Admin authentication middleware layer
+///
+/// Checks the bearer token against `ADMIN_API_TOKEN`. The admin API is
+/// hidden (404) when no token is configured.
+async fn admin_auth_layer(
+    state: axum::extract::State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, crate::error::ApiError> {
+    let Some(admin_token) = state.config.admin.token.as_deref() else {
+        return Err(crate::error::ApiError::NotFound("Not found".to_string()));
+    };
+
+    let token = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or_else(|| crate::error::ApiError::Unauthorized("Missing admin token".to_string()))?;
+
+    if !constant_time_compare(token, admin_token) {
+        tracing::warn!("Rejected admin API request with invalid token");
+        return Err(crate::error::ApiError::Unauthorized("Invalid admin token".to_string()));
+    }
+
+    Ok(next.run(req).await)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,7 +8,49 @@
 /// - `DATABASE_URL`: PostgreSQL connection string
 /// - `API_HOST`: Host to bind to (default: 0.0.0.0)
 /// - `API_PORT`: Port to bind to (default: 8080)
-/// - `JWT_SECRET`: Secret key for JWT signing (required)
+/// - `APP_URL`: Dashboard URL used in email links (default:
+///   http://localhost:3000)
+/// - `TRUST_PROXY_HEADERS`: Take the client IP address from the last
+///   `X-Forwarded-For` entry; only enable behind a proxy that sets it
+///   (default: false)
+/// - `JWT_SECRET`: HS256 secret for JWT signing, key ID `default` (required
+///   unless `JWT_KEYS` is set)
+/// - `JWT_KEYS`: Additional signing keys as comma-separated
+///   `kid:algorithm:path` entries; `algorithm` is HS256, RS256 or EdDSA and
+///   `path` points to the secret or PEM file (optional)
+/// - `JWT_SIGNING_KEY_ID`: Key ID that signs new tokens (default: `default`
+///   when `JWT_SECRET` is set, otherwise the first `JWT_KEYS` entry)
+/// - `ADMIN_API_TOKEN`: Bearer token for the operator admin API (optional,
+///   the admin API is disabled when unset)
+/// - `API_KEY_CACHE_TTL_SECS`: Seconds a validated API key is cached
+///   (default: 30; 0 disables caching)
+/// - `INVITATION_TTL_HOURS`: Hours a tenant invitation stays valid
+///   (default: 168)
+/// - `REQUIRE_EMAIL_VERIFICATION`: Reject logins until the user verified
+///   their email address (default: false)
+/// - `EMAIL_VERIFICATION_TTL_HOURS`: Hours an email verification link stays
+///   valid (default: 48)
+/// - `PASSWORD_RESET_TTL_MINUTES`: Minutes a password reset link stays valid
+///   (default: 60)
+/// - `LOGIN_MAX_FAILURES`: Failed logins that lock an account out
+///   (default: 10)
+/// - `LOGIN_IP_MAX_FAILURES`: Failed logins that lock a client IP address out
+///   (default: 100)
+/// - `LOGIN_LOCKOUT_SECS`: Seconds a login lockout lasts (default: 900)
+/// - `TOTP_ISSUER`: Service name authenticator apps show for two-factor
+///   codes (default: AxonTask)
+/// - `MAIL_BACKEND`: How account emails are sent: `log`, `file` or `smtp`
+///   (default: log)
+/// - `MAIL_FROM`: Sender mailbox (default: `AxonTask <noreply@localhost>`)
+/// - `MAIL_FILE_DIR`: Directory for `.eml` files with the `file` backend
+///   (default: ./mail)
+/// - `SMTP_HOST`: SMTP server (required with the `smtp` backend)
+/// - `SMTP_SECURITY`: `starttls`, `tls` or `none` (default: starttls)
+/// - `SMTP_PORT`: SMTP port (default: 587 for starttls, 465 for tls, 25 for
+///   none)
+/// - `SMTP_USERNAME` / `SMTP_PASSWORD`: SMTP credentials (optional)
+/// - `QUOTA_SOFT_THRESHOLDS`: Comma-separated quota warning thresholds in
+///   percent (default: 80,100; empty disables them)
 /// - `RUST_LOG`: Log level (default: info)
 ///
 /// # Example
@@ -23,8 +65,30 @@
 /// # }
 /// ```
 
+use axontask_shared::auth::api_key_cache::DEFAULT_API_KEY_CACHE_TTL;
+use axontask_shared::auth::jwt_keys::{Algorithm, JwtKey, JwtKeySet, DEFAULT_KEY_ID};
+use axontask_shared::email::{FileMailer, LogMailer, Mailer, SmtpConfig, SmtpMailer, SmtpSecurity};
+use axontask_shared::quota::{parse_thresholds, DEFAULT_SOFT_THRESHOLDS};
+use axontask_shared::redis::login_throttle::LoginThrottleConfig;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Default tenant invitation lifetime (7 days)
+pub const DEFAULT_INVITATION_TTL_HOURS: u64 = 168;
+
+/// Default email verification link lifetime (2 days)
+pub const DEFAULT_EMAIL_VERIFICATION_TTL_HOURS: u64 = 48;
+
+/// Default password reset link lifetime (1 hour)
+pub const DEFAULT_PASSWORD_RESET_TTL_MINUTES: u64 = 60;
+
+/// Default service name shown in authenticator apps
+pub const DEFAULT_TOTP_ISSUER: &str = "AxonTask";
+
+/// Default sender mailbox
+pub const DEFAULT_MAIL_FROM: &str = "AxonTask <noreply@localhost>";
 
 /// Complete application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +101,18 @@ pub struct Config {
 
     /// JWT configuration
     pub jwt: JwtConfig,
+
+    /// Admin API configuration
+    pub admin: AdminConfig,
+
+    /// Authentication configuration
+    pub auth: AuthConfig,
+
+    /// Quota configuration
+    pub quota: QuotaConfig,
+
+    /// Outgoing mail configuration
+    pub mail: MailConfig,
 }
 
 /// API server configuration
@@ -53,6 +129,15 @@ pub struct ApiConfig {
 
     /// Allowed CORS origins (comma-separated)
     pub cors_origins: Vec<String>,
+
+    /// Dashboard URL used in email links (no trailing slash)
+    pub app_url: String,
+
+    /// Whether to take the client IP address from `X-Forwarded-For`
+    ///
+    /// Only safe behind a proxy that sets the header, since clients can
+    /// send any value.
+    pub trust_proxy_headers: bool,
 }
 
 /// Database configuration
@@ -68,11 +153,129 @@ pub struct DatabaseConfig {
 /// JWT configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtConfig {
-    /// Secret key for JWT signing
+    /// Keys that sign and verify tokens
     ///
-    /// IMPORTANT: This must be kept secret and should be at least 32 bytes.
+    /// IMPORTANT: HS256 secrets must be kept secret and be at least 32 bytes.
     /// Generate with: `openssl rand -hex 32`
-    pub secret: String,
+    ///
+    /// Never serialized; a deserialized config has no keys.
+    #[serde(skip)]
+    pub keys: JwtKeySet,
+}
+
+/// Admin API configuration
+///
+/// The admin API (`/v1/admin`) is for operators, not tenant admins: it can
+/// change any tenant's limits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdminConfig {
+    /// Bearer token for the admin API (None disables it)
+    ///
+    /// IMPORTANT: Must be at least 32 bytes. Generate with: `openssl rand -hex 32`
+    pub token: Option<String>,
+}
+
+/// Authentication configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Seconds a validated API key is cached (0 disables caching)
+    ///
+    /// Revoked keys are rejected by other API instances within this time.
+    pub api_key_cache_ttl_secs: u64,
+
+    /// Hours a tenant invitation stays valid
+    pub invitation_ttl_hours: u64,
+
+    /// Whether login requires a verified email address
+    pub require_email_verification: bool,
+
+    /// Hours an email verification link stays valid
+    pub email_verification_ttl_hours: u64,
+
+    /// Minutes a password reset link stays valid
+    pub password_reset_ttl_minutes: u64,
+
+    /// Failed login delays and lockouts
+    pub login_throttle: LoginThrottleConfig,
+
+    /// Service name shown in authenticator apps
+    pub totp_issuer: String,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            api_key_cache_ttl_secs: DEFAULT_API_KEY_CACHE_TTL.as_secs(),
+            invitation_ttl_hours: DEFAULT_INVITATION_TTL_HOURS,
+            require_email_verification: false,
+            email_verification_ttl_hours: DEFAULT_EMAIL_VERIFICATION_TTL_HOURS,
+            password_reset_ttl_minutes: DEFAULT_PASSWORD_RESET_TTL_MINUTES,
+            login_throttle: LoginThrottleConfig::default(),
+            totp_issuer: DEFAULT_TOTP_ISSUER.to_string(),
+        }
+    }
+}
+
+/// Quota configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Soft thresholds in percent of a limit (empty disables warnings)
+    pub soft_thresholds: Vec<u8>,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        QuotaConfig {
+            soft_thresholds: DEFAULT_SOFT_THRESHOLDS.to_vec(),
+        }
+    }
+}
+
+/// Outgoing mail configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailConfig {
+    /// Sender mailbox, e.g. `AxonTask <noreply@example.com>`
+    pub from: String,
+
+    /// How emails are sent
+    pub backend: MailBackend,
+}
+
+/// Mail delivery backend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MailBackend {
+    /// Log messages instead of sending them (development)
+    Log,
+
+    /// Write messages as `.eml` files into a directory
+    File {
+        /// Target directory
+        dir: PathBuf,
+    },
+
+    /// Deliver through an SMTP server
+    Smtp(SmtpConfig),
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        MailConfig {
+            from: DEFAULT_MAIL_FROM.to_string(),
+            backend: MailBackend::Log,
+        }
+    }
+}
+
+impl MailConfig {
+    /// Creates the configured mailer
+    pub fn mailer(&self) -> Arc<dyn Mailer> {
+        match &self.backend {
+            MailBackend::Log => Arc::new(LogMailer),
+            MailBackend::File { dir } => Arc::new(FileMailer::new(&self.from, dir)),
+            MailBackend::Smtp(smtp) => Arc::new(SmtpMailer::new(&self.from, smtp.clone())),
+        }
+    }
 }
 
 impl Config {
@@ -115,6 +318,18 @@ impl Config {
             .filter(|s| !s.is_empty())
             .collect();
 
+        let app_url = env::var("APP_URL")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let trust_proxy_headers = match env::var("TRUST_PROXY_HEADERS") {
+            Ok(value) => value
+                .parse::<bool>()
+                .map_err(|_| anyhow::anyhow!("TRUST_PROXY_HEADERS must be true or false"))?,
+            Err(_) => false,
+        };
+
         let database_url = env::var("DATABASE_URL")
             .map_err(|_| anyhow::anyhow!("DATABASE_URL environment variable is required"))?;
 
@@ -122,27 +337,90 @@ impl Config {
             .unwrap_or_else(|_| "10".to_string())
             .parse::<u32>()?;
 
-        let jwt_secret = env::var("JWT_SECRET")
-            .map_err(|_| anyhow::anyhow!("JWT_SECRET environment variable is required"))?;
+        let jwt_keys = load_jwt_keys()?;
 
-        if jwt_secret.len() < 32 {
-            anyhow::bail!("JWT_SECRET must be at least 32 characters long");
+        let admin_token = env::var("ADMIN_API_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty());
+
+        if admin_token.as_ref().is_some_and(|token| token.len() < 32) {
+            anyhow::bail!("ADMIN_API_TOKEN must be at least 32 characters long");
         }
 
+        let api_key_cache_ttl_secs = match env::var("API_KEY_CACHE_TTL_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .map_err(|e| anyhow::anyhow!("API_KEY_CACHE_TTL_SECS: {}", e))?,
+            Err(_) => DEFAULT_API_KEY_CACHE_TTL.as_secs(),
+        };
+
+        let invitation_ttl_hours = positive_var("INVITATION_TTL_HOURS", DEFAULT_INVITATION_TTL_HOURS)?;
+
+        let require_email_verification = match env::var("REQUIRE_EMAIL_VERIFICATION") {
+            Ok(value) => value
+                .parse::<bool>()
+                .map_err(|_| anyhow::anyhow!("REQUIRE_EMAIL_VERIFICATION must be true or false"))?,
+            Err(_) => false,
+        };
+
+        let email_verification_ttl_hours = positive_var(
+            "EMAIL_VERIFICATION_TTL_HOURS",
+            DEFAULT_EMAIL_VERIFICATION_TTL_HOURS,
+        )?;
+
+        let password_reset_ttl_minutes =
+            positive_var("PASSWORD_RESET_TTL_MINUTES", DEFAULT_PASSWORD_RESET_TTL_MINUTES)?;
+
+        let defaults = LoginThrottleConfig::default();
+        let login_throttle = LoginThrottleConfig {
+            account_max_failures: positive_var(
+                "LOGIN_MAX_FAILURES",
+                defaults.account_max_failures.into(),
+            )?
+            .try_into()?,
+            ip_max_failures: positive_var("LOGIN_IP_MAX_FAILURES", defaults.ip_max_failures.into())?
+                .try_into()?,
+            lockout_secs: positive_var("LOGIN_LOCKOUT_SECS", defaults.lockout_secs)?,
+            ..defaults
+        };
+
+        let totp_issuer = env::var("TOTP_ISSUER")
+            .ok()
+            .filter(|issuer| !issuer.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_TOTP_ISSUER.to_string());
+
+        let soft_thresholds = match env::var("QUOTA_SOFT_THRESHOLDS") {
+            Ok(value) => parse_thresholds(&value)
+                .map_err(|e| anyhow::anyhow!("QUOTA_SOFT_THRESHOLDS: {}", e))?,
+            Err(_) => DEFAULT_SOFT_THRESHOLDS.to_vec(),
+        };
+
         Ok(Self {
             api: ApiConfig {
                 host: api_host,
                 port: api_port,
                 production,
                 cors_origins,
+                app_url,
+                trust_proxy_headers,
             },
             database: DatabaseConfig {
                 url: database_url,
                 max_connections,
             },
-            jwt: JwtConfig {
-                secret: jwt_secret,
+            jwt: JwtConfig { keys: jwt_keys },
+            admin: AdminConfig { token: admin_token },
+            auth: AuthConfig {
+                api_key_cache_ttl_secs,
+                invitation_ttl_hours,
+                require_email_verification,
+                email_verification_ttl_hours,
+                password_reset_ttl_minutes,
+                login_throttle,
+                totp_issuer,
             },
+            quota: QuotaConfig { soft_thresholds },
+            mail: load_mail_config()?,
         })
     }
 
@@ -152,6 +430,129 @@ impl Config {
     }
 }
 
+/// Reads a positive integer variable, or returns the default if unset
+fn positive_var(name: &str, default: u64) -> anyhow::Result<u64> {
+    match env::var(name) {
+        Ok(value) => value
+            .parse::<u64>()
+            .ok()
+            .filter(|value| *value > 0)
+            .ok_or_else(|| anyhow::anyhow!("{} must be a positive number", name)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Loads mail settings from `MAIL_*` and `SMTP_*`
+fn load_mail_config() -> anyhow::Result<MailConfig> {
+    let from = env::var("MAIL_FROM")
+        .ok()
+        .filter(|from| !from.is_empty())
+        .unwrap_or_else(|| DEFAULT_MAIL_FROM.to_string());
+
+    let backend = match env::var("MAIL_BACKEND")
+        .unwrap_or_else(|_| "log".to_string())
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "log" => MailBackend::Log,
+        "file" => MailBackend::File {
+            dir: env::var("MAIL_FILE_DIR")
+                .unwrap_or_else(|_| "./mail".to_string())
+                .into(),
+        },
+        "smtp" => {
+            let host = env::var("SMTP_HOST")
+                .ok()
+                .filter(|host| !host.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("SMTP_HOST is required when MAIL_BACKEND=smtp"))?;
+
+            let security = match env::var("SMTP_SECURITY") {
+                Ok(value) => value.parse::<SmtpSecurity>().map_err(|_| {
+                    anyhow::anyhow!("SMTP_SECURITY must be starttls, tls or none")
+                })?,
+                Err(_) => SmtpSecurity::StartTls,
+            };
+
+            let port = match env::var("SMTP_PORT") {
+                Ok(value) => value
+                    .parse::<u16>()
+                    .map_err(|e| anyhow::anyhow!("SMTP_PORT: {}", e))?,
+                Err(_) => security.default_port(),
+            };
+
+            MailBackend::Smtp(SmtpConfig {
+                host,
+                port,
+                security,
+                username: env::var("SMTP_USERNAME").ok().filter(|v| !v.is_empty()),
+                password: env::var("SMTP_PASSWORD").ok().filter(|v| !v.is_empty()),
+            })
+        }
+        other => anyhow::bail!("MAIL_BACKEND must be log, file or smtp, got {}", other),
+    };
+
+    Ok(MailConfig { from, backend })
+}
+
+/// Loads JWT keys from `JWT_SECRET`, `JWT_KEYS` and `JWT_SIGNING_KEY_ID`
+fn load_jwt_keys() -> anyhow::Result<JwtKeySet> {
+    let mut keys = Vec::new();
+
+    let secret = env::var("JWT_SECRET").ok().filter(|secret| !secret.is_empty());
+    if let Some(secret) = &secret {
+        if secret.len() < 32 {
+            anyhow::bail!("JWT_SECRET must be at least 32 characters long");
+        }
+        keys.push(JwtKey::hs256(DEFAULT_KEY_ID, secret)?);
+    }
+
+    for spec in env::var("JWT_KEYS").unwrap_or_default().split(',') {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            continue;
+        }
+
+        let (kid, algorithm, path) =
+            parse_key_spec(spec).map_err(|e| anyhow::anyhow!("JWT_KEYS: {}", e))?;
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("JWT_KEYS: cannot read {}: {}", path, e))?;
+
+        keys.push(match algorithm {
+            Algorithm::HS256 => JwtKey::hs256(kid, contents.trim())?,
+            _ => JwtKey::from_pem(kid, algorithm, &contents)?,
+        });
+    }
+
+    let signing_kid = match env::var("JWT_SIGNING_KEY_ID") {
+        Ok(kid) => kid,
+        Err(_) if secret.is_some() => DEFAULT_KEY_ID.to_string(),
+        Err(_) => keys
+            .first()
+            .map(|key| key.kid().to_string())
+            .ok_or_else(|| anyhow::anyhow!("JWT_SECRET or JWT_KEYS environment variable is required"))?,
+    };
+
+    Ok(JwtKeySet::new(keys, &signing_kid)?)
+}
+
+/// Parses a `kid:algorithm:path` key entry
+fn parse_key_spec(spec: &str) -> Result<(&str, Algorithm, &str), String> {
+    let mut parts = spec.splitn(3, ':');
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(kid), Some(algorithm), Some(path)) if !kid.is_empty() && !path.is_empty() => {
+            let algorithm = match algorithm.to_ascii_uppercase().as_str() {
+                "HS256" => Algorithm::HS256,
+                "RS256" => Algorithm::RS256,
+                "EDDSA" => Algorithm::EdDSA,
+                other => return Err(format!("unsupported algorithm {} in {}", other, spec)),
+            };
+            Ok((kid, algorithm, path))
+        }
+        _ => Err(format!("expected kid:algorithm:path, got {}", spec)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,16 +565,37 @@ mod tests {
                 port: 8080,
                 production: false,
                 cors_origins: vec!["*".to_string()],
+                app_url: "http://localhost:3000".to_string(),
+                trust_proxy_headers: false,
             },
             database: DatabaseConfig {
                 url: "postgresql://localhost/test".to_string(),
                 max_connections: 10,
             },
             jwt: JwtConfig {
-                secret: "test-secret-key-at-least-32-bytes-long".to_string(),
+                keys: JwtKeySet::from_secret("test-secret-key-at-least-32-bytes-long").unwrap(),
             },
+            admin: AdminConfig::default(),
+            auth: AuthConfig::default(),
+            quota: QuotaConfig::default(),
+            mail: MailConfig::default(),
         };
 
         assert_eq!(config.bind_address(), "127.0.0.1:8080");
     }
+
+    #[test]
+    fn test_parse_key_spec() {
+        assert_eq!(
+            parse_key_spec("2025-01:EdDSA:/etc/axontask/jwt.pem").unwrap(),
+            ("2025-01", Algorithm::EdDSA, "/etc/axontask/jwt.pem")
+        );
+        assert_eq!(
+            parse_key_spec("rsa:rs256:C:\\keys\\jwt.pem").unwrap(),
+            ("rsa", Algorithm::RS256, "C:\\keys\\jwt.pem")
+        );
+        assert!(parse_key_spec("k1:ES256:/key.pem").is_err());
+        assert!(parse_key_spec("k1:HS256").is_err());
+        assert!(parse_key_spec(":HS256:/secret").is_err());
+    }
 }
@@ -220,6 +220,35 @@ impl From<sqlx::Error> for ApiError {
     }
 }
 
+/// Convert quota errors to API errors
+impl From<axontask_shared::quota::QuotaError> for ApiError {
+    fn from(err: axontask_shared::quota::QuotaError) -> Self {
+        match err {
+            axontask_shared::quota::QuotaError::LimitExceeded {
+                quota_type,
+                limit,
+                current,
+            } => ApiError::Forbidden(format!(
+                "{} limit exceeded ({}/{}). Upgrade your plan for higher limits.",
+                quota_type.as_str(),
+                current,
+                limit
+            )),
+            axontask_shared::quota::QuotaError::TenantNotFound(_) => {
+                ApiError::Unauthorized("Tenant not found".to_string())
+            }
+            axontask_shared::quota::QuotaError::DatabaseError(err) => {
+                tracing::error!(error = %err, "Quota check failed");
+                ApiError::InternalError("Failed to check quotas".to_string())
+            }
+            axontask_shared::quota::QuotaError::RedisError(err) => {
+                tracing::error!(error = %err, "Quota check failed");
+                ApiError::InternalError("Failed to check quotas".to_string())
+            }
+        }
+    }
+}
+
 /// Convert auth errors to API errors
 impl From<axontask_shared::auth::middleware::AuthError> for ApiError {
     fn from(err: axontask_shared::auth::middleware::AuthError) -> Self {
@@ -283,11 +312,98 @@ impl From<axontask_shared::auth::jwt::JwtError> for ApiError {
             axontask_shared::auth::jwt::JwtError::InvalidIssuer { .. } => {
                 ApiError::Unauthorized("Invalid token issuer".to_string())
             }
+            axontask_shared::auth::jwt::JwtError::CreateError(_)
+            | axontask_shared::auth::jwt::JwtError::InvalidKey(_) => {
+                ApiError::InternalError(format!("Token signing failed: {}", err))
+            }
             _ => ApiError::Unauthorized(format!("Invalid token: {}", err)),
         }
     }
 }
 
+/// Convert session errors to API errors
+impl From<axontask_shared::auth::session::SessionError> for ApiError {
+    fn from(err: axontask_shared::auth::session::SessionError) -> Self {
+        match err {
+            axontask_shared::auth::session::SessionError::InvalidToken => {
+                ApiError::Unauthorized("Invalid refresh token".to_string())
+            }
+            axontask_shared::auth::session::SessionError::TokenReused => {
+                ApiError::Unauthorized("Refresh token reuse detected; session revoked".to_string())
+            }
+            axontask_shared::auth::session::SessionError::NotAMember => {
+                ApiError::Forbidden("Not a member of the requested tenant".to_string())
+            }
+            axontask_shared::auth::session::SessionError::Jwt(err) => ApiError::from(err),
+            axontask_shared::auth::session::SessionError::DatabaseError(err) => ApiError::from(err),
+        }
+    }
+}
+
+/// Convert member management errors to API errors
+impl From<axontask_shared::auth::members::MemberError> for ApiError {
+    fn from(err: axontask_shared::auth::members::MemberError) -> Self {
+        match err {
+            axontask_shared::auth::members::MemberError::NotFound => {
+                ApiError::NotFound("Member not found".to_string())
+            }
+            axontask_shared::auth::members::MemberError::LastOwner
+            | axontask_shared::auth::members::MemberError::AlreadyMember => {
+                ApiError::Conflict(err.to_string())
+            }
+            axontask_shared::auth::members::MemberError::OwnerRequired
+            | axontask_shared::auth::members::MemberError::WrongEmail => {
+                ApiError::Forbidden(err.to_string())
+            }
+            axontask_shared::auth::members::MemberError::InvalidInvitation => {
+                ApiError::BadRequest(err.to_string())
+            }
+            axontask_shared::auth::members::MemberError::DatabaseError(err) => ApiError::from(err),
+        }
+    }
+}
+
+/// Convert account (verification/reset) errors to API errors
+impl From<axontask_shared::auth::account::AccountError> for ApiError {
+    fn from(err: axontask_shared::auth::account::AccountError) -> Self {
+        match err {
+            axontask_shared::auth::account::AccountError::InvalidToken => {
+                ApiError::BadRequest(err.to_string())
+            }
+            axontask_shared::auth::account::AccountError::WeakPassword(message) => {
+                ApiError::ValidationError(vec![ValidationErrorDetail {
+                    field: "password".to_string(),
+                    message,
+                }])
+            }
+            axontask_shared::auth::account::AccountError::Password(err) => ApiError::from(err),
+            axontask_shared::auth::account::AccountError::DatabaseError(err) => ApiError::from(err),
+        }
+    }
+}
+
+/// Convert two-factor errors to API errors
+impl From<axontask_shared::auth::two_factor::TwoFactorError> for ApiError {
+    fn from(err: axontask_shared::auth::two_factor::TwoFactorError) -> Self {
+        match err {
+            axontask_shared::auth::two_factor::TwoFactorError::AlreadyEnabled
+            | axontask_shared::auth::two_factor::TwoFactorError::NotEnabled => {
+                ApiError::Conflict(err.to_string())
+            }
+            axontask_shared::auth::two_factor::TwoFactorError::NoPendingEnrollment
+            | axontask_shared::auth::two_factor::TwoFactorError::InvalidCode => {
+                ApiError::BadRequest(err.to_string())
+            }
+            axontask_shared::auth::two_factor::TwoFactorError::RequiredByTenant => {
+                ApiError::Forbidden(err.to_string())
+            }
+            axontask_shared::auth::two_factor::TwoFactorError::DatabaseError(err) => {
+                ApiError::from(err)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
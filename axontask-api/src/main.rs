@@ -6,7 +6,8 @@
 //! ## Architecture
 //!
 //! The API server is built with Axum and provides:
-//! - MCP tool endpoints (start_task, stream_task, get_status, cancel_task, resume_task)
+//! - MCP tool endpoints (start_task, stream_task, get_status, cancel_task, resume_task,
+//!   pause_task, unpause_task)
 //! - Authentication (JWT + API keys)
 //! - Rate limiting and quota enforcement
 //! - SSE streaming with backfill and live tail
@@ -17,9 +18,18 @@
 //! cargo run -p axontask-api
 //! ```
 
-use axontask_api::{app, config::Config};
+use axontask_api::{
+    app,
+    config::{Config, MailBackend},
+};
 use axontask_shared::db::pool;
+use axontask_shared::models::idempotency_key::IdempotencyKey;
+use axontask_shared::models::invitation::Invitation;
+use axontask_shared::models::refresh_token::RefreshToken;
+use axontask_shared::models::user_token::UserToken;
+use axontask_shared::redis::{RedisClient, RedisConfig};
 use sqlx::PgPool;
+use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -42,6 +52,10 @@ async fn main() -> anyhow::Result<()> {
     let config = Config::from_env()?;
     tracing::info!("Configuration loaded successfully");
 
+    if config.api.production && matches!(config.mail.backend, MailBackend::Log) {
+        tracing::warn!("MAIL_BACKEND=log: account emails are logged, not sent");
+    }
+
     // Initialize database pool
     let db_config = pool::DatabaseConfig {
         url: config.database.url.clone(),
@@ -60,8 +74,24 @@ async fn main() -> anyhow::Result<()> {
     axontask_shared::db::migrations::run_migrations(&pool).await?;
     tracing::info!("Database migrations completed");
 
+    // Initialize Redis client
+    let redis = RedisClient::new(RedisConfig::from_env()?).await?;
+    tracing::info!("Redis client initialized");
+
+    // Periodically purge expired idempotency keys
+    tokio::spawn(purge_expired_idempotency_keys(pool.clone()));
+
+    // Periodically purge expired refresh tokens
+    tokio::spawn(purge_expired_refresh_tokens(pool.clone()));
+
+    // Periodically purge expired invitations
+    tokio::spawn(purge_expired_invitations(pool.clone()));
+
+    // Periodically purge expired verification and password reset tokens
+    tokio::spawn(purge_expired_user_tokens(pool.clone()));
+
     // Create application state
-    let state = app::AppState::new(pool, config.clone());
+    let state = app::AppState::new(pool, config.clone()).with_redis(redis);
 
     // Build router
     let app = app::build_router(state);
@@ -72,7 +102,8 @@ async fn main() -> anyhow::Result<()> {
 
     let listener = tokio::net::TcpListener::bind(&bind_addr).await?;
 
-    axum::serve(listener, app)
+    // Connection info gives handlers the client address (login throttling)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
@@ -81,6 +112,74 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Deletes expired idempotency keys once an hour
+async fn purge_expired_idempotency_keys(pool: PgPool) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        match IdempotencyKey::delete_expired(&pool).await {
+            Ok(deleted) if deleted > 0 => {
+                tracing::info!(deleted, "Purged expired idempotency keys");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to purge idempotency keys"),
+        }
+    }
+}
+
+/// Deletes expired refresh tokens once an hour
+async fn purge_expired_refresh_tokens(pool: PgPool) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        match RefreshToken::delete_expired(&pool).await {
+            Ok(deleted) if deleted > 0 => {
+                tracing::info!(deleted, "Purged expired refresh tokens");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to purge refresh tokens"),
+        }
+    }
+}
+
+/// Deletes expired, unaccepted invitations once an hour
+async fn purge_expired_invitations(pool: PgPool) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        match Invitation::delete_expired(&pool).await {
+            Ok(deleted) if deleted > 0 => {
+                tracing::info!(deleted, "Purged expired invitations");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to purge invitations"),
+        }
+    }
+}
+
+/// Deletes expired email verification and password reset tokens once an hour
+async fn purge_expired_user_tokens(pool: PgPool) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        match UserToken::delete_expired(&pool).await {
+            Ok(deleted) if deleted > 0 => {
+                tracing::info!(deleted, "Purged expired user tokens");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to purge user tokens"),
+        }
+    }
+}
+
 /// Graceful shutdown handler
 async fn shutdown_signal() {
     tokio::signal::ctrl_c()
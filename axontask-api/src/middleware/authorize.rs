@@ -0,0 +1,244 @@
+/// Per-route authorization middleware
+///
+/// Each authenticated route declares the `Policy` it needs: the permission a
+/// member needs (via their role) and the scope an API key needs. The layer runs
+/// after authentication and rejects the request with `403` before the handler
+/// is called.
+///
+/// # Policies
+///
+/// | Policy | Member role | API key scope |
+/// |--------|-------------|---------------|
+/// | `TASKS_READ` | Viewer+ | `tasks:read` |
+/// | `TASKS_WRITE` | Member+ | `tasks:write` |
+/// | `USAGE_READ` | Viewer+ | `usage:read` |
+/// | `WEBHOOKS_READ` | Admin+ | `webhooks:read` |
+/// | `WEBHOOKS_MANAGE` | Admin+ | `webhooks:manage` |
+/// | `API_KEYS_READ` | Viewer+ | (JWT only) |
+/// | `API_KEYS_MANAGE` | Admin+ | (JWT only) |
+/// | `MEMBERS_READ` | Viewer+ | (JWT only) |
+/// | `MEMBERS_MANAGE` | Admin+ | (JWT only) |
+///
+/// # Example
+///
+/// ```no_run
+/// use axum::{routing::post, Router};
+/// use axontask_api::middleware::authorize::{AuthorizeLayer, TASKS_WRITE};
+/// use sqlx::PgPool;
+///
+/// # fn example(pool: PgPool) {
+/// let app: Router = Router::new().route(
+///     "/start_task",
+///     post(|| async { "started" }).route_layer(AuthorizeLayer::new(pool, TASKS_WRITE)),
+/// );
+/// # }
+/// ```
+
+use crate::error::ApiError;
+use axontask_shared::auth::authorization::{Policy, ResourcePermission};
+use axontask_shared::auth::middleware::AuthContext;
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use sqlx::PgPool;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Read tasks, their status and event streams
+pub const TASKS_READ: Policy = Policy::new(ResourcePermission::Read, "tasks:read");
+
+/// Start, cancel, pause, resume and send input to tasks
+pub const TASKS_WRITE: Policy = Policy::new(ResourcePermission::Write, "tasks:write");
+
+/// Read usage reports
+pub const USAGE_READ: Policy = Policy::new(ResourcePermission::Read, "usage:read");
+
+/// Read webhooks and their deliveries
+pub const WEBHOOKS_READ: Policy = Policy::new(ResourcePermission::Manage, "webhooks:read");
+
+/// Create, update, delete and test webhooks
+pub const WEBHOOKS_MANAGE: Policy = Policy::new(ResourcePermission::Manage, "webhooks:manage");
+
+/// List API keys (the routes only accept JWTs)
+pub const API_KEYS_READ: Policy = Policy::new(ResourcePermission::Read, "api_keys:read");
+
+/// Create and revoke API keys (the routes only accept JWTs)
+pub const API_KEYS_MANAGE: Policy = Policy::new(ResourcePermission::Manage, "api_keys:manage");
+
+/// List tenant members (the routes only accept JWTs)
+pub const MEMBERS_READ: Policy = Policy::new(ResourcePermission::Read, "members:read");
+
+/// Change roles, remove members and manage invitations (the routes only accept JWTs)
+pub const MEMBERS_MANAGE: Policy = Policy::new(ResourcePermission::Manage, "members:manage");
+
+/// Authorization middleware layer
+#[derive(Clone)]
+pub struct AuthorizeLayer {
+    /// Database pool (for member role lookups)
+    db: PgPool,
+
+    /// Policy the route requires
+    policy: Policy,
+}
+
+impl AuthorizeLayer {
+    /// Creates a new authorization layer
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Database connection pool
+    /// * `policy` - Policy the route requires
+    pub fn new(db: PgPool, policy: Policy) -> Self {
+        Self { db, policy }
+    }
+}
+
+impl<S> Layer<S> for AuthorizeLayer {
+    type Service = AuthorizeMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthorizeMiddleware {
+            inner,
+            db: self.db.clone(),
+            policy: self.policy,
+        }
+    }
+}
+
+/// Authorization middleware service
+#[derive(Clone)]
+pub struct AuthorizeMiddleware<S> {
+    inner: S,
+    db: PgPool,
+    policy: Policy,
+}
+
+impl<S> Service<Request> for AuthorizeMiddleware<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Use the service that was polled ready; keep a fresh clone for the next call
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let db = self.db.clone();
+        let policy = self.policy;
+
+        Box::pin(async move {
+            let Some(auth) = request.extensions().get::<AuthContext>().cloned() else {
+                return Ok(ApiError::Unauthorized("Missing credentials".to_string()).into_response());
+            };
+
+            if let Err(e) = policy.authorize(&db, &auth).await {
+                tracing::debug!(
+                    tenant_id = %auth.tenant_id,
+                    user_id = ?auth.user_id,
+                    api_key_id = ?auth.api_key_id,
+                    scope = policy.scope,
+                    error = %e,
+                    "Request not authorized"
+                );
+                return Ok(ApiError::from(e).into_response());
+            }
+
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axontask_shared::auth::middleware::AuthMethod;
+    use axontask_shared::models::membership::MembershipRole;
+    use uuid::Uuid;
+
+    const ROLES: [MembershipRole; 4] = [
+        MembershipRole::Viewer,
+        MembershipRole::Member,
+        MembershipRole::Admin,
+        MembershipRole::Owner,
+    ];
+
+    fn api_key(scopes: &[&str]) -> AuthContext {
+        AuthContext {
+            user_id: None,
+            tenant_id: Uuid::new_v4(),
+            method: AuthMethod::ApiKey,
+            scopes: Some(scopes.iter().map(|s| s.to_string()).collect()),
+            api_key_id: Some(Uuid::new_v4()),
+        }
+    }
+
+    /// Lowest role allowed by each policy
+    #[test]
+    fn test_policy_roles() {
+        let cases = [
+            (TASKS_READ, MembershipRole::Viewer),
+            (TASKS_WRITE, MembershipRole::Member),
+            (USAGE_READ, MembershipRole::Viewer),
+            (WEBHOOKS_READ, MembershipRole::Admin),
+            (WEBHOOKS_MANAGE, MembershipRole::Admin),
+            (API_KEYS_READ, MembershipRole::Viewer),
+            (API_KEYS_MANAGE, MembershipRole::Admin),
+            (MEMBERS_READ, MembershipRole::Viewer),
+            (MEMBERS_MANAGE, MembershipRole::Admin),
+        ];
+        let jwt = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+
+        for (policy, min_role) in cases {
+            for role in ROLES {
+                assert_eq!(
+                    policy.check(&jwt, Some(role)).is_ok(),
+                    role.has_permission(&min_role),
+                    "{:?} on {}",
+                    role,
+                    policy.scope
+                );
+            }
+            assert!(policy.check(&jwt, None).is_err(), "non-member on {}", policy.scope);
+        }
+    }
+
+    /// Scopes allowed by each policy
+    #[test]
+    fn test_policy_scopes() {
+        let cases: [(&[&str], [bool; 5]); 7] = [
+            // key scopes => TASKS_READ, TASKS_WRITE, USAGE_READ, WEBHOOKS_READ, WEBHOOKS_MANAGE
+            (&["tasks:read"], [true, false, false, false, false]),
+            (&["read:task"], [true, false, false, false, false]),
+            (&["tasks:write"], [true, true, false, false, false]),
+            (&["read:task", "write:task"], [true, true, false, false, false]),
+            (&["usage:read"], [false, false, true, false, false]),
+            (&["webhooks:manage"], [false, false, false, true, true]),
+            (&["*"], [true, true, true, true, true]),
+        ];
+        let policies = [TASKS_READ, TASKS_WRITE, USAGE_READ, WEBHOOKS_READ, WEBHOOKS_MANAGE];
+
+        for (scopes, expected) in cases {
+            let auth = api_key(scopes);
+            for (policy, allowed) in policies.iter().zip(expected) {
+                // A member's role never widens an API key's access
+                for role in [None, Some(MembershipRole::Owner)] {
+                    assert_eq!(
+                        policy.check(&auth, role).is_ok(),
+                        allowed,
+                        "{:?} on {}",
+                        scopes,
+                        policy.scope
+                    );
+                }
+            }
+        }
+    }
+}
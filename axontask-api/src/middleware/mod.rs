@@ -4,6 +4,8 @@
 /// - Security headers
 /// - Request logging enhancements
 /// - Rate limiting
+/// - Per-route authorization
 
+pub mod authorize;
 pub mod rate_limit;
 pub mod security;
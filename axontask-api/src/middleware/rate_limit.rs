@@ -11,38 +11,63 @@
 /// - **Pro**: 300 requests/minute (5 requests per second)
 /// - **Enterprise**: 1000 requests/minute (16.67 requests per second)
 ///
+/// Tenants can have custom limits in `tenants.settings.quotas`
+/// (`requests_per_minute`, `burst` and `api_key_requests_per_minute`), see
+/// `RateLimit::for_tenant`.
+///
 /// # Algorithm
 ///
 /// Uses token bucket algorithm:
 /// - Tokens refill at constant rate
-/// - Each request consumes 1 token
-/// - Request blocked if bucket empty
+/// - Each request consumes tokens according to its route cost (default 1)
+/// - Request blocked if bucket has too few tokens
+///
+/// The buckets are updated atomically by a Lua script in Redis (see
+/// `axontask_shared::redis::rate_limit`), so limits hold across API replicas.
+///
+/// # Route Costs
+///
+/// Expensive routes consume more tokens (see `ROUTE_COSTS`), e.g. opening
+/// an event stream costs 5 tokens while a status read costs 1.
 ///
 /// # Storage
 ///
-/// State stored in Redis with keys: `ratelimit:tenant:{tenant_id}`
-/// TTL: 2 minutes (auto-cleanup)
+/// State stored in Redis with keys:
+/// - `ratelimit:tenant:{tenant_id}`, charged for every request of the tenant
+///   (JWT and API key alike)
+/// - `ratelimit:apikey:{api_key_id}`, additionally charged for API key
+///   requests when the tenant has an `api_key_requests_per_minute` limit, so
+///   one runaway integration cannot use up the whole tenant limit
+///
+/// A request is allowed only if every bucket it draws from has enough tokens.
+///
+/// Keys expire once the bucket is full again.
 ///
 /// # Headers
 ///
 /// Response includes rate limit headers:
-/// - `X-RateLimit-Limit`: Total requests allowed per window
-/// - `X-RateLimit-Remaining`: Tokens remaining
+/// - `X-RateLimit-Limit`: Capacity of the most restrictive bucket
+/// - `X-RateLimit-Remaining`: Tokens remaining in that bucket
 /// - `X-RateLimit-Reset`: Unix timestamp when tokens fully replenish
 /// - `Retry-After`: Seconds to wait (429 responses only)
 ///
+/// # Availability
+///
+/// If Redis is not configured or fails, requests are allowed (fail open) and
+/// no rate limit headers are sent.
+///
 /// # Example
 ///
 /// ```no_run
-/// use axontask_api::middleware::rate_limit::RateLimitLayer;
-/// use axum::Router;
+/// use axontask_api::app::AppState;
+/// use axontask_api::middleware::rate_limit::rate_limit_layer;
+/// use axum::{middleware::from_fn_with_state, routing::get, Router};
 ///
-/// # async fn example() {
-/// let rate_limiter = RateLimitLayer::new("redis://localhost:6379");
-///
-/// let app = Router::new()
-///     .route("/api/foo", axum::routing::get(handler))
-///     .layer(rate_limiter);
+/// # fn example(state: AppState) {
+/// // route_layer: the route cost is looked up from the matched path
+/// let app: Router<AppState> = Router::new()
+///     .route("/api/foo", get(handler))
+///     .route_layer(from_fn_with_state(state, rate_limit_layer));
 /// # }
 /// # async fn handler() {}
 /// ```
@@ -50,16 +75,28 @@
 use crate::app::AppState;
 use crate::error::ApiError;
 use axontask_shared::auth::middleware::AuthContext;
-use axontask_shared::models::tenant::{Tenant, TenantPlan};
+use axontask_shared::models::tenant::{QuotaOverrides, Tenant, TenantPlan};
+use axontask_shared::redis::rate_limit::{Bucket, RateLimitDecision};
 use axum::{
-    extract::{Extension, Request, State},
-    http::{HeaderValue, StatusCode},
+    extract::{Extension, MatchedPath, Request, State},
+    http::{HeaderMap, HeaderValue},
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use redis::AsyncCommands;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use uuid::Uuid;
+
+/// Token cost of expensive routes (matched by path suffix)
+///
+/// Routes not listed cost 1 token.
+pub const ROUTE_COSTS: &[(&str, u32)] = &[
+    // Long-lived SSE connection that replays the event backlog
+    ("/tasks/:task_id/stream", 5),
+    // Reopens the same stream from a cursor
+    ("/tasks/:task_id/resume", 5),
+    // Cancels every matching task of the tenant
+    ("/cancel_tasks", 5),
+    // Creates a task and enqueues work
+    ("/start_task", 2),
+];
 
 /// Rate limit configuration for a plan
 #[derive(Debug, Clone, Copy)]
@@ -72,6 +109,9 @@ pub struct RateLimit {
 
     /// Maximum tokens in bucket (burst capacity)
     pub bucket_capacity: u32,
+
+    /// Additional per-API-key limit (requests per minute, none by default)
+    pub api_key_requests_per_minute: Option<u32>,
 }
 
 impl RateLimit {
@@ -82,96 +122,117 @@ impl RateLimit {
                 requests_per_minute: 10,
                 refill_rate: 10.0 / 60.0, // 0.1667 tokens/sec
                 bucket_capacity: 10,
+                api_key_requests_per_minute: None,
             },
             TenantPlan::Entry => RateLimit {
                 requests_per_minute: 60,
                 refill_rate: 1.0, // 1 token/sec
                 bucket_capacity: 60,
+                api_key_requests_per_minute: None,
             },
             TenantPlan::Pro => RateLimit {
                 requests_per_minute: 300,
                 refill_rate: 5.0, // 5 tokens/sec
                 bucket_capacity: 300,
+                api_key_requests_per_minute: None,
             },
             TenantPlan::Enterprise => RateLimit {
                 requests_per_minute: 1000,
                 refill_rate: 16.67, // 16.67 tokens/sec
                 bucket_capacity: 1000,
+                api_key_requests_per_minute: None,
             },
         }
     }
 }
 
-/// Token bucket state stored in Redis
-#[derive(Debug, Clone)]
-struct TokenBucket {
-    /// Current number of tokens
-    tokens: f64,
-
-    /// Last refill timestamp (Unix seconds)
-    last_refill: u64,
-}
-
-impl TokenBucket {
-    /// Creates a new full bucket
-    fn new(capacity: u32) -> Self {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        TokenBucket {
-            tokens: capacity as f64,
-            last_refill: now,
-        }
+impl RateLimit {
+    /// Gets rate limit configuration for a tenant
+    ///
+    /// Plan defaults with the tenant's overrides applied.
+    pub fn for_tenant(tenant: &Tenant) -> Self {
+        let plan = tenant.get_plan().unwrap_or(TenantPlan::Trial);
+        RateLimit::for_plan(plan).with_overrides(&tenant.settings().quotas)
     }
 
-    /// Refills tokens based on elapsed time
-    fn refill(&mut self, rate: f64, capacity: u32) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        let elapsed_secs = (now - self.last_refill) as f64;
-        let new_tokens = elapsed_secs * rate;
-
-        self.tokens = (self.tokens + new_tokens).min(capacity as f64);
-        self.last_refill = now;
-    }
+    /// Applies per-tenant overrides
+    ///
+    /// Overriding `requests_per_minute` without `burst` sets the bucket
+    /// capacity to the new rate, like the plan defaults.
+    pub fn with_overrides(self, overrides: &QuotaOverrides) -> Self {
+        let (requests_per_minute, refill_rate) = match overrides.requests_per_minute {
+            Some(rpm) => (rpm, rpm as f64 / 60.0),
+            None => (self.requests_per_minute, self.refill_rate),
+        };
 
-    /// Attempts to consume N tokens
-    fn try_consume(&mut self, count: f64) -> bool {
-        if self.tokens >= count {
-            self.tokens -= count;
-            true
-        } else {
-            false
+        let bucket_capacity = overrides
+            .burst
+            .or(overrides.requests_per_minute)
+            .unwrap_or(self.bucket_capacity);
+
+        RateLimit {
+            requests_per_minute,
+            refill_rate,
+            bucket_capacity,
+            api_key_requests_per_minute: overrides
+                .api_key_requests_per_minute
+                .or(self.api_key_requests_per_minute),
         }
     }
 
-    /// Calculates seconds until N tokens available
-    fn seconds_until_available(&self, count: f64, rate: f64) -> u64 {
-        let deficit = count - self.tokens;
-        if deficit <= 0.0 {
-            0
-        } else {
-            (deficit / rate).ceil() as u64
+    /// Builds the Redis buckets a request draws from
+    ///
+    /// Every request draws from the tenant's bucket. API key requests also
+    /// draw from the key's own bucket if a per-key limit is configured.
+    pub fn buckets(&self, auth: &AuthContext) -> Vec<Bucket> {
+        let mut buckets = vec![Bucket::new(
+            format!("ratelimit:tenant:{}", auth.tenant_id),
+            self.bucket_capacity,
+            self.refill_rate,
+        )];
+
+        if let (Some(api_key_id), Some(rpm)) = (auth.api_key_id, self.api_key_requests_per_minute) {
+            buckets.push(Bucket::new(
+                format!("ratelimit:apikey:{}", api_key_id),
+                rpm,
+                rpm as f64 / 60.0,
+            ));
         }
+
+        buckets
     }
 }
 
+/// Returns the token cost of a route
+///
+/// # Arguments
+///
+/// * `path` - Matched route path (e.g. `/v1/mcp/tasks/:task_id/stream`)
+pub fn route_cost(path: &str) -> u32 {
+    ROUTE_COSTS
+        .iter()
+        .find(|(suffix, _)| path.ends_with(suffix))
+        .map(|(_, cost)| *cost)
+        .unwrap_or(1)
+}
+
 /// Rate limiting middleware layer
 ///
-/// Checks rate limits before processing requests. Returns 429 if exceeded.
+/// Consumes the route cost from the caller's buckets before processing the
+/// request. Returns 429 if any bucket has too few tokens.
+///
+/// Must be added with `route_layer` (after authentication) so the matched
+/// path is available.
 ///
 /// # Errors
 ///
+/// - 401 Unauthorized: Tenant not found
 /// - 429 Too Many Requests: Rate limit exceeded
-/// - 500 Internal Server Error: Redis connection failure
+/// - 500 Internal Server Error: Database failure
 pub async fn rate_limit_layer(
     State(state): State<AppState>,
     Extension(auth): Extension<AuthContext>,
+    matched_path: Option<MatchedPath>,
     request: Request,
     next: Next,
 ) -> Result<Response, ApiError> {
@@ -187,193 +248,63 @@ pub async fn rate_limit_layer(
             ApiError::Unauthorized("Tenant not found".to_string())
         })?;
 
-    let plan = tenant.get_plan().unwrap_or(TenantPlan::Trial);
-    let rate_limit = RateLimit::for_plan(plan);
-
-    // Check rate limit via Redis
-    // TODO: Initialize Redis connection from state
-    // For now, we'll skip rate limiting and just add headers
-    //
-    // In production:
-    // let allowed = check_rate_limit_redis(
-    //     &state.redis,
-    //     auth.tenant_id,
-    //     rate_limit,
-    // ).await?;
-    //
-    // if !allowed.ok {
-    //     return Err(create_rate_limit_error(allowed));
-    // }
+    let buckets = RateLimit::for_tenant(&tenant).buckets(&auth);
+    let cost = matched_path
+        .as_ref()
+        .map(|path| route_cost(path.as_str()))
+        .unwrap_or(1);
+
+    // Fail open: an unavailable limiter must not take the API down
+    let decision = match state.rate_limiter() {
+        Ok(limiter) => match limiter.check(&buckets, cost).await {
+            Ok(decision) => Some(decision),
+            Err(e) => {
+                tracing::warn!(error = %e, tenant_id = %auth.tenant_id, "Rate limit check failed, allowing request");
+                None
+            }
+        },
+        Err(_) => None,
+    };
+
+    let Some(decision) = decision else {
+        return Ok(next.run(request).await);
+    };
+
+    if !decision.allowed {
+        tracing::info!(
+            tenant_id = %auth.tenant_id,
+            api_key_id = ?auth.api_key_id,
+            cost,
+            retry_after = decision.retry_after_secs(),
+            "Rate limit exceeded"
+        );
+
+        let mut response = create_rate_limit_error(&decision).into_response();
+        insert_rate_limit_headers(response.headers_mut(), &decision);
+        return Ok(response);
+    }
 
     // Proceed with request
     let mut response = next.run(request).await;
-
-    // Add rate limit headers
-    response.headers_mut().insert(
-        "X-RateLimit-Limit",
-        HeaderValue::from_str(&rate_limit.requests_per_minute.to_string()).unwrap(),
-    );
-    response.headers_mut().insert(
-        "X-RateLimit-Remaining",
-        HeaderValue::from_str(&rate_limit.bucket_capacity.to_string()).unwrap(),
-    );
-    response.headers_mut().insert(
-        "X-RateLimit-Reset",
-        HeaderValue::from_str(
-            &(SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-                + 60)
-            .to_string(),
-        )
-        .unwrap(),
-    );
+    insert_rate_limit_headers(response.headers_mut(), &decision);
 
     Ok(response)
 }
 
-/// Result of rate limit check
-#[derive(Debug)]
-pub struct RateLimitResult {
-    /// Whether request is allowed
-    pub ok: bool,
-
-    /// Tokens remaining
-    pub remaining: u32,
-
-    /// Seconds until rate limit resets
-    pub reset_after: u64,
-}
-
-/// Checks rate limit using Redis token bucket
-///
-/// # Arguments
-///
-/// * `redis_url` - Redis connection URL
-/// * `tenant_id` - Tenant ID to rate limit
-/// * `rate_limit` - Rate limit configuration
-///
-/// # Returns
-///
-/// Result indicating if request is allowed and remaining quota
-///
-/// # Errors
-///
-/// Returns error if Redis connection fails
-///
-/// # Implementation
-///
-/// Uses Lua script for atomic token bucket operations:
-/// ```lua
-/// local key = KEYS[1]
-/// local capacity = tonumber(ARGV[1])
-/// local refill_rate = tonumber(ARGV[2])
-/// local now = tonumber(ARGV[3])
-///
-/// local bucket = redis.call('HMGET', key, 'tokens', 'last_refill')
-/// local tokens = tonumber(bucket[1])
-/// local last_refill = tonumber(bucket[2])
-///
-/// if not tokens then
-///     tokens = capacity
-///     last_refill = now
-/// end
-///
-/// local elapsed = now - last_refill
-/// tokens = math.min(capacity, tokens + (elapsed * refill_rate))
-///
-/// if tokens >= 1 then
-///     tokens = tokens - 1
-///     redis.call('HMSET', key, 'tokens', tokens, 'last_refill', now)
-///     redis.call('EXPIRE', key, 120)
-///     return {1, math.floor(tokens), 60}
-/// else
-///     return {0, 0, math.ceil((1 - tokens) / refill_rate)}
-/// end
-/// ```
-#[allow(dead_code)]
-async fn check_rate_limit_redis(
-    redis_url: &str,
-    tenant_id: Uuid,
-    rate_limit: RateLimit,
-) -> Result<RateLimitResult, ApiError> {
-    // Connect to Redis
-    let client = redis::Client::open(redis_url).map_err(|e| {
-        tracing::error!(error = %e, "Failed to create Redis client");
-        ApiError::InternalError("Rate limit service unavailable".to_string())
-    })?;
-
-    let mut conn = client.get_multiplexed_async_connection().await.map_err(|e| {
-        tracing::error!(error = %e, "Failed to connect to Redis");
-        ApiError::InternalError("Rate limit service unavailable".to_string())
-    })?;
-
-    let key = format!("ratelimit:tenant:{}", tenant_id);
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
-    // Lua script for atomic token bucket operations
-    let script = redis::Script::new(
-        r#"
-        local key = KEYS[1]
-        local capacity = tonumber(ARGV[1])
-        local refill_rate = tonumber(ARGV[2])
-        local now = tonumber(ARGV[3])
-
-        local bucket = redis.call('HMGET', key, 'tokens', 'last_refill')
-        local tokens = tonumber(bucket[1])
-        local last_refill = tonumber(bucket[2])
-
-        if not tokens then
-            tokens = capacity
-            last_refill = now
-        end
-
-        local elapsed = now - last_refill
-        tokens = math.min(capacity, tokens + (elapsed * refill_rate))
-
-        if tokens >= 1 then
-            tokens = tokens - 1
-            redis.call('HMSET', key, 'tokens', tokens, 'last_refill', now)
-            redis.call('EXPIRE', key, 120)
-            return {1, math.floor(tokens), 60}
-        else
-            return {0, 0, math.ceil((1 - tokens) / refill_rate)}
-        end
-        "#,
-    );
-
-    let result: Vec<i64> = script
-        .key(&key)
-        .arg(rate_limit.bucket_capacity)
-        .arg(rate_limit.refill_rate)
-        .arg(now)
-        .invoke_async(&mut conn)
-        .await
-        .map_err(|e| {
-            tracing::error!(error = %e, "Rate limit script failed");
-            ApiError::InternalError("Rate limit check failed".to_string())
-        })?;
-
-    Ok(RateLimitResult {
-        ok: result[0] == 1,
-        remaining: result[1] as u32,
-        reset_after: result[2] as u64,
-    })
+/// Adds the `X-RateLimit-*` headers
+fn insert_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert("X-RateLimit-Limit", HeaderValue::from(decision.limit));
+    headers.insert("X-RateLimit-Remaining", HeaderValue::from(decision.remaining));
+    headers.insert("X-RateLimit-Reset", HeaderValue::from(decision.reset_at()));
 }
 
 /// Creates a rate limit exceeded error response
-#[allow(dead_code)]
-fn create_rate_limit_error(result: RateLimitResult) -> ApiError {
+fn create_rate_limit_error(decision: &RateLimitDecision) -> ApiError {
+    let retry_after = decision.retry_after_secs();
+
     ApiError::RateLimitExceeded {
-        retry_after: result.reset_after,
-        message: format!(
-            "Rate limit exceeded. Try again in {} seconds",
-            result.reset_after
-        ),
+        retry_after,
+        message: format!("Rate limit exceeded. Try again in {} seconds", retry_after),
     }
 }
 
@@ -414,69 +345,92 @@ mod tests {
     }
 
     #[test]
-    fn test_token_bucket_new() {
-        let bucket = TokenBucket::new(100);
-        assert_eq!(bucket.tokens, 100.0);
-        assert!(bucket.last_refill > 0);
+    fn test_rate_limit_with_overrides() {
+        let plan = RateLimit::for_plan(TenantPlan::Enterprise);
+
+        let rpm_only = plan.with_overrides(&QuotaOverrides {
+            requests_per_minute: Some(6_000),
+            ..Default::default()
+        });
+        assert_eq!(rpm_only.requests_per_minute, 6_000);
+        assert_eq!(rpm_only.refill_rate, 100.0);
+        assert_eq!(rpm_only.bucket_capacity, 6_000);
+
+        let burst_only = plan.with_overrides(&QuotaOverrides {
+            burst: Some(50),
+            ..Default::default()
+        });
+        assert_eq!(burst_only.requests_per_minute, 1000);
+        assert_eq!(burst_only.refill_rate, 16.67);
+        assert_eq!(burst_only.bucket_capacity, 50);
+
+        let none = plan.with_overrides(&QuotaOverrides::default());
+        assert_eq!(none.bucket_capacity, 1000);
     }
 
     #[test]
-    fn test_token_bucket_consume() {
-        let mut bucket = TokenBucket::new(10);
-        assert!(bucket.try_consume(1.0));
-        assert_eq!(bucket.tokens, 9.0);
-        assert!(bucket.try_consume(5.0));
-        assert_eq!(bucket.tokens, 4.0);
-        assert!(!bucket.try_consume(10.0));
-        assert_eq!(bucket.tokens, 4.0); // Unchanged after failed attempt
+    fn test_route_cost() {
+        assert_eq!(route_cost("/v1/mcp/tasks/:task_id/stream"), 5);
+        assert_eq!(route_cost("/v1/mcp/tasks/:task_id/resume"), 5);
+        assert_eq!(route_cost("/v1/mcp/start_task"), 2);
+        assert_eq!(route_cost("/v1/mcp/tasks/:task_id/status"), 1);
+        assert_eq!(route_cost("/v1/tasks/"), 1);
     }
 
     #[test]
-    fn test_token_bucket_refill() {
-        let mut bucket = TokenBucket {
-            tokens: 5.0,
-            last_refill: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-                - 10, // 10 seconds ago
-        };
-
-        // Refill at 1 token/sec for 10 seconds = 10 tokens
-        bucket.refill(1.0, 100);
-        assert!((bucket.tokens - 15.0).abs() < 0.1);
-    }
-
-    #[test]
-    fn test_token_bucket_refill_capped() {
-        let mut bucket = TokenBucket {
-            tokens: 95.0,
-            last_refill: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-                - 10, // 10 seconds ago
-        };
-
-        // Refill at 1 token/sec for 10 seconds, but capped at capacity
-        bucket.refill(1.0, 100);
-        assert_eq!(bucket.tokens, 100.0); // Capped at capacity
+    fn test_buckets() {
+        let limit = RateLimit::for_plan(TenantPlan::Entry);
+        let tenant_key = |auth: &AuthContext| format!("ratelimit:tenant:{}", auth.tenant_id);
+
+        let jwt = AuthContext::from_jwt(uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let buckets = limit.buckets(&jwt);
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].key, tenant_key(&jwt));
+        assert_eq!(buckets[0].capacity, 60);
+
+        // API keys share the tenant bucket
+        let mut api_key = jwt.clone();
+        let api_key_id = uuid::Uuid::new_v4();
+        api_key.api_key_id = Some(api_key_id);
+        let buckets = limit.buckets(&api_key);
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].key, tenant_key(&api_key));
+
+        // A per-key limit adds the key's bucket on top of the tenant's
+        let per_key = limit.with_overrides(&QuotaOverrides {
+            api_key_requests_per_minute: Some(12),
+            ..Default::default()
+        });
+        let buckets = per_key.buckets(&api_key);
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].key, tenant_key(&api_key));
+        assert_eq!(buckets[0].capacity, 60);
+        assert_eq!(buckets[1].key, format!("ratelimit:apikey:{}", api_key_id));
+        assert_eq!(buckets[1].capacity, 12);
+        assert_eq!(buckets[1].refill_rate, 0.2);
+
+        assert_eq!(per_key.buckets(&jwt).len(), 1);
     }
 
     #[test]
-    fn test_token_bucket_seconds_until_available() {
-        let bucket = TokenBucket {
-            tokens: 2.0,
-            last_refill: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+    fn test_rate_limit_response_headers() {
+        let decision = RateLimitDecision {
+            allowed: false,
+            limit: 10,
+            remaining: 0,
+            reset_after_ms: 60_000,
+            retry_after_ms: 5_500,
+            now_ms: 1_700_000_000_000,
         };
 
-        // Need 5 tokens, have 2, rate is 1/sec -> need 3 seconds
-        assert_eq!(bucket.seconds_until_available(5.0, 1.0), 3);
+        let mut response = create_rate_limit_error(&decision).into_response();
+        insert_rate_limit_headers(response.headers_mut(), &decision);
 
-        // Already have enough
-        assert_eq!(bucket.seconds_until_available(1.0, 1.0), 0);
+        assert_eq!(response.status(), axum::http::StatusCode::TOO_MANY_REQUESTS);
+        let headers = response.headers();
+        assert_eq!(headers["Retry-After"], "6");
+        assert_eq!(headers["X-RateLimit-Limit"], "10");
+        assert_eq!(headers["X-RateLimit-Remaining"], "0");
+        assert_eq!(headers["X-RateLimit-Reset"], "1700000060");
     }
 }
@@ -0,0 +1,260 @@
+/// Operator admin endpoints
+///
+/// This module lets operators configure per-tenant limits (e.g. custom
+/// enterprise contracts). The endpoints are authenticated with the
+/// `ADMIN_API_TOKEN` bearer token, not with tenant credentials, so tenants
+/// cannot raise their own limits.
+///
+/// # Endpoints
+///
+/// - `GET /v1/admin/tenants/:id/quotas` - Overrides and effective limits
+/// - `PUT /v1/admin/tenants/:id/quotas` - Replace quota overrides
+
+use crate::{
+    app::AppState,
+    error::{ApiError, ApiResult, ValidationErrorDetail},
+    middleware::rate_limit::RateLimit,
+};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use axontask_shared::{
+    models::tenant::{QuotaOverrides, Tenant, TenantPlan},
+    quota::QuotaLimits,
+};
+use serde::Serialize;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Limits in effect for a tenant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LimitsResponse {
+    /// Maximum concurrent running tasks
+    pub concurrent_tasks: u32,
+
+    /// Maximum tasks created per 24 hours
+    pub daily_tasks: u32,
+
+    /// Maximum open stream connections
+    pub stream_connections: u32,
+
+    /// Maximum task minutes per calendar month
+    pub task_minutes: u32,
+
+    /// Rate limit refill (requests per minute)
+    pub requests_per_minute: u32,
+
+    /// Rate limit bucket capacity
+    pub burst: u32,
+
+    /// Additional rate limit per API key (requests per minute)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_requests_per_minute: Option<u32>,
+}
+
+impl LimitsResponse {
+    /// Combines quota and rate limits
+    pub fn new(quotas: QuotaLimits, rate_limit: RateLimit) -> Self {
+        LimitsResponse {
+            concurrent_tasks: quotas.concurrent_tasks,
+            daily_tasks: quotas.daily_tasks,
+            stream_connections: quotas.stream_connections,
+            task_minutes: quotas.task_minutes,
+            requests_per_minute: rate_limit.requests_per_minute,
+            burst: rate_limit.bucket_capacity,
+            api_key_requests_per_minute: rate_limit.api_key_requests_per_minute,
+        }
+    }
+}
+
+/// Tenant quotas response
+#[derive(Debug, Serialize)]
+pub struct TenantQuotasResponse {
+    /// Tenant ID
+    pub tenant_id: Uuid,
+
+    /// Billing plan
+    pub plan: String,
+
+    /// Configured overrides (unset fields use the plan default)
+    pub overrides: QuotaOverrides,
+
+    /// Plan defaults
+    pub plan_limits: LimitsResponse,
+
+    /// Limits in effect (plan defaults with overrides applied)
+    pub effective_limits: LimitsResponse,
+}
+
+impl From<&Tenant> for TenantQuotasResponse {
+    fn from(tenant: &Tenant) -> Self {
+        let plan = tenant.get_plan().unwrap_or(TenantPlan::Trial);
+        let overrides = tenant.settings().quotas;
+
+        TenantQuotasResponse {
+            tenant_id: tenant.id,
+            plan: tenant.plan.clone(),
+            overrides,
+            plan_limits: LimitsResponse::new(QuotaLimits::for_plan(plan), RateLimit::for_plan(plan)),
+            effective_limits: LimitsResponse::new(
+                QuotaLimits::for_tenant(tenant),
+                RateLimit::for_tenant(tenant),
+            ),
+        }
+    }
+}
+
+/// Get tenant quotas
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/admin/tenants/:id/quotas
+/// Authorization: Bearer <admin_token>
+/// ```
+///
+/// # Response (200 OK)
+///
+/// ```json
+/// {
+///   "tenant_id": "550e8400-e29b-41d4-a716-446655440000",
+///   "plan": "enterprise",
+///   "overrides": {"concurrent_tasks": 2000},
+///   "plan_limits": {"concurrent_tasks": 500, "daily_tasks": 100000, ...},
+///   "effective_limits": {"concurrent_tasks": 2000, "daily_tasks": 100000, ...}
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid admin token
+/// - `404 Not Found`: Tenant not found, or admin API disabled
+/// - `500 Internal Server Error`: Server error
+pub async fn get_tenant_quotas(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+) -> ApiResult<Json<TenantQuotasResponse>> {
+    let tenant = Tenant::find_by_id(&state.db, tenant_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Tenant not found".to_string()))?;
+
+    Ok(Json(TenantQuotasResponse::from(&tenant)))
+}
+
+/// Replace tenant quota overrides
+///
+/// The body replaces all overrides: omitted fields revert to the plan
+/// default, and `{}` removes every override.
+///
+/// # Endpoint
+///
+/// ```text
+/// PUT /v1/admin/tenants/:id/quotas
+/// Authorization: Bearer <admin_token>
+/// Content-Type: application/json
+///
+/// {
+///   "concurrent_tasks": 2000,
+///   "requests_per_minute": 6000,
+///   "burst": 1000
+/// }
+/// ```
+///
+/// # Response (200 OK)
+///
+/// Same as `GET /v1/admin/tenants/:id/quotas`.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid admin token
+/// - `404 Not Found`: Tenant not found, or admin API disabled
+/// - `422 Unprocessable Entity`: Limit out of range, or unknown field (e.g. a
+///   misspelled limit)
+/// - `500 Internal Server Error`: Server error
+pub async fn update_tenant_quotas(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<Uuid>,
+    Json(overrides): Json<QuotaOverrides>,
+) -> ApiResult<Json<TenantQuotasResponse>> {
+    validate_overrides(&overrides)?;
+
+    let tenant = Tenant::update_quota_overrides(&state.db, tenant_id, &overrides)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Tenant not found".to_string()))?;
+
+    tracing::info!(
+        tenant_id = %tenant.id,
+        overrides = ?overrides,
+        "Updated tenant quota overrides"
+    );
+
+    Ok(Json(TenantQuotasResponse::from(&tenant)))
+}
+
+/// Validates quota overrides
+fn validate_overrides(overrides: &QuotaOverrides) -> ApiResult<()> {
+    overrides.validate().map_err(|e| {
+        let errors: Vec<ValidationErrorDetail> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |error| ValidationErrorDetail {
+                    field: field.to_string(),
+                    message: error
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "Validation failed".to_string()),
+                })
+            })
+            .collect();
+        ApiError::ValidationError(errors)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    #[test]
+    fn test_tenant_quotas_response() {
+        let tenant = Tenant {
+            id: Uuid::new_v4(),
+            name: "Acme Corp".to_string(),
+            plan: "enterprise".to_string(),
+            stripe_customer_id: None,
+            stripe_subscription_id: None,
+            settings: json!({"quotas": {"concurrent_tasks": 2000, "burst": 100}}),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let response = TenantQuotasResponse::from(&tenant);
+        assert_eq!(response.plan_limits.concurrent_tasks, 500);
+        assert_eq!(response.effective_limits.concurrent_tasks, 2000);
+        assert_eq!(response.effective_limits.daily_tasks, 100_000);
+        assert_eq!(response.effective_limits.requests_per_minute, 1000);
+        assert_eq!(response.effective_limits.burst, 100);
+
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["overrides"], json!({"concurrent_tasks": 2000, "burst": 100}));
+    }
+
+    #[test]
+    fn test_validate_overrides() {
+        assert!(validate_overrides(&QuotaOverrides::default()).is_ok());
+
+        let err = validate_overrides(&QuotaOverrides {
+            daily_tasks: Some(0),
+            ..Default::default()
+        })
+        .unwrap_err();
+        match err {
+            ApiError::ValidationError(details) => assert_eq!(details[0].field, "daily_tasks"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
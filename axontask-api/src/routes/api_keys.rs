@@ -37,11 +37,12 @@ pub struct CreateApiKeyRequest {
     /// Available scopes:
     /// - `*`: All permissions
     /// - `tasks:*`: All task permissions
-    /// - `tasks:read`: Read tasks
-    /// - `tasks:write`: Create/update tasks
-    /// - `tasks:delete`: Delete tasks
+    /// - `tasks:read`: Read tasks, their status and streams
+    /// - `tasks:write`: Start, cancel, pause and send input to tasks (implies `tasks:read`)
+    /// - `usage:read`: Read usage reports
     /// - `webhooks:*`: All webhook permissions
-    /// - `webhooks:manage`: Manage webhooks
+    /// - `webhooks:read`: Read webhooks and deliveries
+    /// - `webhooks:manage`: Manage webhooks (implies `webhooks:read`)
     #[validate(length(min = 1, message = "At least one scope is required"))]
     pub scopes: String,
 
@@ -303,5 +304,8 @@ pub async fn revoke_api_key(
         return Err(ApiError::NotFound("API key not found".to_string()));
     }
 
+    // Stop accepting the key here immediately (other instances: within the cache TTL)
+    state.api_keys.invalidate(id);
+
     Ok(Json(RevokeApiKeyResponse { revoked }))
 }
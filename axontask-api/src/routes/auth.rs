@@ -4,27 +4,105 @@
 /// - Registration
 /// - Login
 /// - Token refresh
+/// - Logout and session management
 ///
 /// # Endpoints
 ///
 /// - `POST /v1/auth/register` - Register new user
 /// - `POST /v1/auth/login` - Login and get tokens
-/// - `POST /v1/auth/refresh` - Refresh access token
+/// - `POST /v1/auth/login/mfa` - Complete a login with a two-factor code
+/// - `POST /v1/auth/refresh` - Exchange a refresh token for new tokens
+/// - `POST /v1/auth/logout` - End the session of a refresh token
+/// - `POST /v1/auth/logout-all` - End all of the user's sessions (JWT)
+/// - `GET /v1/auth/sessions` - List the user's active sessions (JWT)
+/// - `DELETE /v1/auth/sessions/:id` - End one of the user's sessions (JWT)
+/// - `POST /v1/auth/switch-tenant` - Move the session to another tenant (JWT)
+/// - `POST /v1/auth/verify-email/request` - Email a verification link
+/// - `POST /v1/auth/verify-email/confirm` - Verify the email address with a token
+/// - `POST /v1/auth/password-reset/request` - Email a password reset link
+/// - `POST /v1/auth/password-reset/confirm` - Set a new password with a token
+/// - `GET /.well-known/jwks.json` - Public keys that verify issued tokens
+///
+/// # Sessions
+///
+/// Each login starts a session. Refresh tokens are single use: every refresh
+/// returns a new refresh token, and presenting a used one again revokes the
+/// whole session (see `axontask_shared::auth::session`).
+///
+/// # Tenants
+///
+/// Users may belong to several tenants. Login returns all of them and issues
+/// tokens for the requested tenant (default: the first one joined);
+/// `switch-tenant` moves the session to another tenant the user belongs to.
+///
+/// # Email Verification and Password Reset
+///
+/// Registration emails a verification link; with `REQUIRE_EMAIL_VERIFICATION`
+/// set, login is refused until the address is verified. The request
+/// endpoints always answer `202 Accepted` and send mail in the background, so
+/// they don't reveal which addresses have accounts (see
+/// `axontask_shared::auth::account`).
+///
+/// # Brute-Force Protection
+///
+/// Failed logins are counted per account and per client IP address. After a
+/// few failures each attempt has to wait a doubling delay, and too many lock
+/// the account or IP out for a while (`429 Too Many Requests` with
+/// `Retry-After`); lockouts are written to the audit log. Unknown emails take
+/// as long as wrong passwords and count the same (see
+/// `axontask_shared::redis::login_throttle`).
+///
+/// # Two-Factor Authentication
+///
+/// For users with two-factor authentication, login returns a short-lived MFA
+/// token instead of session tokens; `login/mfa` exchanges it together with a
+/// code from the authenticator app or a recovery code. Tenants that require
+/// two-factor authentication refuse refreshes and tenant switches of members
+/// without it; logging in to such a tenant returns a short-lived enrollment
+/// token instead, which only works for the enrollment endpoints under
+/// `/v1/auth/2fa`. Without a requested tenant, login prefers the first
+/// tenant the user can enter (see `axontask_shared::auth::two_factor`).
+///
+/// # Signing Keys
+///
+/// Tokens are signed with the configured signing key and name it in their
+/// `kid` header. Any configured key verifies tokens, so keys can be rotated
+/// without logging users out (see `axontask_shared::auth::jwt_keys`).
 
 use crate::{
     app::AppState,
     error::{ApiError, ApiResult, ValidationErrorDetail},
 };
-use axum::{extract::State, Json};
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
 use axontask_shared::{
-    auth::{jwt, password},
+    auth::{
+        account,
+        jwt::{Claims, TokenType},
+        middleware::AuthContext,
+        password, session,
+        two_factor::{self, Factor, TwoFactorError},
+    },
+    email::EmailMessage,
+    redis::login_throttle::LoginThrottle,
     models::{
-        membership::{CreateMembership, Membership, MembershipRole},
+        audit_log::{AuditAction, AuditLog, CreateAuditEntry},
+        membership::{CreateMembership, Membership, MembershipRole, UserTenant},
+        recovery_code::RecoveryCode,
+        refresh_token::{RefreshToken, Session},
         tenant::{CreateTenant, Tenant, TenantPlan},
+        two_factor::UserTwoFactor,
         user::{CreateUser, User},
     },
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use uuid::Uuid;
 use validator::Validate;
 
 /// Register request
@@ -56,11 +134,16 @@ pub struct RegisterResponse {
     /// Tenant ID
     pub tenant_id: String,
 
-    /// Access token (24h)
-    pub access_token: String,
+    /// Whether the email address must be verified before logging in
+    pub email_verification_required: bool,
 
-    /// Refresh token (30d)
-    pub refresh_token: String,
+    /// Access token (24h); omitted until the email is verified if required
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+
+    /// Refresh token (30d); omitted until the email is verified if required
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
 }
 
 /// Login request
@@ -72,6 +155,9 @@ pub struct LoginRequest {
 
     /// Password
     pub password: String,
+
+    /// Tenant to log in to (default: the first tenant the user joined)
+    pub tenant_id: Option<Uuid>,
 }
 
 /// Login response
@@ -80,14 +166,109 @@ pub struct LoginResponse {
     /// User ID
     pub user_id: String,
 
-    /// Default tenant ID
+    /// Tenant the tokens are issued for
     pub tenant_id: String,
 
-    /// Access token (24h)
+    /// Whether a second factor is needed: exchange `mfa_token` and a code at
+    /// `POST /v1/auth/login/mfa`
+    pub mfa_required: bool,
+
+    /// MFA challenge token (5m); only when `mfa_required`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mfa_token: Option<String>,
+
+    /// Whether the tenant requires two-factor authentication the user hasn't
+    /// enabled: enroll with `enrollment_token`, then log in again
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub enrollment_required: bool,
+
+    /// Enrollment token (15m) for `/v1/auth/2fa/setup` and
+    /// `/v1/auth/2fa/enable`; only when `enrollment_required`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enrollment_token: Option<String>,
+
+    /// Access token (24h); omitted until the second factor if required
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+
+    /// Refresh token (30d); omitted until the second factor if required
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+
+    /// All tenants the user belongs to; omitted until the second factor if
+    /// required
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tenants: Vec<TenantResponse>,
+}
+
+/// Second login step request
+#[derive(Debug, Deserialize)]
+pub struct MfaLoginRequest {
+    /// MFA token from the login response
+    pub mfa_token: String,
+
+    /// Code from the authenticator app, or a recovery code
+    pub code: String,
+}
+
+/// Tenant the user belongs to
+#[derive(Debug, Serialize)]
+pub struct TenantResponse {
+    /// Tenant ID
+    pub id: Uuid,
+
+    /// Tenant name
+    pub name: String,
+
+    /// Tenant plan
+    pub plan: String,
+
+    /// User's role within the tenant
+    pub role: MembershipRole,
+
+    /// Whether the tenant requires two-factor authentication
+    pub require_two_factor: bool,
+}
+
+impl From<UserTenant> for TenantResponse {
+    fn from(tenant: UserTenant) -> Self {
+        TenantResponse {
+            id: tenant.tenant_id,
+            name: tenant.name,
+            plan: tenant.plan,
+            role: tenant.role,
+            require_two_factor: tenant.require_two_factor,
+        }
+    }
+}
+
+/// Switch tenant request
+#[derive(Debug, Deserialize)]
+pub struct SwitchTenantRequest {
+    /// Tenant to switch to
+    pub tenant_id: Uuid,
+
+    /// Refresh token of the current session (single use, like a refresh)
+    pub refresh_token: String,
+}
+
+/// Switch tenant response
+#[derive(Debug, Serialize)]
+pub struct SwitchTenantResponse {
+    /// Tenant the tokens are issued for
+    pub tenant_id: Uuid,
+
+    /// User's role within the tenant
+    pub role: MembershipRole,
+
+    /// Access token for the tenant (24h)
     pub access_token: String,
 
-    /// Refresh token (30d)
+    /// New refresh token (30d); the presented token can no longer be used
     pub refresh_token: String,
+
+    /// Access token lifetime in seconds
+    pub expires_in: i64,
 }
 
 /// Refresh token request
@@ -102,12 +283,101 @@ pub struct RefreshRequest {
 pub struct RefreshResponse {
     /// New access token (24h)
     pub access_token: String,
+
+    /// New refresh token (30d); the presented token can no longer be used
+    pub refresh_token: String,
+
+    /// Access token lifetime in seconds
+    pub expires_in: i64,
+}
+
+/// Logout request
+#[derive(Debug, Deserialize)]
+pub struct LogoutRequest {
+    /// Refresh token of the session to end
+    pub refresh_token: String,
+}
+
+/// Logout all sessions response
+#[derive(Debug, Serialize)]
+pub struct LogoutAllResponse {
+    /// Number of refresh tokens revoked
+    pub revoked: u64,
+}
+
+/// Session list item
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    /// Session ID
+    pub id: Uuid,
+
+    /// Tenant context of the session
+    pub tenant_id: Uuid,
+
+    /// User agent of the client that last refreshed
+    pub user_agent: Option<String>,
+
+    /// When the session started (login time)
+    pub created_at: DateTime<Utc>,
+
+    /// When the session was last refreshed
+    pub last_refreshed_at: DateTime<Utc>,
+
+    /// When the session ends unless refreshed
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<Session> for SessionResponse {
+    fn from(session: Session) -> Self {
+        SessionResponse {
+            id: session.id,
+            tenant_id: session.tenant_id,
+            user_agent: session.user_agent,
+            created_at: session.created_at,
+            last_refreshed_at: session.last_refreshed_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+/// List sessions response
+#[derive(Debug, Serialize)]
+pub struct ListSessionsResponse {
+    /// Active sessions, most recently refreshed first
+    pub sessions: Vec<SessionResponse>,
+}
+
+/// Request for a verification or password reset email
+#[derive(Debug, Deserialize, Validate)]
+pub struct AccountEmailRequest {
+    /// Account email address
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+/// Verify email request
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailRequest {
+    /// Token from the verification email
+    pub token: String,
+}
+
+/// Password reset request
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    /// Token from the password reset email
+    pub token: String,
+
+    /// New password (validated for strength)
+    pub password: String,
 }
 
 /// Register a new user (Task 2.8)
 ///
 /// Creates a new user account with an automatically created personal tenant.
-/// The user becomes the owner of the tenant.
+/// The user becomes the owner of the tenant, and a verification link is
+/// emailed to them. When email verification is required, no tokens are
+/// issued; the user logs in after verifying.
 ///
 /// # Endpoint
 ///
@@ -129,6 +399,7 @@ pub struct RefreshResponse {
 /// {
 ///   "user_id": "uuid",
 ///   "tenant_id": "uuid",
+///   "email_verification_required": false,
 ///   "access_token": "eyJ...",
 ///   "refresh_token": "eyJ..."
 /// }
@@ -141,6 +412,7 @@ pub struct RefreshResponse {
 /// - `500 Internal Server Error`: Server error
 pub async fn register(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(req): Json<RegisterRequest>,
 ) -> ApiResult<Json<RegisterResponse>> {
     // Validate request
@@ -211,18 +483,38 @@ pub async fn register(
     )
     .await?;
 
-    // Generate tokens
-    let access_claims = jwt::Claims::new(user.id, tenant.id, jwt::TokenType::Access);
-    let refresh_claims = jwt::Claims::new(user.id, tenant.id, jwt::TokenType::Refresh);
+    // Email a verification link (the account exists either way)
+    if let Err(e) = send_verification_email(&state, &user).await {
+        tracing::warn!(user_id = %user.id, error = %e, "Failed to issue verification email");
+    }
+
+    let email_verification_required = state.config.auth.require_email_verification;
+    if email_verification_required {
+        return Ok(Json(RegisterResponse {
+            user_id: user.id.to_string(),
+            tenant_id: tenant.id.to_string(),
+            email_verification_required,
+            access_token: None,
+            refresh_token: None,
+        }));
+    }
 
-    let access_token = jwt::create_token(&access_claims, state.jwt_secret())?;
-    let refresh_token = jwt::create_token(&refresh_claims, state.jwt_secret())?;
+    // Start a session
+    let tokens = session::start_session(
+        &state.db,
+        state.jwt_keys(),
+        user.id,
+        tenant.id,
+        user_agent(&headers),
+    )
+    .await?;
 
     Ok(Json(RegisterResponse {
         user_id: user.id.to_string(),
         tenant_id: tenant.id.to_string(),
-        access_token,
-        refresh_token,
+        email_verification_required,
+        access_token: Some(tokens.access_token),
+        refresh_token: Some(tokens.refresh_token),
     }))
 }
 
@@ -238,7 +530,8 @@ pub async fn register(
 ///
 /// {
 ///   "email": "user@example.com",
-///   "password": "SecureP@ss123"
+///   "password": "SecureP@ss123",
+///   "tenant_id": "uuid"  // optional
 /// }
 /// ```
 ///
@@ -248,8 +541,44 @@ pub async fn register(
 /// {
 ///   "user_id": "uuid",
 ///   "tenant_id": "uuid",
+///   "mfa_required": false,
 ///   "access_token": "eyJ...",
-///   "refresh_token": "eyJ..."
+///   "refresh_token": "eyJ...",
+///   "tenants": [
+///     {
+///       "id": "uuid",
+///       "name": "Acme Corp",
+///       "plan": "pro",
+///       "role": "member",
+///       "require_two_factor": false
+///     }
+///   ]
+/// }
+/// ```
+///
+/// With two-factor authentication enabled, the tokens and tenants are
+/// replaced by an MFA token for `POST /v1/auth/login/mfa`:
+///
+/// ```json
+/// {
+///   "user_id": "uuid",
+///   "tenant_id": "uuid",
+///   "mfa_required": true,
+///   "mfa_token": "eyJ..."
+/// }
+/// ```
+///
+/// If the tenant requires two-factor authentication and the user hasn't
+/// enabled it, they get an enrollment token for `POST /v1/auth/2fa/setup`
+/// and `POST /v1/auth/2fa/enable` instead, and log in again afterwards:
+///
+/// ```json
+/// {
+///   "user_id": "uuid",
+///   "tenant_id": "uuid",
+///   "mfa_required": false,
+///   "enrollment_required": true,
+///   "enrollment_token": "eyJ..."
 /// }
 /// ```
 ///
@@ -257,9 +586,15 @@ pub async fn register(
 ///
 /// - `400 Bad Request`: Validation failed
 /// - `401 Unauthorized`: Invalid credentials
+/// - `403 Forbidden`: Email address not verified (when required), or not a
+///   member of the requested tenant
+/// - `429 Too Many Requests`: Too many failed attempts for the account or
+///   client IP address; retry after `Retry-After` seconds
 /// - `500 Internal Server Error`: Server error
 pub async fn login(
     State(state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(req): Json<LoginRequest>,
 ) -> ApiResult<Json<LoginResponse>> {
     // Validate request
@@ -281,47 +616,219 @@ pub async fn login(
         ApiError::ValidationError(errors)
     })?;
 
-    // Find user by email
-    let user = User::find_by_email(&state.db, &req.email)
-        .await?
-        .ok_or_else(|| ApiError::Unauthorized("Invalid email or password".to_string()))?;
-
-    // Verify password
-    let valid = password::verify_password(&req.password, &user.password_hash)?;
-    if !valid {
-        return Err(ApiError::Unauthorized(
-            "Invalid email or password".to_string(),
-        ));
+    let ip = client_ip(
+        &headers,
+        connect_info.map(|ConnectInfo(addr)| addr),
+        state.config.api.trust_proxy_headers,
+    );
+
+    // Fail open: an unavailable throttle must not stop all logins
+    let throttle = state.login_throttle().ok();
+    check_login_throttle(throttle.as_ref(), &req.email, ip).await?;
+
+    // Unknown emails cost a verification too, so timing doesn't reveal
+    // which addresses have accounts
+    let user = User::find_by_email(&state.db, &req.email).await?;
+    let valid = match &user {
+        Some(user) => password::verify_password(&req.password, &user.password_hash)?,
+        None => {
+            password::verify_dummy_password(&req.password);
+            false
+        }
+    };
+
+    let user = match user {
+        Some(user) if valid => user,
+        user => {
+            if let Some(throttle) = &throttle {
+                record_login_failure(&state, throttle, &req.email, user.map(|u| u.id), ip).await;
+            }
+            return Err(ApiError::Unauthorized(
+                "Invalid email or password".to_string(),
+            ));
+        }
+    };
+
+    if let Some(throttle) = &throttle {
+        if let Err(e) = throttle.record_success(&req.email).await {
+            tracing::warn!(error = %e, user_id = %user.id, "Failed to clear login failures");
+        }
     }
 
-    // Get user's primary tenant (first membership, typically their personal tenant)
-    let memberships = Membership::list_by_user(&state.db, user.id).await?;
-    let tenant_id = memberships
-        .first()
-        .map(|m| m.tenant_id)
-        .ok_or_else(|| ApiError::InternalError("User has no tenant membership".to_string()))?;
+    // Checked after the password, so it reveals nothing to others
+    if state.config.auth.require_email_verification && !user.email_verified {
+        return Err(ApiError::Forbidden("Email address not verified".to_string()));
+    }
 
-    // Update last login
-    User::update_last_login(&state.db, user.id).await?;
+    let two_factor_enabled = UserTwoFactor::is_enabled_for(&state.db, user.id).await?;
+
+    // Use the requested tenant, or the primary one: the first membership
+    // (typically the user's personal tenant) the user can enter
+    let tenants = Membership::list_tenants(&state.db, user.id).await?;
+    let tenant = match req.tenant_id {
+        Some(tenant_id) => tenants
+            .iter()
+            .find(|t| t.tenant_id == tenant_id)
+            .ok_or_else(|| ApiError::Forbidden("Not a member of the requested tenant".to_string()))?,
+        None => tenants
+            .iter()
+            .find(|t| two_factor_enabled || !t.require_two_factor)
+            .or_else(|| tenants.first())
+            .ok_or_else(|| ApiError::InternalError("User has no tenant membership".to_string()))?,
+    };
+    let tenant_id = tenant.tenant_id;
 
-    // Generate tokens
-    let access_claims = jwt::Claims::new(user.id, tenant_id, jwt::TokenType::Access);
-    let refresh_claims = jwt::Claims::new(user.id, tenant_id, jwt::TokenType::Refresh);
+    // No session until the user enrolls, but they must be able to enroll
+    if tenant.require_two_factor && !two_factor_enabled {
+        let enrollment_token = state
+            .jwt_keys()
+            .sign(&Claims::new(user.id, tenant_id, TokenType::Enrollment))?;
 
-    let access_token = jwt::create_token(&access_claims, state.jwt_secret())?;
-    let refresh_token = jwt::create_token(&refresh_claims, state.jwt_secret())?;
+        return Ok(Json(LoginResponse {
+            user_id: user.id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            mfa_required: false,
+            mfa_token: None,
+            enrollment_required: true,
+            enrollment_token: Some(enrollment_token),
+            access_token: None,
+            refresh_token: None,
+            tenants: Vec::new(),
+        }));
+    }
 
-    Ok(Json(LoginResponse {
-        user_id: user.id.to_string(),
-        tenant_id: tenant_id.to_string(),
-        access_token,
-        refresh_token,
-    }))
+    if two_factor_enabled {
+        let mfa_token = state
+            .jwt_keys()
+            .sign(&Claims::new(user.id, tenant_id, TokenType::Mfa))?;
+
+        return Ok(Json(LoginResponse {
+            user_id: user.id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            mfa_required: true,
+            mfa_token: Some(mfa_token),
+            enrollment_required: false,
+            enrollment_token: None,
+            access_token: None,
+            refresh_token: None,
+            tenants: Vec::new(),
+        }));
+    }
+
+    complete_login(&state, &user, tenant_id, tenants, &headers).await
+}
+
+/// Second login step endpoint
+///
+/// Completes a login of a user with two-factor authentication: exchanges the
+/// MFA token from `POST /v1/auth/login` and a code from the authenticator
+/// app (or an unused recovery code) for session tokens. Wrong codes count as
+/// failed logins.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/login/mfa
+/// Content-Type: application/json
+///
+/// {
+///   "mfa_token": "eyJ...",
+///   "code": "123456"
+/// }
+/// ```
+///
+/// # Response
+///
+/// Same as a login without two-factor authentication:
+///
+/// ```json
+/// {
+///   "user_id": "uuid",
+///   "tenant_id": "uuid",
+///   "mfa_required": false,
+///   "access_token": "eyJ...",
+///   "refresh_token": "eyJ...",
+///   "tenants": [...]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Invalid or expired MFA token, or wrong or used code
+/// - `403 Forbidden`: No longer a member of the tenant
+/// - `409 Conflict`: Two-factor authentication was disabled meanwhile
+/// - `429 Too Many Requests`: Too many failed attempts for the account or
+///   client IP address; retry after `Retry-After` seconds
+/// - `500 Internal Server Error`: Server error
+pub async fn login_mfa(
+    State(state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(req): Json<MfaLoginRequest>,
+) -> ApiResult<Json<LoginResponse>> {
+    let claims = state.jwt_keys().validate_mfa(&req.mfa_token)?;
+
+    let user = User::find_by_id(&state.db, claims.sub)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("Invalid MFA token".to_string()))?;
+
+    let ip = client_ip(
+        &headers,
+        connect_info.map(|ConnectInfo(addr)| addr),
+        state.config.api.trust_proxy_headers,
+    );
+
+    // Codes are throttled like passwords: six digits are quickly guessed
+    let throttle = state.login_throttle().ok();
+    check_login_throttle(throttle.as_ref(), &user.email, ip).await?;
+
+    let factor = match two_factor::verify(&state.db, user.id, &req.code).await {
+        Ok(factor) => factor,
+        Err(TwoFactorError::InvalidCode) => {
+            if let Some(throttle) = &throttle {
+                record_login_failure(&state, throttle, &user.email, Some(user.id), ip).await;
+            }
+            return Err(ApiError::Unauthorized("Invalid two-factor code".to_string()));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(throttle) = &throttle {
+        if let Err(e) = throttle.record_success(&user.email).await {
+            tracing::warn!(error = %e, user_id = %user.id, "Failed to clear login failures");
+        }
+    }
+
+    if factor == Factor::RecoveryCode {
+        let remaining = RecoveryCode::count_unused(&state.db, user.id).await?;
+        tracing::info!(user_id = %user.id, remaining, "Logged in with a recovery code");
+
+        let entry = CreateAuditEntry {
+            tenant_id: None,
+            user_id: Some(user.id),
+            action: AuditAction::RecoveryCodeUsed,
+            ip_address: ip.map(|ip| ip.to_string()),
+            details: serde_json::json!({ "remaining": remaining }),
+        };
+        if let Err(e) = AuditLog::record(&state.db, entry).await {
+            tracing::error!(error = %e, user_id = %user.id, "Failed to audit recovery code use");
+        }
+    }
+
+    // Membership may have changed since the password step
+    let tenants = Membership::list_tenants(&state.db, user.id).await?;
+    if !tenants.iter().any(|t| t.tenant_id == claims.tenant_id) {
+        return Err(ApiError::Forbidden("Not a member of the requested tenant".to_string()));
+    }
+
+    complete_login(&state, &user, claims.tenant_id, tenants, &headers).await
 }
 
 /// Token refresh endpoint (Task 2.10)
 ///
-/// Exchanges a refresh token for a new access token.
+/// Exchanges a refresh token for a new access token and a new refresh token.
+/// The presented refresh token is single use: presenting it again revokes
+/// the whole session.
 ///
 /// # Endpoint
 ///
@@ -338,20 +845,806 @@ pub async fn login(
 ///
 /// ```json
 /// {
-///   "access_token": "eyJ..."
+///   "access_token": "eyJ...",
+///   "refresh_token": "eyJ...",
+///   "expires_in": 86400
 /// }
 /// ```
 ///
 /// # Errors
 ///
-/// - `401 Unauthorized`: Invalid or expired refresh token
+/// - `401 Unauthorized`: Invalid, expired, revoked or reused refresh token
+/// - `403 Forbidden`: The tenant requires two-factor authentication and the
+///   user hasn't enabled it
 /// - `500 Internal Server Error`: Server error
 pub async fn refresh(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(req): Json<RefreshRequest>,
 ) -> ApiResult<Json<RefreshResponse>> {
-    // Use the refresh helper from jwt module
-    let access_token = jwt::refresh_access_token(&req.refresh_token, state.jwt_secret())?;
+    // Checked before the exchange, so a refused refresh doesn't use up the
+    // token; invalid tokens are left to the session
+    if let Ok(claims) = state.jwt_keys().validate_refresh(&req.refresh_token) {
+        require_two_factor_compliance(&state, claims.tenant_id, claims.sub).await?;
+    }
+
+    let tokens = session::refresh_session(
+        &state.db,
+        state.jwt_keys(),
+        &req.refresh_token,
+        user_agent(&headers),
+    )
+    .await?;
+
+    Ok(Json(RefreshResponse {
+        access_token: tokens.access_token,
+        refresh_token: tokens.refresh_token,
+        expires_in: tokens.expires_in,
+    }))
+}
+
+/// Logout endpoint
+///
+/// Ends the session a refresh token belongs to. Always succeeds, so clients
+/// can call it even if the token already expired or was revoked.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/logout
+/// Content-Type: application/json
+///
+/// {
+///   "refresh_token": "eyJ..."
+/// }
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `500 Internal Server Error`: Server error
+pub async fn logout(
+    State(state): State<AppState>,
+    Json(req): Json<LogoutRequest>,
+) -> ApiResult<StatusCode> {
+    if session::end_session(&state.db, &req.refresh_token).await? {
+        tracing::info!("Session ended");
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Logout all sessions endpoint
+///
+/// Ends all of the authenticated user's sessions, across tenants. Access
+/// tokens already issued remain valid until they expire.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/logout-all
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "revoked": 3
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `500 Internal Server Error`: Server error
+pub async fn logout_all(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> ApiResult<Json<LogoutAllResponse>> {
+    let user_id = require_user(&auth)?;
+    let revoked = RefreshToken::revoke_all_for_user(&state.db, user_id).await?;
+
+    tracing::info!(user_id = %user_id, revoked, "All sessions ended");
 
-    Ok(Json(RefreshResponse { access_token }))
+    Ok(Json(LogoutAllResponse { revoked }))
+}
+
+/// List sessions endpoint
+///
+/// Lists the authenticated user's active sessions.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/auth/sessions
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "sessions": [
+///     {
+///       "id": "uuid",
+///       "tenant_id": "uuid",
+///       "user_agent": "Mozilla/5.0 ...",
+///       "created_at": "2025-01-03T10:00:00Z",
+///       "last_refreshed_at": "2025-01-04T09:00:00Z",
+///       "expires_at": "2025-02-03T09:00:00Z"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `500 Internal Server Error`: Server error
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> ApiResult<Json<ListSessionsResponse>> {
+    let user_id = require_user(&auth)?;
+    let sessions = RefreshToken::list_sessions(&state.db, user_id).await?;
+
+    Ok(Json(ListSessionsResponse {
+        sessions: sessions.into_iter().map(SessionResponse::from).collect(),
+    }))
+}
+
+/// Revoke session endpoint
+///
+/// Ends one of the authenticated user's sessions (e.g. a lost device).
+///
+/// # Endpoint
+///
+/// ```text
+/// DELETE /v1/auth/sessions/:id
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `404 Not Found`: Session not found or already ended
+/// - `500 Internal Server Error`: Server error
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let user_id = require_user(&auth)?;
+
+    if RefreshToken::revoke_family(&state.db, user_id, id).await? == 0 {
+        return Err(ApiError::NotFound("Session not found".to_string()));
+    }
+
+    tracing::info!(user_id = %user_id, session_id = %id, "Session ended");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Switch tenant endpoint
+///
+/// Moves the current session to another tenant the user belongs to. The
+/// refresh token is exchanged as in a refresh, and the new tokens are issued
+/// for the tenant; later refreshes stay in it.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/switch-tenant
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// {
+///   "tenant_id": "uuid",
+///   "refresh_token": "eyJ..."
+/// }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "tenant_id": "uuid",
+///   "role": "member",
+///   "access_token": "eyJ...",
+///   "refresh_token": "eyJ...",
+///   "expires_in": 86400
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT, or the refresh token is
+///   invalid, reused or belongs to another user
+/// - `403 Forbidden`: Not a member of the tenant, or the tenant requires
+///   two-factor authentication and the user hasn't enabled it
+/// - `500 Internal Server Error`: Server error
+pub async fn switch_tenant(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    headers: HeaderMap,
+    Json(req): Json<SwitchTenantRequest>,
+) -> ApiResult<Json<SwitchTenantResponse>> {
+    let user_id = require_user(&auth)?;
+
+    require_two_factor_compliance(&state, req.tenant_id, user_id).await?;
+
+    let tokens = session::switch_tenant(
+        &state.db,
+        state.jwt_keys(),
+        &req.refresh_token,
+        user_id,
+        req.tenant_id,
+        user_agent(&headers),
+    )
+    .await?;
+
+    let role = Membership::get_role(&state.db, tokens.tenant_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("Not a member of the requested tenant".to_string()))?;
+
+    tracing::info!(
+        user_id = %user_id,
+        session_id = %tokens.session_id,
+        from_tenant_id = %auth.tenant_id,
+        tenant_id = %tokens.tenant_id,
+        "Session switched tenant"
+    );
+
+    Ok(Json(SwitchTenantResponse {
+        tenant_id: tokens.tenant_id,
+        role,
+        access_token: tokens.access_token,
+        refresh_token: tokens.refresh_token,
+        expires_in: tokens.expires_in,
+    }))
+}
+
+/// Request email verification endpoint
+///
+/// Emails a new verification link if the address belongs to an unverified
+/// account. Always answers `202 Accepted`, so it can't be used to find out
+/// which addresses have accounts. Links are sent at most once a minute per
+/// account; a new link replaces the previous one.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/verify-email/request
+/// Content-Type: application/json
+///
+/// {
+///   "email": "user@example.com"
+/// }
+/// ```
+///
+/// # Response
+///
+/// `202 Accepted`
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Validation failed
+/// - `500 Internal Server Error`: Server error
+pub async fn request_email_verification(
+    State(state): State<AppState>,
+    Json(req): Json<AccountEmailRequest>,
+) -> ApiResult<StatusCode> {
+    // Validate request
+    req.validate().map_err(|e| {
+        let errors: Vec<ValidationErrorDetail> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |error| ValidationErrorDetail {
+                    field: field.to_string(),
+                    message: error
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "Validation failed".to_string()),
+                })
+            })
+            .collect();
+        ApiError::ValidationError(errors)
+    })?;
+
+    if let Some(user) = User::find_by_email(&state.db, req.email.trim()).await? {
+        send_verification_email(&state, &user).await?;
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Confirm email verification endpoint
+///
+/// Marks the email address verified with the token from a verification
+/// email. Tokens are single use.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/verify-email/confirm
+/// Content-Type: application/json
+///
+/// {
+///   "token": "vfy_..."
+/// }
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Invalid, expired or used token
+/// - `500 Internal Server Error`: Server error
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Json(req): Json<VerifyEmailRequest>,
+) -> ApiResult<StatusCode> {
+    let user_id = account::verify_email(&state.db, &req.token).await?;
+
+    tracing::info!(user_id = %user_id, "Email address verified");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Request password reset endpoint
+///
+/// Emails a password reset link if the address belongs to an account.
+/// Always answers `202 Accepted`, so it can't be used to find out which
+/// addresses have accounts. Links are sent at most once a minute per account;
+/// a new link replaces the previous one.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/password-reset/request
+/// Content-Type: application/json
+///
+/// {
+///   "email": "user@example.com"
+/// }
+/// ```
+///
+/// # Response
+///
+/// `202 Accepted`
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Validation failed
+/// - `500 Internal Server Error`: Server error
+pub async fn request_password_reset(
+    State(state): State<AppState>,
+    Json(req): Json<AccountEmailRequest>,
+) -> ApiResult<StatusCode> {
+    // Validate request
+    req.validate().map_err(|e| {
+        let errors: Vec<ValidationErrorDetail> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |error| ValidationErrorDetail {
+                    field: field.to_string(),
+                    message: error
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "Validation failed".to_string()),
+                })
+            })
+            .collect();
+        ApiError::ValidationError(errors)
+    })?;
+
+    if let Some(user) = User::find_by_email(&state.db, req.email.trim()).await? {
+        let ttl = chrono::Duration::minutes(state.config.auth.password_reset_ttl_minutes as i64);
+
+        if let Some((token, plaintext)) = account::issue_password_reset(&state.db, &user, ttl).await? {
+            let reset_url = email_link(&state.config.api.app_url, "reset-password", &plaintext);
+            send_in_background(
+                &state,
+                EmailMessage::password_reset(&token.email, &reset_url, token.expires_at),
+            );
+
+            tracing::info!(user_id = %user.id, "Password reset requested");
+        }
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Confirm password reset endpoint
+///
+/// Sets a new password with the token from a password reset email. Tokens
+/// are single use. All of the user's sessions end, and the email address
+/// counts as verified.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/password-reset/confirm
+/// Content-Type: application/json
+///
+/// {
+///   "token": "rst_...",
+///   "password": "N3w-P@ssword"
+/// }
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Invalid, expired or used token, or a weak password
+/// - `500 Internal Server Error`: Server error
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(req): Json<ResetPasswordRequest>,
+) -> ApiResult<StatusCode> {
+    let user_id = account::reset_password(&state.db, &req.token, &req.password).await?;
+
+    tracing::info!(user_id = %user_id, "Password reset; all sessions ended");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// JWKS endpoint
+///
+/// Publishes the public keys of the configured RS256 and EdDSA signing keys,
+/// including retired keys that still verify tokens. HS256 secrets are never
+/// published; with only HS256 keys the set is empty.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /.well-known/jwks.json
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "keys": [
+///     {
+///       "use": "sig",
+///       "alg": "EdDSA",
+///       "kid": "2025-01",
+///       "kty": "OKP",
+///       "crv": "Ed25519",
+///       "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"
+///     }
+///   ]
+/// }
+/// ```
+pub async fn jwks(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(state.jwt_keys().jwks()),
+    )
+}
+
+/// Issues a verification token and emails the link, unless the address is
+/// already verified or a link was just sent
+async fn send_verification_email(state: &AppState, user: &User) -> ApiResult<()> {
+    let ttl = chrono::Duration::hours(state.config.auth.email_verification_ttl_hours as i64);
+
+    if let Some((token, plaintext)) = account::issue_email_verification(&state.db, user, ttl).await? {
+        let verify_url = email_link(&state.config.api.app_url, "verify-email", &plaintext);
+        send_in_background(
+            state,
+            EmailMessage::email_verification(&token.email, &verify_url, token.expires_at),
+        );
+    }
+
+    Ok(())
+}
+
+/// Sends an account email without waiting for delivery
+///
+/// Responses then take the same time whether or not an email was sent.
+fn send_in_background(state: &AppState, message: EmailMessage) {
+    let mailer = state.mailer.clone();
+
+    tokio::spawn(async move {
+        if let Err(e) = mailer.send(&message).await {
+            tracing::warn!(error = %e, subject = %message.subject, "Failed to send account email");
+        }
+    });
+}
+
+/// Rejects the attempt if the account or client IP address is throttled
+///
+/// Throttle errors are logged and the attempt is allowed.
+pub(crate) async fn check_login_throttle(
+    throttle: Option<&LoginThrottle>,
+    email: &str,
+    ip: Option<IpAddr>,
+) -> ApiResult<()> {
+    let Some(throttle) = throttle else {
+        return Ok(());
+    };
+
+    match throttle.check(email, ip).await {
+        Ok(decision) if !decision.allowed => Err(ApiError::RateLimitExceeded {
+            retry_after: decision.retry_after_secs(),
+            message: "Too many failed login attempts; try again later".to_string(),
+        }),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::warn!(error = %e, "Login throttle check failed, allowing attempt");
+            Ok(())
+        }
+    }
+}
+
+/// Starts a session once all login checks passed
+async fn complete_login(
+    state: &AppState,
+    user: &User,
+    tenant_id: Uuid,
+    tenants: Vec<UserTenant>,
+    headers: &HeaderMap,
+) -> ApiResult<Json<LoginResponse>> {
+    // Update last login
+    User::update_last_login(&state.db, user.id).await?;
+
+    // Start a session
+    let tokens = session::start_session(
+        &state.db,
+        state.jwt_keys(),
+        user.id,
+        tenant_id,
+        user_agent(headers),
+    )
+    .await?;
+
+    Ok(Json(LoginResponse {
+        user_id: user.id.to_string(),
+        tenant_id: tenant_id.to_string(),
+        mfa_required: false,
+        mfa_token: None,
+        enrollment_required: false,
+        enrollment_token: None,
+        access_token: Some(tokens.access_token),
+        refresh_token: Some(tokens.refresh_token),
+        tenants: tenants.into_iter().map(TenantResponse::from).collect(),
+    }))
+}
+
+/// Rejects users without two-factor authentication in tenants that
+/// require it
+async fn require_two_factor_compliance(
+    state: &AppState,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> ApiResult<()> {
+    let required = Tenant::find_by_id(&state.db, tenant_id)
+        .await?
+        .is_some_and(|tenant| tenant.requires_two_factor());
+
+    if required && !UserTwoFactor::is_enabled_for(&state.db, user_id).await? {
+        return Err(two_factor_required());
+    }
+
+    Ok(())
+}
+
+/// Error for sessions in a tenant that requires two-factor authentication
+fn two_factor_required() -> ApiError {
+    ApiError::Forbidden("Tenant requires two-factor authentication; enable it first".to_string())
+}
+
+/// Counts a failed login and audits the lockouts it causes
+///
+/// Errors are logged, never returned: the client gets its 401 either way.
+pub(crate) async fn record_login_failure(
+    state: &AppState,
+    throttle: &LoginThrottle,
+    email: &str,
+    user_id: Option<Uuid>,
+    ip: Option<IpAddr>,
+) {
+    let outcome = match throttle.record_failure(email, ip).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to record login failure");
+            return;
+        }
+    };
+
+    let lockouts = [
+        ("account", outcome.account_locked, outcome.account_failures),
+        ("ip", outcome.ip_locked, outcome.ip_failures),
+    ];
+    for (scope, locked, failures) in lockouts {
+        if !locked {
+            continue;
+        }
+
+        tracing::warn!(scope, user_id = ?user_id, ip = ?ip, failures, "Login locked out");
+
+        // An IP lockout concerns no account in particular
+        let entry = CreateAuditEntry {
+            tenant_id: None,
+            user_id: user_id.filter(|_| scope == "account"),
+            action: AuditAction::LoginLocked,
+            ip_address: ip.map(|ip| ip.to_string()),
+            details: serde_json::json!({
+                "scope": scope,
+                "email": email,
+                "failures": failures,
+                "lockout_secs": throttle.config().lockout_secs,
+            }),
+        };
+        if let Err(e) = AuditLog::record(&state.db, entry).await {
+            tracing::error!(error = %e, scope, "Failed to audit login lockout");
+        }
+    }
+}
+
+/// Gets the client's IP address
+///
+/// With `trust_proxy` set, the last `X-Forwarded-For` entry wins: it was
+/// added by our proxy, while earlier ones come from the client and can be
+/// forged. Otherwise the peer address of the connection is used.
+pub(crate) fn client_ip(
+    headers: &HeaderMap,
+    peer: Option<SocketAddr>,
+    trust_proxy: bool,
+) -> Option<IpAddr> {
+    if trust_proxy {
+        let forwarded = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .last()
+            .and_then(|entry| entry.trim().parse::<IpAddr>().ok());
+
+        if forwarded.is_some() {
+            return forwarded;
+        }
+    }
+
+    peer.map(|addr| addr.ip())
+}
+
+/// Builds a dashboard link carrying a token
+fn email_link(app_url: &str, path: &str, token: &str) -> String {
+    format!("{}/{}?token={}", app_url, path, token)
+}
+
+/// Gets the user of a user-bound credential
+pub(crate) fn require_user(auth: &AuthContext) -> ApiResult<Uuid> {
+    auth.user_id
+        .ok_or_else(|| ApiError::Unauthorized("Sessions require a user token".to_string()))
+}
+
+/// Gets the client's user agent
+fn user_agent(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_ip() {
+        let peer: SocketAddr = "10.0.0.2:51234".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "6.6.6.6, 203.0.113.7".parse().unwrap());
+
+        assert_eq!(
+            client_ip(&headers, Some(peer), true),
+            Some("203.0.113.7".parse().unwrap())
+        );
+        assert_eq!(client_ip(&headers, Some(peer), false), Some(peer.ip()));
+        assert_eq!(client_ip(&HeaderMap::new(), Some(peer), true), Some(peer.ip()));
+        assert_eq!(client_ip(&HeaderMap::new(), None, false), None);
+    }
+
+    #[test]
+    fn test_user_agent() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(user_agent(&headers), None);
+
+        headers.insert(header::USER_AGENT, "axon-cli/1.0".parse().unwrap());
+        assert_eq!(user_agent(&headers), Some("axon-cli/1.0"));
+    }
+
+    #[test]
+    fn test_email_link() {
+        assert_eq!(
+            email_link("https://app.example.com", "reset-password", "rst_abc"),
+            "https://app.example.com/reset-password?token=rst_abc"
+        );
+    }
+
+    #[test]
+    fn test_register_response_omits_missing_tokens() {
+        let response = RegisterResponse {
+            user_id: Uuid::new_v4().to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            email_verification_required: true,
+            access_token: None,
+            refresh_token: None,
+        };
+
+        let json = serde_json::to_value(response).unwrap();
+        assert_eq!(json["email_verification_required"], true);
+        assert!(json.get("access_token").is_none());
+        assert!(json.get("refresh_token").is_none());
+    }
+
+    #[test]
+    fn test_require_user() {
+        let auth = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(require_user(&auth).unwrap(), auth.user_id.unwrap());
+
+        let mut api_key = auth.clone();
+        api_key.user_id = None;
+        assert!(require_user(&api_key).is_err());
+    }
+
+    #[test]
+    fn test_tenant_response() {
+        let tenant = UserTenant {
+            tenant_id: Uuid::new_v4(),
+            name: "Client A".to_string(),
+            plan: "pro".to_string(),
+            role: MembershipRole::Member,
+            joined_at: Utc::now(),
+            require_two_factor: true,
+        };
+
+        let json = serde_json::to_value(TenantResponse::from(tenant.clone())).unwrap();
+        assert_eq!(json["id"], tenant.tenant_id.to_string());
+        assert_eq!(json["name"], "Client A");
+        assert_eq!(json["role"], "member");
+        assert_eq!(json["require_two_factor"], true);
+    }
+
+    #[test]
+    fn test_login_response_omits_tokens_until_mfa() {
+        let response = LoginResponse {
+            user_id: Uuid::new_v4().to_string(),
+            tenant_id: Uuid::new_v4().to_string(),
+            mfa_required: true,
+            mfa_token: Some("eyJ...".to_string()),
+            enrollment_required: false,
+            enrollment_token: None,
+            access_token: None,
+            refresh_token: None,
+            tenants: Vec::new(),
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["mfa_required"], true);
+        assert_eq!(json["mfa_token"], "eyJ...");
+        assert!(json.get("enrollment_required").is_none());
+        assert!(json.get("access_token").is_none());
+        assert!(json.get("refresh_token").is_none());
+        assert!(json.get("tenants").is_none());
+    }
 }
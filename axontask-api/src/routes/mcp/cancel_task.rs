@@ -1,6 +1,6 @@
 /// Cancel task MCP endpoint
 ///
-/// This endpoint allows clients to cancel a running or pending task.
+/// This endpoint allows clients to cancel a pending, running or paused task.
 /// It sends a control message to the worker and updates the task state.
 ///
 /// # Endpoint
@@ -26,9 +26,12 @@
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::routes::mcp::find_task;
+use axontask_shared::auth::authorization::ResourcePermission;
 use axontask_shared::auth::middleware::AuthContext;
 use axontask_shared::models::task::{Task, TaskState};
-use axontask_shared::redis::RedisClient;
+use axontask_shared::models::webhook_delivery::WebhookDelivery;
+use axontask_shared::redis::ControlMessage;
 use axum::{extract::{Path, State}, Extension, Json};
 use serde::Serialize;
 use uuid::Uuid;
@@ -51,10 +54,10 @@ pub struct CancelTaskResponse {
 
 /// Cancel task endpoint handler
 ///
-/// Cancels a running or pending task by:
+/// Cancels a pending, running or paused task by:
 /// 1. Validating task exists and belongs to tenant
 /// 2. Checking if task can be canceled (not already completed)
-/// 3. Sending control message to worker via Redis
+/// 3. Sending control message to worker via Redis (best effort)
 /// 4. Updating task state to "canceled"
 ///
 /// # Authentication
@@ -70,7 +73,7 @@ pub struct CancelTaskResponse {
 /// - 400 Bad Request: Task already completed
 /// - 401 Unauthorized: Missing or invalid authentication
 /// - 403 Forbidden: Task belongs to different tenant
-/// - 404 Not Found: Task does not exist
+/// - 404 Not Found: Task does not exist or is not accessible (members only access their own tasks)
 /// - 500 Internal Server Error: Database or Redis error
 ///
 /// # Example
@@ -105,21 +108,8 @@ pub async fn cancel_task(
         "Canceling task"
     );
 
-    // Find task with tenant isolation
-    let task = Task::find_by_id_and_tenant(&state.db, task_id, auth.tenant_id)
-        .await
-        .map_err(|e| {
-            tracing::error!(error = %e, task_id = %task_id, "Failed to query task");
-            ApiError::InternalError("Failed to query task".to_string())
-        })?
-        .ok_or_else(|| {
-            tracing::warn!(
-                task_id = %task_id,
-                tenant_id = %auth.tenant_id,
-                "Task not found or access denied"
-            );
-            ApiError::NotFound("Task not found".to_string())
-        })?;
+    // Find task with tenant isolation and ownership rules
+    let task = find_task(&state, &auth, task_id, ResourcePermission::Write).await?;
 
     // Check if task can be canceled
     match task.state.as_str() {
@@ -131,7 +121,7 @@ pub async fn cancel_task(
                 message: format!("Task already in terminal state: {}", task.state),
             }));
         }
-        "pending" | "running" => {
+        "pending" | "running" | "paused" => {
             // Can be canceled
         }
         _ => {
@@ -139,13 +129,16 @@ pub async fn cancel_task(
         }
     }
 
-    // Send control message to worker via Redis
-    // TODO: Initialize Redis client from state
-    // For now, we'll just update the database state
-    // In a full implementation:
-    // let redis_client = RedisClient::new(redis_config).await?;
-    // let writer = StreamWriter::new(redis_client);
-    // writer.publish_control_message(task_id, "cancel", None).await?;
+    // Send control message to worker via Redis. Best effort: pending tasks
+    // have no worker yet, and the database state below is authoritative.
+    if let Ok(publisher) = state.control_publisher() {
+        if let Err(e) = publisher
+            .publish(task_id, &ControlMessage::cancel(None))
+            .await
+        {
+            tracing::warn!(error = %e, task_id = %task_id, "Failed to publish cancel message");
+        }
+    }
 
     // Update task state to canceled
     let updated_task = Task::transition_to_canceled(&state.db, task_id)
@@ -155,6 +148,13 @@ pub async fn cancel_task(
             ApiError::InternalError("Failed to cancel task".to_string())
         })?;
 
+    // Notify webhooks. Best effort: deliveries are retried by the worker.
+    if let Some(task) = &updated_task {
+        if let Err(e) = WebhookDelivery::enqueue_for_task(&state.db, task).await {
+            tracing::warn!(error = %e, task_id = %task_id, "Failed to queue webhook deliveries");
+        }
+    }
+
     let task_state = updated_task
         .map(|t| t.state.as_str().to_string())
         .unwrap_or_else(|| TaskState::Pending.as_str().to_string());
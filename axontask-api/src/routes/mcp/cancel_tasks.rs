@@ -0,0 +1,169 @@
+/// Bulk cancel MCP endpoint
+///
+/// This endpoint cancels every pending, running or paused task carrying all
+/// of the given tags, e.g. everything started for a pull request or an agent
+/// session.
+///
+/// # Endpoint
+///
+/// `POST /mcp/cancel_tasks`
+///
+/// # Authentication
+///
+/// Requires either:
+/// - JWT token (Authorization: Bearer <token>)
+/// - API key (X-Api-Key: <key>)
+///
+/// # Example Request
+///
+/// ```json
+/// {
+///   "tags": ["session:abc123"]
+/// }
+/// ```
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "canceled": 2,
+///   "task_ids": [
+///     "550e8400-e29b-41d4-a716-446655440000",
+///     "6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+///   ]
+/// }
+/// ```
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use axontask_shared::auth::authorization::{member_role, own_tasks_only, ResourcePermission};
+use axontask_shared::auth::middleware::AuthContext;
+use axontask_shared::models::task::{normalize_tags, Task};
+use axontask_shared::models::webhook_delivery::WebhookDelivery;
+use axontask_shared::redis::ControlMessage;
+use axum::{extract::State, Extension, Json};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Bulk cancel request
+#[derive(Debug, Clone, Deserialize)]
+pub struct CancelTasksRequest {
+    /// Tasks must carry all of these tags (at least one required)
+    pub tags: Vec<String>,
+}
+
+/// Bulk cancel response
+#[derive(Debug, Clone, Serialize)]
+pub struct CancelTasksResponse {
+    /// Number of tasks canceled
+    pub canceled: usize,
+
+    /// IDs of the canceled tasks
+    pub task_ids: Vec<Uuid>,
+}
+
+/// Bulk cancel endpoint handler
+///
+/// Cancels matching tasks by:
+/// 1. Validating the tag filter
+/// 2. Updating all matching active tasks of the tenant to "canceled"
+/// 3. Sending a cancel control message for each task via Redis (best effort)
+/// 4. Queuing `task.canceled` webhook deliveries (best effort)
+///
+/// Tasks already in a terminal state are not affected. Members only cancel
+/// tasks they created; admins, owners and API keys cancel any matching task.
+///
+/// # Errors
+///
+/// - 401 Unauthorized: Missing or invalid authentication
+/// - 422 Unprocessable Entity: Missing or invalid tags
+/// - 500 Internal Server Error: Database error
+pub async fn cancel_tasks(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(request): Json<CancelTasksRequest>,
+) -> Result<Json<CancelTasksResponse>, ApiError> {
+    let tags = validate_tags(request.tags)?;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        user_id = ?auth.user_id,
+        tags = ?tags,
+        "Canceling tasks by tags"
+    );
+
+    let role = member_role(&state.db, &auth).await?;
+    let created_by = own_tasks_only(&auth, role, ResourcePermission::Write);
+
+    let tasks = Task::cancel_by_tags(&state.db, auth.tenant_id, &tags, created_by)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to cancel tasks");
+            ApiError::InternalError("Failed to cancel tasks".to_string())
+        })?;
+
+    // Notify workers. Best effort: the database state above is authoritative.
+    if let Ok(publisher) = state.control_publisher() {
+        for task in &tasks {
+            if let Err(e) = publisher
+                .publish(task.id, &ControlMessage::cancel(None))
+                .await
+            {
+                tracing::warn!(error = %e, task_id = %task.id, "Failed to publish cancel message");
+            }
+        }
+    }
+
+    // Notify webhooks. Best effort: deliveries are retried by the worker.
+    for task in &tasks {
+        if let Err(e) = WebhookDelivery::enqueue_for_task(&state.db, task).await {
+            tracing::warn!(error = %e, task_id = %task.id, "Failed to queue webhook deliveries");
+        }
+    }
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        canceled = tasks.len(),
+        "Tasks canceled by tags"
+    );
+
+    Ok(Json(CancelTasksResponse {
+        canceled: tasks.len(),
+        task_ids: tasks.into_iter().map(|t| t.id).collect(),
+    }))
+}
+
+/// Normalizes the tag filter, requiring at least one tag
+///
+/// An empty filter would match every task of the tenant.
+fn validate_tags(tags: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let tags = normalize_tags(tags).and_then(|tags| {
+        if tags.is_empty() {
+            Err("At least one tag is required".to_string())
+        } else {
+            Ok(tags)
+        }
+    });
+
+    tags.map_err(|message| {
+        ApiError::ValidationError(vec![crate::error::ValidationErrorDetail {
+            field: "tags".to_string(),
+            message,
+        }])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_tags() {
+        assert_eq!(
+            validate_tags(vec!["pr:42".to_string(), " pr:42".to_string()]).unwrap(),
+            vec!["pr:42"]
+        );
+        assert!(validate_tags(vec![]).is_err());
+        assert!(validate_tags(vec!["bad tag".to_string()]).is_err());
+    }
+}
@@ -2,6 +2,7 @@
 ///
 /// This endpoint returns the current status of a task including:
 /// - Current state (pending, running, succeeded, failed, etc.)
+/// - Tags
 /// - Timestamps (created, started, ended)
 /// - Last event sequence number
 /// - Resource usage metrics
@@ -23,6 +24,7 @@
 ///   "task_id": "550e8400-e29b-41d4-a716-446655440000",
 ///   "name": "deploy-app",
 ///   "state": "running",
+///   "tags": ["deploy", "env:prod"],
 ///   "created_at": "2025-01-04T12:00:00Z",
 ///   "started_at": "2025-01-04T12:00:05Z",
 ///   "ended_at": null,
@@ -36,8 +38,9 @@
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::routes::mcp::find_task;
+use axontask_shared::auth::authorization::ResourcePermission;
 use axontask_shared::auth::middleware::AuthContext;
-use axontask_shared::models::task::Task;
 use axum::{extract::{Path, State}, Extension, Json};
 use chrono::{DateTime, Utc};
 use serde::Serialize;
@@ -55,6 +58,17 @@ pub struct TaskStatusResponse {
     /// Current state
     pub state: String,
 
+    /// Task tags
+    pub tags: Vec<String>,
+
+    /// User who created the task (if created with a JWT)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<Uuid>,
+
+    /// API key that created the task (if created with an API key)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<Uuid>,
+
     /// When task was created
     pub created_at: DateTime<Utc>,
 
@@ -90,6 +104,10 @@ pub struct TaskMetrics {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bytes_streamed: Option<i64>,
 
+    /// Time spent paused in milliseconds (included in duration_ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused_ms: Option<i64>,
+
     /// Task-minutes consumed (for billing)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub task_minutes: Option<f64>,
@@ -111,7 +129,7 @@ pub struct TaskMetrics {
 ///
 /// - 401 Unauthorized: Missing or invalid authentication
 /// - 403 Forbidden: Task belongs to different tenant
-/// - 404 Not Found: Task does not exist
+/// - 404 Not Found: Task does not exist or is not accessible (members only access their own tasks)
 /// - 500 Internal Server Error: Database error
 ///
 /// # Example
@@ -146,21 +164,8 @@ pub async fn get_task_status(
         "Getting task status"
     );
 
-    // Find task with tenant isolation
-    let task = Task::find_by_id_and_tenant(&state.db, task_id, auth.tenant_id)
-        .await
-        .map_err(|e| {
-            tracing::error!(error = %e, task_id = %task_id, "Failed to query task");
-            ApiError::InternalError("Failed to query task".to_string())
-        })?
-        .ok_or_else(|| {
-            tracing::warn!(
-                task_id = %task_id,
-                tenant_id = %auth.tenant_id,
-                "Task not found or access denied"
-            );
-            ApiError::NotFound("Task not found".to_string())
-        })?;
+    // Find task with tenant isolation and ownership rules
+    let task = find_task(&state, &auth, task_id, ResourcePermission::Read).await?;
 
     // Calculate duration if task has started
     let duration_ms = if let (Some(started), Some(ended)) = (task.started_at, task.ended_at) {
@@ -172,14 +177,22 @@ pub async fn get_task_status(
         None
     };
 
-    // Calculate task-minutes (for billing)
-    let task_minutes = duration_ms.map(|ms| ms as f64 / 60000.0);
+    // Time spent paused, including an in-progress pause
+    let paused_ms = i64::from(task.paused_seconds) * 1000
+        + task
+            .paused_at
+            .map(|at| (Utc::now() - at).num_milliseconds())
+            .unwrap_or(0);
+
+    // Calculate task-minutes (for billing, excludes paused time)
+    let task_minutes = duration_ms.map(|ms| (ms - paused_ms).max(0) as f64 / 60000.0);
 
     // Build metrics
     let metrics = if duration_ms.is_some() || task.bytes_streamed > 0 {
         Some(TaskMetrics {
             duration_ms,
             bytes_streamed: Some(task.bytes_streamed),
+            paused_ms: (paused_ms > 0).then_some(paused_ms),
             task_minutes,
         })
     } else {
@@ -191,6 +204,9 @@ pub async fn get_task_status(
         task_id: task.id,
         name: task.name,
         state: task.state.as_str().to_string(),
+        tags: task.tags,
+        created_by: task.created_by,
+        api_key_id: task.api_key_id,
         created_at: task.created_at,
         started_at: task.started_at,
         ended_at: task.ended_at,
@@ -213,6 +229,9 @@ mod tests {
             task_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
             name: "test-task".to_string(),
             state: TaskState::Running.as_str().to_string(),
+            tags: vec![],
+            created_by: None,
+            api_key_id: None,
             created_at: Utc::now(),
             started_at: Some(Utc::now()),
             ended_at: None,
@@ -220,6 +239,7 @@ mod tests {
             metrics: Some(TaskMetrics {
                 duration_ms: Some(5000),
                 bytes_streamed: Some(1024),
+                paused_ms: None,
                 task_minutes: Some(0.083),
             }),
             error: None,
@@ -238,6 +258,9 @@ mod tests {
             task_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
             name: "failed-task".to_string(),
             state: TaskState::Failed.as_str().to_string(),
+            tags: vec![],
+            created_by: None,
+            api_key_id: None,
             created_at: Utc::now(),
             started_at: Some(Utc::now()),
             ended_at: Some(Utc::now()),
@@ -245,6 +268,7 @@ mod tests {
             metrics: Some(TaskMetrics {
                 duration_ms: Some(2000),
                 bytes_streamed: Some(512),
+                paused_ms: None,
                 task_minutes: Some(0.033),
             }),
             error: Some("Connection timeout".to_string()),
@@ -261,6 +285,9 @@ mod tests {
             task_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
             name: "pending-task".to_string(),
             state: TaskState::Pending.as_str().to_string(),
+            tags: vec![],
+            created_by: None,
+            api_key_id: None,
             created_at: Utc::now(),
             started_at: None,
             ended_at: None,
@@ -10,6 +10,10 @@
 /// - `GET /mcp/tasks/:id/stream` - Stream task events (SSE)
 /// - `POST /mcp/tasks/:id/cancel` - Cancel a running task
 /// - `POST /mcp/tasks/:id/resume` - Resume event streaming
+/// - `POST /mcp/tasks/:id/pause` - Pause a running task
+/// - `POST /mcp/tasks/:id/unpause` - Continue a paused task
+/// - `POST /mcp/tasks/:id/input` - Send input to a running task
+/// - `POST /mcp/cancel_tasks` - Cancel all active tasks with the given tags
 ///
 /// # Authentication
 ///
@@ -17,6 +21,13 @@
 /// - JWT token: `Authorization: Bearer <token>`
 /// - API key: `X-Api-Key: <key>`
 ///
+/// # Task Ownership
+///
+/// Members can only see and control tasks they created; admins and owners can
+/// access all of the tenant's tasks, and viewers can read them. API keys can
+/// access all of the tenant's tasks their scopes allow. Tasks the caller may
+/// not access are reported as `404 Not Found`.
+///
 /// # Rate Limiting
 ///
 /// Endpoints are rate-limited based on tenant plan:
@@ -51,14 +62,69 @@
 /// ```
 
 pub mod cancel_task;
+pub mod cancel_tasks;
 pub mod get_status;
+pub mod pause_task;
 pub mod resume_task;
+pub mod send_input;
 pub mod start_task;
 pub mod stream_task;
 
 // Re-export handlers for convenience
 pub use cancel_task::{cancel_task, CancelTaskResponse};
+pub use cancel_tasks::{cancel_tasks, CancelTasksRequest, CancelTasksResponse};
 pub use get_status::{get_task_status, TaskStatusResponse};
+pub use pause_task::{pause_task, unpause_task, PauseTaskResponse};
 pub use resume_task::{resume_task, ResumeTaskRequest};
+pub use send_input::{send_input, SendInputRequest, SendInputResponse};
 pub use start_task::{start_task, StartTaskRequest, StartTaskResponse};
 pub use stream_task::{stream_task, StreamTaskQuery};
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use axontask_shared::auth::authorization::{require_task_access, AuthzError, ResourcePermission};
+use axontask_shared::auth::middleware::AuthContext;
+use axontask_shared::models::task::Task;
+use uuid::Uuid;
+
+/// Finds a task the caller may access
+///
+/// Applies tenant isolation and the task ownership rules. Tasks the caller
+/// may not access are reported as not found, so their existence is not leaked.
+///
+/// # Arguments
+///
+/// * `state` - Application state
+/// * `auth` - Authentication context
+/// * `task_id` - Task ID
+/// * `permission` - Access needed (`Read` to view, `Write` to control)
+pub(crate) async fn find_task(
+    state: &AppState,
+    auth: &AuthContext,
+    task_id: Uuid,
+    permission: ResourcePermission,
+) -> Result<Task, ApiError> {
+    let not_found = || {
+        tracing::warn!(
+            task_id = %task_id,
+            tenant_id = %auth.tenant_id,
+            user_id = ?auth.user_id,
+            "Task not found or access denied"
+        );
+        ApiError::NotFound("Task not found".to_string())
+    };
+
+    let task = Task::find_by_id_and_tenant(&state.db, task_id, auth.tenant_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to query task");
+            ApiError::InternalError("Failed to query task".to_string())
+        })?
+        .ok_or_else(not_found)?;
+
+    match require_task_access(&state.db, auth, &task, permission).await {
+        Ok(()) => Ok(task),
+        Err(AuthzError::NotAuthorized) => Err(not_found()),
+        Err(e) => Err(ApiError::from(e)),
+    }
+}
@@ -0,0 +1,225 @@
+/// Pause/unpause task MCP endpoints
+///
+/// These endpoints suspend and continue execution of a running task. They
+/// send a control message to the worker executing the task and update the
+/// task state.
+///
+/// Time spent paused does not count toward the task timeout or billable
+/// minutes.
+///
+/// # Endpoints
+///
+/// - `POST /mcp/tasks/:task_id/pause` - Pause a running task
+/// - `POST /mcp/tasks/:task_id/unpause` - Continue a paused task
+///
+/// (`/mcp/tasks/:task_id/resume` resumes event *streaming* and is unrelated.)
+///
+/// # Authentication
+///
+/// Requires either:
+/// - JWT token (Authorization: Bearer <token>)
+/// - API key (X-Api-Key: <key>)
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "task_id": "550e8400-e29b-41d4-a716-446655440000",
+///   "paused": true,
+///   "state": "paused",
+///   "message": "Task paused"
+/// }
+/// ```
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::routes::mcp::find_task;
+use axontask_shared::auth::authorization::ResourcePermission;
+use axontask_shared::auth::middleware::AuthContext;
+use axontask_shared::models::task::{Task, TaskState};
+use axontask_shared::redis::ControlMessage;
+use axum::{extract::{Path, State}, Extension, Json};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Pause/unpause task response
+#[derive(Debug, Clone, Serialize)]
+pub struct PauseTaskResponse {
+    /// Task ID
+    pub task_id: Uuid,
+
+    /// Whether the task is now paused
+    pub paused: bool,
+
+    /// Current task state
+    pub state: String,
+
+    /// Descriptive message
+    pub message: String,
+}
+
+/// Pause task endpoint handler
+///
+/// Pauses a running task by:
+/// 1. Validating task exists and belongs to tenant
+/// 2. Updating task state to "paused"
+/// 3. Sending a pause control message to the worker via Redis
+///
+/// If no worker receives the message, the state change is rolled back.
+///
+/// # Errors
+///
+/// - 401 Unauthorized: Missing or invalid authentication
+/// - 404 Not Found: Task does not exist or is not accessible (members only access their own tasks)
+/// - 409 Conflict: Task is not running
+/// - 503 Service Unavailable: Redis not configured or no worker attached
+/// - 500 Internal Server Error: Database or Redis error
+pub async fn pause_task(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<PauseTaskResponse>, ApiError> {
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        user_id = ?auth.user_id,
+        task_id = %task_id,
+        "Pausing task"
+    );
+
+    let task = find_task(&state, &auth, task_id, ResourcePermission::Write).await?;
+    if task.state != TaskState::Running {
+        return Err(ApiError::Conflict(format!(
+            "Only running tasks can be paused (state: {})",
+            task.state
+        )));
+    }
+
+    let publisher = state.control_publisher()?;
+
+    let updated = Task::transition_to_paused(&state.db, task_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to update task state");
+            ApiError::InternalError("Failed to pause task".to_string())
+        })?
+        .ok_or_else(|| ApiError::Conflict("Task is no longer running".to_string()))?;
+
+    // Roll back if the worker cannot be reached
+    let delivered = match publisher.publish(task_id, &ControlMessage::pause(None)).await {
+        Ok(receivers) => receivers > 0,
+        Err(e) => {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to publish pause message");
+            false
+        }
+    };
+
+    if !delivered {
+        Task::transition_to_resumed(&state.db, task_id).await.map_err(|e| {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to roll back pause");
+            ApiError::InternalError("Failed to pause task".to_string())
+        })?;
+
+        return Err(ApiError::ServiceUnavailable(
+            "No worker is attached to this task".to_string(),
+        ));
+    }
+
+    tracing::info!(task_id = %task_id, tenant_id = %auth.tenant_id, "Task paused");
+
+    Ok(Json(PauseTaskResponse {
+        task_id,
+        paused: true,
+        state: updated.state.as_str().to_string(),
+        message: "Task paused".to_string(),
+    }))
+}
+
+/// Unpause task endpoint handler
+///
+/// Continues a paused task by:
+/// 1. Validating task exists and belongs to tenant
+/// 2. Sending a resume control message to the worker via Redis
+/// 3. Updating task state to "running"
+///
+/// If no worker receives the message, the task stays paused.
+///
+/// # Errors
+///
+/// - 401 Unauthorized: Missing or invalid authentication
+/// - 404 Not Found: Task does not exist or is not accessible (members only access their own tasks)
+/// - 409 Conflict: Task is not paused
+/// - 503 Service Unavailable: Redis not configured or no worker attached
+/// - 500 Internal Server Error: Database or Redis error
+pub async fn unpause_task(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<PauseTaskResponse>, ApiError> {
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        user_id = ?auth.user_id,
+        task_id = %task_id,
+        "Unpausing task"
+    );
+
+    let task = find_task(&state, &auth, task_id, ResourcePermission::Write).await?;
+    if task.state != TaskState::Paused {
+        return Err(ApiError::Conflict(format!(
+            "Only paused tasks can be unpaused (state: {})",
+            task.state
+        )));
+    }
+
+    let publisher = state.control_publisher()?;
+
+    // Without a worker the task would be running with nothing executing it
+    let delivered = match publisher.publish(task_id, &ControlMessage::resume(None)).await {
+        Ok(receivers) => receivers > 0,
+        Err(e) => {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to publish resume message");
+            false
+        }
+    };
+
+    if !delivered {
+        return Err(ApiError::ServiceUnavailable(
+            "No worker is attached to this task".to_string(),
+        ));
+    }
+
+    let updated = Task::transition_to_resumed(&state.db, task_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to update task state");
+            ApiError::InternalError("Failed to unpause task".to_string())
+        })?
+        .ok_or_else(|| ApiError::Conflict("Task is no longer paused".to_string()))?;
+
+    tracing::info!(task_id = %task_id, tenant_id = %auth.tenant_id, "Task unpaused");
+
+    Ok(Json(PauseTaskResponse {
+        task_id,
+        paused: false,
+        state: updated.state.as_str().to_string(),
+        message: "Task resumed".to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_task_response_serialization() {
+        let response = PauseTaskResponse {
+            task_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+            paused: true,
+            state: "paused".to_string(),
+            message: "Task paused".to_string(),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"paused\":true"));
+        assert!(json.contains("\"state\":\"paused\""));
+    }
+}
@@ -75,7 +75,7 @@ pub struct ResumeTaskRequest {
 ///
 /// - 401 Unauthorized: Missing or invalid authentication
 /// - 403 Forbidden: Task belongs to different tenant
-/// - 404 Not Found: Task does not exist
+/// - 404 Not Found: Task does not exist or is not accessible (members only access their own tasks)
 /// - 422 Unprocessable Entity: Invalid request body
 /// - 500 Internal Server Error: Database or Redis error
 ///
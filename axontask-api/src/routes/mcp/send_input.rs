@@ -0,0 +1,185 @@
+/// Send input MCP endpoint
+///
+/// This endpoint delivers input to a running task, e.g. to answer a prompt
+/// from an interactive command. Only interactive shell tasks
+/// (`"interactive": true` in the task args) accept input; the input is sent
+/// to the worker via the control channel and written to the command's stdin.
+///
+/// The worker records each input it receives as an `input` event in the
+/// task's event stream. Its `forwarded` flag tells whether the input reached
+/// the command (it doesn't once the command closed its stdin).
+///
+/// # Endpoint
+///
+/// `POST /mcp/tasks/:task_id/input`
+///
+/// # Authentication
+///
+/// Requires either:
+/// - JWT token (Authorization: Bearer <token>)
+/// - API key (X-Api-Key: <key>)
+///
+/// # Example Request
+///
+/// ```json
+/// {
+///   "data": "yes\n"
+/// }
+/// ```
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "task_id": "550e8400-e29b-41d4-a716-446655440000",
+///   "delivered": true,
+///   "bytes": 4
+/// }
+/// ```
+
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::routes::mcp::find_task;
+use axontask_shared::auth::authorization::ResourcePermission;
+use axontask_shared::auth::middleware::AuthContext;
+use axontask_shared::models::task::{accepts_input, TaskState};
+use axontask_shared::redis::ControlMessage;
+use axum::{extract::{Path, State}, Extension, Json};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Send input request
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct SendInputRequest {
+    /// Input data (written as-is; include a trailing newline to answer a prompt)
+    #[validate(length(min = 1, max = 65536))]
+    pub data: String,
+}
+
+/// Send input response
+#[derive(Debug, Clone, Serialize)]
+pub struct SendInputResponse {
+    /// Task ID
+    pub task_id: Uuid,
+
+    /// Whether a worker received the input (see the `input` event for
+    /// whether it reached the command)
+    pub delivered: bool,
+
+    /// Input size in bytes
+    pub bytes: usize,
+}
+
+/// Send input endpoint handler
+///
+/// Delivers input to a running or paused task by:
+/// 1. Validating task exists and belongs to tenant
+/// 2. Checking the task is running or paused and accepts input
+/// 3. Sending an input control message to the worker via Redis
+///
+/// Input sent to a paused task is buffered until it resumes.
+///
+/// # Errors
+///
+/// - 401 Unauthorized: Missing or invalid authentication
+/// - 404 Not Found: Task does not exist or is not accessible (members only access their own tasks)
+/// - 409 Conflict: Task is not running or does not accept input
+/// - 422 Unprocessable Entity: Empty or oversized input
+/// - 503 Service Unavailable: Redis not configured or no worker attached
+/// - 500 Internal Server Error: Database or Redis error
+pub async fn send_input(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(task_id): Path<Uuid>,
+    Json(request): Json<SendInputRequest>,
+) -> Result<Json<SendInputResponse>, ApiError> {
+    // Validate request
+    request.validate().map_err(|e| {
+        let errors = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |err| {
+                    crate::error::ValidationErrorDetail {
+                        field: field.to_string(),
+                        message: err.message.as_ref()
+                            .map(|m| m.to_string())
+                            .unwrap_or_else(|| format!("Validation failed for {}", field)),
+                    }
+                })
+            })
+            .collect();
+        ApiError::ValidationError(errors)
+    })?;
+
+    let bytes = request.data.len();
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        user_id = ?auth.user_id,
+        task_id = %task_id,
+        bytes,
+        "Sending input to task"
+    );
+
+    // Find task with tenant isolation and ownership rules
+    let task = find_task(&state, &auth, task_id, ResourcePermission::Write).await?;
+
+    if !matches!(task.state, TaskState::Running | TaskState::Paused) {
+        return Err(ApiError::Conflict(format!(
+            "Input can only be sent to running tasks (state: {})",
+            task.state
+        )));
+    }
+
+    if !accepts_input(&task.adapter, &task.args) {
+        return Err(ApiError::Conflict(
+            "Task does not accept input (only interactive shell tasks do)".to_string(),
+        ));
+    }
+
+    let receivers = state
+        .control_publisher()?
+        .publish(task_id, &ControlMessage::input(request.data))
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to publish input message");
+            ApiError::InternalError("Failed to send input".to_string())
+        })?;
+
+    if receivers == 0 {
+        return Err(ApiError::ServiceUnavailable(
+            "No worker is attached to this task".to_string(),
+        ));
+    }
+
+    Ok(Json(SendInputResponse {
+        task_id,
+        delivered: true,
+        bytes,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_input_request_validation() {
+        let valid = SendInputRequest {
+            data: "yes\n".to_string(),
+        };
+        assert!(valid.validate().is_ok());
+
+        let empty = SendInputRequest {
+            data: String::new(),
+        };
+        assert!(empty.validate().is_err());
+
+        let oversized = SendInputRequest {
+            data: "a".repeat(65537),
+        };
+        assert!(oversized.validate().is_err());
+    }
+}
@@ -14,6 +14,30 @@
 /// - JWT token (Authorization: Bearer <token>)
 /// - API key (X-Api-Key: <key>)
 ///
+/// # Idempotency
+///
+/// Clients can send an `Idempotency-Key` header (or an `idempotency_key`
+/// field) to make retries safe. Keys are scoped per tenant and kept for
+/// 24 hours:
+/// - Same key, same body: the original response is returned, no new task
+/// - Same key, different body: 409 Conflict
+/// - Same key while the original request is still running: 409 Conflict
+///   (for at most 30 seconds; after that a retry takes the key over)
+///
+/// # Quota Warnings
+///
+/// When the tenant has reached a soft threshold of its daily task or monthly
+/// task minute quota (by default 80% and 100%), the response carries an
+/// `X-AxonTask-Quota-Warning` header, e.g.
+/// `daily_tasks; threshold=80; current=85; limit=100` (one comma-separated
+/// entry per quota). Each threshold also triggers a
+/// `quota.threshold_reached` webhook once per period.
+///
+/// Daily task thresholds are checked with the count already taken to enforce
+/// the quota. Task minutes don't change when a task is created; their
+/// thresholds are recorded by the worker and only reported here. Replayed
+/// responses carry no warnings.
+///
 /// # Example Request
 ///
 /// ```json
@@ -42,17 +66,40 @@
 use crate::app::AppState;
 use crate::error::ApiError;
 use axontask_shared::auth::middleware::AuthContext;
-use axontask_shared::models::task::{CreateTask, Task, TaskState};
-use axontask_shared::quota::{QuotaEnforcer, QuotaError, QuotaType};
-use axum::{extract::State, Extension, Json};
+use axontask_shared::models::idempotency_key::{
+    IdempotencyKey, DEFAULT_RETENTION_HOURS, MAX_KEY_LENGTH,
+};
+use axontask_shared::models::task::{normalize_tags, CreateTask, Task, TaskState};
+use axontask_shared::models::usage::UsageCounter;
+use axontask_shared::quota::{QuotaCheckResult, QuotaError, QuotaType, QuotaWarning};
+use axum::{
+    extract::State,
+    http::{HeaderMap, HeaderValue},
+    Extension, Json,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 use validator::Validate;
 
+/// Idempotency key header
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Soft quota threshold warning header
+pub const QUOTA_WARNING_HEADER: &str = "X-AxonTask-Quota-Warning";
+
+/// Outcome of `start`
+enum Started {
+    /// A task was created; carries the daily task usage counted before it
+    Created(StartTaskResponse, QuotaCheckResult),
+
+    /// The stored response of an idempotency key was replayed
+    Replayed(StartTaskResponse),
+}
+
 /// Start task request
-#[derive(Debug, Clone, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct StartTaskRequest {
     /// Task name (for display/logging)
     #[validate(length(min = 1, max = 255))]
@@ -69,13 +116,21 @@ pub struct StartTaskRequest {
     #[validate(range(min = 1, max = 86400))] // Max 24 hours
     pub timeout_s: Option<i32>,
 
-    /// Optional tags for categorization
+    /// Optional tags for categorization (e.g. "deploy", "pr:42")
+    ///
+    /// Tags can be used to filter task listings and in bulk operations.
     #[serde(default)]
     pub tags: Vec<String>,
+
+    /// Optional idempotency key (alternative to the `Idempotency-Key` header)
+    ///
+    /// Not part of the request fingerprint used to detect key reuse.
+    #[serde(default, skip_serializing)]
+    pub idempotency_key: Option<String>,
 }
 
 /// Start task response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartTaskResponse {
     /// Created task ID
     pub task_id: Uuid,
@@ -92,6 +147,10 @@ pub struct StartTaskResponse {
     /// Estimated timeout (if specified)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout_s: Option<i32>,
+
+    /// Normalized task tags
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Start task endpoint handler
@@ -116,8 +175,9 @@ pub struct StartTaskResponse {
 ///
 /// # Errors
 ///
-/// - 400 Bad Request: Invalid input
+/// - 400 Bad Request: Invalid input or idempotency key
 /// - 401 Unauthorized: Missing or invalid authentication
+/// - 409 Conflict: Idempotency key reused with a different body, or still in progress
 /// - 422 Unprocessable Entity: Validation errors
 /// - 500 Internal Server Error: Database error
 ///
@@ -125,6 +185,7 @@ pub struct StartTaskResponse {
 ///
 /// ```no_run
 /// use axum::extract::State;
+/// use axum::http::HeaderMap;
 /// use axum::Extension;
 /// use axum::Json;
 /// # use crate::routes::mcp::start_task::{start_task, StartTaskRequest};
@@ -143,9 +204,10 @@ pub struct StartTaskResponse {
 ///     args: json!({"app": "myapp"}),
 ///     timeout_s: Some(900),
 ///     tags: vec!["deployment".to_string()],
+///     idempotency_key: None,
 /// };
 ///
-/// let response = start_task(state, auth, Json(request)).await?;
+/// let (_headers, response) = start_task(state, auth, HeaderMap::new(), Json(request)).await?;
 /// println!("Task created: {}", response.task_id);
 /// # Ok(())
 /// # }
@@ -153,8 +215,27 @@ pub struct StartTaskResponse {
 pub async fn start_task(
     State(state): State<AppState>,
     Extension(auth): Extension<AuthContext>,
+    headers: HeaderMap,
     Json(request): Json<StartTaskRequest>,
-) -> Result<Json<StartTaskResponse>, ApiError> {
+) -> Result<(HeaderMap, Json<StartTaskResponse>), ApiError> {
+    let (response, warnings) = match start(&state, &auth, &headers, &request).await? {
+        Started::Created(response, daily_tasks) => {
+            let warnings = quota_warnings(&state, &auth, &daily_tasks).await;
+            (response, warnings)
+        }
+        Started::Replayed(response) => (response, Vec::new()),
+    };
+
+    Ok((warning_headers(&warnings), Json(response)))
+}
+
+/// Validates the request and creates (or replays) the task
+async fn start(
+    state: &AppState,
+    auth: &AuthContext,
+    headers: &HeaderMap,
+    request: &StartTaskRequest,
+) -> Result<Started, ApiError> {
     // Validate request
     request.validate().map_err(|e| {
         let errors = e
@@ -174,6 +255,206 @@ pub async fn start_task(
         ApiError::ValidationError(errors)
     })?;
 
+    let Some(key) = resolve_idempotency_key(headers, request)? else {
+        return create_task(state, auth, request, None).await;
+    };
+
+    // Reserve the key, or replay/reject if it was already used
+    let request_hash = IdempotencyKey::hash_request(&serde_json::to_value(request).map_err(|e| {
+        ApiError::InternalError(format!("Failed to serialize request: {}", e))
+    })?);
+
+    let reserved = IdempotencyKey::reserve(
+        &state.db,
+        auth.tenant_id,
+        &key,
+        &request_hash,
+        chrono::Duration::hours(DEFAULT_RETENTION_HOURS),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "Failed to reserve idempotency key");
+        ApiError::InternalError("Failed to check idempotency key".to_string())
+    })?;
+
+    let Some(reservation) = reserved else {
+        return replay(state, auth, &key, &request_hash).await.map(Started::Replayed);
+    };
+
+    let result = create_task(state, auth, request, Some(&reservation)).await;
+
+    if result.is_err() {
+        // Let the client retry with the same key
+        if let Err(e) = IdempotencyKey::release(&state.db, &reservation).await {
+            tracing::error!(error = %e, "Failed to release idempotency key");
+        }
+    }
+
+    result
+}
+
+/// Checks soft quota thresholds after a task was created
+///
+/// `daily_tasks` is the usage counted before the task was created, so no
+/// usage is counted again here.
+///
+/// Best effort: failures are logged and never fail the request.
+async fn quota_warnings(
+    state: &AppState,
+    auth: &AuthContext,
+    daily_tasks: &QuotaCheckResult,
+) -> Vec<QuotaWarning> {
+    let enforcer = state.quota_enforcer();
+
+    let results = [
+        (
+            QuotaType::DailyTasks,
+            enforcer
+                .record_thresholds(
+                    auth.tenant_id,
+                    QuotaType::DailyTasks,
+                    daily_tasks.current.saturating_add(1),
+                    daily_tasks.limit,
+                )
+                .await,
+        ),
+        (
+            QuotaType::TaskMinutes,
+            enforcer
+                .recorded_threshold(auth.tenant_id, QuotaType::TaskMinutes)
+                .await,
+        ),
+    ];
+
+    let mut warnings = Vec::new();
+    for (quota_type, result) in results {
+        match result {
+            Ok(Some(warning)) => warnings.push(warning),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(error = %e, tenant_id = %auth.tenant_id, quota = quota_type.key(), "Failed to check quota thresholds");
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Builds the `X-AxonTask-Quota-Warning` header (empty if no warnings)
+fn warning_headers(warnings: &[QuotaWarning]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if warnings.is_empty() {
+        return headers;
+    }
+
+    let value = warnings
+        .iter()
+        .map(QuotaWarning::header_value)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        headers.insert(QUOTA_WARNING_HEADER, value);
+    }
+
+    headers
+}
+
+/// Resolves the idempotency key from the header or request body
+///
+/// # Errors
+///
+/// Returns `BadRequest` if the key is empty, too long, not valid text, or
+/// the header and body specify different keys
+fn resolve_idempotency_key(
+    headers: &HeaderMap,
+    request: &StartTaskRequest,
+) -> Result<Option<String>, ApiError> {
+    let header = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|v| {
+            v.to_str().map(str::to_string).map_err(|_| {
+                ApiError::BadRequest("Idempotency-Key header must be valid text".to_string())
+            })
+        })
+        .transpose()?;
+
+    let key = match (header, request.idempotency_key.clone()) {
+        (Some(h), Some(b)) if h != b => {
+            return Err(ApiError::BadRequest(
+                "Idempotency-Key header and idempotency_key field differ".to_string(),
+            ));
+        }
+        (Some(key), _) | (None, Some(key)) => key,
+        (None, None) => return Ok(None),
+    };
+
+    let key = key.trim().to_string();
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "Idempotency key must be 1-{} characters",
+            MAX_KEY_LENGTH
+        )));
+    }
+
+    Ok(Some(key))
+}
+
+/// Returns the stored response for a previously used idempotency key
+///
+/// # Errors
+///
+/// Returns `Conflict` if the key was used with a different body or the
+/// original request has not completed yet
+async fn replay(
+    state: &AppState,
+    auth: &AuthContext,
+    key: &str,
+    request_hash: &[u8],
+) -> Result<StartTaskResponse, ApiError> {
+    let existing = IdempotencyKey::find_active(&state.db, auth.tenant_id, key)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to query idempotency key");
+            ApiError::InternalError("Failed to check idempotency key".to_string())
+        })?
+        // Expired or released between reserve and lookup
+        .ok_or_else(|| {
+            ApiError::Conflict("Idempotency key is being reused, please retry".to_string())
+        })?;
+
+    if !existing.matches(request_hash) {
+        return Err(ApiError::Conflict(
+            "Idempotency key was already used with a different request".to_string(),
+        ));
+    }
+
+    let Some(stored) = existing.response else {
+        return Err(ApiError::Conflict(
+            "A request with this idempotency key is still in progress".to_string(),
+        ));
+    };
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        task_id = ?existing.task_id,
+        "Replaying idempotent start_task response"
+    );
+
+    serde_json::from_value(stored).map_err(|e| {
+        ApiError::InternalError(format!("Failed to decode stored response: {}", e))
+    })
+}
+
+/// Checks quotas and creates the task
+///
+/// With an idempotency key reservation, the task is created and the key
+/// completed in one transaction.
+async fn create_task(
+    state: &AppState,
+    auth: &AuthContext,
+    request: &StartTaskRequest,
+    reservation: Option<&IdempotencyKey>,
+) -> Result<Started, ApiError> {
     tracing::info!(
         tenant_id = %auth.tenant_id,
         user_id = ?auth.user_id,
@@ -185,81 +466,54 @@ pub async fn start_task(
     // TODO: Validate adapter type against whitelist
     // TODO: Check plan-specific adapter access
 
+    let tags = normalize_tags(request.tags.clone()).map_err(|message| {
+        ApiError::ValidationError(vec![crate::error::ValidationErrorDetail {
+            field: "tags".to_string(),
+            message,
+        }])
+    })?;
+
     // Check quota limits before creating task
-    let enforcer = QuotaEnforcer::new(state.db.clone());
+    let enforcer = state.quota_enforcer();
 
     // Check concurrent tasks quota
     enforcer
         .enforce(auth.tenant_id, QuotaType::ConcurrentTasks)
-        .await
-        .map_err(|e| match e {
-            QuotaError::LimitExceeded {
-                quota_type,
-                limit,
-                current,
-            } => ApiError::Forbidden(format!(
-                "{} limit exceeded ({}/{}). Upgrade your plan for higher limits.",
-                quota_type.as_str(),
-                current,
-                limit
-            )),
-            QuotaError::TenantNotFound(_) => {
-                ApiError::Unauthorized("Tenant not found".to_string())
-            }
-            QuotaError::DatabaseError(err) => {
-                tracing::error!(error = %err, "Quota check failed");
-                ApiError::InternalError("Failed to check quotas".to_string())
-            }
-        })?;
+        .await?;
 
-    // Check daily tasks quota
-    enforcer
-        .enforce(auth.tenant_id, QuotaType::DailyTasks)
-        .await
-        .map_err(|e| match e {
-            QuotaError::LimitExceeded {
-                quota_type,
-                limit,
-                current,
-            } => ApiError::Forbidden(format!(
-                "{} limit exceeded ({}/{}). Upgrade your plan for higher limits.",
-                quota_type.as_str(),
-                current,
-                limit
-            )),
-            QuotaError::TenantNotFound(_) => {
-                ApiError::Unauthorized("Tenant not found".to_string())
-            }
-            QuotaError::DatabaseError(err) => {
-                tracing::error!(error = %err, "Quota check failed");
-                ApiError::InternalError("Failed to check quotas".to_string())
-            }
-        })?;
+    // Check daily tasks quota (the count is reused for soft thresholds)
+    let daily_tasks = enforcer
+        .check(auth.tenant_id, QuotaType::DailyTasks)
+        .await?;
+    if !daily_tasks.allowed {
+        return Err(QuotaError::LimitExceeded {
+            quota_type: QuotaType::DailyTasks,
+            limit: daily_tasks.limit,
+            current: daily_tasks.current,
+        }
+        .into());
+    }
 
     // Create task in database
     let create_task = CreateTask {
         tenant_id: auth.tenant_id,
         created_by: auth.user_id,
+        api_key_id: auth.api_key_id,
         name: request.name.clone(),
         adapter: request.adapter.clone(),
         args: request.args.clone(),
         timeout_seconds: request.timeout_s.unwrap_or(3600), // Default 1 hour
+        tags,
     };
 
-    let task = Task::create(&state.db, create_task).await.map_err(|e| {
+    let db_error = |e: sqlx::Error| {
         tracing::error!(error = %e, "Failed to create task in database");
         ApiError::InternalError("Failed to create task".to_string())
-    })?;
+    };
 
-    tracing::info!(
-        task_id = %task.id,
-        tenant_id = %auth.tenant_id,
-        state = ?task.state,
-        "Task created successfully"
-    );
+    let mut tx = state.db.begin().await.map_err(db_error)?;
 
-    // TODO: Enqueue task to worker queue (Redis list or pub/sub)
-    // For now, workers will poll the database for pending tasks
+    let task = Task::create(&mut *tx, create_task).await.map_err(db_error)?;
 
     // Build response
     let response = StartTaskResponse {
@@ -268,9 +522,48 @@ pub async fn start_task(
         status: task.state.as_str().to_string(),
         created_at: task.created_at,
         timeout_s: Some(task.timeout_seconds),
+        tags: task.tags.clone(),
     };
 
-    Ok(Json(response))
+    if let Some(reservation) = reservation {
+        let stored = serde_json::to_value(&response).map_err(|e| {
+            ApiError::InternalError(format!("Failed to serialize response: {}", e))
+        })?;
+
+        let completed = IdempotencyKey::complete(&mut *tx, reservation, task.id, stored)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, task_id = %task.id, "Failed to store idempotent response");
+                ApiError::InternalError("Failed to store idempotency key".to_string())
+            })?;
+
+        // Another request took over the key; dropping the transaction
+        // discards this task
+        if !completed {
+            return Err(ApiError::Conflict(
+                "Idempotency key is being reused, please retry".to_string(),
+            ));
+        }
+    }
+
+    tx.commit().await.map_err(db_error)?;
+
+    tracing::info!(
+        task_id = %task.id,
+        tenant_id = %auth.tenant_id,
+        state = ?task.state,
+        "Task created successfully"
+    );
+
+    // Usage accounting is best effort and never fails the request
+    if let Err(e) = UsageCounter::record_task_created(&state.db, &task).await {
+        tracing::warn!(error = %e, task_id = %task.id, "Failed to record task creation usage");
+    }
+
+    // TODO: Enqueue task to worker queue (Redis list or pub/sub)
+    // For now, workers will poll the database for pending tasks
+
+    Ok(Started::Created(response, daily_tasks))
 }
 
 #[cfg(test)]
@@ -287,6 +580,7 @@ mod tests {
             args: json!({"command": "echo hello"}),
             timeout_s: Some(300),
             tags: vec!["test".to_string()],
+            idempotency_key: None,
         };
         assert!(valid.validate().is_ok());
 
@@ -297,6 +591,7 @@ mod tests {
             args: json!({}),
             timeout_s: None,
             tags: vec![],
+            idempotency_key: None,
         };
         assert!(invalid_name.validate().is_err());
 
@@ -307,6 +602,7 @@ mod tests {
             args: json!({}),
             timeout_s: None,
             tags: vec![],
+            idempotency_key: None,
         };
         assert!(long_name.validate().is_err());
 
@@ -317,6 +613,7 @@ mod tests {
             args: json!({}),
             timeout_s: Some(0),
             tags: vec![],
+            idempotency_key: None,
         };
         assert!(invalid_timeout.validate().is_err());
 
@@ -327,10 +624,91 @@ mod tests {
             args: json!({}),
             timeout_s: Some(100000),
             tags: vec![],
+            idempotency_key: None,
         };
         assert!(invalid_timeout_large.validate().is_err());
     }
 
+    fn request_with_key(key: Option<&str>) -> StartTaskRequest {
+        StartTaskRequest {
+            name: "deploy".to_string(),
+            adapter: "shell".to_string(),
+            args: json!({"command": "make deploy"}),
+            timeout_s: None,
+            tags: vec![],
+            idempotency_key: key.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_resolve_idempotency_key() {
+        let mut headers = HeaderMap::new();
+
+        // No key
+        assert!(resolve_idempotency_key(&headers, &request_with_key(None))
+            .unwrap()
+            .is_none());
+
+        // Body field only
+        let key = resolve_idempotency_key(&headers, &request_with_key(Some("abc"))).unwrap();
+        assert_eq!(key.as_deref(), Some("abc"));
+
+        // Header only
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "deploy-42".parse().unwrap());
+        let key = resolve_idempotency_key(&headers, &request_with_key(None)).unwrap();
+        assert_eq!(key.as_deref(), Some("deploy-42"));
+
+        // Header and matching field
+        assert!(resolve_idempotency_key(&headers, &request_with_key(Some("deploy-42"))).is_ok());
+
+        // Header and different field
+        assert!(resolve_idempotency_key(&headers, &request_with_key(Some("other"))).is_err());
+    }
+
+    #[test]
+    fn test_resolve_idempotency_key_invalid() {
+        let headers = HeaderMap::new();
+
+        assert!(resolve_idempotency_key(&headers, &request_with_key(Some("  "))).is_err());
+
+        let long = "k".repeat(MAX_KEY_LENGTH + 1);
+        assert!(resolve_idempotency_key(&headers, &request_with_key(Some(&long))).is_err());
+    }
+
+    #[test]
+    fn test_idempotency_fingerprint_excludes_key() {
+        let a = serde_json::to_value(request_with_key(Some("a"))).unwrap();
+        let b = serde_json::to_value(request_with_key(Some("b"))).unwrap();
+
+        assert!(a.get("idempotency_key").is_none());
+        assert_eq!(IdempotencyKey::hash_request(&a), IdempotencyKey::hash_request(&b));
+    }
+
+    #[test]
+    fn test_warning_headers() {
+        assert!(warning_headers(&[]).is_empty());
+
+        let headers = warning_headers(&[
+            QuotaWarning {
+                quota_type: QuotaType::DailyTasks,
+                threshold: 80,
+                current: 85,
+                limit: 100,
+            },
+            QuotaWarning {
+                quota_type: QuotaType::TaskMinutes,
+                threshold: 100,
+                current: 1_000,
+                limit: 1_000,
+            },
+        ]);
+        assert_eq!(
+            headers[QUOTA_WARNING_HEADER],
+            "daily_tasks; threshold=80; current=85; limit=100, \
+             task_minutes; threshold=100; current=1000; limit=1000"
+        );
+    }
+
     #[test]
     fn test_start_task_response_serialization() {
         let response = StartTaskResponse {
@@ -339,6 +717,7 @@ mod tests {
             status: "pending".to_string(),
             created_at: Utc::now(),
             timeout_s: Some(900),
+            tags: vec!["deploy".to_string()],
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -48,8 +48,12 @@
 
 use crate::app::AppState;
 use crate::error::ApiError;
+use crate::routes::mcp::find_task;
+use axontask_shared::auth::authorization::ResourcePermission;
 use axontask_shared::auth::middleware::AuthContext;
-use axontask_shared::models::task::Task;
+use axontask_shared::models::usage::UsageCounter;
+use axontask_shared::quota::{QuotaEnforcer, QuotaError, QuotaType};
+use axontask_shared::redis::stream_connections::{Acquire, StreamConnectionTracker};
 use axontask_shared::redis::{RedisClient, RedisConfig, StreamReader};
 use axontask_shared::events::serialization::{deserialize_event, event_stream_key};
 use axum::{
@@ -59,6 +63,7 @@ use axum::{
 };
 use futures::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use std::convert::Infallible;
 use std::time::Duration;
 use tokio_stream::StreamExt as _;
@@ -112,10 +117,12 @@ pub struct HeartbeatData {
 /// # Flow
 ///
 /// 1. **Validate**: Check task exists and belongs to tenant
-/// 2. **Backfill**: Send historical events from Redis Streams (since_seq to latest)
-/// 3. **Live Tail**: Block and wait for new events (XREAD BLOCK)
-/// 4. **Heartbeat**: Send keep-alive every 25 seconds
-/// 5. **Error Handling**: Detect gaps, handle disconnections
+/// 2. **Quota**: Register the connection against the tenant's stream
+///    connection limit (released when the client disconnects)
+/// 3. **Backfill**: Send historical events from Redis Streams (since_seq to latest)
+/// 4. **Live Tail**: Block and wait for new events (XREAD BLOCK)
+/// 5. **Heartbeat**: Send keep-alive every 25 seconds
+/// 6. **Error Handling**: Detect gaps, handle disconnections
 ///
 /// # Authentication
 ///
@@ -128,9 +135,10 @@ pub struct HeartbeatData {
 /// # Errors
 ///
 /// - 401 Unauthorized: Missing or invalid authentication
-/// - 403 Forbidden: Task belongs to different tenant
-/// - 404 Not Found: Task does not exist
+/// - 403 Forbidden: Stream connection limit reached
+/// - 404 Not Found: Task does not exist or is not accessible (members only access their own tasks)
 /// - 500 Internal Server Error: Database or Redis error
+/// - 503 Service Unavailable: Redis not configured
 ///
 /// # Example
 ///
@@ -167,24 +175,60 @@ pub async fn stream_task(
         "Streaming task events"
     );
 
-    // Validate task exists and belongs to tenant
-    let _task = Task::find_by_id_and_tenant(&state.db, task_id, auth.tenant_id)
+    // Validate task exists and is accessible
+    let _task = find_task(&state, &auth, task_id, ResourcePermission::Read).await?;
+
+    // Hold a stream connection slot for as long as the client is connected
+    let limits = QuotaEnforcer::new(state.db.clone())
+        .get_limits(auth.tenant_id)
+        .await?;
+    let tracker = StreamConnectionTracker::new(state.redis()?.clone());
+    let connection = match tracker
+        .acquire(auth.tenant_id, limits.stream_connections)
         .await
-        .map_err(|e| {
-            tracing::error!(error = %e, task_id = %task_id, "Failed to query task");
-            ApiError::InternalError("Failed to query task".to_string())
-        })?
-        .ok_or_else(|| {
-            tracing::warn!(
-                task_id = %task_id,
+        .map_err(QuotaError::from)?
+    {
+        Acquire::Acquired(connection) => connection,
+        Acquire::LimitReached { open } => {
+            tracing::info!(
                 tenant_id = %auth.tenant_id,
-                "Task not found or access denied"
+                open,
+                limit = limits.stream_connections,
+                "Stream connection limit reached"
             );
-            ApiError::NotFound("Task not found".to_string())
-        })?;
+            return Err(QuotaError::LimitExceeded {
+                quota_type: QuotaType::StreamConnections,
+                limit: limits.stream_connections,
+                current: open,
+            }
+            .into());
+        }
+    };
+
+    // Usage accounting is best effort and never blocks the stream
+    if let Err(e) = UsageCounter::record_stream(&state.db, auth.tenant_id, connection.id).await {
+        tracing::warn!(error = %e, tenant_id = %auth.tenant_id, "Failed to record stream usage");
+    }
+
+    let mut meter = StreamMeter {
+        db: state.db.clone(),
+        tenant_id: auth.tenant_id,
+        task_id,
+        connection_id: connection.id,
+        bytes: 0,
+    };
 
-    // Create SSE stream
-    let stream = create_event_stream(task_id, query.since_seq);
+    // Create SSE stream; dropping it (client disconnect) releases the slot
+    // and records the bytes sent. It ends if the slot is lost.
+    let events = futures::StreamExt::take_until(
+        create_event_stream(task_id, query.since_seq),
+        connection.lost(),
+    );
+    let stream = events.map(move |frame| {
+        let _ = &connection;
+        meter.add(frame.bytes);
+        Ok(frame.event)
+    });
 
     Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(25))))
 }
@@ -195,50 +239,116 @@ pub async fn stream_task(
 /// 1. Sends backfill events from Redis
 /// 2. Switches to live tail mode
 /// 3. Sends periodic heartbeats
-fn create_event_stream(
-    task_id: Uuid,
-    since_seq: i64,
-) -> impl Stream<Item = Result<Event, Infallible>> {
+fn create_event_stream(task_id: Uuid, since_seq: i64) -> impl Stream<Item = SseFrame> {
     // TODO: In production, initialize Redis client from config
     // For now, return a mock stream that demonstrates the pattern
 
     stream::iter(vec![
         // Mock backfill events
-        Ok(Event::default()
-            .event("task_event")
-            .id("1234567890-0")
-            .json_data(TaskEventData {
+        SseFrame::json(
+            "task_event",
+            Some("1234567890-0"),
+            &TaskEventData {
                 seq: 0,
                 kind: "started".to_string(),
                 payload: serde_json::json!({"adapter": "shell"}),
                 ts: chrono::Utc::now().to_rfc3339(),
                 hash_prev: None,
                 hash_curr: Some("abcd1234".to_string()),
-            })
-            .unwrap()),
+            },
+        ),
 
         // Mock progress event
-        Ok(Event::default()
-            .event("task_event")
-            .id("1234567891-0")
-            .json_data(TaskEventData {
+        SseFrame::json(
+            "task_event",
+            Some("1234567891-0"),
+            &TaskEventData {
                 seq: 1,
                 kind: "progress".to_string(),
                 payload: serde_json::json!({"percent": 50}),
                 ts: chrono::Utc::now().to_rfc3339(),
                 hash_prev: Some("abcd1234".to_string()),
                 hash_curr: Some("efgh5678".to_string()),
-            })
-            .unwrap()),
+            },
+        ),
 
         // Heartbeat
-        Ok(Event::default()
-            .event("heartbeat")
-            .json_data(HeartbeatData { alive: true })
-            .unwrap()),
+        SseFrame::json("heartbeat", None, &HeartbeatData { alive: true }),
     ])
 }
 
+/// An SSE event and its size on the wire
+struct SseFrame {
+    /// Event to send
+    event: Event,
+
+    /// Encoded size (`event:`, `id:` and `data:` lines)
+    bytes: u64,
+}
+
+impl SseFrame {
+    /// Builds an event with a JSON data line
+    fn json(kind: &str, id: Option<&str>, data: &impl Serialize) -> Self {
+        let data = serde_json::to_string(data).expect("SSE data serializes to JSON");
+
+        // "event: {kind}\n" + "data: {data}\n" + "\n"
+        let mut bytes = 8 + kind.len() + 6 + data.len() + 2;
+        let mut event = Event::default().event(kind).data(data);
+        if let Some(id) = id {
+            // "id: {id}\n"
+            bytes += 4 + id.len() + 1;
+            event = event.id(id);
+        }
+
+        SseFrame {
+            event,
+            bytes: bytes as u64,
+        }
+    }
+}
+
+/// Counts the bytes sent on one connection
+///
+/// The total is recorded as usage when the stream is dropped (client
+/// disconnect or end of stream), keyed by connection so it is counted once.
+struct StreamMeter {
+    db: PgPool,
+    tenant_id: Uuid,
+    task_id: Uuid,
+    connection_id: Uuid,
+    bytes: u64,
+}
+
+impl StreamMeter {
+    /// Counts a sent frame
+    fn add(&mut self, bytes: u64) {
+        self.bytes = self.bytes.saturating_add(bytes);
+    }
+}
+
+impl Drop for StreamMeter {
+    fn drop(&mut self) {
+        if self.bytes == 0 {
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let db = self.db.clone();
+        let (tenant_id, task_id, connection_id) = (self.tenant_id, self.task_id, self.connection_id);
+        let bytes = self.bytes.min(i64::MAX as u64) as i64;
+        runtime.spawn(async move {
+            if let Err(e) =
+                UsageCounter::record_stream_bytes(&db, tenant_id, task_id, connection_id, bytes).await
+            {
+                tracing::warn!(error = %e, task_id = %task_id, bytes, "Failed to record streamed bytes");
+            }
+        });
+    }
+}
+
 /// Real implementation (commented out until Redis is integrated)
 ///
 /// ```rust,ignore
@@ -351,6 +461,16 @@ mod tests {
         assert!(json.contains("Hello"));
     }
 
+    #[test]
+    fn test_sse_frame_size() {
+        let frame = SseFrame::json("heartbeat", None, &HeartbeatData { alive: true });
+        // "event: heartbeat\ndata: {\"alive\":true}\n\n"
+        assert_eq!(frame.bytes, 17 + 21 + 1);
+
+        let with_id = SseFrame::json("heartbeat", Some("1-0"), &HeartbeatData { alive: true });
+        assert_eq!(with_id.bytes, frame.bytes + 8);
+    }
+
     #[test]
     fn test_heartbeat_data_serialization() {
         let data = HeartbeatData { alive: true };
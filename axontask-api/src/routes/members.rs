@@ -0,0 +1,704 @@
+/// Tenant member and invitation endpoints
+///
+/// This module lets admins manage who belongs to a tenant and lets invited
+/// users join. All endpoints require JWT authentication.
+///
+/// # Endpoints
+///
+/// - `GET /v1/members` - List members (viewer+)
+/// - `PATCH /v1/members/:user_id` - Change a member's role (admin+)
+/// - `DELETE /v1/members/:user_id` - Remove a member (admin+)
+/// - `POST /v1/members/invitations` - Invite an email address (admin+)
+/// - `GET /v1/members/invitations` - List pending invitations (admin+)
+/// - `DELETE /v1/members/invitations/:id` - Revoke an invitation (admin+)
+/// - `GET /v1/members/two-factor` - Show whether two-factor authentication is
+///   required (viewer+)
+/// - `PUT /v1/members/two-factor` - Require two-factor authentication or stop
+///   requiring it (admin+)
+/// - `POST /v1/invitations/accept` - Accept an invitation (any logged-in user)
+///
+/// # Owners
+///
+/// Only owners can grant the owner role or change or remove an owner, and a
+/// tenant always keeps at least one owner (`409 Conflict` otherwise).
+///
+/// # Invitations
+///
+/// An invitation is emailed to the invitee with a link to the dashboard
+/// (`APP_URL`) carrying a single-use token. The invitee accepts it while
+/// logged in with the invited email address, then switches to the tenant with
+/// `POST /v1/auth/switch-tenant`.
+///
+/// # Two-Factor Requirement
+///
+/// A tenant can require two-factor authentication of all members: members
+/// without it can't log in to, refresh sessions in or switch to the tenant
+/// until they enable it, and members can't disable it. Only an admin who uses
+/// two-factor authentication can turn the requirement on, so they can't lock
+/// themselves out. The member list shows who has it enabled.
+
+use crate::{
+    app::AppState,
+    error::{ApiError, ApiResult, ValidationErrorDetail},
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use axontask_shared::{
+    auth::{members, middleware::AuthContext, two_factor},
+    email::EmailMessage,
+    models::{
+        audit_log::{AuditAction, AuditLog, CreateAuditEntry},
+        invitation::Invitation,
+        membership::{Membership, MembershipRole, TenantMember},
+        tenant::Tenant,
+        user::User,
+    },
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// List members response
+#[derive(Debug, Serialize)]
+pub struct ListMembersResponse {
+    /// Members, oldest first
+    pub members: Vec<TenantMember>,
+}
+
+/// Change role request
+#[derive(Debug, Deserialize)]
+pub struct UpdateMemberRequest {
+    /// New role
+    pub role: MembershipRole,
+}
+
+/// Change role response
+#[derive(Debug, Serialize)]
+pub struct UpdateMemberResponse {
+    /// User ID
+    pub user_id: Uuid,
+
+    /// New role
+    pub role: MembershipRole,
+}
+
+/// Create invitation request
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateInvitationRequest {
+    /// Email address to invite
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+
+    /// Role granted on acceptance (default: member)
+    #[serde(default = "default_role")]
+    pub role: MembershipRole,
+}
+
+fn default_role() -> MembershipRole {
+    MembershipRole::Member
+}
+
+/// Pending invitation
+#[derive(Debug, Serialize)]
+pub struct InvitationResponse {
+    /// Invitation ID
+    pub id: Uuid,
+
+    /// Invited email address
+    pub email: String,
+
+    /// Role granted on acceptance
+    pub role: MembershipRole,
+
+    /// User who sent the invitation
+    pub invited_by: Option<Uuid>,
+
+    /// When the invitation was sent
+    pub created_at: DateTime<Utc>,
+
+    /// When the invitation expires
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<Invitation> for InvitationResponse {
+    fn from(invitation: Invitation) -> Self {
+        InvitationResponse {
+            id: invitation.id,
+            email: invitation.email,
+            role: invitation.role,
+            invited_by: invitation.invited_by,
+            created_at: invitation.created_at,
+            expires_at: invitation.expires_at,
+        }
+    }
+}
+
+/// Create invitation response
+#[derive(Debug, Serialize)]
+pub struct CreateInvitationResponse {
+    /// The invitation
+    #[serde(flatten)]
+    pub invitation: InvitationResponse,
+
+    /// Whether the invitation email was sent
+    ///
+    /// If false, inviting the address again sends a new invitation.
+    pub email_sent: bool,
+}
+
+/// List invitations response
+#[derive(Debug, Serialize)]
+pub struct ListInvitationsResponse {
+    /// Pending invitations, newest first
+    pub invitations: Vec<InvitationResponse>,
+}
+
+/// Accept invitation request
+#[derive(Debug, Deserialize)]
+pub struct AcceptInvitationRequest {
+    /// Token from the invitation email
+    pub token: String,
+}
+
+/// Accept invitation response
+#[derive(Debug, Serialize)]
+pub struct AcceptInvitationResponse {
+    /// Tenant joined
+    pub tenant_id: Uuid,
+
+    /// Tenant name
+    pub tenant_name: String,
+
+    /// Role in the tenant
+    pub role: MembershipRole,
+}
+
+/// Two-factor requirement of a tenant
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TwoFactorPolicy {
+    /// Whether members must use two-factor authentication
+    pub required: bool,
+}
+
+/// List members endpoint
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/members
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "members": [
+///     {
+///       "user_id": "uuid",
+///       "email": "owner@example.com",
+///       "name": "Jane Doe",
+///       "role": "owner",
+///       "joined_at": "2025-01-03T12:00:00Z",
+///       "two_factor_enabled": true
+///     }
+///   ]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not a member of the tenant
+/// - `500 Internal Server Error`: Server error
+pub async fn list_members(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> ApiResult<Json<ListMembersResponse>> {
+    let members = Membership::list_members(&state.db, auth.tenant_id).await?;
+
+    Ok(Json(ListMembersResponse { members }))
+}
+
+/// Change role endpoint
+///
+/// # Endpoint
+///
+/// ```text
+/// PATCH /v1/members/:user_id
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "role": "admin" }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// { "user_id": "uuid", "role": "admin" }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not an admin, or the change involves the owner role
+///   and the caller is not an owner
+/// - `404 Not Found`: The user is not a member of the tenant
+/// - `409 Conflict`: The member is the tenant's last owner
+/// - `500 Internal Server Error`: Server error
+pub async fn update_member(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(user_id): Path<Uuid>,
+    Json(req): Json<UpdateMemberRequest>,
+) -> ApiResult<Json<UpdateMemberResponse>> {
+    let (actor_id, actor_role) = actor(&state, &auth).await?;
+
+    let membership =
+        members::change_role(&state.db, actor_role, auth.tenant_id, user_id, req.role).await?;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        actor_id = %actor_id,
+        user_id = %user_id,
+        role = membership.role.as_str(),
+        "Member role changed"
+    );
+
+    Ok(Json(UpdateMemberResponse {
+        user_id: membership.user_id,
+        role: membership.role,
+    }))
+}
+
+/// Remove member endpoint
+///
+/// Also ends the member's sessions in the tenant.
+///
+/// # Endpoint
+///
+/// ```text
+/// DELETE /v1/members/:user_id
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not an admin, or the member is an owner and the caller
+///   is not
+/// - `404 Not Found`: The user is not a member of the tenant
+/// - `409 Conflict`: The member is the tenant's last owner
+/// - `500 Internal Server Error`: Server error
+pub async fn remove_member(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(user_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let (actor_id, actor_role) = actor(&state, &auth).await?;
+
+    members::remove_member(&state.db, actor_role, auth.tenant_id, user_id).await?;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        actor_id = %actor_id,
+        user_id = %user_id,
+        "Member removed"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create invitation endpoint
+///
+/// Emails a single-use invitation link to the address. Inviting an address
+/// again replaces its pending invitation.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/members/invitations
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "email": "new.hire@example.com", "role": "member" }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "id": "uuid",
+///   "email": "new.hire@example.com",
+///   "role": "member",
+///   "invited_by": "uuid",
+///   "created_at": "2025-01-03T12:00:00Z",
+///   "expires_at": "2025-01-10T12:00:00Z",
+///   "email_sent": true
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not an admin, or inviting an owner as a non-owner
+/// - `409 Conflict`: The address already belongs to a member
+/// - `422 Unprocessable Entity`: Invalid email
+/// - `500 Internal Server Error`: Server error
+pub async fn create_invitation(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(req): Json<CreateInvitationRequest>,
+) -> ApiResult<Json<CreateInvitationResponse>> {
+    req.validate().map_err(|e| {
+        let errors: Vec<ValidationErrorDetail> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |error| ValidationErrorDetail {
+                    field: field.to_string(),
+                    message: error
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "Validation failed".to_string()),
+                })
+            })
+            .collect();
+        ApiError::ValidationError(errors)
+    })?;
+
+    let (actor_id, actor_role) = actor(&state, &auth).await?;
+
+    let (invitation, token) = members::create_invitation(
+        &state.db,
+        actor_role,
+        auth.tenant_id,
+        actor_id,
+        &req.email,
+        req.role,
+        chrono::Duration::hours(state.config.auth.invitation_ttl_hours as i64),
+    )
+    .await?;
+
+    let tenant = Tenant::find_by_id(&state.db, auth.tenant_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Tenant not found".to_string()))?;
+
+    let message = EmailMessage::invitation(
+        &invitation.email,
+        &tenant.name,
+        invitation.role.as_str(),
+        &accept_url(&state.config.api.app_url, &token),
+        invitation.expires_at,
+    );
+    let email_sent = match state.mailer.send(&message).await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::error!(
+                tenant_id = %auth.tenant_id,
+                invitation_id = %invitation.id,
+                error = %e,
+                "Failed to send invitation email"
+            );
+            false
+        }
+    };
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        actor_id = %actor_id,
+        invitation_id = %invitation.id,
+        role = invitation.role.as_str(),
+        "Invitation created"
+    );
+
+    Ok(Json(CreateInvitationResponse {
+        invitation: invitation.into(),
+        email_sent,
+    }))
+}
+
+/// List invitations endpoint
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/members/invitations
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "invitations": [
+///     {
+///       "id": "uuid",
+///       "email": "new.hire@example.com",
+///       "role": "member",
+///       "invited_by": "uuid",
+///       "created_at": "2025-01-03T12:00:00Z",
+///       "expires_at": "2025-01-10T12:00:00Z"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not an admin
+/// - `500 Internal Server Error`: Server error
+pub async fn list_invitations(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> ApiResult<Json<ListInvitationsResponse>> {
+    let invitations = Invitation::list_pending(&state.db, auth.tenant_id)
+        .await?
+        .into_iter()
+        .map(InvitationResponse::from)
+        .collect();
+
+    Ok(Json(ListInvitationsResponse { invitations }))
+}
+
+/// Revoke invitation endpoint
+///
+/// # Endpoint
+///
+/// ```text
+/// DELETE /v1/members/invitations/:id
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not an admin
+/// - `404 Not Found`: No pending invitation with this ID
+/// - `500 Internal Server Error`: Server error
+pub async fn revoke_invitation(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    if !Invitation::revoke(&state.db, auth.tenant_id, id).await? {
+        return Err(ApiError::NotFound("Invitation not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Accept invitation endpoint
+///
+/// Adds the logged-in user to the inviting tenant. The user's email must
+/// match the invited address. The current tokens stay in their tenant; use
+/// `POST /v1/auth/switch-tenant` to move to the new one.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/invitations/accept
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "token": "inv_..." }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "tenant_id": "uuid",
+///   "tenant_name": "Acme Corp",
+///   "role": "member"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Unknown, expired or already accepted invitation
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: The invitation was sent to another email address
+/// - `409 Conflict`: Already a member of the tenant
+/// - `500 Internal Server Error`: Server error
+pub async fn accept_invitation(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(req): Json<AcceptInvitationRequest>,
+) -> ApiResult<Json<AcceptInvitationResponse>> {
+    let user_id = auth
+        .user_id
+        .ok_or_else(|| ApiError::Unauthorized("Invitations require a user token".to_string()))?;
+
+    let user = User::find_by_id(&state.db, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))?;
+
+    let membership = members::accept_invitation(&state.db, &req.token, user.id, &user.email).await?;
+
+    let tenant = Tenant::find_by_id(&state.db, membership.tenant_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Tenant not found".to_string()))?;
+
+    tracing::info!(
+        tenant_id = %membership.tenant_id,
+        user_id = %user.id,
+        role = membership.role.as_str(),
+        "Invitation accepted"
+    );
+
+    Ok(Json(AcceptInvitationResponse {
+        tenant_id: tenant.id,
+        tenant_name: tenant.name,
+        role: membership.role,
+    }))
+}
+
+/// Get two-factor requirement endpoint
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/members/two-factor
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// { "required": false }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not a member of the tenant
+/// - `404 Not Found`: Tenant not found
+/// - `500 Internal Server Error`: Server error
+pub async fn get_two_factor_policy(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> ApiResult<Json<TwoFactorPolicy>> {
+    let tenant = Tenant::find_by_id(&state.db, auth.tenant_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Tenant not found".to_string()))?;
+
+    Ok(Json(TwoFactorPolicy {
+        required: tenant.requires_two_factor(),
+    }))
+}
+
+/// Set two-factor requirement endpoint
+///
+/// Members already logged in without two-factor authentication keep their
+/// access tokens until they expire, but can't refresh them.
+///
+/// # Endpoint
+///
+/// ```text
+/// PUT /v1/members/two-factor
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "required": true }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// { "required": true }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Not an admin
+/// - `409 Conflict`: Turning the requirement on without using two-factor
+///   authentication
+/// - `500 Internal Server Error`: Server error
+pub async fn set_two_factor_policy(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(req): Json<TwoFactorPolicy>,
+) -> ApiResult<Json<TwoFactorPolicy>> {
+    let (actor_id, _) = actor(&state, &auth).await?;
+
+    two_factor::set_tenant_requirement(&state.db, auth.tenant_id, actor_id, req.required).await?;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        actor_id = %actor_id,
+        required = req.required,
+        "Two-factor requirement changed"
+    );
+
+    let entry = CreateAuditEntry {
+        tenant_id: Some(auth.tenant_id),
+        user_id: Some(actor_id),
+        action: AuditAction::TwoFactorRequirementChanged,
+        ip_address: None,
+        details: serde_json::json!({ "required": req.required }),
+    };
+    if let Err(e) = AuditLog::record(&state.db, entry).await {
+        tracing::error!(error = %e, tenant_id = %auth.tenant_id, "Failed to audit two-factor requirement change");
+    }
+
+    Ok(Json(req))
+}
+
+/// Gets the calling user and their role in the tenant
+async fn actor(state: &AppState, auth: &AuthContext) -> ApiResult<(Uuid, MembershipRole)> {
+    let user_id = auth
+        .user_id
+        .ok_or_else(|| ApiError::Forbidden("Member management requires a user token".to_string()))?;
+
+    let role = Membership::get_role(&state.db, auth.tenant_id, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Forbidden("Not a member of this tenant".to_string()))?;
+
+    Ok((user_id, role))
+}
+
+/// Builds the dashboard link that accepts an invitation
+fn accept_url(app_url: &str, token: &str) -> String {
+    format!("{}/invitations/accept?token={}", app_url, token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_url() {
+        assert_eq!(
+            accept_url("https://app.example.com", "inv_abc"),
+            "https://app.example.com/invitations/accept?token=inv_abc"
+        );
+    }
+
+    #[test]
+    fn test_create_invitation_default_role() {
+        let req: CreateInvitationRequest =
+            serde_json::from_str(r#"{"email": "new.hire@example.com"}"#).unwrap();
+        assert_eq!(req.role, MembershipRole::Member);
+        assert!(req.validate().is_ok());
+
+        let req: CreateInvitationRequest =
+            serde_json::from_str(r#"{"email": "not-an-email", "role": "viewer"}"#).unwrap();
+        assert!(req.validate().is_err());
+    }
+}
@@ -4,10 +4,22 @@
 ///
 /// - `health`: Health check endpoint
 /// - `auth`: Authentication endpoints (register, login, refresh)
+/// - `two_factor`: TOTP two-factor enrollment and recovery codes
 /// - `api_keys`: API key management endpoints
+/// - `members`: Tenant members and invitations
+/// - `tasks`: Task listing
+/// - `usage`: Usage reporting (per day, per adapter, quota consumption)
+/// - `webhooks`: Webhook management endpoints
 /// - `mcp`: MCP tool endpoints (start, stream, status, cancel, resume)
+/// - `admin`: Operator endpoints (per-tenant quota overrides)
 
 pub mod health;
 pub mod auth;
+pub mod two_factor;
 pub mod api_keys;
+pub mod members;
+pub mod tasks;
+pub mod usage;
+pub mod webhooks;
 pub mod mcp;
+pub mod admin;
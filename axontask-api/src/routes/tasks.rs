@@ -0,0 +1,396 @@
+/// Task listing endpoint
+///
+/// This module provides the tenant-wide task listing with filters and
+/// keyset (cursor) pagination.
+///
+/// # Endpoints
+///
+/// - `GET /v1/tasks` - List tasks
+///
+/// # Pagination
+///
+/// Results are ordered by `(created_at, id)`. Each page returns an opaque
+/// `next_cursor`; pass it back as `cursor` to fetch the next page. Unlike
+/// offsets, cursors do not skip or repeat tasks when new tasks are created
+/// between requests.
+///
+/// # Task Ownership
+///
+/// Members only see tasks they created; admins, owners, viewers and API keys
+/// see all of the tenant's tasks.
+
+use crate::{
+    app::AppState,
+    error::{ApiError, ApiResult, ValidationErrorDetail},
+};
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use axontask_shared::{
+    auth::authorization::{member_role, own_tasks_only, ResourcePermission},
+    auth::middleware::AuthContext,
+    models::task::{normalize_tags, SortOrder, Task, TaskCursor, TaskFilter, TaskState},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Default page size
+const DEFAULT_LIMIT: i64 = 20;
+
+/// Maximum page size
+const MAX_LIMIT: i64 = 100;
+
+/// List tasks query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct ListTasksQuery {
+    /// Comma-separated states (e.g. "running,paused")
+    pub state: Option<String>,
+
+    /// Adapter name
+    pub adapter: Option<String>,
+
+    /// Comma-separated tags; tasks must have all of them
+    pub tag: Option<String>,
+
+    /// Creator user ID, or "me" for the authenticated user
+    pub created_by: Option<String>,
+
+    /// ID of the API key that created the task
+    pub api_key_id: Option<Uuid>,
+
+    /// Only tasks created at or after this time
+    pub created_after: Option<DateTime<Utc>>,
+
+    /// Only tasks created before this time
+    pub created_before: Option<DateTime<Utc>>,
+
+    /// Only tasks that ended at or after this time
+    pub ended_after: Option<DateTime<Utc>>,
+
+    /// Only tasks that ended before this time
+    pub ended_before: Option<DateTime<Utc>>,
+
+    /// Sort order by creation time: "desc" (default) or "asc"
+    #[serde(default)]
+    pub sort_order: SortOrder,
+
+    /// Page size (default 20, max 100)
+    pub limit: Option<i64>,
+
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+/// Task list item
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskListItem {
+    /// Task ID
+    pub id: Uuid,
+
+    /// Task name
+    pub name: String,
+
+    /// Adapter type
+    pub adapter: String,
+
+    /// Current state
+    pub state: String,
+
+    /// Task tags
+    pub tags: Vec<String>,
+
+    /// User who created the task (None for API key auth)
+    pub created_by: Option<Uuid>,
+
+    /// API key that created the task (None for JWT auth)
+    pub api_key_id: Option<Uuid>,
+
+    /// When the task started executing
+    pub started_at: Option<DateTime<Utc>>,
+
+    /// When the task ended
+    pub ended_at: Option<DateTime<Utc>>,
+
+    /// Billable minutes used
+    pub minutes_used: i32,
+
+    /// When the task was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Task> for TaskListItem {
+    fn from(task: Task) -> Self {
+        TaskListItem {
+            id: task.id,
+            name: task.name,
+            adapter: task.adapter,
+            state: task.state.as_str().to_string(),
+            tags: task.tags,
+            created_by: task.created_by,
+            api_key_id: task.api_key_id,
+            started_at: task.started_at,
+            ended_at: task.ended_at,
+            minutes_used: task.minutes_used,
+            created_at: task.created_at,
+        }
+    }
+}
+
+/// Cursor pagination info
+#[derive(Debug, Clone, Serialize)]
+pub struct CursorPagination {
+    /// Page size used
+    pub limit: i64,
+
+    /// Whether more tasks follow this page
+    pub has_more: bool,
+
+    /// Cursor for the next page (None on the last page)
+    pub next_cursor: Option<String>,
+}
+
+/// List tasks response
+#[derive(Debug, Clone, Serialize)]
+pub struct ListTasksResponse {
+    /// Tasks on this page
+    pub data: Vec<TaskListItem>,
+
+    /// Pagination info
+    pub pagination: CursorPagination,
+}
+
+/// List tasks
+///
+/// Lists tasks of the authenticated tenant, newest first by default.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/tasks?state=running,paused&tag=session:abc123&created_by=me&limit=20
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "data": [
+///     {
+///       "id": "770e8400-e29b-41d4-a716-446655440000",
+///       "name": "deploy-app",
+///       "adapter": "fly",
+///       "state": "running",
+///       "tags": ["session:abc123"],
+///       "created_by": "550e8400-e29b-41d4-a716-446655440000",
+///       "api_key_id": null,
+///       "started_at": "2025-01-03T10:00:00Z",
+///       "ended_at": null,
+///       "minutes_used": 0,
+///       "created_at": "2025-01-03T09:59:50Z"
+///     }
+///   ],
+///   "pagination": {
+///     "limit": 20,
+///     "has_more": false,
+///     "next_cursor": null
+///   }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: A member filtered by another user's tasks
+/// - `422 Unprocessable Entity`: Invalid filter, limit or cursor
+/// - `500 Internal Server Error`: Server error
+pub async fn list_tasks(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<ListTasksQuery>,
+) -> ApiResult<Json<ListTasksResponse>> {
+    let role = member_role(&state.db, &auth).await?;
+    let filter = build_filter(&query, &auth, own_tasks_only(&auth, role, ResourcePermission::Read))?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(invalid("limit", format!("Limit must be between 1 and {}", MAX_LIMIT)));
+    }
+
+    let after = match &query.cursor {
+        Some(cursor) => Some(
+            TaskCursor::decode(cursor).ok_or_else(|| invalid("cursor", "Invalid cursor".to_string()))?,
+        ),
+        None => None,
+    };
+
+    // Fetch one extra task to know whether another page follows
+    let mut tasks = Task::list(&state.db, auth.tenant_id, &filter, query.sort_order, after, limit + 1)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, tenant_id = %auth.tenant_id, "Failed to list tasks");
+            ApiError::InternalError("Failed to list tasks".to_string())
+        })?;
+
+    let has_more = tasks.len() as i64 > limit;
+    tasks.truncate(limit as usize);
+
+    let next_cursor = if has_more {
+        tasks.last().map(|task| TaskCursor::from_task(task).encode())
+    } else {
+        None
+    };
+
+    Ok(Json(ListTasksResponse {
+        data: tasks.into_iter().map(TaskListItem::from).collect(),
+        pagination: CursorPagination {
+            limit,
+            has_more,
+            next_cursor,
+        },
+    }))
+}
+
+/// Converts query parameters into a task filter
+///
+/// `own_tasks_only` limits the listing to tasks created by that user.
+fn build_filter(
+    query: &ListTasksQuery,
+    auth: &AuthContext,
+    own_tasks_only: Option<Uuid>,
+) -> ApiResult<TaskFilter> {
+    let states = split_list(query.state.as_deref())
+        .into_iter()
+        .map(|s| s.parse::<TaskState>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|message| invalid("state", message))?;
+
+    let tags = normalize_tags(split_list(query.tag.as_deref()))
+        .map_err(|message| invalid("tag", message))?;
+
+    let created_by = match query.created_by.as_deref() {
+        None => None,
+        Some("me") => Some(auth.user_id.ok_or_else(|| {
+            invalid("created_by", "\"me\" requires a user-bound credential".to_string())
+        })?),
+        Some(id) => Some(
+            Uuid::parse_str(id)
+                .map_err(|_| invalid("created_by", "Must be a user ID or \"me\"".to_string()))?,
+        ),
+    };
+
+    let created_by = match (own_tasks_only, created_by) {
+        (Some(user_id), Some(requested)) if requested != user_id => {
+            return Err(ApiError::Forbidden("Members can only list their own tasks".to_string()));
+        }
+        (Some(user_id), _) => Some(user_id),
+        (None, requested) => requested,
+    };
+
+    Ok(TaskFilter {
+        states,
+        adapter: query.adapter.clone(),
+        tags,
+        created_by,
+        api_key_id: query.api_key_id,
+        created_after: query.created_after,
+        created_before: query.created_before,
+        ended_after: query.ended_after,
+        ended_before: query.ended_before,
+    })
+}
+
+/// Splits a comma-separated query value, ignoring empty entries
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds a validation error for a query parameter
+fn invalid(field: &str, message: String) -> ApiError {
+    ApiError::ValidationError(vec![ValidationErrorDetail {
+        field: field.to_string(),
+        message,
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(user_id: Option<Uuid>) -> AuthContext {
+        let mut auth = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+        auth.user_id = user_id;
+        auth
+    }
+
+    #[test]
+    fn test_split_list() {
+        assert_eq!(split_list(Some("running, paused,,")), vec!["running", "paused"]);
+        assert!(split_list(None).is_empty());
+    }
+
+    #[test]
+    fn test_build_filter() {
+        let user_id = Uuid::new_v4();
+        let query = ListTasksQuery {
+            state: Some("running,paused".to_string()),
+            tag: Some("session:abc123".to_string()),
+            created_by: Some("me".to_string()),
+            ..Default::default()
+        };
+
+        let filter = build_filter(&query, &auth(Some(user_id)), None).unwrap();
+        assert_eq!(filter.states, vec![TaskState::Running, TaskState::Paused]);
+        assert_eq!(filter.tags, vec!["session:abc123"]);
+        assert_eq!(filter.created_by, Some(user_id));
+    }
+
+    #[test]
+    fn test_build_filter_invalid() {
+        let bad_state = ListTasksQuery {
+            state: Some("done".to_string()),
+            ..Default::default()
+        };
+        assert!(build_filter(&bad_state, &auth(None), None).is_err());
+
+        // "me" needs a user; API keys are not bound to one
+        let me = ListTasksQuery {
+            created_by: Some("me".to_string()),
+            ..Default::default()
+        };
+        assert!(build_filter(&me, &auth(None), None).is_err());
+    }
+
+    #[test]
+    fn test_build_filter_own_tasks_only() {
+        let user_id = Uuid::new_v4();
+        let auth = auth(Some(user_id));
+
+        // Members are limited to their own tasks, with or without a filter
+        let all = build_filter(&ListTasksQuery::default(), &auth, Some(user_id)).unwrap();
+        assert_eq!(all.created_by, Some(user_id));
+
+        let me = ListTasksQuery {
+            created_by: Some("me".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(build_filter(&me, &auth, Some(user_id)).unwrap().created_by, Some(user_id));
+
+        let other = ListTasksQuery {
+            created_by: Some(Uuid::new_v4().to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            build_filter(&other, &auth, Some(user_id)),
+            Err(ApiError::Forbidden(_))
+        ));
+    }
+}
@@ -0,0 +1,434 @@
+/// Two-factor authentication endpoints
+///
+/// This module lets users turn TOTP two-factor authentication on and off and
+/// manage their recovery codes. All endpoints require JWT authentication.
+///
+/// # Endpoints
+///
+/// - `GET /v1/auth/2fa` - Show two-factor status
+/// - `POST /v1/auth/2fa/setup` - Start enrollment (password)
+/// - `POST /v1/auth/2fa/enable` - Confirm enrollment with a code
+/// - `POST /v1/auth/2fa/disable` - Turn two-factor authentication off
+///   (password and code)
+/// - `POST /v1/auth/2fa/recovery-codes` - Replace the recovery codes (code)
+///
+/// # Enrollment
+///
+/// `setup` returns a secret and an `otpauth://` provisioning URI to show as
+/// a QR code; `enable` confirms a code from the authenticator app and returns
+/// ten recovery codes. Recovery codes are only stored hashed, so they are
+/// shown once. From then on, login asks for a code (see `routes::auth`).
+///
+/// # Codes
+///
+/// Wrong codes count as failed logins of the account, so they can't be
+/// guessed with a stolen access token either.
+
+use crate::{
+    app::AppState,
+    error::{ApiError, ApiResult},
+    routes::auth::{check_login_throttle, client_ip, record_login_failure, require_user},
+};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    Extension, Json,
+};
+use axontask_shared::{
+    auth::{
+        middleware::AuthContext,
+        password,
+        two_factor::{self, Enrollment, TwoFactorError},
+    },
+    models::{
+        audit_log::{AuditAction, AuditLog, CreateAuditEntry},
+        recovery_code::RecoveryCode,
+        two_factor::UserTwoFactor,
+        user::User,
+    },
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+
+/// Two-factor status response
+#[derive(Debug, Serialize)]
+pub struct TwoFactorStatusResponse {
+    /// Whether two-factor authentication is on
+    pub enabled: bool,
+
+    /// Whether an enrollment waits for confirmation
+    pub pending: bool,
+
+    /// When two-factor authentication was turned on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_at: Option<DateTime<Utc>>,
+
+    /// Unused recovery codes
+    pub recovery_codes_remaining: i64,
+}
+
+/// Start enrollment request
+#[derive(Debug, Deserialize)]
+pub struct SetupRequest {
+    /// Current password
+    pub password: String,
+}
+
+/// Request carrying a code from the authenticator app
+#[derive(Debug, Deserialize)]
+pub struct CodeRequest {
+    /// Code from the authenticator app (or a recovery code, except when
+    /// enabling)
+    pub code: String,
+}
+
+/// Disable request
+#[derive(Debug, Deserialize)]
+pub struct DisableRequest {
+    /// Current password
+    pub password: String,
+
+    /// Code from the authenticator app, or a recovery code
+    pub code: String,
+}
+
+/// Recovery codes response
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodesResponse {
+    /// New single-use recovery codes (shown once)
+    pub recovery_codes: Vec<String>,
+}
+
+/// Two-factor status endpoint
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/auth/2fa
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "enabled": true,
+///   "pending": false,
+///   "enabled_at": "2025-01-25T10:00:00Z",
+///   "recovery_codes_remaining": 9
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `500 Internal Server Error`: Server error
+pub async fn status(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> ApiResult<Json<TwoFactorStatusResponse>> {
+    let user_id = require_user(&auth)?;
+
+    let enrollment = UserTwoFactor::find(&state.db, user_id).await?;
+    let recovery_codes_remaining = RecoveryCode::count_unused(&state.db, user_id).await?;
+
+    Ok(Json(TwoFactorStatusResponse {
+        enabled: enrollment.as_ref().is_some_and(UserTwoFactor::is_enabled),
+        pending: enrollment.as_ref().is_some_and(|e| !e.is_enabled()),
+        enabled_at: enrollment.and_then(|e| e.enabled_at),
+        recovery_codes_remaining,
+    }))
+}
+
+/// Start enrollment endpoint
+///
+/// Creates a new secret; starting again before confirming replaces it.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/2fa/setup
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "password": "SecureP@ss123" }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "secret": "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP",
+///   "provisioning_uri": "otpauth://totp/AxonTask:user%40example.com?secret=...&issuer=AxonTask&algorithm=SHA1&digits=6&period=30"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Wrong password
+/// - `409 Conflict`: Two-factor authentication is already enabled
+/// - `500 Internal Server Error`: Server error
+pub async fn setup(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(req): Json<SetupRequest>,
+) -> ApiResult<Json<Enrollment>> {
+    let user = verified_user(&state, &auth, &req.password).await?;
+
+    let enrollment =
+        two_factor::begin_enrollment(&state.db, &user, &state.config.auth.totp_issuer).await?;
+
+    tracing::info!(user_id = %user.id, "Two-factor enrollment started");
+
+    Ok(Json(enrollment))
+}
+
+/// Confirm enrollment endpoint
+///
+/// Turns two-factor authentication on with a code from the authenticator
+/// app and returns recovery codes.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/2fa/enable
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "code": "123456" }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "recovery_codes": ["k7d2m-q4xja", "..."]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: No enrollment started, or wrong code
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `409 Conflict`: Two-factor authentication is already enabled
+/// - `429 Too Many Requests`: Too many wrong codes
+/// - `500 Internal Server Error`: Server error
+pub async fn enable(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(req): Json<CodeRequest>,
+) -> ApiResult<Json<RecoveryCodesResponse>> {
+    let user = current_user(&state, &auth).await?;
+    let ip = request_ip(&state, connect_info, &headers);
+
+    let recovery_codes = throttled(
+        &state,
+        &user,
+        ip,
+        two_factor::confirm_enrollment(&state.db, user.id, &req.code),
+    )
+    .await?;
+
+    tracing::info!(user_id = %user.id, "Two-factor authentication enabled");
+    audit(&state, &user, AuditAction::TwoFactorEnabled, ip).await;
+
+    Ok(Json(RecoveryCodesResponse { recovery_codes }))
+}
+
+/// Disable endpoint
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/2fa/disable
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "password": "SecureP@ss123", "code": "123456" }
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Wrong code
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `403 Forbidden`: Wrong password, or a tenant the user belongs to
+///   requires two-factor authentication
+/// - `409 Conflict`: Two-factor authentication is not enabled
+/// - `429 Too Many Requests`: Too many wrong codes
+/// - `500 Internal Server Error`: Server error
+pub async fn disable(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(req): Json<DisableRequest>,
+) -> ApiResult<StatusCode> {
+    let user = verified_user(&state, &auth, &req.password).await?;
+    let ip = request_ip(&state, connect_info, &headers);
+
+    throttled(
+        &state,
+        &user,
+        ip,
+        two_factor::disable(&state.db, user.id, &req.code),
+    )
+    .await?;
+
+    tracing::info!(user_id = %user.id, "Two-factor authentication disabled");
+    audit(&state, &user, AuditAction::TwoFactorDisabled, ip).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Regenerate recovery codes endpoint
+///
+/// Replaces all recovery codes; the old ones stop working.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/auth/2fa/recovery-codes
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// { "code": "123456" }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "recovery_codes": ["k7d2m-q4xja", "..."]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `400 Bad Request`: Wrong code
+/// - `401 Unauthorized`: Missing or invalid JWT token
+/// - `409 Conflict`: Two-factor authentication is not enabled
+/// - `429 Too Many Requests`: Too many wrong codes
+/// - `500 Internal Server Error`: Server error
+pub async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(req): Json<CodeRequest>,
+) -> ApiResult<Json<RecoveryCodesResponse>> {
+    let user = current_user(&state, &auth).await?;
+    let ip = request_ip(&state, connect_info, &headers);
+
+    let recovery_codes = throttled(
+        &state,
+        &user,
+        ip,
+        two_factor::regenerate_recovery_codes(&state.db, user.id, &req.code),
+    )
+    .await?;
+
+    tracing::info!(user_id = %user.id, "Recovery codes regenerated");
+    audit(&state, &user, AuditAction::RecoveryCodesRegenerated, ip).await;
+
+    Ok(Json(RecoveryCodesResponse { recovery_codes }))
+}
+
+/// Gets the authenticated user
+async fn current_user(state: &AppState, auth: &AuthContext) -> ApiResult<User> {
+    let user_id = require_user(auth)?;
+
+    User::find_by_id(&state.db, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))
+}
+
+/// Gets the authenticated user after checking their password again
+async fn verified_user(state: &AppState, auth: &AuthContext, password: &str) -> ApiResult<User> {
+    let user = current_user(state, auth).await?;
+
+    if !password::verify_password(password, &user.password_hash)? {
+        return Err(ApiError::Forbidden("Invalid password".to_string()));
+    }
+
+    Ok(user)
+}
+
+/// Gets the client's IP address for throttling and auditing
+fn request_ip(
+    state: &AppState,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: &HeaderMap,
+) -> Option<IpAddr> {
+    client_ip(
+        headers,
+        connect_info.map(|ConnectInfo(addr)| addr),
+        state.config.api.trust_proxy_headers,
+    )
+}
+
+/// Runs a code check under the login throttle
+///
+/// Wrong codes count as failed logins; a throttled account is refused before
+/// the code is checked.
+async fn throttled<T>(
+    state: &AppState,
+    user: &User,
+    ip: Option<IpAddr>,
+    check: impl Future<Output = Result<T, TwoFactorError>>,
+) -> ApiResult<T> {
+    let throttle = state.login_throttle().ok();
+    check_login_throttle(throttle.as_ref(), &user.email, ip).await?;
+
+    match check.await {
+        Err(TwoFactorError::InvalidCode) => {
+            if let Some(throttle) = &throttle {
+                record_login_failure(state, throttle, &user.email, Some(user.id), ip).await;
+            }
+            Err(TwoFactorError::InvalidCode.into())
+        }
+        result => Ok(result?),
+    }
+}
+
+/// Writes an account-level audit entry; failures are logged
+async fn audit(state: &AppState, user: &User, action: AuditAction, ip: Option<IpAddr>) {
+    let entry = CreateAuditEntry {
+        tenant_id: None,
+        user_id: Some(user.id),
+        action,
+        ip_address: ip.map(|ip| ip.to_string()),
+        details: serde_json::json!({}),
+    };
+
+    if let Err(e) = AuditLog::record(&state.db, entry).await {
+        tracing::error!(error = %e, user_id = %user.id, action = action.as_str(), "Failed to audit two-factor change");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_response_omits_enabled_at_while_off() {
+        let response = TwoFactorStatusResponse {
+            enabled: false,
+            pending: true,
+            enabled_at: None,
+            recovery_codes_remaining: 0,
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["pending"], true);
+        assert!(json.get("enabled_at").is_none());
+    }
+}
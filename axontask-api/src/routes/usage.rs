@@ -0,0 +1,482 @@
+/// Usage reporting endpoint
+///
+/// This module reports a tenant's metered usage (see
+/// `axontask_shared::models::usage`) for billing reconciliation.
+///
+/// # Endpoints
+///
+/// - `GET /v1/usage` - Usage per day, period totals, per-adapter breakdown
+///   and current quota consumption (JSON or CSV)
+///
+/// # Periods
+///
+/// `period` selects a calendar period containing today (UTC): `day`, `week`
+/// (Monday to Sunday), `month` (default) or `year`. `start` and `end`
+/// (inclusive dates) select a custom range instead, of at most 366 days.
+
+use crate::{
+    app::AppState,
+    error::{ApiError, ApiResult, ValidationErrorDetail},
+};
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use axontask_shared::{
+    auth::middleware::AuthContext,
+    models::usage::{AdapterUsage, UsageCounter},
+    quota::{QuotaCheckResult, QuotaType},
+};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Longest custom range (days)
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// Usage query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct UsageQuery {
+    /// Calendar period: day, week, month (default) or year
+    pub period: Option<String>,
+
+    /// Custom range start (inclusive, YYYY-MM-DD)
+    pub start: Option<NaiveDate>,
+
+    /// Custom range end (inclusive, YYYY-MM-DD)
+    pub end: Option<NaiveDate>,
+
+    /// Response format: json (default) or csv
+    pub format: Option<String>,
+}
+
+/// Usage totals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct UsageTotals {
+    /// Billable task minutes
+    pub task_minutes: i64,
+
+    /// Tasks created
+    pub tasks_created: i64,
+
+    /// Stream connections opened
+    pub streams: i64,
+
+    /// Bytes streamed via SSE
+    pub bytes_streamed: i64,
+}
+
+/// Usage for one day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailyUsage {
+    /// Day (UTC)
+    pub date: NaiveDate,
+
+    /// Usage on that day
+    #[serde(flatten)]
+    pub usage: UsageTotals,
+}
+
+/// Usage for one adapter
+#[derive(Debug, Clone, Serialize)]
+pub struct AdapterUsageResponse {
+    /// Adapter name
+    pub adapter: String,
+
+    /// Tasks created
+    pub tasks: i64,
+
+    /// Billable task minutes
+    pub task_minutes: i64,
+
+    /// Bytes streamed via SSE
+    pub bytes_streamed: i64,
+}
+
+impl From<AdapterUsage> for AdapterUsageResponse {
+    fn from(usage: AdapterUsage) -> Self {
+        AdapterUsageResponse {
+            adapter: usage.adapter,
+            tasks: usage.tasks,
+            task_minutes: usage.task_minutes,
+            bytes_streamed: usage.bytes,
+        }
+    }
+}
+
+/// Current consumption of one quota
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaUsage {
+    /// Current usage
+    pub current: u32,
+
+    /// Limit (plan default or tenant override)
+    pub limit: u32,
+
+    /// Remaining before the limit is reached
+    pub remaining: u32,
+}
+
+impl From<QuotaCheckResult> for QuotaUsage {
+    fn from(result: QuotaCheckResult) -> Self {
+        QuotaUsage {
+            current: result.current,
+            limit: result.limit,
+            remaining: result.remaining,
+        }
+    }
+}
+
+/// Current quota consumption
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotasResponse {
+    /// Running and paused tasks
+    pub concurrent_tasks: QuotaUsage,
+
+    /// Tasks created in the last 24 hours
+    pub daily_tasks: QuotaUsage,
+
+    /// Open stream connections
+    pub stream_connections: QuotaUsage,
+
+    /// Task minutes used this calendar month
+    pub task_minutes: QuotaUsage,
+}
+
+/// Usage response
+#[derive(Debug, Serialize)]
+pub struct UsageResponse {
+    /// Period name, or "custom" for a start/end range
+    pub period: String,
+
+    /// First day (inclusive)
+    pub period_start: NaiveDate,
+
+    /// Last day (inclusive)
+    pub period_end: NaiveDate,
+
+    /// Totals for the period
+    pub usage: UsageTotals,
+
+    /// Usage per day (every day of the period, oldest first)
+    pub daily: Vec<DailyUsage>,
+
+    /// Usage per adapter (tasks created in the period)
+    pub by_adapter: Vec<AdapterUsageResponse>,
+
+    /// Current quota consumption
+    pub quotas: QuotasResponse,
+}
+
+/// Get usage
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/usage?period=month
+/// GET /v1/usage?start=2025-01-01&end=2025-01-31&format=csv
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response (200 OK)
+///
+/// ```json
+/// {
+///   "period": "month",
+///   "period_start": "2025-01-01",
+///   "period_end": "2025-01-31",
+///   "usage": {"task_minutes": 5432, "tasks_created": 1250, "streams": 450, "bytes_streamed": 1073741824},
+///   "daily": [{"date": "2025-01-01", "task_minutes": 120, ...}],
+///   "by_adapter": [{"adapter": "shell", "tasks": 900, "task_minutes": 5000, "bytes_streamed": 1048576}],
+///   "quotas": {"concurrent_tasks": {"current": 5, "limit": 100, "remaining": 95}, ...}
+/// }
+/// ```
+///
+/// With `format=csv`, returns the daily rows as `text/csv`.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: API key without `usage:read`
+/// - `422 Unprocessable Entity`: Invalid period, range or format
+/// - `500 Internal Server Error`: Server error
+pub async fn get_usage(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<UsageQuery>,
+) -> ApiResult<Response> {
+    let format = parse_format(query.format.as_deref())?;
+    let (period, start, end) = resolve_range(&query, Utc::now().date_naive())?;
+
+    let counters = UsageCounter::get_range(&state.db, auth.tenant_id, start, end).await?;
+    let daily = fill_days(start, end, &counters);
+
+    if format == Format::Csv {
+        let filename = format!("usage-{}-{}.csv", start, end);
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", filename),
+                ),
+            ],
+            render_csv(&daily),
+        )
+            .into_response());
+    }
+
+    let aggregate = UsageCounter::get_aggregate(&state.db, auth.tenant_id, start, end).await?;
+    let by_adapter = UsageCounter::get_adapter_breakdown(&state.db, auth.tenant_id, start, end).await?;
+
+    let enforcer = state.quota_enforcer();
+
+    let quotas = QuotasResponse {
+        concurrent_tasks: enforcer.check(auth.tenant_id, QuotaType::ConcurrentTasks).await?.into(),
+        daily_tasks: enforcer.check(auth.tenant_id, QuotaType::DailyTasks).await?.into(),
+        stream_connections: enforcer.check(auth.tenant_id, QuotaType::StreamConnections).await?.into(),
+        task_minutes: enforcer.check(auth.tenant_id, QuotaType::TaskMinutes).await?.into(),
+    };
+
+    Ok(Json(UsageResponse {
+        period,
+        period_start: start,
+        period_end: end,
+        usage: UsageTotals {
+            task_minutes: aggregate.total_task_minutes,
+            tasks_created: aggregate.total_tasks_created,
+            streams: aggregate.total_streams,
+            bytes_streamed: aggregate.total_bytes,
+        },
+        daily,
+        by_adapter: by_adapter.into_iter().map(AdapterUsageResponse::from).collect(),
+        quotas,
+    })
+    .into_response())
+}
+
+/// Response format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+/// Parses the `format` parameter
+fn parse_format(format: Option<&str>) -> ApiResult<Format> {
+    match format {
+        None | Some("json") => Ok(Format::Json),
+        Some("csv") => Ok(Format::Csv),
+        Some(_) => Err(invalid("format", "Must be json or csv".to_string())),
+    }
+}
+
+/// Resolves the reporting range
+///
+/// Returns the period name and the first and last day (inclusive).
+fn resolve_range(query: &UsageQuery, today: NaiveDate) -> ApiResult<(String, NaiveDate, NaiveDate)> {
+    if query.start.is_some() || query.end.is_some() {
+        if query.period.is_some() {
+            return Err(invalid(
+                "period",
+                "Use either period or start/end, not both".to_string(),
+            ));
+        }
+
+        let (Some(start), Some(end)) = (query.start, query.end) else {
+            return Err(invalid("start", "start and end must be given together".to_string()));
+        };
+
+        if start > end {
+            return Err(invalid("end", "end must not be before start".to_string()));
+        }
+        if (end - start).num_days() >= MAX_RANGE_DAYS {
+            return Err(invalid(
+                "end",
+                format!("Range must be at most {} days", MAX_RANGE_DAYS),
+            ));
+        }
+
+        return Ok(("custom".to_string(), start, end));
+    }
+
+    let period = query.period.as_deref().unwrap_or("month");
+    let (start, end) = match period {
+        "day" => (today, today),
+        "week" => {
+            let start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            (start, start + Duration::days(6))
+        }
+        "month" => {
+            let start = today.with_day(1).expect("day 1 exists");
+            let next = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+            }
+            .expect("valid date");
+            (start, next - Duration::days(1))
+        }
+        "year" => (
+            NaiveDate::from_ymd_opt(today.year(), 1, 1).expect("valid date"),
+            NaiveDate::from_ymd_opt(today.year(), 12, 31).expect("valid date"),
+        ),
+        _ => {
+            return Err(invalid(
+                "period",
+                "Must be one of: day, week, month, year".to_string(),
+            ))
+        }
+    };
+
+    Ok((period.to_string(), start, end))
+}
+
+/// Builds one row per day of the range, with zeros for days without usage
+fn fill_days(start: NaiveDate, end: NaiveDate, counters: &[UsageCounter]) -> Vec<DailyUsage> {
+    let by_day: HashMap<NaiveDate, &UsageCounter> =
+        counters.iter().map(|counter| (counter.period, counter)).collect();
+
+    start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .map(|date| DailyUsage {
+            date,
+            usage: by_day
+                .get(&date)
+                .map(|counter| UsageTotals {
+                    task_minutes: counter.task_minutes as i64,
+                    tasks_created: counter.tasks_created as i64,
+                    streams: counter.streams as i64,
+                    bytes_streamed: counter.bytes,
+                })
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Renders daily usage as CSV
+fn render_csv(daily: &[DailyUsage]) -> String {
+    let mut csv = String::from("date,task_minutes,tasks_created,streams,bytes_streamed\n");
+    for day in daily {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{}",
+            day.date,
+            day.usage.task_minutes,
+            day.usage.tasks_created,
+            day.usage.streams,
+            day.usage.bytes_streamed
+        );
+    }
+    csv
+}
+
+/// Builds a validation error for a query parameter
+fn invalid(field: &str, message: String) -> ApiError {
+    ApiError::ValidationError(vec![ValidationErrorDetail {
+        field: field.to_string(),
+        message,
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn period(name: &str) -> UsageQuery {
+        UsageQuery {
+            period: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_calendar_periods() {
+        let today = date(2024, 12, 18); // Wednesday
+
+        let (name, start, end) = resolve_range(&UsageQuery::default(), today).unwrap();
+        assert_eq!((name.as_str(), start, end), ("month", date(2024, 12, 1), date(2024, 12, 31)));
+
+        let (_, start, end) = resolve_range(&period("week"), today).unwrap();
+        assert_eq!((start, end), (date(2024, 12, 16), date(2024, 12, 22)));
+
+        let (_, start, end) = resolve_range(&period("day"), today).unwrap();
+        assert_eq!((start, end), (today, today));
+
+        let (_, start, end) = resolve_range(&period("year"), today).unwrap();
+        assert_eq!((start, end), (date(2024, 1, 1), date(2024, 12, 31)));
+
+        // Leap February
+        let (_, _, end) = resolve_range(&period("month"), date(2024, 2, 10)).unwrap();
+        assert_eq!(end, date(2024, 2, 29));
+
+        assert!(resolve_range(&period("quarter"), today).is_err());
+    }
+
+    #[test]
+    fn test_resolve_custom_range() {
+        let today = date(2025, 3, 1);
+        let range = |start, end| UsageQuery {
+            start,
+            end,
+            ..Default::default()
+        };
+
+        let (name, start, end) =
+            resolve_range(&range(Some(date(2025, 1, 1)), Some(date(2025, 1, 31))), today).unwrap();
+        assert_eq!((name.as_str(), start, end), ("custom", date(2025, 1, 1), date(2025, 1, 31)));
+
+        // Missing end, reversed, too long
+        assert!(resolve_range(&range(Some(date(2025, 1, 1)), None), today).is_err());
+        assert!(resolve_range(&range(Some(date(2025, 2, 1)), Some(date(2025, 1, 1))), today).is_err());
+        assert!(resolve_range(&range(Some(date(2023, 1, 1)), Some(date(2025, 1, 1))), today).is_err());
+
+        // Period and range together
+        let both = UsageQuery {
+            period: Some("month".to_string()),
+            ..range(Some(date(2025, 1, 1)), Some(date(2025, 1, 2)))
+        };
+        assert!(resolve_range(&both, today).is_err());
+    }
+
+    #[test]
+    fn test_fill_days_and_csv() {
+        let counters = vec![UsageCounter {
+            tenant_id: Uuid::new_v4(),
+            period: date(2025, 1, 2),
+            task_minutes: 12,
+            streams: 3,
+            bytes: 2048,
+            tasks_created: 4,
+        }];
+
+        let daily = fill_days(date(2025, 1, 1), date(2025, 1, 3), &counters);
+        assert_eq!(daily.len(), 3);
+        assert_eq!(daily[0].usage, UsageTotals::default());
+        assert_eq!(daily[1].usage.task_minutes, 12);
+
+        assert_eq!(
+            render_csv(&daily),
+            "date,task_minutes,tasks_created,streams,bytes_streamed\n\
+             2025-01-01,0,0,0,0\n\
+             2025-01-02,12,4,3,2048\n\
+             2025-01-03,0,0,0,0\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(parse_format(None).unwrap(), Format::Json);
+        assert_eq!(parse_format(Some("csv")).unwrap(), Format::Csv);
+        assert!(parse_format(Some("xml")).is_err());
+    }
+}
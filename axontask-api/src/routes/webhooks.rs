@@ -0,0 +1,975 @@
+/// Webhook management endpoints
+///
+/// This module provides CRUD endpoints for webhook configurations.
+/// All endpoints require the admin role (and the `webhooks:manage` scope for
+/// API keys).
+///
+/// # Endpoints
+///
+/// - `POST /v1/webhooks` - Create webhook (returns the secret once)
+/// - `GET /v1/webhooks` - List webhooks
+/// - `GET /v1/webhooks/:id` - Get webhook
+/// - `PATCH /v1/webhooks/:id` - Update URL, events or active status
+/// - `DELETE /v1/webhooks/:id` - Delete webhook
+/// - `POST /v1/webhooks/:id/toggle` - Enable or disable webhook
+/// - `POST /v1/webhooks/:id/rotate-secret` - Rotate the signing secret
+/// - `POST /v1/webhooks/:id/test` - Send a test delivery
+/// - `GET /v1/webhooks/:id/deliveries` - Delivery log
+/// - `POST /v1/webhooks/:id/deliveries/:delivery_id/redeliver` - Redeliver
+///
+/// Webhook URLs must use http or https and point to a public address:
+/// loopback, private, link-local and other internal addresses are rejected,
+/// both here and when deliveries are sent (see `axontask_shared::webhooks`).
+
+use crate::{
+    app::AppState,
+    error::{ApiError, ApiResult, ValidationErrorDetail},
+    routes::tasks::CursorPagination,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use axontask_shared::{
+    auth::middleware::AuthContext,
+    models::webhook::{
+        default_events, validate_events, CreateWebhook, UpdateWebhook, Webhook,
+        DEFAULT_SECRET_OVERLAP_HOURS,
+    },
+    models::webhook_delivery::{test_payload, DeliveryStatus, WebhookDelivery, TEST_EVENT},
+    webhooks::{check_target, WebhookSender},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Maximum overlap window for secret rotation (hours)
+const MAX_SECRET_OVERLAP_HOURS: i64 = 168;
+
+/// Default delivery log page size
+const DEFAULT_DELIVERIES_LIMIT: i64 = 20;
+
+/// Maximum delivery log page size
+const MAX_DELIVERIES_LIMIT: i64 = 100;
+
+/// Create webhook request
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateWebhookRequest {
+    /// Callback URL (http:// or https://)
+    #[validate(url(message = "Must be a valid URL"), length(max = 2048))]
+    pub url: String,
+
+    /// Event types to subscribe to (default: task.succeeded, task.failed)
+    pub events: Option<Vec<String>>,
+
+    /// Whether the webhook starts active (default: true)
+    pub active: Option<bool>,
+}
+
+/// Update webhook request
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateWebhookRequest {
+    /// New callback URL
+    #[validate(url(message = "Must be a valid URL"), length(max = 2048))]
+    pub url: Option<String>,
+
+    /// New event subscriptions
+    pub events: Option<Vec<String>>,
+
+    /// New active status
+    pub active: Option<bool>,
+}
+
+/// Toggle webhook request
+#[derive(Debug, Deserialize)]
+pub struct ToggleWebhookRequest {
+    /// Whether the webhook should be active
+    pub active: bool,
+}
+
+/// Rotate secret request
+#[derive(Debug, Default, Deserialize)]
+pub struct RotateSecretRequest {
+    /// How long the previous secret stays valid (default: 24, max: 168)
+    pub overlap_hours: Option<i64>,
+}
+
+/// Webhook response (without secret)
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    /// Webhook ID
+    pub id: Uuid,
+
+    /// Callback URL
+    pub url: String,
+
+    /// Subscribed event types
+    pub events: Vec<String>,
+
+    /// Whether the webhook is active
+    pub active: bool,
+
+    /// When the previous secret stops being used (only during rotation)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_secret_expires_at: Option<DateTime<Utc>>,
+
+    /// Created at
+    pub created_at: DateTime<Utc>,
+
+    /// Updated at
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        let previous_secret_expires_at = webhook
+            .previous_secret_expires_at
+            .filter(|expires_at| *expires_at > Utc::now());
+
+        WebhookResponse {
+            id: webhook.id,
+            url: webhook.url,
+            events: webhook.events,
+            active: webhook.active,
+            previous_secret_expires_at,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+/// Webhook response including the secret
+///
+/// Only returned on creation and secret rotation.
+#[derive(Debug, Serialize)]
+pub struct WebhookWithSecretResponse {
+    /// Webhook details
+    #[serde(flatten)]
+    pub webhook: WebhookResponse,
+
+    /// Signing secret (ONLY returned here)
+    ///
+    /// IMPORTANT: Store it securely as it cannot be retrieved later.
+    pub secret: String,
+}
+
+/// List webhooks response
+#[derive(Debug, Serialize)]
+pub struct ListWebhooksResponse {
+    /// Webhooks
+    pub data: Vec<WebhookResponse>,
+}
+
+/// List deliveries query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct ListDeliveriesQuery {
+    /// Only deliveries with this status (pending, succeeded, dead)
+    pub status: Option<String>,
+
+    /// Page size (default 20, max 100)
+    pub limit: Option<i64>,
+
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<Uuid>,
+}
+
+/// Webhook delivery response
+#[derive(Debug, Serialize)]
+pub struct DeliveryResponse {
+    /// Delivery ID (sent in the X-AxonTask-Delivery header)
+    pub id: Uuid,
+
+    /// Event type
+    pub event: String,
+
+    /// Task that triggered the event (None for test deliveries)
+    pub task_id: Option<Uuid>,
+
+    /// Delivery status (pending, succeeded, dead)
+    pub status: String,
+
+    /// Attempts made so far
+    pub attempts: i32,
+
+    /// Maximum number of attempts
+    pub max_attempts: i32,
+
+    /// When the next attempt is due (pending deliveries only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+
+    /// When the last attempt was made
+    pub last_attempt_at: Option<DateTime<Utc>>,
+
+    /// Request body
+    pub request_body: JsonValue,
+
+    /// HTTP status of the last attempt
+    pub response_status: Option<i32>,
+
+    /// Response body of the last attempt (truncated)
+    pub response_body: Option<String>,
+
+    /// Duration of the last attempt in milliseconds
+    pub latency_ms: Option<i32>,
+
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+
+    /// Created at
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for DeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        let next_attempt_at = (delivery.status == DeliveryStatus::Pending.as_str())
+            .then_some(delivery.next_attempt_at);
+
+        DeliveryResponse {
+            id: delivery.id,
+            event: delivery.event,
+            task_id: delivery.task_id,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            max_attempts: delivery.max_attempts,
+            next_attempt_at,
+            last_attempt_at: delivery.last_attempt_at,
+            request_body: delivery.payload,
+            response_status: delivery.response_status,
+            response_body: delivery.response_body,
+            latency_ms: delivery.latency_ms,
+            error: delivery.error,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+/// List deliveries response
+#[derive(Debug, Serialize)]
+pub struct ListDeliveriesResponse {
+    /// Deliveries on this page, newest first
+    pub data: Vec<DeliveryResponse>,
+
+    /// Pagination info
+    pub pagination: CursorPagination,
+}
+
+/// Test delivery response
+#[derive(Debug, Serialize)]
+pub struct TestWebhookResponse {
+    /// Webhook ID
+    pub webhook_id: Uuid,
+
+    /// Delivery ID (listed in the delivery log)
+    pub delivery_id: Uuid,
+
+    /// Whether the endpoint responded with a 2xx status
+    pub test_sent: bool,
+
+    /// HTTP status (None if no response was received)
+    pub status_code: Option<i32>,
+
+    /// Request duration in milliseconds
+    pub response_time_ms: i32,
+
+    /// Error if the delivery failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Create webhook
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/webhooks
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// {
+///   "url": "https://myapp.com/webhooks/axontask",
+///   "events": ["task.succeeded", "task.failed"]
+/// }
+/// ```
+///
+/// # Response (201 Created)
+///
+/// ```json
+/// {
+///   "id": "990e8400-e29b-41d4-a716-446655440000",
+///   "url": "https://myapp.com/webhooks/axontask",
+///   "events": ["task.succeeded", "task.failed"],
+///   "active": true,
+///   "secret": "whsec_3f9a...",
+///   "created_at": "2025-01-03T10:00:00Z",
+///   "updated_at": "2025-01-03T10:00:00Z"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `422 Unprocessable Entity`: Invalid or non-public URL, or invalid events
+/// - `500 Internal Server Error`: Server error
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> ApiResult<(StatusCode, Json<WebhookWithSecretResponse>)> {
+    validate_request(&req)?;
+    validate_url(&req.url).await?;
+
+    let events = req.events.unwrap_or_else(default_events);
+    validate_events(&events).map_err(|message| invalid("events", message))?;
+
+    let mut webhook = Webhook::create(
+        &state.db,
+        CreateWebhook {
+            tenant_id: auth.tenant_id,
+            url: req.url,
+            events,
+        },
+    )
+    .await?;
+
+    if req.active == Some(false) {
+        Webhook::toggle_active(&state.db, webhook.id, false).await?;
+        webhook.active = false;
+    }
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        webhook_id = %webhook.id,
+        "Webhook created"
+    );
+
+    let secret = webhook.secret_string();
+
+    Ok((
+        StatusCode::CREATED,
+        Json(WebhookWithSecretResponse {
+            webhook: webhook.into(),
+            secret,
+        }),
+    ))
+}
+
+/// List webhooks
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/webhooks
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `500 Internal Server Error`: Server error
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+) -> ApiResult<Json<ListWebhooksResponse>> {
+    let webhooks = Webhook::list_by_tenant(&state.db, auth.tenant_id).await?;
+
+    Ok(Json(ListWebhooksResponse {
+        data: webhooks.into_iter().map(WebhookResponse::from).collect(),
+    }))
+}
+
+/// Get webhook
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/webhooks/:id
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook not found
+/// - `500 Internal Server Error`: Server error
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<WebhookResponse>> {
+    let webhook = find_webhook(&state, &auth, id).await?;
+
+    Ok(Json(webhook.into()))
+}
+
+/// Update webhook
+///
+/// Only the fields present in the request are changed.
+///
+/// # Endpoint
+///
+/// ```text
+/// PATCH /v1/webhooks/:id
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// {
+///   "events": ["task.started", "task.succeeded", "task.failed"]
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook not found
+/// - `422 Unprocessable Entity`: Invalid or non-public URL, or invalid events
+/// - `500 Internal Server Error`: Server error
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateWebhookRequest>,
+) -> ApiResult<Json<WebhookResponse>> {
+    validate_request(&req)?;
+    if let Some(url) = &req.url {
+        validate_url(url).await?;
+    }
+    if let Some(events) = &req.events {
+        validate_events(events).map_err(|message| invalid("events", message))?;
+    }
+
+    // Ensure the webhook belongs to the tenant
+    find_webhook(&state, &auth, id).await?;
+
+    let webhook = Webhook::update(
+        &state.db,
+        id,
+        UpdateWebhook {
+            url: req.url,
+            events: req.events,
+            regenerate_secret: false,
+            active: req.active,
+        },
+    )
+    .await?
+    .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+
+    tracing::info!(tenant_id = %auth.tenant_id, webhook_id = %id, "Webhook updated");
+
+    Ok(Json(webhook.into()))
+}
+
+/// Delete webhook
+///
+/// # Endpoint
+///
+/// ```text
+/// DELETE /v1/webhooks/:id
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// `204 No Content`
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook not found
+/// - `500 Internal Server Error`: Server error
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    if !Webhook::delete_with_tenant(&state.db, id, auth.tenant_id).await? {
+        return Err(ApiError::NotFound("Webhook not found".to_string()));
+    }
+
+    tracing::info!(tenant_id = %auth.tenant_id, webhook_id = %id, "Webhook deleted");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Enable or disable a webhook
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/webhooks/:id/toggle
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// {
+///   "active": false
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook not found
+/// - `500 Internal Server Error`: Server error
+pub async fn toggle_webhook(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ToggleWebhookRequest>,
+) -> ApiResult<Json<WebhookResponse>> {
+    let mut webhook = find_webhook(&state, &auth, id).await?;
+
+    Webhook::toggle_active(&state.db, id, req.active).await?;
+    webhook.active = req.active;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        webhook_id = %id,
+        active = req.active,
+        "Webhook toggled"
+    );
+
+    Ok(Json(webhook.into()))
+}
+
+/// Rotate webhook secret
+///
+/// Generates a new secret. Deliveries are signed with both the new and the
+/// previous secret until the overlap window ends, so receivers can deploy
+/// the new secret without rejecting callbacks.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/webhooks/:id/rotate-secret
+/// Authorization: Bearer <jwt_token>
+/// Content-Type: application/json
+///
+/// {
+///   "overlap_hours": 24
+/// }
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "id": "990e8400-e29b-41d4-a716-446655440000",
+///   "url": "https://myapp.com/webhooks/axontask",
+///   "events": ["task.succeeded", "task.failed"],
+///   "active": true,
+///   "previous_secret_expires_at": "2025-01-04T10:00:00Z",
+///   "secret": "whsec_7b2c...",
+///   "created_at": "2025-01-03T10:00:00Z",
+///   "updated_at": "2025-01-03T10:00:00Z"
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook not found
+/// - `422 Unprocessable Entity`: Invalid overlap
+/// - `500 Internal Server Error`: Server error
+pub async fn rotate_webhook_secret(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+    req: Option<Json<RotateSecretRequest>>,
+) -> ApiResult<Json<WebhookWithSecretResponse>> {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    let overlap = overlap_duration(req.overlap_hours)?;
+
+    let webhook = Webhook::rotate_secret(&state.db, id, auth.tenant_id, overlap)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))?;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        webhook_id = %id,
+        overlap_hours = overlap.num_hours(),
+        "Webhook secret rotated"
+    );
+
+    let secret = webhook.secret_string();
+
+    Ok(Json(WebhookWithSecretResponse {
+        webhook: webhook.into(),
+        secret,
+    }))
+}
+
+/// Send a test delivery
+///
+/// Sends a signed `webhook.test` event immediately and reports the result.
+/// Works for inactive webhooks, so an endpoint can be checked before it is
+/// enabled. Test deliveries are logged but never retried.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/webhooks/:id/test
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "webhook_id": "990e8400-e29b-41d4-a716-446655440000",
+///   "delivery_id": "aa0e8400-e29b-41d4-a716-446655440000",
+///   "test_sent": true,
+///   "status_code": 200,
+///   "response_time_ms": 123
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook not found
+/// - `500 Internal Server Error`: Server error
+pub async fn test_webhook(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<TestWebhookResponse>> {
+    let webhook = find_webhook(&state, &auth, id).await?;
+
+    let delivery_id = Uuid::new_v4();
+    let payload = test_payload(&webhook);
+    let attempt = WebhookSender::default()
+        .send(&webhook, delivery_id, TEST_EVENT, &payload)
+        .await;
+
+    WebhookDelivery::record_sent(&state.db, delivery_id, &webhook, TEST_EVENT, payload, &attempt)
+        .await?;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        webhook_id = %id,
+        status = ?attempt.response_status,
+        "Test webhook sent"
+    );
+
+    Ok(Json(TestWebhookResponse {
+        webhook_id: webhook.id,
+        delivery_id,
+        test_sent: attempt.is_success(),
+        status_code: attempt.response_status,
+        response_time_ms: attempt.latency_ms,
+        error: attempt.error,
+    }))
+}
+
+/// List deliveries
+///
+/// Returns the delivery log of a webhook, newest first, including the request
+/// body and the outcome of the last attempt.
+///
+/// # Endpoint
+///
+/// ```text
+/// GET /v1/webhooks/:id/deliveries?status=dead&limit=20
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "data": [
+///     {
+///       "id": "aa0e8400-e29b-41d4-a716-446655440000",
+///       "event": "task.failed",
+///       "task_id": "770e8400-e29b-41d4-a716-446655440000",
+///       "status": "dead",
+///       "attempts": 6,
+///       "max_attempts": 6,
+///       "last_attempt_at": "2025-01-03T10:01:31Z",
+///       "request_body": {"event": "task.failed", "task": {...}},
+///       "response_status": 502,
+///       "response_body": "Bad Gateway",
+///       "latency_ms": 87,
+///       "error": "HTTP 502 Bad Gateway",
+///       "created_at": "2025-01-03T10:01:00Z"
+///     }
+///   ],
+///   "pagination": {
+///     "limit": 20,
+///     "has_more": false,
+///     "next_cursor": null
+///   }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook not found
+/// - `422 Unprocessable Entity`: Invalid status or limit
+/// - `500 Internal Server Error`: Server error
+pub async fn list_deliveries(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListDeliveriesQuery>,
+) -> ApiResult<Json<ListDeliveriesResponse>> {
+    let status = query
+        .status
+        .as_deref()
+        .map(str::parse::<DeliveryStatus>)
+        .transpose()
+        .map_err(|message| invalid("status", message))?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_DELIVERIES_LIMIT);
+    if !(1..=MAX_DELIVERIES_LIMIT).contains(&limit) {
+        return Err(invalid(
+            "limit",
+            format!("Limit must be between 1 and {}", MAX_DELIVERIES_LIMIT),
+        ));
+    }
+
+    let webhook = find_webhook(&state, &auth, id).await?;
+
+    // Fetch one extra delivery to know whether another page follows
+    let mut deliveries =
+        WebhookDelivery::list_by_webhook(&state.db, webhook.id, status, query.cursor, limit + 1)
+            .await?;
+
+    let has_more = deliveries.len() as i64 > limit;
+    deliveries.truncate(limit as usize);
+
+    let next_cursor = if has_more {
+        deliveries.last().map(|delivery| delivery.id.to_string())
+    } else {
+        None
+    };
+
+    Ok(Json(ListDeliveriesResponse {
+        data: deliveries.into_iter().map(DeliveryResponse::from).collect(),
+        pagination: CursorPagination {
+            limit,
+            has_more,
+            next_cursor,
+        },
+    }))
+}
+
+/// Redeliver
+///
+/// Queues a new delivery with the same event and payload as an earlier one
+/// (typically a dead-lettered delivery). The new delivery gets its own ID and
+/// retry schedule and is sent by the worker within seconds.
+///
+/// # Endpoint
+///
+/// ```text
+/// POST /v1/webhooks/:id/deliveries/:delivery_id/redeliver
+/// Authorization: Bearer <jwt_token>
+/// ```
+///
+/// # Response (202 Accepted)
+///
+/// The new delivery, as in the delivery log.
+///
+/// # Errors
+///
+/// - `401 Unauthorized`: Missing or invalid authentication
+/// - `403 Forbidden`: Not an admin, or API key without `webhooks:manage`
+/// - `404 Not Found`: Webhook or delivery not found
+/// - `409 Conflict`: Webhook is disabled
+/// - `500 Internal Server Error`: Server error
+pub async fn redeliver(
+    State(state): State<AppState>,
+    Extension(auth): Extension<AuthContext>,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<(StatusCode, Json<DeliveryResponse>)> {
+    let webhook = find_webhook(&state, &auth, id).await?;
+
+    // The dispatcher dead-letters deliveries of disabled webhooks
+    if !webhook.active {
+        return Err(ApiError::Conflict(
+            "Webhook is disabled; enable it before redelivering".to_string(),
+        ));
+    }
+
+    let delivery = WebhookDelivery::redeliver(&state.db, delivery_id, webhook.id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Delivery not found".to_string()))?;
+
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        webhook_id = %id,
+        original_delivery_id = %delivery_id,
+        delivery_id = %delivery.id,
+        "Webhook redelivery queued"
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(delivery.into())))
+}
+
+/// Finds a webhook with tenant isolation
+async fn find_webhook(state: &AppState, auth: &AuthContext, id: Uuid) -> ApiResult<Webhook> {
+    Webhook::find_by_id_and_tenant(&state.db, id, auth.tenant_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Webhook not found".to_string()))
+}
+
+/// Validates the overlap window for secret rotation
+fn overlap_duration(overlap_hours: Option<i64>) -> ApiResult<Duration> {
+    let hours = overlap_hours.unwrap_or(DEFAULT_SECRET_OVERLAP_HOURS);
+    if !(0..=MAX_SECRET_OVERLAP_HOURS).contains(&hours) {
+        return Err(invalid(
+            "overlap_hours",
+            format!("Overlap must be between 0 and {} hours", MAX_SECRET_OVERLAP_HOURS),
+        ));
+    }
+
+    Ok(Duration::hours(hours))
+}
+
+/// Checks that a webhook URL uses http or https and targets a public address
+async fn validate_url(url: &str) -> ApiResult<()> {
+    check_target(url).await.map_err(|message| invalid("url", message))
+}
+
+/// Maps validator errors to an API validation error
+fn validate_request(req: &impl Validate) -> ApiResult<()> {
+    req.validate().map_err(|e| {
+        let errors: Vec<ValidationErrorDetail> = e
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |error| ValidationErrorDetail {
+                    field: field.to_string(),
+                    message: error
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "Validation failed".to_string()),
+                })
+            })
+            .collect();
+        ApiError::ValidationError(errors)
+    })
+}
+
+/// Builds a validation error for a single field
+fn invalid(field: &str, message: String) -> ApiError {
+    ApiError::ValidationError(vec![ValidationErrorDetail {
+        field: field.to_string(),
+        message,
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_webhook_request_validation() {
+        let valid = CreateWebhookRequest {
+            url: "https://example.com/hooks".to_string(),
+            events: None,
+            active: None,
+        };
+        assert!(valid.validate().is_ok());
+
+        let invalid_url = CreateWebhookRequest {
+            url: "not a url".to_string(),
+            events: None,
+            active: None,
+        };
+        assert!(invalid_url.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_url() {
+        assert!(validate_url("https://93.184.216.34/hooks").await.is_ok());
+        assert!(validate_url("ftp://example.com").await.is_err());
+        assert!(validate_url("http://localhost:8080/hooks").await.is_err());
+        assert!(validate_url("http://169.254.169.254/latest/meta-data/").await.is_err());
+        assert!(validate_url("http://10.0.0.5/hooks").await.is_err());
+    }
+
+    #[test]
+    fn test_overlap_duration() {
+        assert_eq!(overlap_duration(None).unwrap(), Duration::hours(24));
+        assert_eq!(overlap_duration(Some(0)).unwrap(), Duration::zero());
+        assert!(overlap_duration(Some(-1)).is_err());
+        assert!(overlap_duration(Some(MAX_SECRET_OVERLAP_HOURS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_secret_only_in_secret_response() {
+        let webhook = WebhookResponse {
+            id: Uuid::new_v4(),
+            url: "https://example.com/hooks".to_string(),
+            events: vec!["task.failed".to_string()],
+            active: true,
+            previous_secret_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let json = serde_json::to_value(&webhook).unwrap();
+        assert!(json.get("secret").is_none());
+        assert!(json.get("previous_secret_expires_at").is_none());
+
+        let with_secret = WebhookWithSecretResponse {
+            webhook,
+            secret: "whsec_abcd".to_string(),
+        };
+        let json = serde_json::to_value(&with_secret).unwrap();
+        assert_eq!(json["secret"], "whsec_abcd");
+        assert_eq!(json["url"], "https://example.com/hooks");
+    }
+
+    #[test]
+    fn test_delivery_response() {
+        let delivery = WebhookDelivery {
+            id: Uuid::new_v4(),
+            webhook_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            task_id: None,
+            event: TEST_EVENT.to_string(),
+            payload: serde_json::json!({"event": TEST_EVENT}),
+            status: "dead".to_string(),
+            attempts: 1,
+            max_attempts: 1,
+            next_attempt_at: Utc::now(),
+            last_attempt_at: Some(Utc::now()),
+            response_status: Some(500),
+            response_body: Some("oops".to_string()),
+            latency_ms: Some(12),
+            error: Some("HTTP 500 Internal Server Error".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        // No next attempt for finished deliveries
+        let json = serde_json::to_value(DeliveryResponse::from(delivery.clone())).unwrap();
+        assert!(json.get("next_attempt_at").is_none());
+        assert_eq!(json["request_body"]["event"], TEST_EVENT);
+        assert_eq!(json["response_status"], 500);
+
+        let pending = WebhookDelivery {
+            status: "pending".to_string(),
+            ..delivery
+        };
+        let json = serde_json::to_value(DeliveryResponse::from(pending)).unwrap();
+        assert!(json.get("next_attempt_at").is_some());
+    }
+}
@@ -0,0 +1,293 @@
+/// Email verification and password reset tests
+///
+/// Tests the verification gate on login and the password reset flow through
+/// the auth endpoints. Emails are captured by a recording mailer; they are
+/// sent in the background, so tests wait for them.
+
+mod common;
+
+use async_trait::async_trait;
+use axontask_api::app::{build_router, AppState};
+use axontask_shared::email::{EmailError, EmailMessage, Mailer};
+use axontask_shared::models::tenant::Tenant;
+use axontask_shared::models::user::{UpdateUser, User};
+use axontask_shared::models::user_token::{CreateUserToken, TokenPurpose, UserToken};
+use axum::http::StatusCode;
+use common::{register, send, RegisteredUser, TestContext, PASSWORD};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Mailer that keeps messages for inspection
+#[derive(Default)]
+struct RecordingMailer {
+    messages: Mutex<Vec<EmailMessage>>,
+}
+
+impl RecordingMailer {
+    /// Number of messages sent to `to`
+    fn count(&self, to: &str) -> usize {
+        self.messages.lock().unwrap().iter().filter(|m| m.to == to).count()
+    }
+
+    /// Waits for a message to `to` with a token of the given prefix and
+    /// returns the token
+    async fn token_for(&self, to: &str, prefix: &str) -> String {
+        let marker = format!("token={}", prefix);
+
+        for _ in 0..100 {
+            {
+                let messages = self.messages.lock().unwrap();
+                if let Some(message) = messages.iter().rev().find(|m| m.to == to && m.body.contains(&marker)) {
+                    let start = message.body.find("token=").unwrap() + "token=".len();
+                    return message.body[start..].split_whitespace().next().unwrap().to_string();
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        panic!("no email with {} sent to {}", marker, to);
+    }
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        self.messages.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+/// Builds an app with a recording mailer
+fn app(ctx: &TestContext, require_verification: bool) -> (axum::Router, Arc<RecordingMailer>) {
+    let mut config = ctx.config.clone();
+    config.auth.require_email_verification = require_verification;
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let app = build_router(
+        AppState::new(ctx.db.clone(), config)
+            .with_redis(ctx.redis.clone())
+            .with_mailer(mailer.clone()),
+    );
+
+    (app, mailer)
+}
+
+/// Test that login waits for email verification when required
+#[tokio::test]
+async fn test_email_verification() {
+    let ctx = TestContext::new().await.unwrap();
+    let (app, mailer) = app(&ctx, true);
+    let email = format!("verify-{}@example.com", Uuid::new_v4());
+    let credentials = json!({ "email": email, "password": PASSWORD });
+
+    // Registration issues no tokens and emails a link
+    let (status, _, body) = send(
+        &app,
+        "POST",
+        "/v1/auth/register",
+        None,
+        credentials.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["email_verification_required"], true);
+    assert!(body.get("access_token").is_none());
+    let tenant_id: Uuid = body["tenant_id"].as_str().unwrap().parse().unwrap();
+    let token = mailer.token_for(&email, "vfy_").await;
+
+    assert_eq!(send(&app, "POST", "/v1/auth/login", None, credentials.clone()).await.0, StatusCode::FORBIDDEN);
+
+    // Tokens are checked and single use
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/auth/verify-email/confirm",
+        None,
+        json!({ "token": "vfy_wrong" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/auth/verify-email/confirm",
+        None,
+        json!({ "token": token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/auth/verify-email/confirm",
+        None,
+        json!({ "token": token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _, body) = send(&app, "POST", "/v1/auth/login", None, credentials).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    // Verified and unknown addresses get the same answer and no email
+    let sent = mailer.count(&email);
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/auth/verify-email/request",
+        None,
+        json!({ "email": email }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let unknown = json!({ "email": format!("nobody-{}@example.com", Uuid::new_v4()) });
+    let (status, _, _) = send(&app, "POST", "/v1/auth/verify-email/request", None, unknown).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(mailer.count(&email), sent);
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test resetting a password, which ends all sessions
+#[tokio::test]
+async fn test_password_reset() {
+    let ctx = TestContext::new().await.unwrap();
+    let (app, mailer) = app(&ctx, false);
+    let email = format!("reset-{}@example.com", Uuid::new_v4());
+
+    let RegisteredUser { tenant_id, refresh_token, .. } = register(&app, &email).await;
+
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/auth/password-reset/request",
+        None,
+        json!({ "email": email }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let token = mailer.token_for(&email, "rst_").await;
+
+    // A second request within the resend interval sends nothing
+    let sent = mailer.count(&email);
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/auth/password-reset/request",
+        None,
+        json!({ "email": email }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(mailer.count(&email), sent);
+
+    // A weak password doesn't use up the token
+    let weak = json!({ "token": token, "password": "short" });
+    assert_eq!(
+        send(&app, "POST", "/v1/auth/password-reset/confirm", None, weak).await.0,
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+
+    let reset = json!({ "token": token, "password": "N3w-P@ssword" });
+    let (status, _, body) = send(
+        &app,
+        "POST",
+        "/v1/auth/password-reset/confirm",
+        None,
+        reset.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{}", body);
+    assert_eq!(
+        send(&app, "POST", "/v1/auth/password-reset/confirm", None, reset).await.0,
+        StatusCode::BAD_REQUEST
+    );
+
+    // Existing sessions ended; only the new password works
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/auth/refresh",
+        None,
+        json!({ "refresh_token": refresh_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let old = json!({ "email": email, "password": PASSWORD });
+    assert_eq!(send(&app, "POST", "/v1/auth/login", None, old).await.0, StatusCode::UNAUTHORIZED);
+    let new = json!({ "email": email, "password": "N3w-P@ssword" });
+    assert_eq!(send(&app, "POST", "/v1/auth/login", None, new).await.0, StatusCode::OK);
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that a reset uses up every outstanding link, and that links sent to
+/// a previous address don't work
+#[tokio::test]
+async fn test_password_reset_links() {
+    let ctx = TestContext::new().await.unwrap();
+    let (app, mailer) = app(&ctx, false);
+    let email = format!("links-{}@example.com", Uuid::new_v4());
+
+    let RegisteredUser { tenant_id, user_id, .. } = register(&app, &email).await;
+
+    send(&app, "POST", "/v1/auth/password-reset/request", None, json!({ "email": email })).await;
+    let token = mailer.token_for(&email, "rst_").await;
+
+    // A second outstanding link (e.g. issued on another replica)
+    let other = UserToken::generate_token(TokenPurpose::PasswordReset);
+    sqlx::query(
+        "INSERT INTO user_tokens (user_id, purpose, email, token_hash, expires_at) VALUES ($1, $2, $3, $4, NOW() + INTERVAL '1 hour')",
+    )
+    .bind(user_id)
+    .bind(TokenPurpose::PasswordReset)
+    .bind(&email)
+    .bind(UserToken::hash_token(&other))
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+
+    let reset = json!({ "token": token, "password": "N3w-P@ssword" });
+    assert_eq!(send(&app, "POST", "/v1/auth/password-reset/confirm", None, reset).await.0, StatusCode::NO_CONTENT);
+    let reset = json!({ "token": other, "password": "An0ther-P@ssword" });
+    assert_eq!(send(&app, "POST", "/v1/auth/password-reset/confirm", None, reset).await.0, StatusCode::BAD_REQUEST);
+
+    // A link sent before an email change doesn't work
+    let stale = UserToken::generate_token(TokenPurpose::PasswordReset);
+    UserToken::issue(
+        &ctx.db,
+        CreateUserToken {
+            user_id,
+            purpose: TokenPurpose::PasswordReset,
+            email: email.clone(),
+            token: stale.clone(),
+            expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+        },
+    )
+    .await
+    .unwrap();
+    User::update(
+        &ctx.db,
+        user_id,
+        UpdateUser {
+            email: Some(format!("moved-{}@example.com", Uuid::new_v4())),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let reset = json!({ "token": stale, "password": "An0ther-P@ssword" });
+    assert_eq!(send(&app, "POST", "/v1/auth/password-reset/confirm", None, reset).await.0, StatusCode::BAD_REQUEST);
+    let login = json!({ "email": email, "password": "An0ther-P@ssword" });
+    assert_eq!(send(&app, "POST", "/v1/auth/login", None, login).await.0, StatusCode::UNAUTHORIZED);
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
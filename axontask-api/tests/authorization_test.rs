@@ -0,0 +1,260 @@
+/// Authorization matrix tests
+///
+/// Every authenticated route is called as each member role and with API keys
+/// of different scopes, checking who gets `403 Forbidden`:
+/// - Members (JWT) are checked against their role
+/// - API keys are checked against their scopes
+/// - API key and member management only accept JWTs
+/// - The admin API only accepts the operator token
+
+mod common;
+
+use axontask_shared::auth::jwt::{Claims, TokenType};
+use axontask_shared::models::api_key::{ApiKey, CreateApiKey};
+use axontask_shared::models::membership::{CreateMembership, Membership, MembershipRole};
+use axontask_shared::models::user::{CreateUser, User};
+use axum::http::StatusCode;
+use common::{send, send_with_headers, TestContext};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Lowest role allowed on a route
+#[derive(Debug, Clone, Copy)]
+enum MinRole {
+    Viewer,
+    Member,
+    Admin,
+}
+
+impl MinRole {
+    fn allows(self, role: MembershipRole) -> bool {
+        let required = match self {
+            MinRole::Viewer => MembershipRole::Viewer,
+            MinRole::Member => MembershipRole::Member,
+            MinRole::Admin => MembershipRole::Admin,
+        };
+        role.has_permission(&required)
+    }
+}
+
+/// Route under test: method, path, lowest role, API key scope (None = JWT only)
+fn routes() -> Vec<(&'static str, String, MinRole, Option<&'static str>)> {
+    let task = Uuid::new_v4();
+    let webhook = Uuid::new_v4();
+    let delivery = Uuid::new_v4();
+
+    vec![
+        ("POST", "/v1/mcp/start_task".to_string(), MinRole::Member, Some("tasks:write")),
+        ("GET", format!("/v1/mcp/tasks/{}/status", task), MinRole::Viewer, Some("tasks:read")),
+        ("POST", format!("/v1/mcp/tasks/{}/cancel", task), MinRole::Member, Some("tasks:write")),
+        ("GET", format!("/v1/mcp/tasks/{}/stream", task), MinRole::Viewer, Some("tasks:read")),
+        ("POST", format!("/v1/mcp/tasks/{}/resume", task), MinRole::Viewer, Some("tasks:read")),
+        ("POST", format!("/v1/mcp/tasks/{}/pause", task), MinRole::Member, Some("tasks:write")),
+        ("POST", format!("/v1/mcp/tasks/{}/unpause", task), MinRole::Member, Some("tasks:write")),
+        ("POST", format!("/v1/mcp/tasks/{}/input", task), MinRole::Member, Some("tasks:write")),
+        ("POST", "/v1/mcp/cancel_tasks".to_string(), MinRole::Member, Some("tasks:write")),
+        ("GET", "/v1/tasks".to_string(), MinRole::Viewer, Some("tasks:read")),
+        ("GET", "/v1/usage".to_string(), MinRole::Viewer, Some("usage:read")),
+        ("POST", "/v1/webhooks".to_string(), MinRole::Admin, Some("webhooks:manage")),
+        ("GET", "/v1/webhooks".to_string(), MinRole::Admin, Some("webhooks:read")),
+        ("GET", format!("/v1/webhooks/{}", webhook), MinRole::Admin, Some("webhooks:read")),
+        ("PATCH", format!("/v1/webhooks/{}", webhook), MinRole::Admin, Some("webhooks:manage")),
+        ("DELETE", format!("/v1/webhooks/{}", webhook), MinRole::Admin, Some("webhooks:manage")),
+        ("POST", format!("/v1/webhooks/{}/toggle", webhook), MinRole::Admin, Some("webhooks:manage")),
+        ("POST", format!("/v1/webhooks/{}/rotate-secret", webhook), MinRole::Admin, Some("webhooks:manage")),
+        ("POST", format!("/v1/webhooks/{}/test", webhook), MinRole::Admin, Some("webhooks:manage")),
+        ("GET", format!("/v1/webhooks/{}/deliveries", webhook), MinRole::Admin, Some("webhooks:read")),
+        (
+            "POST",
+            format!("/v1/webhooks/{}/deliveries/{}/redeliver", webhook, delivery),
+            MinRole::Admin,
+            Some("webhooks:manage"),
+        ),
+        ("POST", "/v1/api-keys".to_string(), MinRole::Admin, None),
+        ("GET", "/v1/api-keys".to_string(), MinRole::Viewer, None),
+        ("POST", format!("/v1/api-keys/{}", Uuid::new_v4()), MinRole::Admin, None),
+        ("GET", "/v1/members".to_string(), MinRole::Viewer, None),
+        ("PATCH", format!("/v1/members/{}", Uuid::new_v4()), MinRole::Admin, None),
+        ("DELETE", format!("/v1/members/{}", Uuid::new_v4()), MinRole::Admin, None),
+        ("POST", "/v1/members/invitations".to_string(), MinRole::Admin, None),
+        ("GET", "/v1/members/invitations".to_string(), MinRole::Admin, None),
+        ("DELETE", format!("/v1/members/invitations/{}", Uuid::new_v4()), MinRole::Admin, None),
+    ]
+}
+
+/// Creates a user with a role in the test tenant and returns a JWT for them
+async fn member_token(ctx: &TestContext, role: MembershipRole) -> String {
+    let user = User::create(
+        &ctx.db,
+        CreateUser {
+            email: format!("authz-{}@example.com", Uuid::new_v4()),
+            password_hash: "test_hash".to_string(),
+            name: None,
+            avatar_url: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    Membership::create(
+        &ctx.db,
+        CreateMembership {
+            tenant_id: ctx.tenant.id,
+            user_id: user.id,
+            role,
+        },
+    )
+    .await
+    .unwrap();
+
+    let claims = Claims::new(user.id, ctx.tenant.id, TokenType::Access);
+    ctx.config.jwt.keys.sign(&claims).unwrap()
+}
+
+/// Creates an API key with scopes in the test tenant
+async fn api_key(ctx: &TestContext, scopes: &[&str]) -> String {
+    let (_, plaintext) = ApiKey::create(
+        &ctx.db,
+        CreateApiKey {
+            tenant_id: ctx.tenant.id,
+            name: format!("authz {:?}", scopes),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    plaintext
+}
+
+/// Test role × route
+#[tokio::test]
+async fn test_member_role_matrix() {
+    let ctx = TestContext::new().await.unwrap();
+
+    for role in [
+        MembershipRole::Viewer,
+        MembershipRole::Member,
+        MembershipRole::Admin,
+        MembershipRole::Owner,
+    ] {
+        let token = member_token(&ctx, role).await;
+
+        for (method, uri, min_role, _) in routes() {
+            let (status, _, _) = send(&ctx.app, method, &uri, Some(&token), json!({})).await;
+
+            if min_role.allows(role) {
+                assert!(
+                    status != StatusCode::FORBIDDEN && status != StatusCode::UNAUTHORIZED,
+                    "{:?} should be allowed on {} {}, got {}",
+                    role,
+                    method,
+                    uri,
+                    status
+                );
+            } else {
+                assert_eq!(
+                    status,
+                    StatusCode::FORBIDDEN,
+                    "{:?} should be forbidden on {} {}",
+                    role,
+                    method,
+                    uri
+                );
+            }
+        }
+    }
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test API key scope × route
+#[tokio::test]
+async fn test_api_key_scope_matrix() {
+    let ctx = TestContext::new().await.unwrap();
+
+    let key_scopes: [&[&str]; 7] = [
+        &["tasks:read"],
+        &["read:task"],
+        &["tasks:write"],
+        &["usage:read"],
+        &["webhooks:read"],
+        &["webhooks:manage"],
+        &["*"],
+    ];
+
+    for scopes in key_scopes {
+        let granted: Vec<String> = scopes.iter().map(|s| s.to_string()).collect();
+        let key = api_key(&ctx, scopes).await;
+
+        for header in ["authorization", "x-api-key"] {
+            for (method, uri, _, required) in routes() {
+                let (status, _, _) = if header == "authorization" {
+                    send(&ctx.app, method, &uri, Some(&key), json!({})).await
+                } else {
+                    send_with_headers(&ctx.app, method, &uri, None, &[(header, &key)], json!({})).await
+                };
+
+                match required {
+                    // API key and member management only accept JWTs
+                    None => assert!(
+                        status == StatusCode::UNAUTHORIZED || status == StatusCode::BAD_REQUEST,
+                        "{:?} should be rejected on {} {}, got {}",
+                        scopes,
+                        method,
+                        uri,
+                        status
+                    ),
+                    Some(scope) if axontask_shared::auth::api_key::has_scope(&granted, scope) => {
+                        assert!(
+                            status != StatusCode::FORBIDDEN && status != StatusCode::UNAUTHORIZED,
+                            "{:?} should be allowed on {} {}, got {}",
+                            scopes,
+                            method,
+                            uri,
+                            status
+                        )
+                    }
+                    Some(_) => assert_eq!(
+                        status,
+                        StatusCode::FORBIDDEN,
+                        "{:?} should be forbidden on {} {}",
+                        scopes,
+                        method,
+                        uri
+                    ),
+                }
+            }
+        }
+    }
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that tenant credentials never reach the operator admin API
+#[tokio::test]
+async fn test_admin_routes_reject_tenant_credentials() {
+    let ctx = TestContext::new().await.unwrap();
+
+    let owner = member_token(&ctx, MembershipRole::Owner).await;
+    let key = api_key(&ctx, &["*"]).await;
+    let uri = format!("/v1/admin/tenants/{}/quotas", ctx.tenant.id);
+
+    for token in [&owner, &key] {
+        for method in ["GET", "PUT"] {
+            let (status, _, _) = send(&ctx.app, method, &uri, Some(token), json!({})).await;
+
+            // 404 when the admin API is disabled, 401 otherwise
+            assert!(
+                status == StatusCode::UNAUTHORIZED || status == StatusCode::NOT_FOUND,
+                "{} {} should be rejected, got {}",
+                method,
+                uri,
+                status
+            );
+        }
+    }
+
+    ctx.cleanup().await.unwrap();
+}
@@ -9,15 +9,25 @@
 
 use axontask_api::app::{build_router, AppState};
 use axontask_api::config::Config;
-use axontask_shared::auth::jwt::{Claims, TokenType, create_token};
+use axontask_shared::auth::jwt::{Claims, TokenType};
 use axontask_shared::models::membership::{CreateMembership, Membership, MembershipRole};
 use axontask_shared::models::tenant::{CreateTenant, Tenant, TenantPlan};
 use axontask_shared::models::user::{CreateUser, User};
 use axontask_shared::redis::{RedisClient, RedisConfig};
+use axum::body::Body;
+use axum::http::{HeaderMap, Request, StatusCode};
+use serde_json::{json, Value};
 use sqlx::PgPool;
 use std::sync::Arc;
+use tower::Service as _;
 use uuid::Uuid;
 
+/// Password of users created with `register`
+pub const PASSWORD: &str = "SecureP@ss123";
+
+/// User agent of requests made with `send`
+pub const USER_AGENT: &str = "axontask-tests/1.0";
+
 /// Test context containing all necessary resources
 pub struct TestContext {
     pub db: PgPool,
@@ -80,10 +90,10 @@ impl TestContext {
 
         // Generate JWT token
         let claims = Claims::new(user.id, tenant.id, TokenType::Access);
-        let jwt_token = create_token(&claims, &config.jwt.secret)?;
+        let jwt_token = config.jwt.keys.sign(&claims)?;
 
         // Build app
-        let state = AppState::new(db.clone(), config.clone());
+        let state = AppState::new(db.clone(), config.clone()).with_redis(redis.clone());
         let app = build_router(state);
 
         Ok(TestContext {
@@ -124,10 +134,12 @@ pub async fn create_test_task(
         CreateTask {
             tenant_id: ctx.tenant.id,
             created_by: Some(ctx.user.id),
+            api_key_id: None,
             name: name.to_string(),
             adapter: adapter.to_string(),
             args,
             timeout_seconds: 60,
+            tags: vec![],
         },
     )
     .await?;
@@ -135,6 +147,89 @@ pub async fn create_test_task(
     Ok(task.id)
 }
 
+/// Sends a JSON request, with a bearer token if given
+///
+/// Returns the status, response headers and body (`Null` if not JSON).
+pub async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    bearer: Option<&str>,
+    body: Value,
+) -> (StatusCode, HeaderMap, Value) {
+    send_with_headers(app, method, uri, bearer, &[], body).await
+}
+
+/// Sends a JSON request with extra headers (e.g. `x-forwarded-for`)
+///
+/// Same as `send` otherwise.
+pub async fn send_with_headers(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    bearer: Option<&str>,
+    headers: &[(&str, &str)],
+    body: Value,
+) -> (StatusCode, HeaderMap, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("user-agent", USER_AGENT);
+    if let Some(token) = bearer {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let response = app
+        .clone()
+        .call(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (
+        status,
+        headers,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+/// A user created through `POST /v1/auth/register`
+pub struct RegisteredUser {
+    /// User ID
+    pub user_id: Uuid,
+
+    /// The user's personal tenant
+    pub tenant_id: Uuid,
+
+    /// Access token for the personal tenant
+    pub access_token: String,
+
+    /// Refresh token for the personal tenant
+    pub refresh_token: String,
+}
+
+/// Registers a user with `PASSWORD`
+pub async fn register(app: &axum::Router, email: &str) -> RegisteredUser {
+    let credentials = json!({ "email": email, "password": PASSWORD });
+    let (status, _, body) = send(app, "POST", "/v1/auth/register", None, credentials).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    RegisteredUser {
+        user_id: body["user_id"].as_str().unwrap().parse().unwrap(),
+        tenant_id: body["tenant_id"].as_str().unwrap().parse().unwrap(),
+        access_token: body["access_token"].as_str().unwrap().to_string(),
+        refresh_token: body["refresh_token"].as_str().unwrap().to_string(),
+    }
+}
+
 /// Helper to wait for condition with timeout
 pub async fn wait_for<F, Fut>(
     condition: F,
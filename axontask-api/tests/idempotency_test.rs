@@ -0,0 +1,106 @@
+/// Idempotency key tests
+///
+/// Tests that `start_task` replays completed keys and that a reservation
+/// left behind by a crashed request is taken over once its lease expires.
+
+mod common;
+
+use axontask_shared::models::idempotency_key::{
+    IdempotencyKey, DEFAULT_RETENTION_HOURS, RESERVATION_LEASE_SECS,
+};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use common::TestContext;
+use serde_json::{json, Value};
+use tower::Service as _;
+
+/// Starts a task with an idempotency key and returns the status and body
+async fn start_task(ctx: &TestContext, key: &str, body: &Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/mcp/start_task")
+        .header("authorization", ctx.auth_header())
+        .header("content-type", "application/json")
+        .header("idempotency-key", key)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = ctx.app.clone().call(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_completed_key_replays_response() {
+    let ctx = TestContext::new().await.unwrap();
+    let body = json!({"name": "replay", "adapter": "mock", "args": {}});
+
+    let (status, first) = start_task(&ctx, "replay-1", &body).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, second) = start_task(&ctx, "replay-1", &body).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["task_id"], second["task_id"]);
+
+    ctx.cleanup().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_stale_reservation_is_taken_over() {
+    let ctx = TestContext::new().await.unwrap();
+    let body = json!({"name": "takeover", "adapter": "mock", "args": {}});
+    // Fingerprint of the request as `start_task` computes it
+    let hash = IdempotencyKey::hash_request(&json!({
+        "name": "takeover", "adapter": "mock", "args": {}, "timeout_s": null, "tags": []
+    }));
+    let retention = chrono::Duration::hours(DEFAULT_RETENTION_HOURS);
+
+    // A request that reserved the key and died before creating its task
+    IdempotencyKey::reserve(&ctx.db, ctx.tenant.id, "takeover-1", &hash, retention)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Retries are refused while the lease is held
+    let (status, error) = start_task(&ctx, "takeover-1", &body).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(error.to_string().contains("in progress"));
+
+    sqlx::query(
+        "UPDATE idempotency_keys SET created_at = created_at - make_interval(secs => $3) WHERE tenant_id = $1 AND key = $2",
+    )
+    .bind(ctx.tenant.id)
+    .bind("takeover-1")
+    .bind((RESERVATION_LEASE_SECS + 1) as f64)
+    .execute(&ctx.db)
+    .await
+    .unwrap();
+
+    let crashed = IdempotencyKey::find_active(&ctx.db, ctx.tenant.id, "takeover-1")
+        .await
+        .unwrap()
+        .unwrap();
+
+    // After the lease, a retry takes the key over and creates the task
+    let (status, created) = start_task(&ctx, "takeover-1", &body).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(created["task_id"].is_string());
+
+    // The crashed request can no longer complete or release the key
+    let completed = IdempotencyKey::complete(&ctx.db, &crashed, uuid::Uuid::new_v4(), json!({}))
+        .await
+        .unwrap();
+    assert!(!completed);
+    assert!(!IdempotencyKey::release(&ctx.db, &crashed).await.unwrap());
+
+    let (status, replayed) = start_task(&ctx, "takeover-1", &body).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replayed["task_id"], created["task_id"]);
+
+    ctx.cleanup().await.unwrap();
+}
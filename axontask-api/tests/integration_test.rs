@@ -81,6 +81,7 @@ async fn test_full_task_lifecycle() {
             poll_interval_secs: 1,
             max_concurrent_tasks: 5,
             batch_size: 5,
+            ..Default::default()
         },
     );
 
@@ -232,6 +233,7 @@ async fn test_timeout_enforcement() {
         axontask_shared::models::task::CreateTask {
             tenant_id: ctx.tenant.id,
             created_by: Some(ctx.user.id),
+            api_key_id: None,
             name: "timeout-test".to_string(),
             adapter: "mock".to_string(),
             args: json!({
@@ -239,6 +241,7 @@ async fn test_timeout_enforcement() {
                 "should_fail": false
             }),
             timeout_seconds: 2, // 2 second timeout
+            tags: vec![],
         },
     )
     .await
@@ -372,6 +375,43 @@ async fn test_authentication_required() {
     ctx.cleanup().await.unwrap();
 }
 
+/// Test that the JWKS is public and tokens name their signing key
+#[tokio::test]
+async fn test_jwks() {
+    let ctx = TestContext::new().await.unwrap();
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/.well-known/jwks.json")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = ctx.app.clone().call(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let jwks: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    // Every published key is an asymmetric key of the configured set
+    let published: Vec<&str> = jwks["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|key| key["kid"].as_str().unwrap())
+        .collect();
+    for key in ctx.config.jwt.keys.keys() {
+        assert_eq!(published.contains(&key.kid()), key.jwk().is_some());
+    }
+
+    let header = jsonwebtoken::decode_header(&ctx.jwt_token).unwrap();
+    assert_eq!(
+        header.kid.as_deref(),
+        ctx.config.jwt.keys.signing_key().map(|key| key.kid())
+    );
+
+    ctx.cleanup().await.unwrap();
+}
+
 /// Test get task status endpoint
 #[tokio::test]
 async fn test_get_task_status() {
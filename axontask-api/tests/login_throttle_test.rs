@@ -0,0 +1,144 @@
+/// Login brute-force protection tests
+///
+/// Tests progressive delays, lockouts and their audit entries on the login
+/// endpoint. Requests carry an `X-Forwarded-For` address with proxy headers
+/// trusted, so each test has its own client IP.
+
+mod common;
+
+use axontask_api::app::{build_router, AppState};
+use axontask_shared::models::audit_log::{AuditAction, AuditLog};
+use axontask_shared::models::tenant::Tenant;
+use axum::http::{header, HeaderMap, StatusCode};
+use common::{register, send_with_headers, TestContext, PASSWORD};
+use serde_json::{json, Value};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Builds an app with low limits: delays after 2 failures, lockout after 4
+fn app(ctx: &TestContext) -> axum::Router {
+    let mut config = ctx.config.clone();
+    config.api.trust_proxy_headers = true;
+    config.auth.login_throttle.account_free_attempts = 2;
+    config.auth.login_throttle.account_max_failures = 4;
+    config.auth.login_throttle.ip_free_attempts = 100;
+    config.auth.login_throttle.base_delay_ms = 200;
+    config.auth.login_throttle.lockout_secs = 60;
+
+    build_router(AppState::new(ctx.db.clone(), config).with_redis(ctx.redis.clone()))
+}
+
+/// Random client address, so tests don't share IP counters
+fn client_ip() -> String {
+    let bytes = Uuid::new_v4().into_bytes();
+    format!("10.{}.{}.{}", bytes[0], bytes[1], bytes[2])
+}
+
+/// Attempts a login from `ip`
+async fn login(app: &axum::Router, ip: &str, body: Value) -> (StatusCode, HeaderMap, Value) {
+    send_with_headers(
+        app,
+        "POST",
+        "/v1/auth/login",
+        None,
+        &[("x-forwarded-for", ip)],
+        body,
+    )
+    .await
+}
+
+/// Reads the `Retry-After` header
+fn retry_after(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::RETRY_AFTER)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+/// Test that failures delay further attempts and a success clears them
+#[tokio::test]
+async fn test_progressive_delay() {
+    let ctx = TestContext::new().await.unwrap();
+    let app = app(&ctx);
+    let ip = client_ip();
+    let email = format!("delay-{}@example.com", Uuid::new_v4());
+    let tenant_id = register(&app, &email).await.tenant_id;
+    let wrong = json!({ "email": email, "password": "Wr0ng-P@ss" });
+    let right = json!({ "email": email, "password": PASSWORD });
+
+    for _ in 0..2 {
+        let (status, _, _) = login(&app, &ip, wrong.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    // Even the right password has to wait
+    let (status, headers, body) = login(&app, &ip, right.clone()).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(retry_after(&headers).as_deref(), Some("1"));
+    assert_eq!(body["error"], "rate_limit_exceeded");
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let (status, _, body) = login(&app, &ip, right.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    // The success cleared the account's failures
+    let (status, _, _) = login(&app, &ip, wrong).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _, _) = login(&app, &ip, right).await;
+    assert_eq!(status, StatusCode::OK);
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that repeated failures lock the account out and are audited
+#[tokio::test]
+async fn test_lockout() {
+    let ctx = TestContext::new().await.unwrap();
+    let app = app(&ctx);
+    let email = format!("lockout-{}@example.com", Uuid::new_v4());
+    let user = register(&app, &email).await;
+    let (tenant_id, user_id) = (user.tenant_id, user.user_id);
+    let wrong = json!({ "email": email, "password": "Wr0ng-P@ss" });
+
+    // Fresh IPs and waits past the delays: only the account counter applies
+    for delay_ms in [0, 0, 200, 400] {
+        tokio::time::sleep(Duration::from_millis(delay_ms + 50)).await;
+        let (status, _, body) = login(&app, &client_ip(), wrong.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", body);
+    }
+
+    let right = json!({ "email": email, "password": PASSWORD });
+    let (status, headers, _) = login(&app, &client_ip(), right).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(retry_after(&headers).unwrap().parse::<u64>().unwrap() > 50);
+
+    let entries = AuditLog::list_for_user(&ctx.db, user_id, 10).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].action, AuditAction::LoginLocked.as_str());
+    assert_eq!(entries[0].details["scope"], "account");
+    assert_eq!(entries[0].details["failures"], 4);
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that unknown emails are throttled like wrong passwords
+#[tokio::test]
+async fn test_unknown_email_throttled() {
+    let ctx = TestContext::new().await.unwrap();
+    let app = app(&ctx);
+    let ip = client_ip();
+    let unknown = json!({
+        "email": format!("nobody-{}@example.com", Uuid::new_v4()),
+        "password": "Wr0ng-P@ss",
+    });
+
+    for _ in 0..2 {
+        let (status, _, _) = login(&app, &ip, unknown.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+    let (status, _, _) = login(&app, &ip, unknown).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    ctx.cleanup().await.unwrap();
+}
@@ -0,0 +1,309 @@
+/// Member management tests
+///
+/// Tests role changes, removal, the last-owner guard and the invitation flow
+/// through the member endpoints. Invitation emails are captured by a
+/// recording mailer instead of being logged.
+
+mod common;
+
+use async_trait::async_trait;
+use axontask_api::app::{build_router, AppState};
+use axontask_shared::auth::jwt::{Claims, TokenType};
+use axontask_shared::email::{EmailError, EmailMessage, Mailer};
+use axontask_shared::models::membership::{CreateMembership, Membership, MembershipRole};
+use axontask_shared::models::tenant::{CreateTenant, Tenant, TenantPlan};
+use axontask_shared::models::user::{CreateUser, User};
+use axum::http::StatusCode;
+use common::{send, TestContext};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Mailer that keeps messages for inspection
+#[derive(Default)]
+struct RecordingMailer {
+    messages: Mutex<Vec<EmailMessage>>,
+}
+
+impl RecordingMailer {
+    /// Extracts the invitation token from the last message sent to `to`
+    fn token_for(&self, to: &str) -> String {
+        let messages = self.messages.lock().unwrap();
+        let message = messages.iter().rev().find(|m| m.to == to).expect("no email sent");
+        let start = message.body.find("token=").expect("no token in email") + "token=".len();
+
+        message.body[start..].split_whitespace().next().unwrap().to_string()
+    }
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        self.messages.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+/// Creates a user, optionally with a role in the test tenant, and returns
+/// the user and a JWT for the test tenant
+async fn user(ctx: &TestContext, role: Option<MembershipRole>) -> (User, String) {
+    let user = User::create(
+        &ctx.db,
+        CreateUser {
+            email: format!("members-{}@example.com", Uuid::new_v4()),
+            password_hash: "test_hash".to_string(),
+            name: None,
+            avatar_url: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    if let Some(role) = role {
+        Membership::create(
+            &ctx.db,
+            CreateMembership {
+                tenant_id: ctx.tenant.id,
+                user_id: user.id,
+                role,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let token = ctx
+        .config
+        .jwt
+        .keys
+        .sign(&Claims::new(user.id, ctx.tenant.id, TokenType::Access))
+        .unwrap();
+
+    (user, token)
+}
+
+/// Test role changes, the owner rules and removal
+#[tokio::test]
+async fn test_member_roles() {
+    let ctx = TestContext::new().await.unwrap();
+    let owner = &ctx.jwt_token;
+    let (admin, admin_token) = user(&ctx, Some(MembershipRole::Admin)).await;
+    let (member, _) = user(&ctx, Some(MembershipRole::Member)).await;
+
+    let (status, _, body) = send(
+        &ctx.app,
+        "GET",
+        "/v1/members",
+        Some(&admin_token),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["members"].as_array().unwrap().len(), 3);
+    assert_eq!(body["members"][0]["user_id"], ctx.user.id.to_string());
+    assert_eq!(body["members"][0]["role"], "owner");
+
+    // Admins manage non-owners
+    let uri = format!("/v1/members/{}", member.id);
+    let (status, _, body) = send(
+        &ctx.app,
+        "PATCH",
+        &uri,
+        Some(&admin_token),
+        json!({ "role": "viewer" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["role"], "viewer");
+
+    // ...but cannot grant or touch the owner role
+    let (status, _, _) = send(
+        &ctx.app,
+        "PATCH",
+        &uri,
+        Some(&admin_token),
+        json!({ "role": "owner" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let owner_uri = format!("/v1/members/{}", ctx.user.id);
+    let (status, _, _) = send(
+        &ctx.app,
+        "DELETE",
+        &owner_uri,
+        Some(&admin_token),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // The only owner cannot step down or be removed
+    let (status, _, _) = send(
+        &ctx.app,
+        "PATCH",
+        &owner_uri,
+        Some(owner),
+        json!({ "role": "admin" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _, _) = send(&ctx.app, "DELETE", &owner_uri, Some(owner), Value::Null).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // With a second owner, the first can step down
+    let admin_uri = format!("/v1/members/{}", admin.id);
+    let (status, _, _) = send(
+        &ctx.app,
+        "PATCH",
+        &admin_uri,
+        Some(owner),
+        json!({ "role": "owner" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, _) = send(
+        &ctx.app,
+        "PATCH",
+        &owner_uri,
+        Some(owner),
+        json!({ "role": "admin" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        Membership::get_role(&ctx.db, ctx.tenant.id, ctx.user.id).await.unwrap(),
+        Some(MembershipRole::Admin)
+    );
+
+    // Removal
+    let (status, _, _) = send(&ctx.app, "DELETE", &uri, Some(&admin_token), Value::Null).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, _) = send(&ctx.app, "DELETE", &uri, Some(&admin_token), Value::Null).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!Membership::has_access(&ctx.db, ctx.tenant.id, member.id).await.unwrap());
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test inviting, listing, revoking and accepting invitations
+#[tokio::test]
+async fn test_invitations() {
+    let ctx = TestContext::new().await.unwrap();
+    let mailer = Arc::new(RecordingMailer::default());
+    let app = build_router(
+        AppState::new(ctx.db.clone(), ctx.config.clone())
+            .with_redis(ctx.redis.clone())
+            .with_mailer(mailer.clone()),
+    );
+    let owner = &ctx.jwt_token;
+
+    // The invitee already has an account (in another tenant)
+    let (invitee, _) = user(&ctx, None).await;
+    let personal = Tenant::create(
+        &ctx.db,
+        CreateTenant {
+            name: format!("Personal {}", Uuid::new_v4()),
+            plan: TenantPlan::Trial,
+        },
+    )
+    .await
+    .unwrap();
+    let invitee_token = ctx
+        .config
+        .jwt
+        .keys
+        .sign(&Claims::new(invitee.id, personal.id, TokenType::Access))
+        .unwrap();
+
+    let (status, _, body) = send(
+        &app,
+        "POST",
+        "/v1/members/invitations",
+        Some(owner),
+        json!({ "email": invitee.email.to_uppercase(), "role": "admin" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["role"], "admin");
+    assert_eq!(body["email_sent"], true);
+    let token = mailer.token_for(&invitee.email.to_uppercase());
+
+    // Inviting a current member is rejected
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/members/invitations",
+        Some(owner),
+        json!({ "email": ctx.user.email }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // A second, wrong-address invitation shows up in the list and can be revoked
+    let (_, _, other) = send(
+        &app,
+        "POST",
+        "/v1/members/invitations",
+        Some(owner),
+        json!({ "email": format!("other-{}@example.com", Uuid::new_v4()) }),
+    )
+    .await;
+    let other_token = mailer.token_for(other["email"].as_str().unwrap());
+    let (status, _, body) = send(
+        &app,
+        "GET",
+        "/v1/members/invitations",
+        Some(owner),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["invitations"].as_array().unwrap().len(), 2);
+
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/invitations/accept",
+        Some(&invitee_token),
+        json!({ "token": other_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let revoke_uri = format!("/v1/members/invitations/{}", other["id"].as_str().unwrap());
+    assert_eq!(send(&app, "DELETE", &revoke_uri, Some(owner), Value::Null).await.0, StatusCode::NO_CONTENT);
+    assert_eq!(send(&app, "DELETE", &revoke_uri, Some(owner), Value::Null).await.0, StatusCode::NOT_FOUND);
+
+    // Accepting adds the invitee with the invited role, once
+    let (status, _, body) = send(
+        &app,
+        "POST",
+        "/v1/invitations/accept",
+        Some(&invitee_token),
+        json!({ "token": token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["tenant_id"], ctx.tenant.id.to_string());
+    assert_eq!(body["role"], "admin");
+    assert_eq!(
+        Membership::get_role(&ctx.db, ctx.tenant.id, invitee.id).await.unwrap(),
+        Some(MembershipRole::Admin)
+    );
+
+    let (status, _, _) = send(
+        &app,
+        "POST",
+        "/v1/invitations/accept",
+        Some(&invitee_token),
+        json!({ "token": token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, _, body) = send(&app, "GET", "/v1/members/invitations", Some(owner), Value::Null).await;
+    assert!(body["invitations"].as_array().unwrap().is_empty());
+
+    Tenant::delete(&ctx.db, personal.id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
@@ -0,0 +1,249 @@
+/// Session tests
+///
+/// Tests refresh token rotation, reuse detection, logout, session listing and
+/// tenant switching through the auth endpoints.
+
+mod common;
+
+use axum::http::StatusCode;
+use axontask_shared::models::membership::{CreateMembership, Membership, MembershipRole};
+use axontask_shared::models::tenant::Tenant;
+use common::{register, send, RegisteredUser, TestContext, PASSWORD, USER_AGENT};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Registers a user with a random email
+async fn register_user(ctx: &TestContext) -> RegisteredUser {
+    register(&ctx.app, &format!("session-{}@example.com", Uuid::new_v4())).await
+}
+
+/// Exchanges a refresh token
+async fn refresh(ctx: &TestContext, refresh_token: &str) -> (StatusCode, Value) {
+    let refresh = json!({ "refresh_token": refresh_token });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/refresh", None, refresh).await;
+    (status, body)
+}
+
+/// Test that refresh tokens rotate and reuse revokes the session
+#[tokio::test]
+async fn test_refresh_rotation_and_reuse() {
+    let ctx = TestContext::new().await.unwrap();
+    let first = register_user(&ctx).await.refresh_token;
+
+    // Rotation returns a new refresh token
+    let (status, body) = refresh(&ctx, &first).await;
+    assert_eq!(status, StatusCode::OK);
+    let second = body["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(first, second);
+    assert!(body["access_token"].is_string());
+    assert!(body["expires_in"].as_i64().unwrap() > 0);
+
+    // Reusing the first token is rejected and revokes the family...
+    assert_eq!(refresh(&ctx, &first).await.0, StatusCode::UNAUTHORIZED);
+
+    // ...so the newest token no longer works either
+    assert_eq!(refresh(&ctx, &second).await.0, StatusCode::UNAUTHORIZED);
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test logout of a single session
+#[tokio::test]
+async fn test_logout() {
+    let ctx = TestContext::new().await.unwrap();
+    let refresh_token = register_user(&ctx).await.refresh_token;
+
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/logout",
+        None,
+        json!({ "refresh_token": refresh_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    assert_eq!(refresh(&ctx, &refresh_token).await.0, StatusCode::UNAUTHORIZED);
+
+    // Logging out again still succeeds
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/logout",
+        None,
+        json!({ "refresh_token": refresh_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test listing sessions, revoking one and logging out of all
+#[tokio::test]
+async fn test_sessions() {
+    let ctx = TestContext::new().await.unwrap();
+    let RegisteredUser { access_token, refresh_token, .. } = register_user(&ctx).await;
+
+    let (status, _, body) = send(
+        &ctx.app,
+        "GET",
+        "/v1/auth/sessions",
+        Some(&access_token),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let sessions = body["sessions"].as_array().unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["user_agent"], USER_AGENT);
+
+    // Rotation keeps the session ID
+    let session_id = sessions[0]["id"].as_str().unwrap().to_string();
+    let (_, body) = refresh(&ctx, &refresh_token).await;
+    let refresh_token = body["refresh_token"].as_str().unwrap().to_string();
+    let (_, _, body) = send(
+        &ctx.app,
+        "GET",
+        "/v1/auth/sessions",
+        Some(&access_token),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(body["sessions"][0]["id"], session_id.as_str());
+
+    // Revoke it by ID
+    let uri = format!("/v1/auth/sessions/{}", session_id);
+    assert_eq!(
+        send(&ctx.app, "DELETE", &uri, Some(&access_token), Value::Null).await.0,
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(
+        send(&ctx.app, "DELETE", &uri, Some(&access_token), Value::Null).await.0,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(refresh(&ctx, &refresh_token).await.0, StatusCode::UNAUTHORIZED);
+
+    // Log out everywhere
+    let (status, _, body) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/logout-all",
+        Some(&access_token),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["revoked"], 0);
+
+    // Session routes require a JWT
+    assert_eq!(
+        send(&ctx.app, "GET", "/v1/auth/sessions", None, Value::Null).await.0,
+        StatusCode::UNAUTHORIZED
+    );
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test login tenant selection and switching tenants
+#[tokio::test]
+async fn test_switch_tenant() {
+    let ctx = TestContext::new().await.unwrap();
+    let email = format!("consultant-{}@example.com", Uuid::new_v4());
+    let registered = register(&ctx.app, &email).await;
+    let user_id = registered.user_id;
+    let personal = registered.tenant_id.to_string();
+
+    // Join the test tenant as a member
+    Membership::create(
+        &ctx.db,
+        CreateMembership {
+            tenant_id: ctx.tenant.id,
+            user_id,
+            role: MembershipRole::Member,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Login lists both tenants and defaults to the first one joined
+    let login = json!({ "email": email, "password": PASSWORD });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, login).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["tenant_id"], personal.as_str());
+    assert_eq!(body["tenants"].as_array().unwrap().len(), 2);
+    assert_eq!(body["tenants"][1]["id"], ctx.tenant.id.to_string());
+    assert_eq!(body["tenants"][1]["role"], "member");
+
+    // Login can pick a tenant, but only one the user belongs to
+    let login = json!({ "email": email, "password": PASSWORD, "tenant_id": ctx.tenant.id });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, login).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["tenant_id"], ctx.tenant.id.to_string());
+
+    let login = json!({ "email": email, "password": PASSWORD, "tenant_id": Uuid::new_v4() });
+    assert_eq!(send(&ctx.app, "POST", "/v1/auth/login", None, login).await.0, StatusCode::FORBIDDEN);
+
+    // Switch from the personal tenant to the test tenant
+    let login = json!({ "email": email, "password": PASSWORD });
+    let (_, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, login).await;
+    let access_token = body["access_token"].as_str().unwrap().to_string();
+    let refresh_token = body["refresh_token"].as_str().unwrap().to_string();
+
+    // Switching to a tenant the user doesn't belong to keeps the session usable
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/switch-tenant",
+        Some(&access_token),
+        json!({ "tenant_id": Uuid::new_v4(), "refresh_token": refresh_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _, body) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/switch-tenant",
+        Some(&access_token),
+        json!({ "tenant_id": ctx.tenant.id, "refresh_token": refresh_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["tenant_id"], ctx.tenant.id.to_string());
+    assert_eq!(body["role"], "member");
+    let switched_token = body["access_token"].as_str().unwrap().to_string();
+    let refresh_token = body["refresh_token"].as_str().unwrap().to_string();
+
+    // The new access token works in the test tenant
+    let (status, _, _) = send(
+        &ctx.app,
+        "GET",
+        "/v1/tasks",
+        Some(&switched_token),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Refreshing stays in the test tenant
+    assert_eq!(refresh(&ctx, &refresh_token).await.0, StatusCode::OK);
+    let (_, _, body) = send(
+        &ctx.app,
+        "GET",
+        "/v1/auth/sessions",
+        Some(&switched_token),
+        Value::Null,
+    )
+    .await;
+    let tenants: Vec<&str> = body["sessions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|session| session["tenant_id"].as_str().unwrap())
+        .collect();
+    assert!(tenants.contains(&ctx.tenant.id.to_string().as_str()));
+
+    Tenant::delete(&ctx.db, personal.parse().unwrap()).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
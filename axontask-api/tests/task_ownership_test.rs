@@ -0,0 +1,194 @@
+/// Task ownership tests
+///
+/// Members only see and control tasks they created:
+/// - Members get `404 Not Found` for other users' tasks
+/// - Admins and owners access all of the tenant's tasks
+/// - Viewers read all of the tenant's tasks
+/// - Task listings and bulk cancellation only include a member's own tasks
+/// - Tasks started with an API key record the key
+
+mod common;
+
+use axontask_shared::auth::jwt::{Claims, TokenType};
+use axontask_shared::models::api_key::{ApiKey, CreateApiKey};
+use axontask_shared::models::membership::{CreateMembership, Membership, MembershipRole};
+use axontask_shared::models::task::{CreateTask, Task, TaskState};
+use axontask_shared::models::user::{CreateUser, User};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use common::TestContext;
+use tower::Service as _;
+use uuid::Uuid;
+
+/// Creates a user with a role in the test tenant and returns their ID and a JWT
+async fn member(ctx: &TestContext, role: MembershipRole) -> (Uuid, String) {
+    let user = User::create(
+        &ctx.db,
+        CreateUser {
+            email: format!("owner-{}@example.com", Uuid::new_v4()),
+            password_hash: "test_hash".to_string(),
+            name: None,
+            avatar_url: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    Membership::create(
+        &ctx.db,
+        CreateMembership {
+            tenant_id: ctx.tenant.id,
+            user_id: user.id,
+            role,
+        },
+    )
+    .await
+    .unwrap();
+
+    let claims = Claims::new(user.id, ctx.tenant.id, TokenType::Access);
+    (user.id, ctx.config.jwt.keys.sign(&claims).unwrap())
+}
+
+/// Creates a pending task
+async fn task(ctx: &TestContext, created_by: Option<Uuid>, api_key_id: Option<Uuid>, tag: &str) -> Task {
+    Task::create(
+        &ctx.db,
+        CreateTask {
+            tenant_id: ctx.tenant.id,
+            created_by,
+            api_key_id,
+            name: "ownership".to_string(),
+            adapter: "mock".to_string(),
+            args: serde_json::json!({}),
+            timeout_seconds: 60,
+            tags: vec![tag.to_string()],
+        },
+    )
+    .await
+    .unwrap()
+}
+
+/// Sends a request and returns the status and body
+async fn send(ctx: &TestContext, method: &str, uri: &str, token: &str, body: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = ctx.app.clone().call(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+/// Test per-task access by role
+#[tokio::test]
+async fn test_task_access_by_role() {
+    let ctx = TestContext::new().await.unwrap();
+
+    let (alice, alice_token) = member(&ctx, MembershipRole::Member).await;
+    let (_, bob_token) = member(&ctx, MembershipRole::Member).await;
+    let (_, viewer_token) = member(&ctx, MembershipRole::Viewer).await;
+    let (_, admin_token) = member(&ctx, MembershipRole::Admin).await;
+
+    let alices = task(&ctx, Some(alice), None, "ownership").await;
+    let status_uri = format!("/v1/mcp/tasks/{}/status", alices.id);
+    let cancel_uri = format!("/v1/mcp/tasks/{}/cancel", alices.id);
+
+    // Other members cannot see or cancel the task
+    assert_eq!(send(&ctx, "GET", &status_uri, &bob_token, "").await.0, StatusCode::NOT_FOUND);
+    assert_eq!(send(&ctx, "POST", &cancel_uri, &bob_token, "{}").await.0, StatusCode::NOT_FOUND);
+
+    // Viewers can read it, but not cancel it
+    assert_eq!(send(&ctx, "GET", &status_uri, &viewer_token, "").await.0, StatusCode::OK);
+    assert_eq!(send(&ctx, "POST", &cancel_uri, &viewer_token, "{}").await.0, StatusCode::FORBIDDEN);
+
+    // The creator and admins can
+    let (status, body) = send(&ctx, "GET", &status_uri, &alice_token, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["created_by"], alice.to_string());
+    assert_eq!(send(&ctx, "GET", &status_uri, &admin_token, "").await.0, StatusCode::OK);
+    assert_eq!(send(&ctx, "POST", &cancel_uri, &admin_token, "{}").await.0, StatusCode::OK);
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that listings and bulk cancellation only include a member's own tasks
+#[tokio::test]
+async fn test_member_listing_and_bulk_cancel() {
+    let ctx = TestContext::new().await.unwrap();
+
+    let (alice, alice_token) = member(&ctx, MembershipRole::Member).await;
+    let (bob, _) = member(&ctx, MembershipRole::Member).await;
+
+    let alices = task(&ctx, Some(alice), None, "session:shared").await;
+    let bobs = task(&ctx, Some(bob), None, "session:shared").await;
+
+    let (status, body) = send(&ctx, "GET", "/v1/tasks", &alice_token, "").await;
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| task["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![alices.id.to_string()]);
+
+    // Filtering by another user is rejected
+    let uri = format!("/v1/tasks?created_by={}", bob);
+    assert_eq!(send(&ctx, "GET", &uri, &alice_token, "").await.0, StatusCode::FORBIDDEN);
+
+    // Bulk cancel leaves Bob's task alone
+    let (status, body) = send(
+        &ctx,
+        "POST",
+        "/v1/mcp/cancel_tasks",
+        &alice_token,
+        r#"{"tags": ["session:shared"]}"#,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["task_ids"], serde_json::json!([alices.id]));
+
+    let bobs = Task::find_by_id(&ctx.db, bobs.id).await.unwrap().unwrap();
+    assert_eq!(bobs.state, TaskState::Pending);
+
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that API key tasks are attributable to the key
+#[tokio::test]
+async fn test_api_key_task_attribution() {
+    let ctx = TestContext::new().await.unwrap();
+
+    let (key, plaintext) = ApiKey::create(
+        &ctx.db,
+        CreateApiKey {
+            tenant_id: ctx.tenant.id,
+            name: "ci".to_string(),
+            scopes: vec!["tasks:read".to_string()],
+            expires_at: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let by_key = task(&ctx, None, Some(key.id), "ownership").await;
+    task(&ctx, Some(ctx.user.id), None, "ownership").await;
+
+    let uri = format!("/v1/tasks?api_key_id={}", key.id);
+    let (status, body) = send(&ctx, "GET", &uri, &plaintext, "").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let data = body["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["id"], by_key.id.to_string());
+    assert_eq!(data[0]["api_key_id"], key.id.to_string());
+    assert!(data[0]["created_by"].is_null());
+
+    ctx.cleanup().await.unwrap();
+}
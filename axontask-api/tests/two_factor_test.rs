@@ -0,0 +1,341 @@
+/// Two-factor authentication tests
+///
+/// Tests enrollment, the second login step with TOTP and recovery codes, and
+/// tenants that require two-factor authentication. Codes are computed from
+/// the stored secret as an authenticator app would.
+
+mod common;
+
+use axontask_shared::auth::totp;
+use axontask_shared::models::audit_log::{AuditAction, AuditLog};
+use axontask_shared::models::membership::{CreateMembership, Membership, MembershipRole};
+use axontask_shared::models::tenant::Tenant;
+use axontask_shared::models::two_factor::UserTwoFactor;
+use axum::http::StatusCode;
+use common::{register, send, RegisteredUser, TestContext, PASSWORD};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Enables two-factor authentication; returns the secret, the step of the
+/// confirming code and the recovery codes
+async fn enable_two_factor(
+    ctx: &TestContext,
+    user_id: Uuid,
+    token: &str,
+) -> (Vec<u8>, i64, Vec<String>) {
+    let (status, _, body) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/2fa/setup",
+        Some(token),
+        json!({ "password": PASSWORD }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(body["provisioning_uri"]
+        .as_str()
+        .unwrap()
+        .starts_with("otpauth://totp/"));
+
+    let secret = UserTwoFactor::find(&ctx.db, user_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .secret;
+    assert_eq!(body["secret"], totp::encode_secret(&secret));
+
+    let step = totp::step_at(chrono::Utc::now().timestamp());
+    let code = totp::code_at(&secret, step);
+    let (status, _, body) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/2fa/enable",
+        Some(token),
+        json!({ "code": code }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let recovery_codes = body["recovery_codes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|code| code.as_str().unwrap().to_string())
+        .collect();
+
+    (secret, step, recovery_codes)
+}
+
+/// Test the second login step with TOTP and recovery codes
+#[tokio::test]
+async fn test_login_with_second_factor() {
+    let ctx = TestContext::new().await.unwrap();
+    let email = format!("mfa-{}@example.com", Uuid::new_v4());
+    let RegisteredUser {
+        tenant_id,
+        user_id,
+        access_token: token,
+        ..
+    } = register(&ctx.app, &email).await;
+
+    let (secret, step, recovery_codes) = enable_two_factor(&ctx, user_id, &token).await;
+    assert_eq!(recovery_codes.len(), 10);
+
+    let (status, _, body) = send(&ctx.app, "GET", "/v1/auth/2fa", Some(&token), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["enabled"], true);
+    assert_eq!(body["recovery_codes_remaining"], 10);
+
+    // The password alone yields only an MFA token
+    let credentials = json!({ "email": email, "password": PASSWORD });
+    let (status, _, body) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/login",
+        None,
+        credentials.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["mfa_required"], true);
+    assert!(body.get("access_token").is_none());
+    let mfa_token = body["mfa_token"].as_str().unwrap().to_string();
+
+    // The MFA token is not an access token
+    let (status, _, _) = send(&ctx.app, "GET", "/v1/auth/2fa", Some(&mfa_token), json!({})).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The enrollment code was used already
+    let replay = json!({ "mfa_token": mfa_token, "code": totp::code_at(&secret, step) });
+    let (status, _, _) = send(&ctx.app, "POST", "/v1/auth/login/mfa", None, replay).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let next = json!({ "mfa_token": mfa_token, "code": totp::code_at(&secret, step + 1) });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login/mfa", None, next).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["mfa_required"], false);
+    assert!(body["access_token"].is_string());
+    assert_eq!(body["tenants"].as_array().unwrap().len(), 1);
+
+    // Recovery codes work once
+    let (_, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, credentials).await;
+    let mfa_token = body["mfa_token"].as_str().unwrap().to_string();
+    let recovery = json!({ "mfa_token": mfa_token, "code": recovery_codes[0].to_uppercase() });
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/login/mfa",
+        None,
+        recovery.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, _) = send(&ctx.app, "POST", "/v1/auth/login/mfa", None, recovery).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let entries = AuditLog::list_for_user(&ctx.db, user_id, 10).await.unwrap();
+    let actions: Vec<&str> = entries.iter().map(|entry| entry.action.as_str()).collect();
+    assert!(actions.contains(&AuditAction::TwoFactorEnabled.as_str()));
+    assert!(actions.contains(&AuditAction::RecoveryCodeUsed.as_str()));
+
+    // Disabling takes the password and a code
+    let disable = json!({ "password": PASSWORD, "code": recovery_codes[1] });
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/2fa/disable",
+        Some(&token),
+        disable,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!UserTwoFactor::is_enabled_for(&ctx.db, user_id)
+        .await
+        .unwrap());
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that a tenant can require two-factor authentication of its members
+#[tokio::test]
+async fn test_tenant_requirement() {
+    let ctx = TestContext::new().await.unwrap();
+    let owner_email = format!("owner-{}@example.com", Uuid::new_v4());
+    let member_email = format!("member-{}@example.com", Uuid::new_v4());
+    let owner = register(&ctx.app, &owner_email).await;
+    let (tenant_id, owner_id, owner_token) = (owner.tenant_id, owner.user_id, owner.access_token);
+    let member = register(&ctx.app, &member_email).await;
+    let (member_tenant_id, member_id, member_token) =
+        (member.tenant_id, member.user_id, member.access_token);
+
+    Membership::create(
+        &ctx.db,
+        CreateMembership {
+            tenant_id,
+            user_id: member_id,
+            role: MembershipRole::Member,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Owners without two-factor authentication can't lock themselves out
+    let required = json!({ "required": true });
+    let (status, _, _) = send(
+        &ctx.app,
+        "PUT",
+        "/v1/members/two-factor",
+        Some(&owner_token),
+        required.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, _, recovery_codes) = enable_two_factor(&ctx, owner_id, &owner_token).await;
+    let (status, _, body) = send(
+        &ctx.app,
+        "PUT",
+        "/v1/members/two-factor",
+        Some(&owner_token),
+        required,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["required"], true);
+
+    let (status, _, body) = send(
+        &ctx.app,
+        "GET",
+        "/v1/members",
+        Some(&owner_token),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let owner = body["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|member| member["user_id"] == owner_id.to_string())
+        .unwrap();
+    assert_eq!(owner["two_factor_enabled"], true);
+
+    // The member can't enter the tenant without enrolling, but can still
+    // use their own
+    let login = json!({ "email": member_email, "password": PASSWORD, "tenant_id": tenant_id });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, login).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["enrollment_required"], true);
+    assert!(body.get("access_token").is_none());
+
+    let login = json!({ "email": member_email, "password": PASSWORD });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, login).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["tenant_id"], member_tenant_id.to_string());
+
+    let switch = json!({
+        "tenant_id": tenant_id,
+        "refresh_token": body["refresh_token"],
+    });
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/switch-tenant",
+        Some(&member_token),
+        switch,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Members of the tenant can't turn two-factor authentication off
+    let disable = json!({ "password": PASSWORD, "code": recovery_codes[0] });
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/2fa/disable",
+        Some(&owner_token),
+        disable,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let entries = AuditLog::list_for_user(&ctx.db, owner_id, 10)
+        .await
+        .unwrap();
+    let change = entries
+        .iter()
+        .find(|entry| entry.action == AuditAction::TwoFactorRequirementChanged.as_str())
+        .unwrap();
+    assert_eq!(change.tenant_id, Some(tenant_id));
+    assert_eq!(change.details["required"], true);
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    Tenant::delete(&ctx.db, member_tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
+
+/// Test that members of a tenant that requires two-factor authentication can
+/// enroll from the login response
+#[tokio::test]
+async fn test_enrollment_at_login() {
+    let ctx = TestContext::new().await.unwrap();
+    let email = format!("enroll-{}@example.com", Uuid::new_v4());
+    let RegisteredUser {
+        tenant_id, user_id, ..
+    } = register(&ctx.app, &email).await;
+
+    // The user's only tenant requires two-factor authentication
+    Tenant::set_require_two_factor(&ctx.db, tenant_id, true)
+        .await
+        .unwrap();
+
+    let login = json!({ "email": email, "password": PASSWORD });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, login.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["tenant_id"], tenant_id.to_string());
+    assert_eq!(body["enrollment_required"], true);
+    assert!(body.get("access_token").is_none());
+    assert!(body.get("refresh_token").is_none());
+    let enrollment_token = body["enrollment_token"].as_str().unwrap().to_string();
+
+    // The enrollment token only works for enrollment
+    let (status, _, _) = send(
+        &ctx.app,
+        "GET",
+        "/v1/members",
+        Some(&enrollment_token),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _, _) = send(
+        &ctx.app,
+        "POST",
+        "/v1/auth/2fa/disable",
+        Some(&enrollment_token),
+        json!({ "password": PASSWORD, "code": "000000" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (secret, step, _) = enable_two_factor(&ctx, user_id, &enrollment_token).await;
+
+    // Logging in again asks for the second factor and opens a session
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login", None, login).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["mfa_required"], true);
+    assert!(body.get("enrollment_required").is_none());
+
+    let mfa = json!({
+        "mfa_token": body["mfa_token"],
+        "code": totp::code_at(&secret, step + 1),
+    });
+    let (status, _, body) = send(&ctx.app, "POST", "/v1/auth/login/mfa", None, mfa).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(body["access_token"].is_string());
+
+    Tenant::delete(&ctx.db, tenant_id).await.unwrap();
+    ctx.cleanup().await.unwrap();
+}
@@ -0,0 +1,231 @@
+/// Email verification and password reset
+///
+/// This module issues and redeems the single-use tokens sent in account
+/// emails (see `models::user_token`). Sending the emails is up to the caller,
+/// which knows the link format and the mailer.
+///
+/// # Rules
+///
+/// - A new token replaces the user's unused token of the same purpose
+/// - Tokens for a user are issued at most once per `RESEND_INTERVAL`, so the
+///   request endpoints can't be used to flood an inbox
+/// - Verification and password resets only apply while the user still has
+///   the address the token was sent to
+/// - A password reset uses up all of the user's reset tokens, ends all of
+///   their sessions, and verifies the address (receiving the email proves
+///   ownership)
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::auth::account::{issue_password_reset, reset_password};
+/// use axontask_shared::models::user::User;
+/// use chrono::Duration;
+/// use sqlx::PgPool;
+///
+/// # async fn example(pool: PgPool, user: User) -> Result<(), Box<dyn std::error::Error>> {
+/// if let Some((token, plaintext)) = issue_password_reset(&pool, &user, Duration::hours(1)).await? {
+///     // Email `plaintext` to `token.email`
+/// }
+///
+/// // Later, from the reset form
+/// let user_id = reset_password(&pool, "rst_...", "N3w-P@ssword").await?;
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::password::{self, PasswordError};
+use crate::models::refresh_token::RefreshToken;
+use crate::models::user::User;
+use crate::models::user_token::{CreateUserToken, TokenPurpose, UserToken};
+
+/// Minimum time between two tokens of the same purpose for a user (seconds)
+pub const RESEND_INTERVAL_SECS: i64 = 60;
+
+/// Error type for account operations
+#[derive(Debug, thiserror::Error)]
+pub enum AccountError {
+    /// The token is unknown, expired or already used
+    #[error("Invalid or expired token")]
+    InvalidToken,
+
+    /// The new password doesn't meet the strength rules
+    #[error("{0}")]
+    WeakPassword(String),
+
+    /// Password hashing failed
+    #[error(transparent)]
+    Password(#[from] PasswordError),
+
+    /// Database error
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+/// Issues an email verification token
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `user` - User whose address to verify
+/// * `ttl` - How long the token stays valid
+///
+/// # Returns
+///
+/// The token and its plaintext (send it to `token.email`; it is not stored),
+/// or None if the address is already verified or a token was issued less
+/// than `RESEND_INTERVAL_SECS` ago
+///
+/// # Errors
+///
+/// Returns an error if the database operation fails
+pub async fn issue_email_verification(
+    pool: &PgPool,
+    user: &User,
+    ttl: Duration,
+) -> Result<Option<(UserToken, String)>, AccountError> {
+    if user.email_verified {
+        return Ok(None);
+    }
+
+    issue(pool, user, TokenPurpose::EmailVerification, ttl).await
+}
+
+/// Issues a password reset token
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `user` - User whose password to reset
+/// * `ttl` - How long the token stays valid
+///
+/// # Returns
+///
+/// The token and its plaintext (send it to `token.email`; it is not stored),
+/// or None if a token was issued less than `RESEND_INTERVAL_SECS` ago
+///
+/// # Errors
+///
+/// Returns an error if the database operation fails
+pub async fn issue_password_reset(
+    pool: &PgPool,
+    user: &User,
+    ttl: Duration,
+) -> Result<Option<(UserToken, String)>, AccountError> {
+    issue(pool, user, TokenPurpose::PasswordReset, ttl).await
+}
+
+/// Issues a token unless one was issued within the resend interval
+async fn issue(
+    pool: &PgPool,
+    user: &User,
+    purpose: TokenPurpose,
+    ttl: Duration,
+) -> Result<Option<(UserToken, String)>, AccountError> {
+    if let Some(issued_at) = UserToken::last_issued_at(pool, user.id, purpose).await? {
+        if Utc::now() - issued_at < Duration::seconds(RESEND_INTERVAL_SECS) {
+            return Ok(None);
+        }
+    }
+
+    let plaintext = UserToken::generate_token(purpose);
+    let token = UserToken::issue(
+        pool,
+        CreateUserToken {
+            user_id: user.id,
+            purpose,
+            email: user.email.clone(),
+            token: plaintext.clone(),
+            expires_at: Utc::now() + ttl,
+        },
+    )
+    .await?;
+
+    Ok(Some((token, plaintext)))
+}
+
+/// Verifies a user's email address with a token from a verification email
+///
+/// # Returns
+///
+/// The ID of the verified user
+///
+/// # Errors
+///
+/// - `InvalidToken` if the token is unknown, expired or used, or the user
+///   changed their address since it was sent
+pub async fn verify_email(pool: &PgPool, token: &str) -> Result<Uuid, AccountError> {
+    let token = UserToken::consume(pool, token, TokenPurpose::EmailVerification)
+        .await?
+        .ok_or(AccountError::InvalidToken)?;
+
+    if !User::mark_email_verified(pool, token.user_id, &token.email).await? {
+        return Err(AccountError::InvalidToken);
+    }
+
+    Ok(token.user_id)
+}
+
+/// Sets a new password with a token from a password reset email
+///
+/// The password is checked before the token is used, so a rejected password
+/// doesn't burn the link. In one transaction, the token and any other reset
+/// token of the user are used up, the password is changed and all of the
+/// user's sessions end.
+///
+/// # Returns
+///
+/// The ID of the user whose password changed
+///
+/// # Errors
+///
+/// - `WeakPassword` if the password doesn't meet the strength rules
+/// - `InvalidToken` if the token is unknown, expired or already used, or the
+///   user changed their address since it was sent
+pub async fn reset_password(
+    pool: &PgPool,
+    token: &str,
+    new_password: &str,
+) -> Result<Uuid, AccountError> {
+    password::validate_password_strength(new_password).map_err(AccountError::WeakPassword)?;
+    let password_hash = password::hash_password(new_password)?;
+
+    let mut tx = pool.begin().await?;
+
+    let token = UserToken::consume(&mut *tx, token, TokenPurpose::PasswordReset)
+        .await?
+        .ok_or(AccountError::InvalidToken)?;
+
+    // Other links, e.g. from an earlier request, must not work afterwards
+    UserToken::invalidate_all(&mut *tx, token.user_id, TokenPurpose::PasswordReset).await?;
+
+    if !User::reset_password(&mut *tx, token.user_id, &token.email, &password_hash).await? {
+        // Sent to a previous address: the links are used up, nothing else changes
+        tx.commit().await?;
+        return Err(AccountError::InvalidToken);
+    }
+
+    RefreshToken::revoke_all_for_user(&mut *tx, token.user_id).await?;
+    tx.commit().await?;
+
+    Ok(token.user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(AccountError::InvalidToken.to_string(), "Invalid or expired token");
+        assert_eq!(
+            AccountError::WeakPassword("Password must be at least 8 characters long".to_string())
+                .to_string(),
+            "Password must be at least 8 characters long"
+        );
+    }
+}
@@ -41,7 +41,7 @@ use sha2::{Digest, Sha256};
 const KEY_RANDOM_LENGTH: usize = 32;
 
 /// API key prefix
-const KEY_PREFIX: &str = "axon_";
+pub const KEY_PREFIX: &str = "axon_";
 
 /// Total length of an API key (prefix + random)
 pub const API_KEY_LENGTH: usize = KEY_PREFIX.len() + KEY_RANDOM_LENGTH;
@@ -285,12 +285,40 @@ pub fn parse_scopes(scopes_str: &str) -> Vec<String> {
         .collect()
 }
 
+/// Maps a legacy scope name to its `resource:action` form
+///
+/// Keys created before scopes were enforced use `read:task` style names
+/// (the `api_keys.scopes` column default), and `admin` grants everything.
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::api_key::canonical_scope;
+///
+/// assert_eq!(canonical_scope("read:task"), "tasks:read");
+/// assert_eq!(canonical_scope("admin"), "*");
+/// assert_eq!(canonical_scope("usage:read"), "usage:read");
+/// ```
+pub fn canonical_scope(scope: &str) -> &str {
+    match scope {
+        "admin" => "*",
+        "read:task" => "tasks:read",
+        "write:task" => "tasks:write",
+        "read:webhook" => "webhooks:read",
+        "write:webhook" => "webhooks:manage",
+        other => other,
+    }
+}
+
 /// Checks if a scope list contains a required scope
 ///
 /// Supports wildcard matching with `*`:
 /// - `tasks:*` matches `tasks:read`, `tasks:write`, etc.
 /// - `*` matches everything
 ///
+/// `<resource>:write` and `<resource>:manage` also grant `<resource>:read`,
+/// and legacy names are accepted (see `canonical_scope`).
+///
 /// # Arguments
 ///
 /// * `scopes` - List of granted scopes
@@ -323,6 +351,8 @@ pub fn parse_scopes(scopes_str: &str) -> Vec<String> {
 /// ```
 pub fn has_scope(scopes: &[String], required: &str) -> bool {
     for scope in scopes {
+        let scope = canonical_scope(scope);
+
         // Global wildcard
         if scope == "*" {
             return true;
@@ -340,6 +370,15 @@ pub fn has_scope(scopes: &[String], required: &str) -> bool {
                 return true;
             }
         }
+
+        // Write access implies read access (e.g., "tasks:write" grants "tasks:read")
+        if let (Some((resource, "read")), Some((granted, "write" | "manage"))) =
+            (required.split_once(':'), scope.split_once(':'))
+        {
+            if resource == granted {
+                return true;
+            }
+        }
     }
 
     false
@@ -515,6 +554,33 @@ mod tests {
         assert!(!has_scope(&scopes, "users:read"));
     }
 
+    #[test]
+    fn test_has_scope_write_implies_read() {
+        let scopes = vec!["tasks:write".to_string(), "webhooks:manage".to_string()];
+
+        assert!(has_scope(&scopes, "tasks:read"));
+        assert!(has_scope(&scopes, "webhooks:read"));
+        assert!(!has_scope(&scopes, "usage:read"));
+
+        let read_only = vec!["tasks:read".to_string()];
+        assert!(!has_scope(&read_only, "tasks:write"));
+    }
+
+    #[test]
+    fn test_has_scope_legacy_names() {
+        let scopes = vec!["read:task".to_string()];
+        assert!(has_scope(&scopes, "tasks:read"));
+        assert!(!has_scope(&scopes, "tasks:write"));
+
+        let scopes = vec!["write:task".to_string(), "read:webhook".to_string()];
+        assert!(has_scope(&scopes, "tasks:write"));
+        assert!(has_scope(&scopes, "webhooks:read"));
+        assert!(!has_scope(&scopes, "webhooks:manage"));
+
+        let scopes = vec!["admin".to_string()];
+        assert!(has_scope(&scopes, "webhooks:manage"));
+    }
+
     #[test]
     fn test_has_scope_global_wildcard() {
         let admin_scopes = vec!["*".to_string()];
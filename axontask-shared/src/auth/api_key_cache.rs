@@ -0,0 +1,232 @@
+/// In-memory cache of validated API keys
+///
+/// Authenticating an API key costs a database round trip (`ApiKey::validate`,
+/// which also updates `last_used_at`). Agents using API keys make many small
+/// requests, so validated keys are cached briefly per process.
+///
+/// # Consistency
+///
+/// - Entries expire after the TTL (30 seconds by default), so `last_used_at`
+///   is at most one TTL behind
+/// - Revoking a key invalidates it in this process immediately; other API
+///   instances stop accepting it within one TTL
+/// - Expired keys (`expires_at`) are never returned, even if cached
+///
+/// Only valid keys are cached, keyed by their SHA-256 hash.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::auth::api_key_cache::ApiKeyCache;
+/// use axontask_shared::models::api_key::ApiKey;
+/// use std::time::Duration;
+///
+/// # async fn example(pool: sqlx::PgPool, plaintext_key: &str) -> Result<(), sqlx::Error> {
+/// let cache = ApiKeyCache::new(Duration::from_secs(30));
+/// let key_hash = ApiKey::hash_key(plaintext_key);
+///
+/// let api_key = match cache.get(&key_hash) {
+///     Some(api_key) => Some(api_key),
+///     None => ApiKey::validate(&pool, plaintext_key).await?,
+/// };
+///
+/// if let Some(api_key) = api_key {
+///     cache.insert(key_hash, api_key);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use crate::models::api_key::ApiKey;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Default time a validated key is cached (30 seconds)
+pub const DEFAULT_API_KEY_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Cached API key entry
+#[derive(Debug, Clone)]
+struct CachedApiKey {
+    /// Validated key record
+    api_key: ApiKey,
+
+    /// When the key was validated against the database
+    cached_at: Instant,
+}
+
+/// In-memory API key cache
+///
+/// Cheap to clone; clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct ApiKeyCache {
+    /// Entries keyed by key hash
+    entries: Arc<Mutex<HashMap<String, CachedApiKey>>>,
+
+    /// Time an entry stays valid (zero disables caching)
+    ttl: Duration,
+}
+
+impl Default for ApiKeyCache {
+    fn default() -> Self {
+        ApiKeyCache::new(DEFAULT_API_KEY_CACHE_TTL)
+    }
+}
+
+impl ApiKeyCache {
+    /// Creates a new cache
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - Time a validated key is cached (zero disables caching)
+    pub fn new(ttl: Duration) -> Self {
+        ApiKeyCache {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    /// Gets a cached key by hash
+    ///
+    /// # Returns
+    ///
+    /// The key if it was validated within the TTL and has not expired since
+    pub fn get(&self, key_hash: &str) -> Option<ApiKey> {
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.get(key_hash) {
+            Some(entry) if entry.cached_at.elapsed() < self.ttl && !entry.api_key.is_expired() => {
+                Some(entry.api_key.clone())
+            }
+            Some(_) => {
+                entries.remove(key_hash);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches a validated key
+    ///
+    /// Also drops stale entries, so the cache only holds keys used within the
+    /// last TTL.
+    pub fn insert(&self, key_hash: String, api_key: ApiKey) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| entry.cached_at.elapsed() < self.ttl);
+        entries.insert(
+            key_hash,
+            CachedApiKey {
+                api_key,
+                cached_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Removes a key from the cache (e.g. after it is revoked)
+    pub fn invalidate(&self, api_key_id: Uuid) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, entry| entry.api_key.id != api_key_id);
+    }
+
+    /// Gets the number of cached keys
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Checks if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the cache TTL
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn api_key(expires_at: Option<chrono::DateTime<Utc>>) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            key_prefix: "axon_test".to_string(),
+            key_hash: "hash".to_string(),
+            scopes: vec!["read:task".to_string()],
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked: false,
+            revoked_at: None,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_insert_and_get() {
+        let cache = ApiKeyCache::default();
+        let key = api_key(None);
+
+        assert!(cache.get("hash").is_none());
+
+        cache.insert("hash".to_string(), key.clone());
+        assert_eq!(cache.get("hash").unwrap().id, key.id);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = ApiKeyCache::new(Duration::from_millis(20));
+        cache.insert("hash".to_string(), api_key(None));
+
+        std::thread::sleep(Duration::from_millis(30));
+
+        assert!(cache.get("hash").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_expired_key_not_returned() {
+        let cache = ApiKeyCache::default();
+        cache.insert(
+            "hash".to_string(),
+            api_key(Some(Utc::now() - chrono::Duration::seconds(1))),
+        );
+
+        assert!(cache.get("hash").is_none());
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let cache = ApiKeyCache::default();
+        let key = api_key(None);
+        let other = api_key(None);
+
+        cache.insert("hash".to_string(), key.clone());
+        cache.insert("other".to_string(), other);
+
+        cache.invalidate(key.id);
+
+        assert!(cache.get("hash").is_none());
+        assert!(cache.get("other").is_some());
+    }
+
+    #[test]
+    fn test_zero_ttl_disables_cache() {
+        let cache = ApiKeyCache::new(Duration::ZERO);
+        cache.insert("hash".to_string(), api_key(None));
+
+        assert!(cache.is_empty());
+        assert!(cache.get("hash").is_none());
+    }
+}
@@ -12,6 +12,10 @@
 /// 3. **Resource-Level Permissions**: Additional checks for specific resources
 /// 4. **Scope-Based Permissions**: For API keys with limited scopes
 ///
+/// Users (JWT) are checked against their role; API keys belong to the tenant
+/// rather than a member, so they are checked against their scopes only. A
+/// `Policy` names both requirements for a route.
+///
 /// # Example
 ///
 /// ```no_run
@@ -39,8 +43,9 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use super::middleware::AuthContext;
+use super::middleware::{AuthContext, AuthMethod};
 use crate::models::membership::{Membership, MembershipRole};
+use crate::models::task::Task;
 
 /// Error type for authorization checks
 #[derive(Debug, thiserror::Error)]
@@ -97,6 +102,64 @@ impl ResourcePermission {
     }
 }
 
+/// Authorization policy for a route
+///
+/// Names the permission a member needs (via their role) and the scope an
+/// API key needs.
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::authorization::{Policy, ResourcePermission};
+/// use axontask_shared::auth::middleware::AuthContext;
+/// use axontask_shared::models::membership::MembershipRole;
+/// use uuid::Uuid;
+///
+/// const TASKS_WRITE: Policy = Policy::new(ResourcePermission::Write, "tasks:write");
+///
+/// let auth = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+/// assert!(TASKS_WRITE.check(&auth, Some(MembershipRole::Member)).is_ok());
+/// assert!(TASKS_WRITE.check(&auth, Some(MembershipRole::Viewer)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policy {
+    /// Permission required of members
+    pub permission: ResourcePermission,
+
+    /// Scope required of API keys
+    pub scope: &'static str,
+}
+
+impl Policy {
+    /// Creates a new policy
+    pub const fn new(permission: ResourcePermission, scope: &'static str) -> Self {
+        Policy { permission, scope }
+    }
+
+    /// Checks an auth context against the policy
+    ///
+    /// # Arguments
+    ///
+    /// * `auth` - Authentication context
+    /// * `role` - Member's role in the tenant (None if not a member; ignored for API keys)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the member's role or the key's scopes are insufficient
+    pub fn check(&self, auth: &AuthContext, role: Option<MembershipRole>) -> Result<(), AuthzError> {
+        check_permission(auth, role, self.permission, self.scope)
+    }
+
+    /// Authorizes an auth context, looking up the member's role
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not authorized or the role lookup fails
+    pub async fn authorize(&self, pool: &PgPool, auth: &AuthContext) -> Result<(), AuthzError> {
+        require_permission(pool, auth, self.permission, self.scope).await
+    }
+}
+
 /// Checks if a user is a member of a tenant
 ///
 /// # Arguments
@@ -229,11 +292,49 @@ pub fn require_scope(auth: &AuthContext, required_scope: &str) -> Result<(), Aut
     Ok(())
 }
 
+/// Checks an auth context's permission given the member's role
+///
+/// - JWT: the member's role must grant `permission`
+/// - API key: the key's scopes must include `scope`
+///
+/// # Arguments
+///
+/// * `auth` - Authentication context
+/// * `role` - Member's role in the tenant (None if not a member; ignored for API keys)
+/// * `permission` - Required permission level (for users)
+/// * `scope` - Required scope (for API keys)
+///
+/// # Errors
+///
+/// Returns `NotMember`, `InsufficientRole` or `MissingScope`
+pub fn check_permission(
+    auth: &AuthContext,
+    role: Option<MembershipRole>,
+    permission: ResourcePermission,
+    scope: &str,
+) -> Result<(), AuthzError> {
+    match auth.method {
+        AuthMethod::ApiKey => require_scope(auth, scope),
+        AuthMethod::Jwt => {
+            let role = role.ok_or(AuthzError::NotMember(auth.tenant_id))?;
+            let required = permission.min_role();
+
+            if !role.has_permission(&required) {
+                return Err(AuthzError::InsufficientRole {
+                    required,
+                    actual: role,
+                });
+            }
+
+            Ok(())
+        }
+    }
+}
+
 /// Checks if auth context has permission for a resource
 ///
-/// Combines role and scope checks:
-/// 1. Verifies user has required role in tenant
-/// 2. Verifies scope (for API keys)
+/// Users (JWT) must have the required role in the tenant; API keys must have
+/// the required scope.
 ///
 /// # Arguments
 ///
@@ -263,13 +364,31 @@ pub async fn require_permission(
     permission: ResourcePermission,
     scope: &str,
 ) -> Result<(), AuthzError> {
-    // Check role
-    require_role(pool, auth.tenant_id, auth.user_id, permission.min_role()).await?;
+    let role = member_role(pool, auth).await?;
 
-    // Check scope (for API keys)
-    require_scope(auth, scope)?;
+    check_permission(auth, role, permission, scope)
+}
 
-    Ok(())
+/// Looks up the member's role for an auth context
+///
+/// # Returns
+///
+/// The user's role in the tenant, or None for non-members and API keys
+/// (which are not members, so only their scopes apply)
+///
+/// # Errors
+///
+/// Returns an error if the database query fails
+pub async fn member_role(
+    pool: &PgPool,
+    auth: &AuthContext,
+) -> Result<Option<MembershipRole>, AuthzError> {
+    match (auth.method, auth.user_id) {
+        (AuthMethod::Jwt, Some(user_id)) => {
+            Ok(Membership::get_role(pool, auth.tenant_id, user_id).await?)
+        }
+        _ => Ok(None),
+    }
 }
 
 /// Checks if user owns a resource
@@ -352,6 +471,121 @@ pub async fn require_access(
     require_permission(pool, auth, permission, scope).await
 }
 
+/// Gets the creator a member's task access is limited to
+///
+/// Task ownership rules:
+/// - **Owner/Admin**: all of the tenant's tasks
+/// - **Member**: only tasks they created (`created_by`)
+/// - **Viewer**: reads all of the tenant's tasks; never writes
+/// - **API key**: all of the tenant's tasks (keys belong to the tenant, and
+///   only admins can create them)
+///
+/// # Arguments
+///
+/// * `auth` - Authentication context
+/// * `role` - Member's role in the tenant (ignored for API keys)
+/// * `permission` - Access needed (`Read` to view, `Write` to control)
+///
+/// # Returns
+///
+/// The user ID tasks must be created by, or None if all tasks are accessible
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::authorization::{own_tasks_only, ResourcePermission};
+/// use axontask_shared::auth::middleware::AuthContext;
+/// use axontask_shared::models::membership::MembershipRole;
+/// use uuid::Uuid;
+///
+/// let user_id = Uuid::new_v4();
+/// let auth = AuthContext::from_jwt(user_id, Uuid::new_v4());
+///
+/// let member = own_tasks_only(&auth, Some(MembershipRole::Member), ResourcePermission::Read);
+/// assert_eq!(member, Some(user_id));
+///
+/// let admin = own_tasks_only(&auth, Some(MembershipRole::Admin), ResourcePermission::Write);
+/// assert_eq!(admin, None);
+/// ```
+pub fn own_tasks_only(
+    auth: &AuthContext,
+    role: Option<MembershipRole>,
+    permission: ResourcePermission,
+) -> Option<Uuid> {
+    match (auth.method, role) {
+        (AuthMethod::ApiKey, _) => None,
+        (AuthMethod::Jwt, Some(role)) if role.can_view_all_tasks() => None,
+        (AuthMethod::Jwt, Some(MembershipRole::Viewer)) if permission == ResourcePermission::Read => None,
+        // A JWT always carries a user ID; match no task otherwise
+        (AuthMethod::Jwt, _) => Some(auth.user_id.unwrap_or(Uuid::nil())),
+    }
+}
+
+/// Checks an auth context's access to a task given the member's role
+///
+/// Applies the role check for `permission` and then the ownership rules of
+/// `own_tasks_only`. API key scopes are checked by the route's policy.
+///
+/// # Arguments
+///
+/// * `auth` - Authentication context
+/// * `role` - Member's role in the tenant (None if not a member; ignored for API keys)
+/// * `task` - Task being accessed
+/// * `permission` - Access needed (`Read` to view, `Write` to control)
+///
+/// # Errors
+///
+/// - `NotMember` / `InsufficientRole` if the member's role is insufficient
+/// - `NotAuthorized` if the task belongs to another tenant or, for members,
+///   was created by someone else
+pub fn check_task_access(
+    auth: &AuthContext,
+    role: Option<MembershipRole>,
+    task: &Task,
+    permission: ResourcePermission,
+) -> Result<(), AuthzError> {
+    if task.tenant_id != auth.tenant_id {
+        return Err(AuthzError::NotAuthorized);
+    }
+
+    if auth.method == AuthMethod::Jwt {
+        check_permission(auth, role, permission, "")?;
+    }
+
+    match own_tasks_only(auth, role, permission) {
+        Some(user_id) if task.created_by != Some(user_id) => Err(AuthzError::NotAuthorized),
+        _ => Ok(()),
+    }
+}
+
+/// Checks if auth context can access a task
+///
+/// Looks up the member's role and applies `check_task_access`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use axontask_shared::auth::authorization::{require_task_access, ResourcePermission};
+/// # use axontask_shared::auth::middleware::AuthContext;
+/// # use axontask_shared::models::task::Task;
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool, auth: AuthContext, task: Task) -> Result<(), Box<dyn std::error::Error>> {
+/// // Members may only cancel their own tasks
+/// require_task_access(&pool, &auth, &task, ResourcePermission::Write).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn require_task_access(
+    pool: &PgPool,
+    auth: &AuthContext,
+    task: &Task,
+    permission: ResourcePermission,
+) -> Result<(), AuthzError> {
+    let role = member_role(pool, auth).await?;
+
+    check_task_access(auth, role, task, permission)
+}
+
 /// Checks if user can manage billing
 ///
 /// Only owners can manage billing (change plans, update payment methods, etc.)
@@ -419,6 +653,80 @@ mod tests {
         assert!(require_scope(&auth, "tasks:delete").is_err());
     }
 
+    fn api_key_auth(scopes: &[&str]) -> AuthContext {
+        let mut auth = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+        auth.user_id = None;
+        auth.method = AuthMethod::ApiKey;
+        auth.scopes = Some(scopes.iter().map(|s| s.to_string()).collect());
+        auth
+    }
+
+    #[test]
+    fn test_check_permission_roles() {
+        let auth = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+        let roles = [
+            MembershipRole::Viewer,
+            MembershipRole::Member,
+            MembershipRole::Admin,
+            MembershipRole::Owner,
+        ];
+        let permissions = [
+            ResourcePermission::Read,
+            ResourcePermission::Write,
+            ResourcePermission::Manage,
+            ResourcePermission::Own,
+        ];
+
+        for (role_level, role) in roles.iter().enumerate() {
+            for (permission_level, permission) in permissions.iter().enumerate() {
+                let result = check_permission(&auth, Some(*role), *permission, "unused");
+                assert_eq!(
+                    result.is_ok(),
+                    role_level >= permission_level,
+                    "{:?} with {:?}",
+                    role,
+                    permission
+                );
+            }
+        }
+
+        // Not a member
+        assert!(matches!(
+            check_permission(&auth, None, ResourcePermission::Read, "tasks:read"),
+            Err(AuthzError::NotMember(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_permission_api_key_scopes() {
+        let auth = api_key_auth(&["tasks:read"]);
+
+        // Role is ignored for API keys
+        assert!(check_permission(&auth, None, ResourcePermission::Read, "tasks:read").is_ok());
+        assert!(check_permission(&auth, Some(MembershipRole::Owner), ResourcePermission::Write, "tasks:write").is_err());
+        assert!(matches!(
+            check_permission(&auth, None, ResourcePermission::Write, "tasks:write"),
+            Err(AuthzError::MissingScope(_))
+        ));
+
+        let auth = api_key_auth(&["tasks:write"]);
+        assert!(check_permission(&auth, None, ResourcePermission::Write, "tasks:write").is_ok());
+        assert!(check_permission(&auth, None, ResourcePermission::Read, "tasks:read").is_ok());
+        assert!(check_permission(&auth, None, ResourcePermission::Manage, "webhooks:manage").is_err());
+    }
+
+    #[test]
+    fn test_policy_check() {
+        const WEBHOOKS_MANAGE: Policy = Policy::new(ResourcePermission::Manage, "webhooks:manage");
+
+        let jwt = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+        assert!(WEBHOOKS_MANAGE.check(&jwt, Some(MembershipRole::Admin)).is_ok());
+        assert!(WEBHOOKS_MANAGE.check(&jwt, Some(MembershipRole::Member)).is_err());
+
+        assert!(WEBHOOKS_MANAGE.check(&api_key_auth(&["webhooks:*"]), None).is_ok());
+        assert!(WEBHOOKS_MANAGE.check(&api_key_auth(&["tasks:*"]), None).is_err());
+    }
+
     #[test]
     fn test_require_ownership() {
         let user_id = Uuid::new_v4();
@@ -442,4 +750,84 @@ mod tests {
         let err = AuthzError::NotAuthorized;
         assert!(err.to_string().contains("Not authorized"));
     }
+
+    fn task(tenant_id: Uuid, created_by: Option<Uuid>) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            tenant_id,
+            created_by,
+            api_key_id: None,
+            name: "Test".to_string(),
+            adapter: "mock".to_string(),
+            args: serde_json::json!({}),
+            tags: vec![],
+            state: crate::models::task::TaskState::Running,
+            started_at: None,
+            ended_at: None,
+            cursor: 0,
+            bytes_streamed: 0,
+            minutes_used: 0,
+            timeout_seconds: 3600,
+            error_message: None,
+            exit_code: None,
+            paused_at: None,
+            paused_seconds: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_check_task_access_roles() {
+        let user_id = Uuid::new_v4();
+        let auth = AuthContext::from_jwt(user_id, Uuid::new_v4());
+        let own = task(auth.tenant_id, Some(user_id));
+        let other = task(auth.tenant_id, Some(Uuid::new_v4()));
+        let by_api_key = task(auth.tenant_id, None);
+
+        // (role, read own, write own, read other, write other)
+        let cases = [
+            (MembershipRole::Viewer, true, false, true, false),
+            (MembershipRole::Member, true, true, false, false),
+            (MembershipRole::Admin, true, true, true, true),
+            (MembershipRole::Owner, true, true, true, true),
+        ];
+
+        for (role, read_own, write_own, read_other, write_other) in cases {
+            let check = |task: &Task, permission| check_task_access(&auth, Some(role), task, permission).is_ok();
+
+            assert_eq!(check(&own, ResourcePermission::Read), read_own, "{:?} read own", role);
+            assert_eq!(check(&own, ResourcePermission::Write), write_own, "{:?} write own", role);
+            assert_eq!(check(&other, ResourcePermission::Read), read_other, "{:?} read other", role);
+            assert_eq!(check(&other, ResourcePermission::Write), write_other, "{:?} write other", role);
+            assert_eq!(check(&by_api_key, ResourcePermission::Read), read_other, "{:?} read key task", role);
+        }
+
+        // Not a member
+        assert!(matches!(
+            check_task_access(&auth, None, &own, ResourcePermission::Read),
+            Err(AuthzError::NotMember(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_task_access_api_key() {
+        let auth = api_key_auth(&["tasks:write"]);
+        let member_task = task(auth.tenant_id, Some(Uuid::new_v4()));
+
+        assert!(check_task_access(&auth, None, &member_task, ResourcePermission::Write).is_ok());
+        assert_eq!(own_tasks_only(&auth, None, ResourcePermission::Write), None);
+    }
+
+    #[test]
+    fn test_check_task_access_other_tenant() {
+        let auth = AuthContext::from_jwt(Uuid::new_v4(), Uuid::new_v4());
+        let foreign = task(Uuid::new_v4(), auth.user_id);
+
+        assert!(matches!(
+            check_task_access(&auth, Some(MembershipRole::Owner), &foreign, ResourcePermission::Read),
+            Err(AuthzError::NotAuthorized)
+        ));
+        assert!(check_task_access(&api_key_auth(&["*"]), None, &foreign, ResourcePermission::Read).is_err());
+    }
 }
@@ -1,11 +1,15 @@
 /// JWT token generation and validation module
 ///
 /// This module provides JWT (JSON Web Token) functionality for user authentication.
-/// Tokens are signed using HS256 (HMAC-SHA256) and include claims for user/tenant identity.
+/// Tokens include claims for user/tenant identity.
+///
+/// The functions here sign and validate with a single HS256 secret. The API
+/// uses a [`JwtKeySet`](super::jwt_keys::JwtKeySet) instead, which supports
+/// multiple keys, RS256/EdDSA and key rotation.
 ///
 /// # Security
 ///
-/// - **Algorithm**: HS256 (HMAC with SHA-256)
+/// - **Algorithm**: HS256 (HMAC with SHA-256), or per key with `JwtKeySet`
 /// - **Expiration**: Configurable (default 24 hours for access, 30 days for refresh)
 /// - **Validation**: Signature, expiration, and issuer checks
 /// - **Secret Management**: Secrets should be at least 32 bytes (256 bits)
@@ -14,6 +18,8 @@
 ///
 /// - **Access Token**: Short-lived (24h), used for API authentication
 /// - **Refresh Token**: Long-lived (30d), used to obtain new access tokens
+/// - **MFA Challenge Token**: Short-lived (5m), issued after the password
+///   check for accounts with two-factor authentication
 ///
 /// # Example
 ///
@@ -37,10 +43,11 @@
 /// ```
 
 use chrono::{DateTime, Duration, Utc};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::jwt_keys::JwtKeySet;
+
 /// Error type for JWT operations
 #[derive(Debug, thiserror::Error)]
 pub enum JwtError {
@@ -63,6 +70,10 @@ pub enum JwtError {
     /// Invalid issuer
     #[error("Invalid issuer: expected {expected}, got {actual}")]
     InvalidIssuer { expected: String, actual: String },
+
+    /// Invalid signing key configuration
+    #[error("Invalid signing key: {0}")]
+    InvalidKey(String),
 }
 
 /// Token type identifier
@@ -74,6 +85,14 @@ pub enum TokenType {
 
     /// Refresh token (long-lived, 30 days)
     Refresh,
+
+    /// MFA challenge token (5 minutes): proves the password was checked and
+    /// is exchanged for a session with a second factor
+    Mfa,
+
+    /// Enrollment token (15 minutes): lets a user whose tenant requires
+    /// two-factor authentication set it up, and nothing else
+    Enrollment,
 }
 
 impl TokenType {
@@ -82,6 +101,8 @@ impl TokenType {
         match self {
             TokenType::Access => Duration::hours(24),
             TokenType::Refresh => Duration::days(30),
+            TokenType::Mfa => Duration::minutes(5),
+            TokenType::Enrollment => Duration::minutes(15),
         }
     }
 
@@ -90,6 +111,8 @@ impl TokenType {
         match self {
             TokenType::Access => "access",
             TokenType::Refresh => "refresh",
+            TokenType::Mfa => "mfa",
+            TokenType::Enrollment => "enrollment",
         }
     }
 }
@@ -105,6 +128,8 @@ impl TokenType {
 /// - `iat`: Issued at timestamp
 /// - `exp`: Expiration timestamp
 /// - `nbf`: Not before timestamp
+/// - `jti`: Token ID (unique per token, so tokens issued in the same second
+///   differ)
 ///
 /// # Custom Claims
 ///
@@ -127,6 +152,10 @@ pub struct Claims {
     /// Not before (Unix timestamp)
     pub nbf: i64,
 
+    /// Token ID (nil for tokens issued before token IDs were added)
+    #[serde(default)]
+    pub jti: Uuid,
+
     /// Tenant ID (custom claim)
     pub tenant_id: Uuid,
 
@@ -169,6 +198,7 @@ impl Claims {
             iat: now.timestamp(),
             exp: expiration.timestamp(),
             nbf: now.timestamp(),
+            jti: Uuid::new_v4(),
             tenant_id,
             token_type,
         }
@@ -212,6 +242,7 @@ impl Claims {
             iat: now.timestamp(),
             exp: expiration.timestamp(),
             nbf: now.timestamp(),
+            jti: Uuid::new_v4(),
             tenant_id,
             token_type,
         }
@@ -235,7 +266,8 @@ impl Claims {
 
 /// Creates a JWT token from claims
 ///
-/// Signs the token using HS256 (HMAC-SHA256) with the provided secret.
+/// Signs the token using HS256 (HMAC-SHA256) with the provided secret
+/// (key ID `default`).
 ///
 /// # Arguments
 ///
@@ -277,11 +309,7 @@ impl Claims {
 /// # }
 /// ```
 pub fn create_token(claims: &Claims, secret: &str) -> Result<String, JwtError> {
-    let header = Header::new(Algorithm::HS256);
-    let key = EncodingKey::from_secret(secret.as_bytes());
-
-    encode(&header, claims, &key)
-        .map_err(|e| JwtError::CreateError(format!("Token encoding failed: {}", e)))
+    JwtKeySet::from_secret_unchecked(secret).sign(claims)
 }
 
 /// Validates a JWT token and extracts claims
@@ -332,25 +360,7 @@ pub fn create_token(claims: &Claims, secret: &str) -> Result<String, JwtError> {
 /// # }
 /// ```
 pub fn validate_token(token: &str, secret: &str) -> Result<Claims, JwtError> {
-    let key = DecodingKey::from_secret(secret.as_bytes());
-
-    let mut validation = Validation::new(Algorithm::HS256);
-    validation.set_issuer(&["axontask"]);
-    validation.validate_exp = true;
-    validation.validate_nbf = true;
-
-    let token_data = decode::<Claims>(token, &key, &validation).map_err(|e| {
-        match e.kind() {
-            jsonwebtoken::errors::ErrorKind::ExpiredSignature => JwtError::Expired,
-            jsonwebtoken::errors::ErrorKind::InvalidIssuer => JwtError::InvalidIssuer {
-                expected: "axontask".to_string(),
-                actual: "unknown".to_string(),
-            },
-            _ => JwtError::ValidationError(format!("Token validation failed: {}", e)),
-        }
-    })?;
-
-    Ok(token_data.claims)
+    JwtKeySet::from_secret_unchecked(secret).validate(token)
 }
 
 /// Validates token and checks it's an access token
@@ -378,7 +388,7 @@ pub fn validate_access_token(token: &str, secret: &str) -> Result<Claims, JwtErr
 
     if claims.token_type != TokenType::Access {
         return Err(JwtError::ValidationError(
-            "Expected access token, got refresh token".to_string(),
+            format!("Expected access token, got {} token", claims.token_type.as_str()),
         ));
     }
 
@@ -410,7 +420,7 @@ pub fn validate_refresh_token(token: &str, secret: &str) -> Result<Claims, JwtEr
 
     if claims.token_type != TokenType::Refresh {
         return Err(JwtError::ValidationError(
-            "Expected refresh token, got access token".to_string(),
+            format!("Expected refresh token, got {} token", claims.token_type.as_str()),
         ));
     }
 
@@ -422,6 +432,9 @@ pub fn validate_refresh_token(token: &str, secret: &str) -> Result<Claims, JwtEr
 /// Takes a valid refresh token and generates a new access token
 /// with the same user/tenant context.
 ///
+/// This check is stateless: it does not rotate the refresh token or check
+/// whether it was revoked. The API uses `session::refresh_session` instead.
+///
 /// # Arguments
 ///
 /// * `refresh_token` - Valid refresh token
@@ -648,4 +661,19 @@ mod tests {
         assert_eq!(validated_new.sub, user_id);
         assert_eq!(validated_new.tenant_id, tenant_id);
     }
+
+    #[test]
+    fn test_tokens_are_unique() {
+        let user_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+        let secret = "my-secret-key-for-testing-purposes";
+
+        // Same claims in the same second still produce different tokens
+        let first = create_token(&Claims::new(user_id, tenant_id, TokenType::Refresh), secret).unwrap();
+        let second = create_token(&Claims::new(user_id, tenant_id, TokenType::Refresh), secret).unwrap();
+        assert_ne!(first, second);
+
+        let claims = validate_refresh_token(&first, secret).unwrap();
+        assert!(!claims.jti.is_nil());
+    }
 }
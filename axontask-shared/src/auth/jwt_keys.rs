@@ -0,0 +1,707 @@
+/// JWT signing keys with key IDs and rotation
+///
+/// A `JwtKeySet` holds every key tokens may be signed with, each identified by
+/// a key ID (`kid`). Exactly one key signs new tokens; tokens are accepted if
+/// any key in the set verifies them. This lets keys be rotated in stages
+/// without logging anyone out.
+///
+/// # Algorithms
+///
+/// - **HS256**: Shared secret (at least 32 bytes). Never published.
+/// - **RS256**: RSA key (at least 2048 bits), PKCS#1 or PKCS#8 PEM
+/// - **EdDSA**: Ed25519 key, PKCS#8 PEM
+///
+/// Asymmetric keys may be configured with only their public PEM: they verify
+/// tokens but cannot sign. Their public halves are published as a JWKS so
+/// other services can verify tokens without holding a secret.
+///
+/// # Rotation
+///
+/// 1. Add the new key to every instance (still signing with the old key), so
+///    it is published and accepted everywhere
+/// 2. Switch the signing key ID to the new key
+/// 3. Remove the old key once tokens signed with it have expired (30 days,
+///    the refresh token lifetime)
+///
+/// # Key Selection
+///
+/// Tokens carry the signing key's ID in their `kid` header and are verified
+/// with that key only; a token naming an unknown key is rejected. Tokens
+/// without a `kid` (issued before key IDs were added) are tried against every
+/// key of the token's algorithm.
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::jwt::{Claims, TokenType};
+/// use axontask_shared::auth::jwt_keys::{JwtKey, JwtKeySet};
+/// use uuid::Uuid;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let old = JwtKey::hs256("2025-01", "old-secret-key-at-least-32-bytes-long")?;
+/// let new = JwtKey::hs256("2025-02", "new-secret-key-at-least-32-bytes-long")?;
+///
+/// // Sign with the old key while the new one is rolled out
+/// let before = JwtKeySet::new(vec![old.clone(), new.clone()], "2025-01")?;
+/// let token = before.sign(&Claims::new(Uuid::new_v4(), Uuid::new_v4(), TokenType::Access))?;
+///
+/// // After switching, tokens signed with the old key are still accepted
+/// let after = JwtKeySet::new(vec![old, new], "2025-02")?;
+/// assert!(after.validate_access(&token).is_ok());
+/// # Ok(())
+/// # }
+/// ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{decode, decode_header, encode, DecodingKey, EncodingKey, Header, Validation};
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
+use rsa::traits::PublicKeyParts;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+
+use super::jwt::{Claims, JwtError, TokenType};
+
+pub use jsonwebtoken::Algorithm;
+
+/// Key ID of the key built from a single shared secret
+pub const DEFAULT_KEY_ID: &str = "default";
+
+/// Minimum HS256 secret length in bytes
+pub const MIN_SECRET_LENGTH: usize = 32;
+
+/// Minimum RSA key size in bits
+pub const MIN_RSA_BITS: usize = 2048;
+
+/// Token issuer
+const ISSUER: &str = "axontask";
+
+/// A signing or verification key
+#[derive(Clone)]
+pub struct JwtKey {
+    kid: String,
+    algorithm: Algorithm,
+    encoding: Option<EncodingKey>,
+    decoding: DecodingKey,
+    jwk: Option<Jwk>,
+}
+
+impl JwtKey {
+    /// Creates an HS256 key from a shared secret
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::InvalidKey` if the secret is shorter than
+    /// `MIN_SECRET_LENGTH` bytes
+    pub fn hs256(kid: impl Into<String>, secret: &str) -> Result<Self, JwtError> {
+        let kid = kid.into();
+
+        if secret.len() < MIN_SECRET_LENGTH {
+            return Err(JwtError::InvalidKey(format!(
+                "{}: HS256 secret must be at least {} bytes",
+                kid, MIN_SECRET_LENGTH
+            )));
+        }
+
+        Ok(Self::hs256_unchecked(kid, secret))
+    }
+
+    /// Creates an HS256 key without checking the secret length
+    ///
+    /// Used by the single-secret helpers in `jwt`, which predate key sets.
+    pub(crate) fn hs256_unchecked(kid: impl Into<String>, secret: &str) -> Self {
+        Self {
+            kid: kid.into(),
+            algorithm: Algorithm::HS256,
+            encoding: Some(EncodingKey::from_secret(secret.as_bytes())),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            jwk: None,
+        }
+    }
+
+    /// Creates an RS256 or EdDSA key from a PEM
+    ///
+    /// A private key PEM gives a key that can sign and verify; a public key
+    /// PEM gives a verification-only key.
+    ///
+    /// # Arguments
+    ///
+    /// * `kid` - Key ID
+    /// * `algorithm` - `RS256` or `EdDSA`
+    /// * `pem` - PEM-encoded private or public key
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::InvalidKey` if the algorithm is not supported, the
+    /// PEM cannot be parsed, or an RSA key is smaller than `MIN_RSA_BITS`
+    pub fn from_pem(kid: impl Into<String>, algorithm: Algorithm, pem: &str) -> Result<Self, JwtError> {
+        let kid = kid.into();
+        let invalid = |e: &dyn fmt::Display| JwtError::InvalidKey(format!("{}: {}", kid, e));
+        let private = pem.contains("PRIVATE KEY");
+
+        let (parameters, encoding) = match algorithm {
+            Algorithm::EdDSA => {
+                let public = if private {
+                    ed25519_dalek::SigningKey::from_pkcs8_pem(pem)
+                        .map_err(|e| invalid(&e))?
+                        .verifying_key()
+                } else {
+                    ed25519_dalek::VerifyingKey::from_public_key_pem(pem).map_err(|e| invalid(&e))?
+                };
+
+                let parameters = AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(public.as_bytes()),
+                });
+                let encoding = private
+                    .then(|| EncodingKey::from_ed_pem(pem.as_bytes()))
+                    .transpose()
+                    .map_err(|e| invalid(&e))?;
+
+                (parameters, encoding)
+            }
+            Algorithm::RS256 => {
+                let public = if private {
+                    RsaPrivateKey::from_pkcs8_pem(pem)
+                        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+                        .map_err(|e| invalid(&e))?
+                        .to_public_key()
+                } else {
+                    RsaPublicKey::from_public_key_pem(pem)
+                        .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+                        .map_err(|e| invalid(&e))?
+                };
+
+                if public.size() * 8 < MIN_RSA_BITS {
+                    return Err(invalid(&format!(
+                        "RSA key must be at least {} bits",
+                        MIN_RSA_BITS
+                    )));
+                }
+
+                let parameters = AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(public.n().to_bytes_be()),
+                    e: URL_SAFE_NO_PAD.encode(public.e().to_bytes_be()),
+                });
+                let encoding = private
+                    .then(|| EncodingKey::from_rsa_pem(pem.as_bytes()))
+                    .transpose()
+                    .map_err(|e| invalid(&e))?;
+
+                (parameters, encoding)
+            }
+            other => {
+                return Err(invalid(&format!(
+                    "unsupported algorithm {:?} (expected HS256, RS256 or EdDSA)",
+                    other
+                )))
+            }
+        };
+
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(match algorithm {
+                    Algorithm::EdDSA => KeyAlgorithm::EdDSA,
+                    _ => KeyAlgorithm::RS256,
+                }),
+                key_id: Some(kid.clone()),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        };
+        let decoding = DecodingKey::from_jwk(&jwk).map_err(|e| invalid(&e))?;
+
+        Ok(Self {
+            kid,
+            algorithm,
+            encoding,
+            decoding,
+            jwk: Some(jwk),
+        })
+    }
+
+    /// Gets the key ID
+    pub fn kid(&self) -> &str {
+        &self.kid
+    }
+
+    /// Gets the signing algorithm
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Checks if the key can sign tokens (has its private or secret part)
+    pub fn can_sign(&self) -> bool {
+        self.encoding.is_some()
+    }
+
+    /// Gets the public key as a JWK (None for HS256 secrets)
+    pub fn jwk(&self) -> Option<&Jwk> {
+        self.jwk.as_ref()
+    }
+}
+
+impl fmt::Debug for JwtKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print key material
+        f.debug_struct("JwtKey")
+            .field("kid", &self.kid)
+            .field("algorithm", &self.algorithm)
+            .field("can_sign", &self.can_sign())
+            .finish()
+    }
+}
+
+/// Set of keys used to sign and verify tokens
+///
+/// Cheap to clone (the keys are shared). The default set is empty: it can
+/// neither sign nor verify tokens.
+#[derive(Clone, Default)]
+pub struct JwtKeySet {
+    keys: Arc<Vec<JwtKey>>,
+    signing: Option<usize>,
+}
+
+impl JwtKeySet {
+    /// Creates a key set
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - All keys tokens may be signed with
+    /// * `signing_kid` - ID of the key that signs new tokens
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::InvalidKey` if key IDs are duplicated, the signing
+    /// key is not in the set, or it cannot sign (public key only)
+    pub fn new(keys: Vec<JwtKey>, signing_kid: &str) -> Result<Self, JwtError> {
+        for (i, key) in keys.iter().enumerate() {
+            if keys[..i].iter().any(|other| other.kid == key.kid) {
+                return Err(JwtError::InvalidKey(format!("duplicate key ID {}", key.kid)));
+            }
+        }
+
+        let signing = keys
+            .iter()
+            .position(|key| key.kid == signing_kid)
+            .ok_or_else(|| JwtError::InvalidKey(format!("signing key {} is not configured", signing_kid)))?;
+
+        if !keys[signing].can_sign() {
+            return Err(JwtError::InvalidKey(format!(
+                "signing key {} has no private key",
+                signing_kid
+            )));
+        }
+
+        Ok(Self {
+            keys: Arc::new(keys),
+            signing: Some(signing),
+        })
+    }
+
+    /// Creates a key set with a single HS256 secret (key ID `default`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::InvalidKey` if the secret is too short
+    pub fn from_secret(secret: &str) -> Result<Self, JwtError> {
+        Self::new(vec![JwtKey::hs256(DEFAULT_KEY_ID, secret)?], DEFAULT_KEY_ID)
+    }
+
+    /// Creates a single-secret key set without checking the secret length
+    pub(crate) fn from_secret_unchecked(secret: &str) -> Self {
+        Self {
+            keys: Arc::new(vec![JwtKey::hs256_unchecked(DEFAULT_KEY_ID, secret)]),
+            signing: Some(0),
+        }
+    }
+
+    /// Gets the key that signs new tokens (None for an empty set)
+    pub fn signing_key(&self) -> Option<&JwtKey> {
+        self.signing.map(|i| &self.keys[i])
+    }
+
+    /// Gets all keys
+    pub fn keys(&self) -> &[JwtKey] {
+        &self.keys
+    }
+
+    /// Signs claims with the signing key
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::CreateError` if the set is empty or encoding fails
+    pub fn sign(&self, claims: &Claims) -> Result<String, JwtError> {
+        let (key, encoding) = self
+            .signing_key()
+            .and_then(|key| Some((key, key.encoding.as_ref()?)))
+            .ok_or_else(|| JwtError::CreateError("No signing key configured".to_string()))?;
+
+        let mut header = Header::new(key.algorithm);
+        header.kid = Some(key.kid.clone());
+
+        encode(&header, claims, encoding)
+            .map_err(|e| JwtError::CreateError(format!("Token encoding failed: {}", e)))
+    }
+
+    /// Validates a token against the key set and extracts its claims
+    ///
+    /// Verifies the signature, expiry, not-before time and issuer.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidFormat` if the token header cannot be decoded
+    /// - `ValidationError` if the key is unknown or the signature is invalid
+    /// - `Expired` if the token has expired
+    /// - `InvalidIssuer` if the issuer is not "axontask"
+    pub fn validate(&self, token: &str) -> Result<Claims, JwtError> {
+        let header = decode_header(token)
+            .map_err(|e| JwtError::InvalidFormat(format!("Invalid token header: {}", e)))?;
+
+        let candidates: Vec<&JwtKey> = match &header.kid {
+            Some(kid) => {
+                let key = self
+                    .keys
+                    .iter()
+                    .find(|key| &key.kid == kid)
+                    .ok_or_else(|| JwtError::ValidationError(format!("Unknown signing key {}", kid)))?;
+                vec![key]
+            }
+            None => self.keys.iter().filter(|key| key.algorithm == header.alg).collect(),
+        };
+
+        let mut result = Err(JwtError::ValidationError(format!(
+            "No key for algorithm {:?}",
+            header.alg
+        )));
+
+        for key in candidates {
+            // Each key only accepts its own algorithm (no algorithm confusion)
+            let mut validation = Validation::new(key.algorithm);
+            validation.set_issuer(&[ISSUER]);
+            validation.validate_exp = true;
+            validation.validate_nbf = true;
+
+            result = decode::<Claims>(token, &key.decoding, &validation)
+                .map(|data| data.claims)
+                .map_err(|e| match e.kind() {
+                    jsonwebtoken::errors::ErrorKind::ExpiredSignature => JwtError::Expired,
+                    jsonwebtoken::errors::ErrorKind::InvalidIssuer => JwtError::InvalidIssuer {
+                        expected: ISSUER.to_string(),
+                        actual: "unknown".to_string(),
+                    },
+                    _ => JwtError::ValidationError(format!("Token validation failed: {}", e)),
+                });
+
+            if result.is_ok() {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Validates a token and checks it's an access token
+    ///
+    /// # Errors
+    ///
+    /// Same as `validate`, plus `ValidationError` for other token types
+    pub fn validate_access(&self, token: &str) -> Result<Claims, JwtError> {
+        let claims = self.validate(token)?;
+
+        if claims.token_type != TokenType::Access {
+            return Err(JwtError::ValidationError(
+                format!("Expected access token, got {} token", claims.token_type.as_str()),
+            ));
+        }
+
+        Ok(claims)
+    }
+
+    /// Validates a token and checks it's a refresh token
+    ///
+    /// # Errors
+    ///
+    /// Same as `validate`, plus `ValidationError` for other token types
+    pub fn validate_refresh(&self, token: &str) -> Result<Claims, JwtError> {
+        let claims = self.validate(token)?;
+
+        if claims.token_type != TokenType::Refresh {
+            return Err(JwtError::ValidationError(
+                format!("Expected refresh token, got {} token", claims.token_type.as_str()),
+            ));
+        }
+
+        Ok(claims)
+    }
+
+    /// Validates a token and checks it's an MFA challenge token
+    ///
+    /// # Errors
+    ///
+    /// Same as `validate`, plus `ValidationError` for other token types
+    pub fn validate_mfa(&self, token: &str) -> Result<Claims, JwtError> {
+        let claims = self.validate(token)?;
+
+        if claims.token_type != TokenType::Mfa {
+            return Err(JwtError::ValidationError(format!(
+                "Expected MFA challenge token, got {} token",
+                claims.token_type.as_str()
+            )));
+        }
+
+        Ok(claims)
+    }
+
+    /// Validates a token that may enroll two-factor authentication
+    ///
+    /// Accepts access tokens and enrollment tokens.
+    ///
+    /// # Errors
+    ///
+    /// Same as `validate`, plus `ValidationError` for other token types
+    pub fn validate_enrollment(&self, token: &str) -> Result<Claims, JwtError> {
+        let claims = self.validate(token)?;
+
+        if !matches!(claims.token_type, TokenType::Access | TokenType::Enrollment) {
+            return Err(JwtError::ValidationError(format!(
+                "Expected access or enrollment token, got {} token",
+                claims.token_type.as_str()
+            )));
+        }
+
+        Ok(claims)
+    }
+
+    /// Gets the public keys as a JWKS
+    ///
+    /// Includes every asymmetric key, whether or not it signs, so tokens
+    /// signed before a rotation can still be verified. HS256 secrets are
+    /// never included.
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.keys.iter().filter_map(|key| key.jwk.clone()).collect(),
+        }
+    }
+}
+
+impl fmt::Debug for JwtKeySet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtKeySet")
+            .field("keys", &self.keys)
+            .field("signing_kid", &self.signing_key().map(JwtKey::kid))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::pkcs8::{spki::der::pem::LineEnding, EncodePrivateKey, EncodePublicKey};
+    use uuid::Uuid;
+
+    const SECRET: &str = "test-secret-key-at-least-32-bytes-long";
+
+    fn claims(token_type: TokenType) -> Claims {
+        Claims::new(Uuid::new_v4(), Uuid::new_v4(), token_type)
+    }
+
+    fn ed25519_pems(seed: u8) -> (String, String) {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+        (
+            key.to_pkcs8_pem(LineEnding::LF).unwrap().to_string(),
+            key.verifying_key().to_public_key_pem(LineEnding::LF).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_hs256_secret_length() {
+        assert!(JwtKey::hs256("k1", "short").is_err());
+        assert!(JwtKey::hs256("k1", SECRET).is_ok());
+        assert!(JwtKeySet::from_secret("short").is_err());
+    }
+
+    #[test]
+    fn test_sign_sets_kid() {
+        let keys = JwtKeySet::from_secret(SECRET).unwrap();
+        let token = keys.sign(&claims(TokenType::Access)).unwrap();
+
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.kid.as_deref(), Some(DEFAULT_KEY_ID));
+        assert_eq!(header.alg, Algorithm::HS256);
+        assert!(keys.validate_access(&token).is_ok());
+        assert!(keys.validate_refresh(&token).is_err());
+    }
+
+    #[test]
+    fn test_mfa_tokens_only_validate_as_mfa() {
+        let keys = JwtKeySet::from_secret(SECRET).unwrap();
+        let token = keys.sign(&claims(TokenType::Mfa)).unwrap();
+
+        assert!(keys.validate_mfa(&token).is_ok());
+        assert!(keys.validate_access(&token).is_err());
+        assert!(keys.validate_refresh(&token).is_err());
+
+        let access = keys.sign(&claims(TokenType::Access)).unwrap();
+        assert!(keys.validate_mfa(&access).is_err());
+    }
+
+    #[test]
+    fn test_enrollment_tokens_only_validate_for_enrollment() {
+        let keys = JwtKeySet::from_secret(SECRET).unwrap();
+        let token = keys.sign(&claims(TokenType::Enrollment)).unwrap();
+
+        assert!(keys.validate_enrollment(&token).is_ok());
+        assert!(keys.validate_access(&token).is_err());
+        assert!(keys.validate_refresh(&token).is_err());
+        assert!(keys.validate_mfa(&token).is_err());
+
+        let access = keys.sign(&claims(TokenType::Access)).unwrap();
+        assert!(keys.validate_enrollment(&access).is_ok());
+        let mfa = keys.sign(&claims(TokenType::Mfa)).unwrap();
+        assert!(keys.validate_enrollment(&mfa).is_err());
+    }
+
+    #[test]
+    fn test_eddsa_roundtrip() {
+        let (private, public) = ed25519_pems(1);
+        let signer = JwtKeySet::new(vec![JwtKey::from_pem("ed1", Algorithm::EdDSA, &private).unwrap()], "ed1").unwrap();
+        let token = signer.sign(&claims(TokenType::Refresh)).unwrap();
+        assert!(signer.validate_refresh(&token).is_ok());
+
+        // A verifier only needs the public key
+        let public = JwtKey::from_pem("ed1", Algorithm::EdDSA, &public).unwrap();
+        assert!(!public.can_sign());
+        let verifier = JwtKeySet {
+            keys: Arc::new(vec![public]),
+            signing: None,
+        };
+        assert!(verifier.sign(&claims(TokenType::Access)).is_err());
+        assert!(verifier.validate_refresh(&token).is_ok());
+    }
+
+    #[test]
+    fn test_rs256_roundtrip_and_jwks() {
+        let private = RsaPrivateKey::new(&mut rand::rngs::OsRng, MIN_RSA_BITS).unwrap();
+        let pem = private.to_pkcs8_pem(LineEnding::LF).unwrap();
+
+        let keys = JwtKeySet::new(
+            vec![
+                JwtKey::from_pem("rsa1", Algorithm::RS256, &pem).unwrap(),
+                JwtKey::hs256("hmac", SECRET).unwrap(),
+            ],
+            "rsa1",
+        )
+        .unwrap();
+        let token = keys.sign(&claims(TokenType::Access)).unwrap();
+        assert!(keys.validate_access(&token).is_ok());
+
+        // Only the RSA key is published
+        let jwks = keys.jwks();
+        assert_eq!(jwks.keys.len(), 1);
+        let jwk = jwks.find("rsa1").unwrap();
+        let decoding = DecodingKey::from_jwk(jwk).unwrap();
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&[ISSUER]);
+        assert!(decode::<Claims>(&token, &decoding, &validation).is_ok());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let (old_pem, _) = ed25519_pems(1);
+        let (new_pem, _) = ed25519_pems(2);
+        let old = JwtKey::from_pem("old", Algorithm::EdDSA, &old_pem).unwrap();
+        let new = JwtKey::from_pem("new", Algorithm::EdDSA, &new_pem).unwrap();
+
+        let before = JwtKeySet::new(vec![old.clone()], "old").unwrap();
+        let staged = JwtKeySet::new(vec![old.clone(), new.clone()], "old").unwrap();
+        let after = JwtKeySet::new(vec![old, new.clone()], "new").unwrap();
+        let retired = JwtKeySet::new(vec![new], "new").unwrap();
+
+        let old_token = before.sign(&claims(TokenType::Access)).unwrap();
+        let new_token = after.sign(&claims(TokenType::Access)).unwrap();
+
+        // Staged instances accept tokens from instances that already switched
+        assert!(staged.validate(&new_token).is_ok());
+        assert!(after.validate(&old_token).is_ok());
+
+        // Once the old key is removed, its tokens are rejected
+        assert!(retired.validate(&new_token).is_ok());
+        assert!(matches!(retired.validate(&old_token), Err(JwtError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_legacy_token_without_kid() {
+        let legacy = encode(
+            &Header::new(Algorithm::HS256),
+            &claims(TokenType::Access),
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap();
+
+        let (pem, _) = ed25519_pems(3);
+        let keys = JwtKeySet::new(
+            vec![
+                JwtKey::from_pem("ed", Algorithm::EdDSA, &pem).unwrap(),
+                JwtKey::hs256(DEFAULT_KEY_ID, SECRET).unwrap(),
+            ],
+            "ed",
+        )
+        .unwrap();
+        assert!(keys.validate_access(&legacy).is_ok());
+    }
+
+    #[test]
+    fn test_kid_selects_key_and_algorithm() {
+        let (pem, public) = ed25519_pems(4);
+        let ed = JwtKey::from_pem("ed", Algorithm::EdDSA, &pem).unwrap();
+
+        // An HS256 token naming the EdDSA key is rejected, even if signed with
+        // the key's public bytes as the HMAC secret
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("ed".to_string());
+        let forged = encode(&header, &claims(TokenType::Access), &EncodingKey::from_secret(public.as_bytes())).unwrap();
+
+        let keys = JwtKeySet::new(vec![ed], "ed").unwrap();
+        assert!(keys.validate(&forged).is_err());
+    }
+
+    #[test]
+    fn test_key_set_errors() {
+        let (_, public) = ed25519_pems(5);
+        let public = JwtKey::from_pem("pub", Algorithm::EdDSA, &public).unwrap();
+        let hmac = JwtKey::hs256("hmac", SECRET).unwrap();
+
+        assert!(JwtKeySet::new(vec![hmac.clone()], "missing").is_err());
+        assert!(JwtKeySet::new(vec![hmac.clone(), hmac.clone()], "hmac").is_err());
+        assert!(JwtKeySet::new(vec![public.clone(), hmac], "pub").is_err());
+        assert!(JwtKey::from_pem("bad", Algorithm::EdDSA, "not a pem").is_err());
+        assert!(JwtKey::from_pem("es", Algorithm::ES256, "pem").is_err());
+    }
+
+    #[test]
+    fn test_empty_key_set() {
+        let keys = JwtKeySet::default();
+        let token = JwtKeySet::from_secret(SECRET).unwrap().sign(&claims(TokenType::Access)).unwrap();
+
+        assert!(keys.signing_key().is_none());
+        assert!(keys.sign(&claims(TokenType::Access)).is_err());
+        assert!(keys.validate(&token).is_err());
+    }
+
+    #[test]
+    fn test_debug_hides_key_material() {
+        let keys = JwtKeySet::from_secret(SECRET).unwrap();
+        assert!(!format!("{:?}", keys).contains(SECRET));
+    }
+}
@@ -0,0 +1,345 @@
+/// Tenant member management and invitations
+///
+/// This module holds the rules for changing who belongs to a tenant. Route
+/// policies already require admins for these operations; the rules here add
+/// what depends on the members involved.
+///
+/// # Rules
+///
+/// - Only owners can grant the owner role, or change or remove an owner
+/// - A tenant always keeps at least one owner
+/// - Removing a member revokes their sessions in the tenant
+/// - An invitation can only be accepted once, before it expires, by a user
+///   whose email matches the invited address
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::auth::members::{accept_invitation, create_invitation};
+/// use axontask_shared::models::membership::MembershipRole;
+/// use chrono::Duration;
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, tenant_id: Uuid, admin_id: Uuid, invitee_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
+/// let (invitation, token) = create_invitation(
+///     &pool,
+///     MembershipRole::Admin,
+///     tenant_id,
+///     admin_id,
+///     "new.hire@example.com",
+///     MembershipRole::Member,
+///     Duration::days(7),
+/// )
+/// .await?;
+///
+/// // Later, logged in as new.hire@example.com
+/// let membership = accept_invitation(&pool, &token, invitee_id, "new.hire@example.com").await?;
+/// assert_eq!(membership.tenant_id, invitation.tenant_id);
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::invitation::{CreateInvitation, Invitation};
+use crate::models::membership::{Membership, MembershipRole, OwnerGuarded};
+use crate::models::refresh_token::RefreshToken;
+use crate::models::user::User;
+
+/// Error type for member operations
+#[derive(Debug, thiserror::Error)]
+pub enum MemberError {
+    /// The user is not a member of the tenant
+    #[error("Member not found")]
+    NotFound,
+
+    /// The change would leave the tenant without an owner
+    #[error("A tenant must keep at least one owner")]
+    LastOwner,
+
+    /// Only owners can grant, change or remove the owner role
+    #[error("Only owners can grant, change or remove the owner role")]
+    OwnerRequired,
+
+    /// The user (or invited email) already belongs to the tenant
+    #[error("Already a member of the tenant")]
+    AlreadyMember,
+
+    /// The invitation token is unknown, expired or already accepted
+    #[error("Invalid or expired invitation")]
+    InvalidInvitation,
+
+    /// The invitation was sent to a different email address
+    #[error("Invitation was sent to a different email address")]
+    WrongEmail,
+
+    /// Database error
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+/// Checks if an actor may move a membership from one role to another
+///
+/// `from` is None for new members (invitations), `to` is None for removals.
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::members::check_role_change;
+/// use axontask_shared::models::membership::MembershipRole;
+///
+/// // Admins manage members and admins...
+/// assert!(check_role_change(MembershipRole::Admin, Some(MembershipRole::Member), Some(MembershipRole::Admin)).is_ok());
+/// // ...but not owners
+/// assert!(check_role_change(MembershipRole::Admin, Some(MembershipRole::Owner), None).is_err());
+/// ```
+pub fn check_role_change(
+    actor: MembershipRole,
+    from: Option<MembershipRole>,
+    to: Option<MembershipRole>,
+) -> Result<(), MemberError> {
+    let touches_owner = from == Some(MembershipRole::Owner) || to == Some(MembershipRole::Owner);
+
+    if touches_owner && actor != MembershipRole::Owner {
+        return Err(MemberError::OwnerRequired);
+    }
+
+    Ok(())
+}
+
+/// Changes a member's role
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `actor` - Role of the user making the change
+/// * `tenant_id` - Tenant ID
+/// * `user_id` - Member to change
+/// * `role` - New role
+///
+/// # Errors
+///
+/// - `NotFound` if the user is not a member
+/// - `OwnerRequired` if the change involves the owner role and the actor is not an owner
+/// - `LastOwner` if the member is the tenant's only owner
+pub async fn change_role(
+    pool: &PgPool,
+    actor: MembershipRole,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    role: MembershipRole,
+) -> Result<Membership, MemberError> {
+    let current = Membership::get_role(pool, tenant_id, user_id)
+        .await?
+        .ok_or(MemberError::NotFound)?;
+    check_role_change(actor, Some(current), Some(role))?;
+
+    match Membership::update_role_keeping_owner(pool, tenant_id, user_id, role).await? {
+        OwnerGuarded::Changed(membership) => Ok(membership),
+        OwnerGuarded::NotFound => Err(MemberError::NotFound),
+        OwnerGuarded::LastOwner => Err(MemberError::LastOwner),
+    }
+}
+
+/// Removes a member from a tenant
+///
+/// Also revokes the member's sessions in the tenant. Access tokens already
+/// issued stop working at the next role check, since the user is no longer
+/// a member.
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `actor` - Role of the user removing the member
+/// * `tenant_id` - Tenant ID
+/// * `user_id` - Member to remove
+///
+/// # Returns
+///
+/// The removed membership
+///
+/// # Errors
+///
+/// - `NotFound` if the user is not a member
+/// - `OwnerRequired` if the member is an owner and the actor is not
+/// - `LastOwner` if the member is the tenant's only owner
+pub async fn remove_member(
+    pool: &PgPool,
+    actor: MembershipRole,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> Result<Membership, MemberError> {
+    let current = Membership::get_role(pool, tenant_id, user_id)
+        .await?
+        .ok_or(MemberError::NotFound)?;
+    check_role_change(actor, Some(current), None)?;
+
+    let membership = match Membership::delete_keeping_owner(pool, tenant_id, user_id).await? {
+        OwnerGuarded::Changed(membership) => membership,
+        OwnerGuarded::NotFound => return Err(MemberError::NotFound),
+        OwnerGuarded::LastOwner => return Err(MemberError::LastOwner),
+    };
+
+    RefreshToken::revoke_for_tenant(pool, user_id, tenant_id).await?;
+
+    Ok(membership)
+}
+
+/// Invites an email address to join a tenant
+///
+/// Replaces any pending invitation for the same address.
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `actor` - Role of the user sending the invitation
+/// * `tenant_id` - Tenant ID
+/// * `invited_by` - User sending the invitation
+/// * `email` - Address to invite
+/// * `role` - Role granted on acceptance
+/// * `ttl` - How long the invitation stays valid
+///
+/// # Returns
+///
+/// The invitation and its plaintext token (send it to the invitee; it is not
+/// stored)
+///
+/// # Errors
+///
+/// - `OwnerRequired` if inviting an owner and the actor is not an owner
+/// - `AlreadyMember` if a member of the tenant has this email
+pub async fn create_invitation(
+    pool: &PgPool,
+    actor: MembershipRole,
+    tenant_id: Uuid,
+    invited_by: Uuid,
+    email: &str,
+    role: MembershipRole,
+    ttl: Duration,
+) -> Result<(Invitation, String), MemberError> {
+    check_role_change(actor, None, Some(role))?;
+
+    if let Some(user) = User::find_by_email(pool, email.trim()).await? {
+        if Membership::has_access(pool, tenant_id, user.id).await? {
+            return Err(MemberError::AlreadyMember);
+        }
+    }
+
+    let token = Invitation::generate_token();
+    let invitation = Invitation::create(
+        pool,
+        CreateInvitation {
+            tenant_id,
+            email: email.trim().to_string(),
+            role,
+            invited_by: Some(invited_by),
+            token: token.clone(),
+            expires_at: Utc::now() + ttl,
+        },
+    )
+    .await?;
+
+    Ok((invitation, token))
+}
+
+/// Accepts an invitation, adding the user to the tenant
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `token` - Invitation token from the email
+/// * `user_id` - User accepting the invitation
+/// * `email` - The user's email address
+///
+/// # Errors
+///
+/// - `InvalidInvitation` if the token is unknown, expired or already accepted
+/// - `WrongEmail` if the invitation was sent to another address
+/// - `AlreadyMember` if the user already belongs to the tenant
+pub async fn accept_invitation(
+    pool: &PgPool,
+    token: &str,
+    user_id: Uuid,
+    email: &str,
+) -> Result<Membership, MemberError> {
+    let invitation = Invitation::find_by_token(pool, token)
+        .await?
+        .filter(Invitation::is_pending)
+        .ok_or(MemberError::InvalidInvitation)?;
+
+    if !invitation.is_for_email(email) {
+        return Err(MemberError::WrongEmail);
+    }
+
+    if Membership::has_access(pool, invitation.tenant_id, user_id).await? {
+        return Err(MemberError::AlreadyMember);
+    }
+
+    // None: accepted or joined concurrently
+    Invitation::accept(pool, invitation.id, user_id)
+        .await?
+        .ok_or(MemberError::InvalidInvitation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROLES: [MembershipRole; 4] = [
+        MembershipRole::Viewer,
+        MembershipRole::Member,
+        MembershipRole::Admin,
+        MembershipRole::Owner,
+    ];
+
+    #[test]
+    fn test_owner_changes_anything() {
+        for from in ROLES.map(Some).into_iter().chain([None]) {
+            for to in ROLES.map(Some).into_iter().chain([None]) {
+                assert!(check_role_change(MembershipRole::Owner, from, to).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_admin_cannot_touch_owners() {
+        let non_owners = [
+            Some(MembershipRole::Viewer),
+            Some(MembershipRole::Member),
+            Some(MembershipRole::Admin),
+        ];
+
+        for from in non_owners.into_iter().chain([None]) {
+            for to in non_owners.into_iter().chain([None]) {
+                assert!(check_role_change(MembershipRole::Admin, from, to).is_ok());
+            }
+
+            assert!(matches!(
+                check_role_change(MembershipRole::Admin, from, Some(MembershipRole::Owner)),
+                Err(MemberError::OwnerRequired)
+            ));
+        }
+
+        for to in ROLES.map(Some).into_iter().chain([None]) {
+            assert!(matches!(
+                check_role_change(MembershipRole::Admin, Some(MembershipRole::Owner), to),
+                Err(MemberError::OwnerRequired)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            MemberError::LastOwner.to_string(),
+            "A tenant must keep at least one owner"
+        );
+        assert_eq!(
+            MemberError::InvalidInvitation.to_string(),
+            "Invalid or expired invitation"
+        );
+    }
+}
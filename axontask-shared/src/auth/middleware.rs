@@ -9,6 +9,9 @@
 /// - **JWT Middleware**: Validates Bearer tokens from Authorization header
 /// - **API Key Middleware**: Validates API keys from X-Api-Key header
 ///
+/// Layers that accept either credential use `extract_credentials` to tell them
+/// apart and `authenticate_api_key` to validate keys through an `ApiKeyCache`.
+///
 /// # Request Extensions
 ///
 /// After successful authentication, middleware adds:
@@ -31,7 +34,7 @@
 
 use axum::{
     extract::Request,
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -39,9 +42,13 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::api_key_cache::ApiKeyCache;
 use super::jwt::{validate_access_token, JwtError};
 use crate::models::api_key::ApiKey;
 
+/// Header carrying an API key (alternative to `Authorization: Bearer axon_...`)
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
 /// Authentication method used
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -168,6 +175,109 @@ impl IntoResponse for AuthError {
     }
 }
 
+/// Credentials presented by a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// Bearer JWT access token
+    Jwt(String),
+
+    /// API key (`axon_...`)
+    ApiKey(String),
+}
+
+/// Extracts credentials from request headers
+///
+/// Accepts, in order of precedence:
+/// - `X-Api-Key: axon_...`
+/// - `Authorization: Bearer axon_...` (API key)
+/// - `Authorization: Bearer <jwt>`
+///
+/// # Errors
+///
+/// Returns `MissingCredentials` if neither header is present, or
+/// `InvalidFormat` if the Authorization header is not a Bearer token.
+///
+/// # Example
+///
+/// ```
+/// use axum::http::HeaderMap;
+/// use axontask_shared::auth::middleware::{extract_credentials, Credentials};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("X-Api-Key", "axon_abcdefghijklmnopqrstuvwxyz123456".parse().unwrap());
+///
+/// assert_eq!(
+///     extract_credentials(&headers).unwrap(),
+///     Credentials::ApiKey("axon_abcdefghijklmnopqrstuvwxyz123456".to_string())
+/// );
+/// ```
+pub fn extract_credentials(headers: &HeaderMap) -> Result<Credentials, AuthError> {
+    if let Some(api_key) = headers.get(API_KEY_HEADER) {
+        let api_key = api_key
+            .to_str()
+            .map_err(|_| AuthError::InvalidFormat("Invalid API key format".to_string()))?;
+        return Ok(Credentials::ApiKey(api_key.to_string()));
+    }
+
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .ok_or(AuthError::MissingCredentials)?;
+
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| AuthError::InvalidFormat("Expected Bearer token".to_string()))?;
+
+    if token.starts_with(super::api_key::KEY_PREFIX) {
+        Ok(Credentials::ApiKey(token.to_string()))
+    } else {
+        Ok(Credentials::Jwt(token.to_string()))
+    }
+}
+
+/// Authenticates an API key
+///
+/// Serves recently validated keys from the cache; otherwise validates the key
+/// against the database (which updates `last_used_at`) and caches it.
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `cache` - Validated key cache
+/// * `key` - Plaintext API key
+///
+/// # Returns
+///
+/// The API key record
+///
+/// # Errors
+///
+/// Returns `InvalidFormat` for malformed keys, `InvalidApiKey` for unknown,
+/// revoked or expired keys, and `DatabaseError` if validation fails.
+pub async fn authenticate_api_key(
+    pool: &PgPool,
+    cache: &ApiKeyCache,
+    key: &str,
+) -> Result<ApiKey, AuthError> {
+    if !super::api_key::validate_api_key_format(key) {
+        return Err(AuthError::InvalidFormat("Invalid API key format".to_string()));
+    }
+
+    let key_hash = ApiKey::hash_key(key);
+    if let Some(api_key) = cache.get(&key_hash) {
+        return Ok(api_key);
+    }
+
+    let api_key = ApiKey::validate(pool, key)
+        .await
+        .map_err(|e| AuthError::DatabaseError(format!("Database error: {}", e)))?
+        .ok_or_else(|| AuthError::InvalidApiKey("Invalid or revoked API key".to_string()))?;
+
+    cache.insert(key_hash, api_key.clone());
+
+    Ok(api_key)
+}
+
 /// JWT authentication middleware
 ///
 /// Validates JWT tokens from the `Authorization: Bearer <token>` header.
@@ -286,7 +396,7 @@ pub async fn api_key_auth_middleware(
     // Extract X-Api-Key header
     let api_key_header = req
         .headers()
-        .get("X-Api-Key")
+        .get(API_KEY_HEADER)
         .and_then(|v| v.to_str().ok())
         .ok_or(AuthError::MissingCredentials)?;
 
@@ -392,6 +502,42 @@ mod tests {
         assert!(context.has_scope("anything"));
     }
 
+    #[test]
+    fn test_extract_credentials() {
+        let key = "axon_abcdefghijklmnopqrstuvwxyz123456";
+
+        let mut headers = HeaderMap::new();
+        assert!(matches!(
+            extract_credentials(&headers),
+            Err(AuthError::MissingCredentials)
+        ));
+
+        headers.insert(header::AUTHORIZATION, "Bearer eyJhbGciOi".parse().unwrap());
+        assert_eq!(
+            extract_credentials(&headers).unwrap(),
+            Credentials::Jwt("eyJhbGciOi".to_string())
+        );
+
+        headers.insert(header::AUTHORIZATION, format!("Bearer {}", key).parse().unwrap());
+        assert_eq!(
+            extract_credentials(&headers).unwrap(),
+            Credentials::ApiKey(key.to_string())
+        );
+
+        headers.insert(header::AUTHORIZATION, "Basic dXNlcjpwYXNz".parse().unwrap());
+        assert!(matches!(
+            extract_credentials(&headers),
+            Err(AuthError::InvalidFormat(_))
+        ));
+
+        // X-Api-Key takes precedence over Authorization
+        headers.insert("x-api-key", key.parse().unwrap());
+        assert_eq!(
+            extract_credentials(&headers).unwrap(),
+            Credentials::ApiKey(key.to_string())
+        );
+    }
+
     #[test]
     fn test_auth_error_into_response() {
         let err = AuthError::MissingCredentials;
@@ -6,13 +6,21 @@
 ///
 /// - [`password`]: Argon2id password hashing and validation
 /// - [`jwt`]: JWT token generation and validation
+/// - [`jwt_keys`]: Signing key sets with key IDs, RS256/EdDSA and rotation
 /// - [`api_key`]: API key generation and validation utilities
+/// - [`api_key_cache`]: In-memory cache of validated API keys
+/// - [`session`]: Login sessions with rotating refresh tokens
+/// - [`members`]: Tenant member management and invitations
+/// - [`account`]: Email verification and password reset
+/// - [`totp`]: Time-based one-time passwords (RFC 6238)
+/// - [`two_factor`]: TOTP two-factor enrollment, login codes and recovery codes
 ///
 /// # Security Features
 ///
 /// - **Password Hashing**: Argon2id with 64 MB memory, 3 iterations
-/// - **JWT Tokens**: HS256 signing with configurable expiration
+/// - **JWT Tokens**: HS256, RS256 or EdDSA signing with rotatable keys
 /// - **API Keys**: Secure random generation with SHA-256 hashing
+/// - **Two-Factor Authentication**: Optional TOTP with hashed recovery codes
 /// - **Constant-time Comparison**: All verification uses constant-time operations
 ///
 /// # Example
@@ -35,6 +43,13 @@
 
 pub mod password;
 pub mod jwt;
+pub mod jwt_keys;
 pub mod api_key;
+pub mod api_key_cache;
+pub mod session;
+pub mod members;
+pub mod account;
+pub mod totp;
+pub mod two_factor;
 pub mod middleware;
 pub mod authorization;
@@ -41,6 +41,7 @@ use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2, ParamsBuilder, Version,
 };
+use std::sync::OnceLock;
 
 /// Error type for password hashing operations
 #[derive(Debug, thiserror::Error)]
@@ -177,6 +178,30 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, PasswordError
     }
 }
 
+/// Runs a password verification for an account that doesn't exist
+///
+/// Verifies the password against a fixed hash with the same parameters as
+/// real ones, so a login for an unknown email takes as long as one with a
+/// wrong password and response times don't reveal which emails have
+/// accounts. The hash is computed on first use.
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::password::verify_dummy_password;
+///
+/// // User lookup found nothing: spend the same time, then reject
+/// verify_dummy_password("guessed_password");
+/// ```
+pub fn verify_dummy_password(password: &str) {
+    static DUMMY_HASH: OnceLock<Option<String>> = OnceLock::new();
+
+    let hash = DUMMY_HASH.get_or_init(|| hash_password("axontask-dummy-password").ok());
+    if let Some(hash) = hash {
+        let _ = verify_password(password, hash);
+    }
+}
+
 /// Validates password strength
 ///
 /// Checks that password meets minimum security requirements:
@@ -378,6 +403,28 @@ mod tests {
         assert!(result.unwrap_err().contains("special character"));
     }
 
+    #[test]
+    fn test_dummy_verification_takes_as_long() {
+        let hash = hash_password("correct_password").expect("Hash should succeed");
+        verify_dummy_password("warm_up");
+
+        let start = std::time::Instant::now();
+        let _ = verify_password("wrong_password", &hash);
+        let real_duration = start.elapsed();
+
+        let start = std::time::Instant::now();
+        verify_dummy_password("wrong_password");
+        let dummy_duration = start.elapsed();
+
+        let ratio = real_duration.as_micros() as f64 / dummy_duration.as_micros() as f64;
+        assert!(
+            ratio > 0.5 && ratio < 2.0,
+            "Timing difference too large: real={:?}, dummy={:?}",
+            real_duration,
+            dummy_duration
+        );
+    }
+
     #[test]
     fn test_timing_attack_resistance() {
         // This test verifies that verification time doesn't leak information
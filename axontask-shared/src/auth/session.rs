@@ -0,0 +1,315 @@
+/// Login sessions with rotating refresh tokens
+///
+/// A session starts at login (or registration) and is identified by its
+/// refresh token family. Access tokens stay stateless; refresh tokens are
+/// stored (hashed) so they can be rotated, revoked and listed.
+///
+/// # Refresh Flow
+///
+/// 1. **Validate**: The refresh token's signature and expiry are checked
+/// 2. **Look up**: The token must be stored and its session not revoked
+/// 3. **Rotate**: The token is marked used and a new token is issued in the
+///    same family, along with a new access token
+/// 4. **Reuse detection**: A token that was already used revokes its whole
+///    family, logging out both the legitimate client and whoever copied it
+///
+/// Revoking a session stops its refresh token from being exchanged; access
+/// tokens already issued remain valid until they expire.
+///
+/// # Tenant Switching
+///
+/// A session belongs to one tenant at a time. Switching tenants exchanges the
+/// refresh token like a refresh, but the new tokens are issued for the other
+/// tenant, so later refreshes stay in it.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::auth::jwt_keys::JwtKeySet;
+/// use axontask_shared::auth::session::{refresh_session, start_session};
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, user_id: Uuid, tenant_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
+/// let keys = JwtKeySet::from_secret("your-secret-key-at-least-32-bytes")?;
+///
+/// // Login
+/// let tokens = start_session(&pool, &keys, user_id, tenant_id, None).await?;
+///
+/// // Later: exchange the refresh token (the old one is now used)
+/// let rotated = refresh_session(&pool, &keys, &tokens.refresh_token, None).await?;
+/// assert_eq!(rotated.session_id, tokens.session_id);
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::jwt::{Claims, JwtError, TokenType};
+use super::jwt_keys::JwtKeySet;
+use crate::models::membership::Membership;
+use crate::models::refresh_token::{CreateRefreshToken, RefreshToken};
+
+/// Maximum stored user agent length
+pub const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Error type for session operations
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    /// Refresh token is unknown, or its session was revoked
+    #[error("Invalid refresh token")]
+    InvalidToken,
+
+    /// A used refresh token was presented again; the session was revoked
+    #[error("Refresh token reuse detected")]
+    TokenReused,
+
+    /// The user is not a member of the requested tenant
+    #[error("Not a member of the tenant")]
+    NotAMember,
+
+    /// Token signing or validation failed
+    #[error(transparent)]
+    Jwt(#[from] JwtError),
+
+    /// Database error
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+/// Tokens issued for a session
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    /// Session (refresh token family) ID
+    pub session_id: Uuid,
+
+    /// Tenant the tokens are issued for
+    pub tenant_id: Uuid,
+
+    /// Access token
+    pub access_token: String,
+
+    /// Refresh token (single use)
+    pub refresh_token: String,
+
+    /// Access token lifetime in seconds
+    pub expires_in: i64,
+}
+
+/// Starts a new session (login)
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `keys` - JWT signing keys
+/// * `user_id` - User logging in
+/// * `tenant_id` - Tenant context of the session
+/// * `user_agent` - Client user agent (shown in session listings)
+///
+/// # Errors
+///
+/// Returns an error if token creation or the database operation fails
+pub async fn start_session(
+    pool: &PgPool,
+    keys: &JwtKeySet,
+    user_id: Uuid,
+    tenant_id: Uuid,
+    user_agent: Option<&str>,
+) -> Result<TokenPair, SessionError> {
+    issue_tokens(pool, keys, user_id, tenant_id, Uuid::new_v4(), Utc::now(), user_agent).await
+}
+
+/// Exchanges a refresh token for new tokens (rotation)
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `keys` - JWT signing keys
+/// * `refresh_token` - Refresh token presented by the client
+/// * `user_agent` - Client user agent (kept from login if None)
+///
+/// # Errors
+///
+/// - `Jwt` if the token is malformed, expired or not a refresh token
+/// - `InvalidToken` if the token is unknown or its session was revoked
+/// - `TokenReused` if the token was already exchanged (the session is revoked)
+pub async fn refresh_session(
+    pool: &PgPool,
+    keys: &JwtKeySet,
+    refresh_token: &str,
+    user_agent: Option<&str>,
+) -> Result<TokenPair, SessionError> {
+    rotate(pool, keys, refresh_token, user_agent, None).await
+}
+
+/// Moves a session to another tenant
+///
+/// Exchanges the refresh token like `refresh_session`, issuing the new tokens
+/// for `tenant_id`.
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `keys` - JWT signing keys
+/// * `refresh_token` - Refresh token of the session to move
+/// * `user_id` - Authenticated user (must own the session)
+/// * `tenant_id` - Tenant to switch to
+/// * `user_agent` - Client user agent (kept from login if None)
+///
+/// # Errors
+///
+/// - `NotAMember` if the user does not belong to the tenant (the refresh
+///   token is not used up)
+/// - `InvalidToken` if the session belongs to another user, plus the errors
+///   of `refresh_session`
+pub async fn switch_tenant(
+    pool: &PgPool,
+    keys: &JwtKeySet,
+    refresh_token: &str,
+    user_id: Uuid,
+    tenant_id: Uuid,
+    user_agent: Option<&str>,
+) -> Result<TokenPair, SessionError> {
+    if !Membership::has_access(pool, tenant_id, user_id).await? {
+        return Err(SessionError::NotAMember);
+    }
+
+    rotate(pool, keys, refresh_token, user_agent, Some((user_id, tenant_id))).await
+}
+
+/// Ends the session a refresh token belongs to (logout)
+///
+/// # Returns
+///
+/// True if a session was revoked; false if the token is unknown or its
+/// session had already ended
+///
+/// # Errors
+///
+/// Returns an error if the database operation fails
+pub async fn end_session(pool: &PgPool, refresh_token: &str) -> Result<bool, SessionError> {
+    match RefreshToken::find_by_token(pool, refresh_token).await? {
+        Some(stored) => {
+            let revoked = RefreshToken::revoke_family(pool, stored.user_id, stored.family_id).await?;
+            Ok(revoked > 0)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Exchanges a refresh token, optionally moving its session to a tenant
+///
+/// `switch` is the (user, tenant) to move to; the session must be the user's.
+async fn rotate(
+    pool: &PgPool,
+    keys: &JwtKeySet,
+    refresh_token: &str,
+    user_agent: Option<&str>,
+    switch: Option<(Uuid, Uuid)>,
+) -> Result<TokenPair, SessionError> {
+    keys.validate_refresh(refresh_token)?;
+
+    let stored = RefreshToken::find_by_token(pool, refresh_token)
+        .await?
+        .ok_or(SessionError::InvalidToken)?;
+
+    if stored.revoked_at.is_some() {
+        return Err(SessionError::InvalidToken);
+    }
+
+    if switch.is_some_and(|(user_id, _)| user_id != stored.user_id) {
+        return Err(SessionError::InvalidToken);
+    }
+
+    // A concurrent exchange of the same token also counts as reuse
+    if stored.used_at.is_some() || !RefreshToken::mark_used(pool, stored.id).await? {
+        let revoked = RefreshToken::revoke_family(pool, stored.user_id, stored.family_id).await?;
+
+        tracing::warn!(
+            user_id = %stored.user_id,
+            session_id = %stored.family_id,
+            revoked,
+            "Refresh token reuse detected, session revoked"
+        );
+
+        return Err(SessionError::TokenReused);
+    }
+
+    issue_tokens(
+        pool,
+        keys,
+        stored.user_id,
+        switch.map_or(stored.tenant_id, |(_, tenant_id)| tenant_id),
+        stored.family_id,
+        stored.family_created_at,
+        user_agent.or(stored.user_agent.as_deref()),
+    )
+    .await
+}
+
+/// Issues an access token and the next refresh token of a family
+async fn issue_tokens(
+    pool: &PgPool,
+    keys: &JwtKeySet,
+    user_id: Uuid,
+    tenant_id: Uuid,
+    family_id: Uuid,
+    family_created_at: DateTime<Utc>,
+    user_agent: Option<&str>,
+) -> Result<TokenPair, SessionError> {
+    let access_claims = Claims::new(user_id, tenant_id, TokenType::Access);
+    let refresh_claims = Claims::new(user_id, tenant_id, TokenType::Refresh);
+
+    let access_token = keys.sign(&access_claims)?;
+    let refresh_token = keys.sign(&refresh_claims)?;
+
+    RefreshToken::create(
+        pool,
+        CreateRefreshToken {
+            family_id,
+            user_id,
+            tenant_id,
+            token: refresh_token.clone(),
+            user_agent: user_agent.map(truncate_user_agent),
+            family_created_at,
+            expires_at: DateTime::from_timestamp(refresh_claims.exp, 0).unwrap_or_else(Utc::now),
+        },
+    )
+    .await?;
+
+    Ok(TokenPair {
+        session_id: family_id,
+        tenant_id,
+        access_token,
+        refresh_token,
+        expires_in: access_claims.exp - access_claims.iat,
+    })
+}
+
+/// Truncates a user agent to `MAX_USER_AGENT_LENGTH` characters
+fn truncate_user_agent(user_agent: &str) -> String {
+    user_agent.chars().take(MAX_USER_AGENT_LENGTH).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_user_agent() {
+        assert_eq!(truncate_user_agent("curl/8.0"), "curl/8.0");
+
+        let long = "x".repeat(MAX_USER_AGENT_LENGTH + 10);
+        assert_eq!(truncate_user_agent(&long).len(), MAX_USER_AGENT_LENGTH);
+    }
+
+    #[test]
+    fn test_session_error_display() {
+        assert_eq!(SessionError::InvalidToken.to_string(), "Invalid refresh token");
+        assert_eq!(SessionError::TokenReused.to_string(), "Refresh token reuse detected");
+        assert_eq!(SessionError::NotAMember.to_string(), "Not a member of the tenant");
+        assert_eq!(SessionError::Jwt(JwtError::Expired).to_string(), "Token has expired");
+    }
+}
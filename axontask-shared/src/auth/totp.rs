@@ -0,0 +1,264 @@
+/// Time-based one-time passwords (RFC 6238)
+///
+/// This module generates TOTP secrets and provisioning URIs for
+/// authenticator apps, and checks the codes they show.
+///
+/// # Parameters
+///
+/// HMAC-SHA1, 6 digits, 30 second steps: the defaults every authenticator
+/// app supports. Codes from one step before or after the current one are
+/// accepted to allow for clock drift.
+///
+/// # Replay
+///
+/// `verify` returns the time step a code matched. Callers store the last
+/// used step and pass it back, so a code can't be used twice.
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::totp;
+///
+/// let secret = totp::generate_secret();
+/// let uri = totp::provisioning_uri(&secret, "AxonTask", "user@example.com");
+/// assert!(uri.starts_with("otpauth://totp/AxonTask:user%40example.com?secret="));
+///
+/// // The code an authenticator app shows right now
+/// let now = chrono::Utc::now().timestamp();
+/// let code = totp::code_at(&secret, totp::step_at(now));
+/// assert_eq!(totp::verify(&secret, &code, now, None), Some(totp::step_at(now)));
+/// ```
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+/// Secret length in bytes (160 bits, as recommended by RFC 4226)
+pub const SECRET_LENGTH: usize = 20;
+
+/// Digits in a code
+pub const DIGITS: u32 = 6;
+
+/// Seconds per time step
+pub const PERIOD_SECS: i64 = 30;
+
+/// Steps before and after the current one whose codes are accepted
+pub const ALLOWED_DRIFT_STEPS: i64 = 1;
+
+/// Base32 alphabet (RFC 4648)
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Generates a random secret
+pub fn generate_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; SECRET_LENGTH];
+    rand::thread_rng().fill_bytes(&mut secret);
+    secret
+}
+
+/// Encodes a secret as unpadded base32, the form authenticator apps accept
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::auth::totp::encode_secret;
+///
+/// assert_eq!(encode_secret(b"foobar"), "MZXW6YTBOI");
+/// ```
+pub fn encode_secret(secret: &[u8]) -> String {
+    let mut encoded = String::with_capacity(secret.len().div_ceil(5) * 8);
+    let mut buffer: u64 = 0;
+    let mut bits = 0;
+
+    for &byte in secret {
+        buffer = (buffer << 8) | u64::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    encoded
+}
+
+/// Builds the `otpauth://` URI that authenticator apps scan as a QR code
+///
+/// # Arguments
+///
+/// * `secret` - TOTP secret
+/// * `issuer` - Service name shown in the app
+/// * `account` - Account name shown in the app (the user's email)
+pub fn provisioning_uri(secret: &[u8], issuer: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        encode_secret(secret),
+        percent_encode(issuer),
+        DIGITS,
+        PERIOD_SECS
+    )
+}
+
+/// Time step of a Unix timestamp
+pub fn step_at(unix_secs: i64) -> i64 {
+    unix_secs.div_euclid(PERIOD_SECS)
+}
+
+/// Code for a time step (RFC 4226 HOTP with the step as counter)
+pub fn code_at(secret: &[u8], step: i64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(&(step as u64).to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // Dynamic truncation
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+
+    format!(
+        "{:0width$}",
+        value % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+/// Checks a code
+///
+/// # Arguments
+///
+/// * `secret` - TOTP secret
+/// * `code` - Code entered by the user (spaces are ignored)
+/// * `now` - Current Unix timestamp
+/// * `last_used_step` - Step of the last accepted code; codes of this or
+///   earlier steps are rejected
+///
+/// # Returns
+///
+/// The step the code matched, or None if it doesn't match an allowed step
+pub fn verify(secret: &[u8], code: &str, now: i64, last_used_step: Option<i64>) -> Option<i64> {
+    let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let current = step_at(now);
+    (current - ALLOWED_DRIFT_STEPS..=current + ALLOWED_DRIFT_STEPS)
+        .filter(|step| last_used_step.is_none_or(|last| *step > last))
+        .find(|step| constant_time_eq(code_at(secret, *step).as_bytes(), code.as_bytes()))
+}
+
+/// Compares two byte strings in constant time
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 appendix B secret for SHA-1
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // Last 6 digits of the RFC's 8-digit codes
+        let vectors = [
+            (59, "287082"),
+            (1_111_111_109, "081804"),
+            (1_111_111_111, "050471"),
+            (1_234_567_890, "005924"),
+            (2_000_000_000, "279037"),
+            (20_000_000_000, "353130"),
+        ];
+
+        for (time, expected) in vectors {
+            assert_eq!(
+                code_at(RFC_SECRET, step_at(time)),
+                expected,
+                "time {}",
+                time
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_secret() {
+        // RFC 4648 test vectors, without padding
+        assert_eq!(encode_secret(b""), "");
+        assert_eq!(encode_secret(b"f"), "MY");
+        assert_eq!(encode_secret(b"fo"), "MZXQ");
+        assert_eq!(encode_secret(b"foo"), "MZXW6");
+        assert_eq!(encode_secret(b"foob"), "MZXW6YQ");
+        assert_eq!(encode_secret(b"fooba"), "MZXW6YTB");
+        assert_eq!(encode_secret(&generate_secret()).len(), 32);
+    }
+
+    #[test]
+    fn test_verify_allows_drift() {
+        let now = 1_111_111_111;
+        let step = step_at(now);
+
+        assert_eq!(
+            verify(RFC_SECRET, &code_at(RFC_SECRET, step - 1), now, None),
+            Some(step - 1)
+        );
+        assert_eq!(
+            verify(RFC_SECRET, &code_at(RFC_SECRET, step + 1), now, None),
+            Some(step + 1)
+        );
+        assert_eq!(
+            verify(RFC_SECRET, &code_at(RFC_SECRET, step - 2), now, None),
+            None
+        );
+        assert_eq!(verify(RFC_SECRET, "050 471", now, None), Some(step));
+    }
+
+    #[test]
+    fn test_verify_rejects_used_steps() {
+        let now = 1_111_111_111;
+        let step = step_at(now);
+        let code = code_at(RFC_SECRET, step);
+
+        assert_eq!(verify(RFC_SECRET, &code, now, Some(step - 1)), Some(step));
+        assert_eq!(verify(RFC_SECRET, &code, now, Some(step)), None);
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_codes() {
+        let now = 1_111_111_111;
+        for code in ["", "05047", "0504711", "05047a", "-50471"] {
+            assert_eq!(verify(RFC_SECRET, code, now, None), None, "{:?}", code);
+        }
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let uri = provisioning_uri(b"foobar", "Axon Task", "user+2fa@example.com");
+        assert_eq!(
+            uri,
+            "otpauth://totp/Axon%20Task:user%2B2fa%40example.com?secret=MZXW6YTBOI\
+             &issuer=Axon%20Task&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
@@ -0,0 +1,301 @@
+/// TOTP two-factor authentication
+///
+/// This module holds the rules for enrolling in, using and leaving
+/// two-factor authentication (codes themselves are in `auth::totp`).
+///
+/// # Flow
+///
+/// 1. **Enroll**: `begin_enrollment` creates a secret; the user scans its
+///    provisioning URI into an authenticator app
+/// 2. **Confirm**: `confirm_enrollment` checks a code from the app, enables
+///    two-factor authentication and returns recovery codes (shown once)
+/// 3. **Log in**: after the password, `verify` checks a code from the app or
+///    an unused recovery code
+///
+/// # Rules
+///
+/// - Each TOTP code and each recovery code is accepted once
+/// - Disabling two-factor authentication or generating new recovery codes
+///   takes a current code
+/// - A tenant can require two-factor authentication of its members; only an
+///   admin who uses it can turn that on, and members of such a tenant can't
+///   turn it off
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::auth::two_factor::{begin_enrollment, confirm_enrollment, verify};
+/// use axontask_shared::models::user::User;
+/// use sqlx::PgPool;
+///
+/// # async fn example(pool: PgPool, user: User) -> Result<(), Box<dyn std::error::Error>> {
+/// let enrollment = begin_enrollment(&pool, &user, "AxonTask").await?;
+/// // Show enrollment.provisioning_uri as a QR code
+///
+/// let recovery_codes = confirm_enrollment(&pool, user.id, "123456").await?;
+/// // Show the recovery codes once
+///
+/// // At the next login, after the password
+/// verify(&pool, user.id, "654321").await?;
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::totp;
+use crate::models::membership::Membership;
+use crate::models::recovery_code::RecoveryCode;
+use crate::models::tenant::Tenant;
+use crate::models::two_factor::UserTwoFactor;
+use crate::models::user::User;
+
+/// Error type for two-factor operations
+#[derive(Debug, thiserror::Error)]
+pub enum TwoFactorError {
+    /// Enrollment was already confirmed
+    #[error("Two-factor authentication is already enabled")]
+    AlreadyEnabled,
+
+    /// The user doesn't use two-factor authentication
+    #[error("Two-factor authentication is not enabled")]
+    NotEnabled,
+
+    /// There is no enrollment to confirm
+    #[error("No two-factor enrollment in progress")]
+    NoPendingEnrollment,
+
+    /// The code is wrong, expired or already used
+    #[error("Invalid two-factor code")]
+    InvalidCode,
+
+    /// A tenant the user belongs to requires two-factor authentication
+    #[error("A tenant you belong to requires two-factor authentication")]
+    RequiredByTenant,
+
+    /// Database error
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+/// A started enrollment
+#[derive(Debug, Clone, Serialize)]
+pub struct Enrollment {
+    /// Secret as base32, for manual entry in an authenticator app
+    pub secret: String,
+
+    /// `otpauth://` URI to show as a QR code
+    pub provisioning_uri: String,
+}
+
+/// Second factor a login was completed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Factor {
+    /// Code from the authenticator app
+    Totp,
+
+    /// Single-use recovery code
+    RecoveryCode,
+}
+
+/// Starts enrollment with a new secret
+///
+/// Starting again before confirming replaces the secret.
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `user` - User enrolling
+/// * `issuer` - Service name shown in the authenticator app
+///
+/// # Errors
+///
+/// - `AlreadyEnabled` if two-factor authentication is already on
+pub async fn begin_enrollment(
+    pool: &PgPool,
+    user: &User,
+    issuer: &str,
+) -> Result<Enrollment, TwoFactorError> {
+    let secret = totp::generate_secret();
+
+    UserTwoFactor::begin(pool, user.id, &secret)
+        .await?
+        .ok_or(TwoFactorError::AlreadyEnabled)?;
+
+    Ok(Enrollment {
+        secret: totp::encode_secret(&secret),
+        provisioning_uri: totp::provisioning_uri(&secret, issuer, &user.email),
+    })
+}
+
+/// Confirms enrollment with a code from the authenticator app
+///
+/// # Returns
+///
+/// New recovery codes (only their hashes are stored; show them once)
+///
+/// # Errors
+///
+/// - `AlreadyEnabled` if enrollment was already confirmed
+/// - `NoPendingEnrollment` if enrollment wasn't started
+/// - `InvalidCode` if the code doesn't match the new secret
+pub async fn confirm_enrollment(
+    pool: &PgPool,
+    user_id: Uuid,
+    code: &str,
+) -> Result<Vec<String>, TwoFactorError> {
+    let enrollment = UserTwoFactor::find(pool, user_id)
+        .await?
+        .ok_or(TwoFactorError::NoPendingEnrollment)?;
+
+    if enrollment.is_enabled() {
+        return Err(TwoFactorError::AlreadyEnabled);
+    }
+
+    let step = totp::verify(&enrollment.secret, code, Utc::now().timestamp(), None)
+        .ok_or(TwoFactorError::InvalidCode)?;
+
+    if !UserTwoFactor::enable(pool, user_id, step).await? {
+        return Err(TwoFactorError::AlreadyEnabled);
+    }
+
+    let codes = RecoveryCode::generate_codes();
+    RecoveryCode::replace_all(pool, user_id, &codes).await?;
+
+    Ok(codes)
+}
+
+/// Checks a second factor: a code from the authenticator app or an unused
+/// recovery code
+///
+/// An accepted code is used up.
+///
+/// # Returns
+///
+/// Which factor was used
+///
+/// # Errors
+///
+/// - `NotEnabled` if the user doesn't use two-factor authentication
+/// - `InvalidCode` if the code is wrong or already used
+pub async fn verify(pool: &PgPool, user_id: Uuid, code: &str) -> Result<Factor, TwoFactorError> {
+    let enrollment = UserTwoFactor::find(pool, user_id)
+        .await?
+        .filter(UserTwoFactor::is_enabled)
+        .ok_or(TwoFactorError::NotEnabled)?;
+
+    let now = Utc::now().timestamp();
+    if let Some(step) = totp::verify(&enrollment.secret, code, now, enrollment.last_used_step) {
+        // Loses to a concurrent request with the same code
+        if UserTwoFactor::use_step(pool, user_id, step).await? {
+            return Ok(Factor::Totp);
+        }
+        return Err(TwoFactorError::InvalidCode);
+    }
+
+    if RecoveryCode::consume(pool, user_id, code).await? {
+        return Ok(Factor::RecoveryCode);
+    }
+
+    Err(TwoFactorError::InvalidCode)
+}
+
+/// Turns two-factor authentication off
+///
+/// # Errors
+///
+/// - `RequiredByTenant` if a tenant the user belongs to requires it
+/// - `NotEnabled` and `InvalidCode` as in `verify`
+pub async fn disable(pool: &PgPool, user_id: Uuid, code: &str) -> Result<(), TwoFactorError> {
+    let tenants = Membership::list_tenants(pool, user_id).await?;
+    if tenants.iter().any(|tenant| tenant.require_two_factor) {
+        return Err(TwoFactorError::RequiredByTenant);
+    }
+
+    verify(pool, user_id, code).await?;
+
+    UserTwoFactor::delete(pool, user_id).await?;
+    RecoveryCode::delete_all(pool, user_id).await?;
+
+    Ok(())
+}
+
+/// Replaces the user's recovery codes
+///
+/// # Returns
+///
+/// The new codes (show them once)
+///
+/// # Errors
+///
+/// - `NotEnabled` and `InvalidCode` as in `verify`
+pub async fn regenerate_recovery_codes(
+    pool: &PgPool,
+    user_id: Uuid,
+    code: &str,
+) -> Result<Vec<String>, TwoFactorError> {
+    verify(pool, user_id, code).await?;
+
+    let codes = RecoveryCode::generate_codes();
+    RecoveryCode::replace_all(pool, user_id, &codes).await?;
+
+    Ok(codes)
+}
+
+/// Sets whether a tenant requires two-factor authentication of its members
+///
+/// # Arguments
+///
+/// * `pool` - Database connection pool
+/// * `tenant_id` - Tenant to change
+/// * `actor_id` - Admin making the change
+/// * `required` - Whether to require it
+///
+/// # Errors
+///
+/// - `NotEnabled` when turning the requirement on without using two-factor
+///   authentication, which would lock the admin out of the tenant
+pub async fn set_tenant_requirement(
+    pool: &PgPool,
+    tenant_id: Uuid,
+    actor_id: Uuid,
+    required: bool,
+) -> Result<(), TwoFactorError> {
+    if required && !UserTwoFactor::is_enabled_for(pool, actor_id).await? {
+        return Err(TwoFactorError::NotEnabled);
+    }
+
+    Tenant::set_require_two_factor(pool, tenant_id, required).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            TwoFactorError::InvalidCode.to_string(),
+            "Invalid two-factor code"
+        );
+        assert_eq!(
+            TwoFactorError::RequiredByTenant.to_string(),
+            "A tenant you belong to requires two-factor authentication"
+        );
+    }
+
+    #[test]
+    fn test_factor_serialization() {
+        assert_eq!(serde_json::to_string(&Factor::Totp).unwrap(), "\"totp\"");
+        assert_eq!(
+            serde_json::to_string(&Factor::RecoveryCode).unwrap(),
+            "\"recovery_code\""
+        );
+    }
+}
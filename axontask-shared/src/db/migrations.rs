@@ -88,7 +88,7 @@ pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateE
     info!("Starting database migrations");
 
     // Run migrations from the migrations/ directory
-    let migrations = sqlx::migrate!("./migrations");
+    let migrations = sqlx::migrate!("../migrations");
 
     match migrations.run(pool).await {
         Ok(()) => {
@@ -0,0 +1,112 @@
+/// File sink mailer
+///
+/// Writes each message as an RFC 5322 `.eml` file into a directory instead of
+/// delivering it. Useful in development and end-to-end tests, where links can
+/// be read from the files; any mail client opens them.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::email::{EmailMessage, FileMailer, Mailer};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mailer = FileMailer::new("AxonTask <noreply@localhost>", "./mail");
+/// mailer
+///     .send(&EmailMessage::new("user@example.com", "Hello", "Welcome to AxonTask"))
+///     .await?;
+/// // ./mail/20250123T101500.123Z-<uuid>.eml
+/// # Ok(())
+/// # }
+/// ```
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::{EmailError, EmailMessage, Mailer};
+
+/// Mailer that writes messages to `.eml` files
+#[derive(Debug, Clone)]
+pub struct FileMailer {
+    /// Sender mailbox
+    from: String,
+
+    /// Directory the files are written to (created on first send)
+    dir: PathBuf,
+}
+
+impl FileMailer {
+    /// Creates a file mailer
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Sender mailbox, e.g. `AxonTask <noreply@example.com>`
+    /// * `dir` - Directory the files are written to
+    pub fn new(from: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        FileMailer {
+            from: from.into(),
+            dir: dir.into(),
+        }
+    }
+
+    /// Returns the directory the files are written to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| {
+            EmailError::Delivery(format!("cannot create {}: {}", self.dir.display(), e))
+        })?;
+
+        // Timestamp first, so files sort in sending order
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            Uuid::new_v4()
+        ));
+        tokio::fs::write(&path, message.to_rfc5322(&self.from))
+            .await
+            .map_err(|e| EmailError::Delivery(format!("cannot write {}: {}", path.display(), e)))?;
+
+        tracing::debug!(to = %message.to, path = %path.display(), "Email written to file");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_writes_eml_files() {
+        let dir = std::env::temp_dir().join(format!("axontask-mail-{}", Uuid::new_v4()));
+        let mailer = FileMailer::new("AxonTask <noreply@example.com>", &dir);
+
+        for subject in ["First", "Second"] {
+            let message = EmailMessage::new("user@example.com", subject, "Body");
+            mailer.send(&message).await.unwrap();
+        }
+
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|path| path.extension().unwrap() == "eml"));
+
+        let contents: Vec<String> = files
+            .iter()
+            .map(|path| std::fs::read_to_string(path).unwrap())
+            .collect();
+        assert!(contents.iter().all(|c| c.contains("To: user@example.com\r\n")));
+        assert!(contents.iter().any(|c| c.contains("Subject: First\r\n")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,289 @@
+/// Outgoing email
+///
+/// This module defines the `Mailer` trait used for account emails (tenant
+/// invitations, email verification and password resets) and its backends.
+///
+/// # Backends
+///
+/// - `LogMailer`: Logs each message (including its links) instead of sending
+///   it. Meant for development; messages contain single-use tokens, so don't
+///   use it where logs are shared.
+/// - `FileMailer`: Writes each message to a `.eml` file in a directory, for
+///   inspection in development and end-to-end tests.
+/// - `SmtpMailer`: Delivers through an SMTP server (STARTTLS, implicit TLS or
+///   plaintext, with optional SMTP AUTH) using `lettre`.
+///
+/// Other providers implement `Mailer` and are attached to the API state.
+///
+/// # Example
+///
+/// ```
+/// use axontask_shared::email::{EmailMessage, LogMailer, Mailer};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mailer = LogMailer;
+/// mailer
+///     .send(&EmailMessage::new("user@example.com", "Hello", "Welcome to AxonTask"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+
+pub mod file;
+pub mod smtp;
+
+// Re-export commonly used types
+pub use file::FileMailer;
+pub use smtp::{SmtpConfig, SmtpMailer, SmtpSecurity};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Error type for email delivery
+#[derive(Debug, thiserror::Error)]
+pub enum EmailError {
+    /// The message could not be delivered
+    #[error("Email delivery failed: {0}")]
+    Delivery(String),
+}
+
+/// A plain text email
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    /// Recipient address
+    pub to: String,
+
+    /// Subject line
+    pub subject: String,
+
+    /// Plain text body
+    pub body: String,
+}
+
+impl EmailMessage {
+    /// Creates a message
+    pub fn new(to: impl Into<String>, subject: impl Into<String>, body: impl Into<String>) -> Self {
+        EmailMessage {
+            to: to.into(),
+            subject: subject.into(),
+            body: body.into(),
+        }
+    }
+
+    /// Creates a tenant invitation
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - Invited email address
+    /// * `tenant_name` - Name of the tenant the invitee joins
+    /// * `role` - Role granted on acceptance
+    /// * `accept_url` - Link that accepts the invitation (contains the token)
+    /// * `expires_at` - When the invitation expires
+    pub fn invitation(
+        to: &str,
+        tenant_name: &str,
+        role: &str,
+        accept_url: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        EmailMessage::new(
+            to,
+            format!("You're invited to join {} on AxonTask", tenant_name),
+            format!(
+                "You have been invited to join {} on AxonTask as {}.\n\n\
+                 Accept the invitation: {}\n\n\
+                 The invitation expires on {}. If you weren't expecting it, you can ignore this email.\n",
+                tenant_name,
+                role,
+                accept_url,
+                expires_at.format("%Y-%m-%d %H:%M UTC"),
+            ),
+        )
+    }
+
+    /// Creates an email verification message
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - Address to verify
+    /// * `verify_url` - Link that confirms the address (contains the token)
+    /// * `expires_at` - When the link expires
+    pub fn email_verification(to: &str, verify_url: &str, expires_at: DateTime<Utc>) -> Self {
+        EmailMessage::new(
+            to,
+            "Verify your email address for AxonTask",
+            format!(
+                "Please confirm that this is your email address for AxonTask.\n\n\
+                 Verify your email: {}\n\n\
+                 The link expires on {}. If you didn't create an AxonTask account, you can ignore this email.\n",
+                verify_url,
+                expires_at.format("%Y-%m-%d %H:%M UTC"),
+            ),
+        )
+    }
+
+    /// Creates a password reset message
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - Account email address
+    /// * `reset_url` - Link to the password reset form (contains the token)
+    /// * `expires_at` - When the link expires
+    pub fn password_reset(to: &str, reset_url: &str, expires_at: DateTime<Utc>) -> Self {
+        EmailMessage::new(
+            to,
+            "Reset your AxonTask password",
+            format!(
+                "Someone asked to reset the password of your AxonTask account.\n\n\
+                 Choose a new password: {}\n\n\
+                 The link expires on {} and can be used once. If you didn't ask for a reset, \
+                 you can ignore this email; your password stays unchanged.\n",
+                reset_url,
+                expires_at.format("%Y-%m-%d %H:%M UTC"),
+            ),
+        )
+    }
+
+    /// Formats the message as an RFC 5322 document with CRLF line endings
+    ///
+    /// Line breaks in the recipient and subject are dropped so they cannot
+    /// inject headers; non-ASCII subjects are encoded per RFC 2047.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Sender mailbox, e.g. `AxonTask <noreply@example.com>`
+    pub fn to_rfc5322(&self, from: &str) -> String {
+        let subject = single_line(&self.subject);
+        let subject = if subject.is_ascii() {
+            subject
+        } else {
+            format!("=?utf-8?B?{}?=", BASE64.encode(subject.as_bytes()))
+        };
+        let domain = address(from).rsplit_once('@').map_or("localhost", |(_, domain)| domain);
+
+        let mut message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMessage-ID: <{}@{}>\r\n\
+             MIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\n\
+             Content-Transfer-Encoding: 8bit\r\n\r\n",
+            single_line(from),
+            single_line(&self.to),
+            subject,
+            Utc::now().to_rfc2822(),
+            Uuid::new_v4(),
+            domain,
+        );
+        for line in self.body.lines() {
+            message.push_str(line);
+            message.push_str("\r\n");
+        }
+
+        message
+    }
+}
+
+/// Extracts the address from a mailbox (`Name <user@example.com>` or a bare address)
+pub fn address(mailbox: &str) -> &str {
+    match (mailbox.rfind('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => mailbox[start + 1..end].trim(),
+        _ => mailbox.trim(),
+    }
+}
+
+/// Removes line breaks from a header value
+fn single_line(value: &str) -> String {
+    value.chars().filter(|c| *c != '\r' && *c != '\n').collect()
+}
+
+/// Sends emails
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Sends one message
+    ///
+    /// # Errors
+    ///
+    /// Returns `Delivery` if the message could not be sent
+    async fn send(&self, message: &EmailMessage) -> Result<(), EmailError>;
+}
+
+/// Mailer that logs messages instead of sending them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        tracing::info!(
+            to = %message.to,
+            subject = %message.subject,
+            body = %message.body,
+            "Email not sent (log-only mailer)"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invitation_message() {
+        let message = EmailMessage::invitation(
+            "new.hire@example.com",
+            "Acme Corp",
+            "member",
+            "https://app.example.com/invitations/accept?token=inv_abc",
+            Utc::now(),
+        );
+
+        assert_eq!(message.to, "new.hire@example.com");
+        assert!(message.subject.contains("Acme Corp"));
+        assert!(message.body.contains("as member"));
+        assert!(message.body.contains("token=inv_abc"));
+    }
+
+    #[test]
+    fn test_account_messages() {
+        let verification = EmailMessage::email_verification(
+            "user@example.com",
+            "https://app.example.com/verify-email?token=vfy_abc",
+            Utc::now(),
+        );
+        assert!(verification.body.contains("token=vfy_abc"));
+
+        let reset = EmailMessage::password_reset(
+            "user@example.com",
+            "https://app.example.com/reset-password?token=rst_abc",
+            Utc::now(),
+        );
+        assert!(reset.subject.contains("password"));
+        assert!(reset.body.contains("token=rst_abc"));
+    }
+
+    #[test]
+    fn test_to_rfc5322() {
+        let message = EmailMessage::new("user@example.com\r\nBcc: x@evil.test", "Héllo", "Line 1\nLine 2");
+        let formatted = message.to_rfc5322("AxonTask <noreply@example.com>");
+
+        assert!(formatted.starts_with("From: AxonTask <noreply@example.com>\r\n"));
+        assert!(formatted.contains("To: user@example.comBcc: x@evil.test\r\n"));
+        assert!(formatted.contains("Subject: =?utf-8?B?SMOpbGxv?=\r\n"));
+        assert!(formatted.contains("@example.com>\r\n"));
+        assert!(formatted.ends_with("\r\n\r\nLine 1\r\nLine 2\r\n"));
+    }
+
+    #[test]
+    fn test_address() {
+        assert_eq!(address("AxonTask <noreply@example.com>"), "noreply@example.com");
+        assert_eq!(address(" user@example.com "), "user@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_log_mailer() {
+        let message = EmailMessage::new("user@example.com", "Subject", "Body");
+        assert!(LogMailer.send(&message).await.is_ok());
+    }
+}
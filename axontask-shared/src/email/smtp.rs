@@ -0,0 +1,361 @@
+/// SMTP mailer
+///
+/// Delivers messages through an SMTP server with `lettre`, one connection per
+/// message.
+///
+/// # Security Modes
+///
+/// - `StartTls` (default, port 587): Connects in plaintext and upgrades with
+///   STARTTLS before authenticating; fails if the server doesn't support it
+/// - `Tls` (port 465): TLS from the first byte ("SMTPS")
+/// - `None` (port 25): Plaintext. Only for local relays such as a Postfix
+///   sidecar or Mailpit, since credentials and tokens travel unencrypted
+///
+/// Server certificates are verified against the Mozilla root store
+/// (`webpki-roots`, through rustls). With credentials, the mailer
+/// authenticates with AUTH PLAIN or LOGIN, whichever the server offers.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::email::{EmailMessage, Mailer, SmtpConfig, SmtpMailer, SmtpSecurity};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mailer = SmtpMailer::new(
+///     "AxonTask <noreply@example.com>",
+///     SmtpConfig {
+///         host: "smtp.example.com".to_string(),
+///         port: 587,
+///         security: SmtpSecurity::StartTls,
+///         username: Some("apikey".to_string()),
+///         password: Some("secret".to_string()),
+///     },
+/// );
+///
+/// mailer
+///     .send(&EmailMessage::new("user@example.com", "Hello", "Welcome to AxonTask"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+
+use async_trait::async_trait;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::client::{Tls, TlsParameters};
+use lettre::transport::smtp::extension::ClientId;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::{address, EmailError, EmailMessage, Mailer};
+
+/// Time limit for delivering one message, including connecting
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Plaintext (local relays only)
+    None,
+
+    /// Plaintext upgraded with STARTTLS
+    StartTls,
+
+    /// TLS from the start (SMTPS)
+    Tls,
+}
+
+impl SmtpSecurity {
+    /// Conventional port for the mode
+    pub fn default_port(&self) -> u16 {
+        match self {
+            SmtpSecurity::None => 25,
+            SmtpSecurity::StartTls => 587,
+            SmtpSecurity::Tls => 465,
+        }
+    }
+}
+
+impl FromStr for SmtpSecurity {
+    type Err = String;
+
+    /// Parses a security mode (`none`, `starttls` or `tls`, case-insensitive)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(SmtpSecurity::None),
+            "starttls" => Ok(SmtpSecurity::StartTls),
+            "tls" => Ok(SmtpSecurity::Tls),
+            _ => Err(format!("Invalid SMTP security mode: {}", s)),
+        }
+    }
+}
+
+/// SMTP server settings
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    /// Server host name (also used to verify its certificate)
+    pub host: String,
+
+    /// Server port
+    pub port: u16,
+
+    /// How the connection is secured
+    pub security: SmtpSecurity,
+
+    /// Username for SMTP AUTH (None skips authentication)
+    pub username: Option<String>,
+
+    /// Password for SMTP AUTH
+    ///
+    /// Never serialized.
+    #[serde(skip_serializing)]
+    pub password: Option<String>,
+}
+
+impl fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("security", &self.security)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Mailer that delivers through an SMTP server
+#[derive(Clone)]
+pub struct SmtpMailer {
+    /// Sender mailbox
+    from: String,
+
+    /// Server settings
+    config: SmtpConfig,
+}
+
+impl SmtpMailer {
+    /// Creates an SMTP mailer
+    ///
+    /// Doesn't connect; each message opens its own connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Sender mailbox, e.g. `AxonTask <noreply@example.com>`
+    /// * `config` - Server settings
+    pub fn new(from: impl Into<String>, config: SmtpConfig) -> Self {
+        SmtpMailer {
+            from: from.into(),
+            config,
+        }
+    }
+
+    /// Name sent with EHLO: the sender's domain
+    fn helo_name(&self) -> &str {
+        address(&self.from)
+            .rsplit_once('@')
+            .map_or("localhost", |(_, domain)| domain)
+    }
+
+    /// Builds a transport for the configured server
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, EmailError> {
+        let tls = match self.config.security {
+            SmtpSecurity::None => Tls::None,
+            SmtpSecurity::StartTls => Tls::Required(self.tls_parameters()?),
+            SmtpSecurity::Tls => Tls::Wrapper(self.tls_parameters()?),
+        };
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.config.host)
+            .port(self.config.port)
+            .tls(tls)
+            .hello_name(ClientId::Domain(self.helo_name().to_string()))
+            .timeout(Some(SEND_TIMEOUT));
+
+        if let Some(username) = &self.config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                self.config.password.clone().unwrap_or_default(),
+            ));
+        }
+
+        Ok(builder.build())
+    }
+
+    /// TLS settings verifying the server certificate against its host name
+    fn tls_parameters(&self) -> Result<TlsParameters, EmailError> {
+        TlsParameters::new(self.config.host.clone()).map_err(|e| {
+            EmailError::Delivery(format!("invalid SMTP host {}: {}", self.config.host, e))
+        })
+    }
+
+    /// Builds the MIME message
+    ///
+    /// Addresses are parsed rather than copied into headers, so a recipient
+    /// containing line breaks is rejected instead of injecting headers.
+    fn build_message(&self, message: &EmailMessage) -> Result<Message, EmailError> {
+        let mailbox = |value: &str| {
+            value
+                .parse::<Mailbox>()
+                .map_err(|e| EmailError::Delivery(format!("invalid address {}: {}", value, e)))
+        };
+
+        Message::builder()
+            .from(mailbox(&self.from)?)
+            .to(mailbox(&message.to)?)
+            .subject(message.subject.as_str())
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body.clone())
+            .map_err(|e| EmailError::Delivery(format!("cannot build message: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        let email = self.build_message(message)?;
+        let transport = self.transport()?;
+
+        tokio::time::timeout(SEND_TIMEOUT, transport.send(email))
+            .await
+            .map_err(|_| EmailError::Delivery("SMTP delivery timed out".to_string()))?
+            .map_err(|e| EmailError::Delivery(format!("SMTP delivery failed: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Runs a one-connection SMTP server that answers RCPT with `rcpt_reply`
+    ///
+    /// Returns the server address and a handle resolving to the received
+    /// commands and message data.
+    async fn fake_server(
+        rcpt_reply: &'static str,
+    ) -> (u16, tokio::task::JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(socket);
+            let mut commands = Vec::new();
+            let mut data = String::new();
+
+            stream.write_all(b"220 fake ESMTP\r\n").await.unwrap();
+            loop {
+                let mut line = String::new();
+                if stream.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                let command = line.trim_end().to_string();
+                commands.push(command.clone());
+
+                let reply = match command.split(' ').next().unwrap() {
+                    "EHLO" => "250-fake\r\n250 AUTH PLAIN\r\n",
+                    "AUTH" => "235 ok\r\n",
+                    "MAIL" => "250 ok\r\n",
+                    "RCPT" => rcpt_reply,
+                    "DATA" => {
+                        stream.write_all(b"354 go ahead\r\n").await.unwrap();
+                        loop {
+                            let mut line = String::new();
+                            stream.read_line(&mut line).await.unwrap();
+                            if line == ".\r\n" {
+                                break;
+                            }
+                            data.push_str(&line);
+                        }
+                        "250 queued\r\n"
+                    }
+                    "QUIT" => "221 bye\r\n",
+                    _ => "500 unknown\r\n",
+                };
+                stream.write_all(reply.as_bytes()).await.unwrap();
+                if reply.starts_with("221") || reply.starts_with('5') {
+                    break;
+                }
+            }
+
+            (commands, data)
+        });
+
+        (port, handle)
+    }
+
+    fn mailer(port: u16) -> SmtpMailer {
+        SmtpMailer::new(
+            "AxonTask <noreply@example.com>",
+            SmtpConfig {
+                host: "127.0.0.1".to_string(),
+                port,
+                security: SmtpSecurity::None,
+                username: Some("user".to_string()),
+                password: Some("pass".to_string()),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_delivers_message() {
+        let (port, server) = fake_server("250 ok\r\n").await;
+        let message = EmailMessage::new("User <user@example.com>", "Hello", "Hi\n.hidden line\nBye");
+
+        mailer(port).send(&message).await.unwrap();
+
+        let (commands, data) = server.await.unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                "EHLO example.com".to_string(),
+                format!("AUTH PLAIN {}", BASE64.encode("\0user\0pass")),
+                "MAIL FROM:<noreply@example.com>".to_string(),
+                "RCPT TO:<user@example.com>".to_string(),
+                "DATA".to_string(),
+                "QUIT".to_string(),
+            ]
+        );
+        assert!(data.contains("Subject: Hello\r\n"));
+        assert!(data.contains("\r\nHi\r\n..hidden line\r\nBye\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_recipient() {
+        let (port, _server) = fake_server("550 no such user\r\n").await;
+        let message = EmailMessage::new("nobody@example.com", "Hello", "Hi");
+
+        let error = mailer(port).send(&message).await.unwrap_err().to_string();
+        assert!(error.contains("permanent error (550)"), "{}", error);
+        assert!(error.contains("no such user"), "{}", error);
+    }
+
+    #[test]
+    fn test_rejects_header_injection() {
+        let message = EmailMessage::new("user@example.com\r\nBcc: x@evil.test", "Hello", "Hi");
+
+        assert!(mailer(25).build_message(&message).is_err());
+    }
+
+    #[test]
+    fn test_security_modes() {
+        assert_eq!("STARTTLS".parse::<SmtpSecurity>(), Ok(SmtpSecurity::StartTls));
+        assert!("ssl".parse::<SmtpSecurity>().is_err());
+        assert_eq!(SmtpSecurity::Tls.default_port(), 465);
+    }
+
+    #[test]
+    fn test_debug_redacts_password() {
+        let debug = format!("{:?}", mailer(25).config);
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("pass\""));
+    }
+}
@@ -8,6 +8,8 @@
 //! - `models`: Database models and data structures
 //! - `auth`: Authentication and authorization utilities
 //! - `redis`: Redis client and stream utilities
+//! - `webhooks`: Signed webhook delivery over HTTP
+//! - `email`: Outgoing account emails (invitations)
 //! - `integrity`: Hash chain and receipt generation
 //! - `config`: Configuration management
 //! - `error`: Common error types
@@ -16,12 +18,14 @@
 pub mod auth; // Phase 2: Authentication System
 // pub mod config;
 pub mod db; // Phase 1: Core Data Layer
+pub mod email; // Account emails
 pub mod events; // Phase 4: Event serialization
 // pub mod error;
 // pub mod integrity;
 pub mod models; // Phase 1: Database models
 pub mod quota; // Phase 5: Quota enforcement
 pub mod redis; // Phase 4: Redis Streams Infrastructure
+pub mod webhooks; // Webhook delivery
 
 /// Current version of the AxonTask shared library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
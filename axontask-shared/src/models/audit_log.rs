@@ -0,0 +1,211 @@
+/// Audit log model and database operations
+///
+/// This module records security-relevant events, such as accounts locked out
+/// after repeated failed logins or changes to two-factor authentication.
+/// Entries are append-only.
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE audit_log (
+///     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+///     tenant_id UUID REFERENCES tenants(id) ON DELETE CASCADE,
+///     user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+///     action VARCHAR(64) NOT NULL,
+///     ip_address VARCHAR(45),
+///     details JSONB NOT NULL DEFAULT '{}',
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::audit_log::{AuditAction, AuditLog, CreateAuditEntry};
+/// use serde_json::json;
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+/// AuditLog::record(&pool, CreateAuditEntry {
+///     tenant_id: None,
+///     user_id: Some(user_id),
+///     action: AuditAction::LoginLocked,
+///     ip_address: Some("203.0.113.7".to_string()),
+///     details: json!({ "scope": "account", "failures": 10 }),
+/// }).await?;
+///
+/// let recent = AuditLog::list_for_user(&pool, user_id, 50).await?;
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Kind of audited event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditAction {
+    /// An account or IP address was locked out after failed logins
+    #[serde(rename = "auth.login_locked")]
+    LoginLocked,
+
+    /// A user enabled two-factor authentication
+    #[serde(rename = "auth.two_factor_enabled")]
+    TwoFactorEnabled,
+
+    /// A user disabled two-factor authentication
+    #[serde(rename = "auth.two_factor_disabled")]
+    TwoFactorDisabled,
+
+    /// A user generated new recovery codes
+    #[serde(rename = "auth.recovery_codes_regenerated")]
+    RecoveryCodesRegenerated,
+
+    /// A user logged in with a recovery code
+    #[serde(rename = "auth.recovery_code_used")]
+    RecoveryCodeUsed,
+
+    /// An admin changed whether a tenant requires two-factor authentication
+    #[serde(rename = "tenant.two_factor_requirement_changed")]
+    TwoFactorRequirementChanged,
+}
+
+impl AuditAction {
+    /// Converts the action to its stored name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::LoginLocked => "auth.login_locked",
+            AuditAction::TwoFactorEnabled => "auth.two_factor_enabled",
+            AuditAction::TwoFactorDisabled => "auth.two_factor_disabled",
+            AuditAction::RecoveryCodesRegenerated => "auth.recovery_codes_regenerated",
+            AuditAction::RecoveryCodeUsed => "auth.recovery_code_used",
+            AuditAction::TwoFactorRequirementChanged => "tenant.two_factor_requirement_changed",
+        }
+    }
+}
+
+/// Audit log entry
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuditLog {
+    /// Unique entry ID
+    pub id: Uuid,
+
+    /// Tenant the event belongs to (None for account-level events)
+    pub tenant_id: Option<Uuid>,
+
+    /// User the event concerns (None if unknown or deleted)
+    pub user_id: Option<Uuid>,
+
+    /// Event name, e.g. `auth.login_locked`
+    pub action: String,
+
+    /// Client IP address the event came from
+    pub ip_address: Option<String>,
+
+    /// Event-specific data
+    pub details: JsonValue,
+
+    /// When the event happened
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for recording an event
+#[derive(Debug, Clone)]
+pub struct CreateAuditEntry {
+    /// Tenant the event belongs to
+    pub tenant_id: Option<Uuid>,
+
+    /// User the event concerns
+    pub user_id: Option<Uuid>,
+
+    /// Kind of event
+    pub action: AuditAction,
+
+    /// Client IP address
+    pub ip_address: Option<String>,
+
+    /// Event-specific data
+    pub details: JsonValue,
+}
+
+impl AuditLog {
+    /// Records an event
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn record(pool: &PgPool, data: CreateAuditEntry) -> Result<Self, sqlx::Error> {
+        let entry = sqlx::query_as::<_, AuditLog>(
+            r#"
+            INSERT INTO audit_log (tenant_id, user_id, action, ip_address, details)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, tenant_id, user_id, action, ip_address, details, created_at
+            "#,
+        )
+        .bind(data.tenant_id)
+        .bind(data.user_id)
+        .bind(data.action.as_str())
+        .bind(&data.ip_address)
+        .bind(&data.details)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    /// Lists a user's most recent events, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn list_for_user(
+        pool: &PgPool,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let entries = sqlx::query_as::<_, AuditLog>(
+            r#"
+            SELECT id, tenant_id, user_id, action, ip_address, details, created_at
+            FROM audit_log
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_names() {
+        assert_eq!(AuditAction::LoginLocked.as_str(), "auth.login_locked");
+
+        let actions = [
+            AuditAction::LoginLocked,
+            AuditAction::TwoFactorEnabled,
+            AuditAction::TwoFactorDisabled,
+            AuditAction::RecoveryCodesRegenerated,
+            AuditAction::RecoveryCodeUsed,
+            AuditAction::TwoFactorRequirementChanged,
+        ];
+        for action in actions {
+            assert_eq!(
+                serde_json::to_string(&action).unwrap(),
+                format!("\"{}\"", action.as_str())
+            );
+        }
+    }
+}
@@ -0,0 +1,333 @@
+/// Idempotency key model and database operations
+///
+/// This module stores client-supplied idempotency keys for `start_task`, so
+/// a retried request returns the original response instead of creating a
+/// duplicate task.
+///
+/// # Lifecycle
+///
+/// 1. **Reserve**: The first request with a key inserts a row holding the
+///    request hash (no response yet)
+/// 2. **Complete**: The response is stored in the same transaction that
+///    creates the task, so a task never exists without its key completed
+/// 3. **Replay**: Later requests with the same key and body get the stored
+///    response; a different body is rejected
+/// 4. **Expire**: After the retention window the key can be reused
+///
+/// If the original request fails, its reservation is released so the client
+/// can retry with the same key. A reservation that is neither completed nor
+/// released (e.g. the process died) is a lease: after
+/// `RESERVATION_LEASE_SECS` a retry takes it over. Completing and releasing
+/// only apply to the reservation they were given (identified by its
+/// `created_at`), so a request that lost its lease cannot complete the key.
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE idempotency_keys (
+///     tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+///     key VARCHAR(255) NOT NULL,
+///     request_hash BYTEA NOT NULL,
+///     task_id UUID REFERENCES tasks(id) ON DELETE CASCADE,
+///     response JSONB,
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     expires_at TIMESTAMPTZ NOT NULL,
+///     PRIMARY KEY (tenant_id, key)
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::idempotency_key::{IdempotencyKey, DEFAULT_RETENTION_HOURS};
+/// use chrono::Duration;
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, tenant_id: Uuid) -> Result<(), sqlx::Error> {
+/// let body = serde_json::json!({"name": "deploy", "adapter": "shell"});
+/// let hash = IdempotencyKey::hash_request(&body);
+/// let retention = Duration::hours(DEFAULT_RETENTION_HOURS);
+///
+/// match IdempotencyKey::reserve(&pool, tenant_id, "deploy-42", &hash, retention).await? {
+///     Some(_) => println!("First request, create the task"),
+///     None => println!("Key already used, replay or reject"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Default retention window for idempotency keys (hours)
+pub const DEFAULT_RETENTION_HOURS: i64 = 24;
+
+/// How long an in-progress reservation blocks retries (seconds)
+pub const RESERVATION_LEASE_SECS: i64 = 30;
+
+/// Maximum idempotency key length
+pub const MAX_KEY_LENGTH: usize = 255;
+
+/// Idempotency key model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct IdempotencyKey {
+    /// Tenant the key belongs to
+    pub tenant_id: Uuid,
+
+    /// Client-supplied key
+    pub key: String,
+
+    /// SHA-256 of the canonical request body
+    #[serde(skip_serializing)]
+    pub request_hash: Vec<u8>,
+
+    /// Task created by the original request (None while in progress)
+    pub task_id: Option<Uuid>,
+
+    /// Original response body (None while in progress)
+    pub response: Option<JsonValue>,
+
+    /// When the key was reserved (identifies the reservation)
+    pub created_at: DateTime<Utc>,
+
+    /// When the key can be reused
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyKey {
+    /// Hashes a request body for comparison on replay
+    ///
+    /// JSON object keys are serialized in sorted order, so logically equal
+    /// bodies hash the same regardless of field order.
+    pub fn hash_request(body: &JsonValue) -> Vec<u8> {
+        let bytes = serde_json::to_vec(body).expect("JSON values always serialize");
+        Sha256::digest(&bytes).to_vec()
+    }
+
+    /// Checks whether the original request has completed
+    pub fn is_completed(&self) -> bool {
+        self.response.is_some()
+    }
+
+    /// Checks whether a request hash matches the original request
+    pub fn matches(&self, request_hash: &[u8]) -> bool {
+        self.request_hash == request_hash
+    }
+
+    /// Reserves a key for a new request
+    ///
+    /// Inserts the key, or takes over an expired key with the same name or an
+    /// in-progress reservation older than `RESERVATION_LEASE_SECS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `key` - Client-supplied key
+    /// * `request_hash` - Hash of the request body (see `hash_request`)
+    /// * `retention` - How long the key stays active
+    ///
+    /// # Returns
+    ///
+    /// The reservation, or None if the key is already in use
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn reserve(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        key: &str,
+        request_hash: &[u8],
+        retention: Duration,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let reserved = sqlx::query_as::<_, IdempotencyKey>(
+            r#"
+            INSERT INTO idempotency_keys (tenant_id, key, request_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tenant_id, key) DO UPDATE
+            SET request_hash = EXCLUDED.request_hash,
+                task_id = NULL,
+                response = NULL,
+                created_at = NOW(),
+                expires_at = EXCLUDED.expires_at
+            WHERE idempotency_keys.expires_at <= NOW()
+               OR (idempotency_keys.response IS NULL AND idempotency_keys.created_at <= $5)
+            RETURNING tenant_id, key, request_hash, task_id, response, created_at, expires_at
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(key)
+        .bind(request_hash)
+        .bind(Utc::now() + retention)
+        .bind(Utc::now() - Duration::seconds(RESERVATION_LEASE_SECS))
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(reserved)
+    }
+
+    /// Finds an unexpired key
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn find_active(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        key: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let found = sqlx::query_as::<_, IdempotencyKey>(
+            r#"
+            SELECT tenant_id, key, request_hash, task_id, response, created_at, expires_at
+            FROM idempotency_keys
+            WHERE tenant_id = $1 AND key = $2 AND expires_at > NOW()
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(found)
+    }
+
+    /// Stores the response for a reservation
+    ///
+    /// Run it in the transaction that creates the task, and roll back if it
+    /// returns false.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - Connection or transaction
+    /// * `reservation` - Reservation returned by `reserve`
+    /// * `task_id` - Created task
+    /// * `response` - Response to replay
+    ///
+    /// # Returns
+    ///
+    /// True if the reservation was completed, false if it was taken over by
+    /// another request in the meantime
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn complete<'e>(
+        executor: impl PgExecutor<'e>,
+        reservation: &IdempotencyKey,
+        task_id: Uuid,
+        response: JsonValue,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET task_id = $4, response = $5
+            WHERE tenant_id = $1 AND key = $2 AND created_at = $3 AND response IS NULL
+            "#,
+        )
+        .bind(reservation.tenant_id)
+        .bind(&reservation.key)
+        .bind(reservation.created_at)
+        .bind(task_id)
+        .bind(response)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Releases an in-progress reservation
+    ///
+    /// Called when the original request fails, so it can be retried with the
+    /// same key. Completed keys and reservations taken over by another
+    /// request are never released.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn release(pool: &PgPool, reservation: &IdempotencyKey) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE tenant_id = $1 AND key = $2 AND created_at = $3 AND response IS NULL
+            "#,
+        )
+        .bind(reservation.tenant_id)
+        .bind(&reservation.key)
+        .bind(reservation.created_at)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Deletes expired keys
+    ///
+    /// # Returns
+    ///
+    /// Number of keys deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn delete_expired(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= NOW()")
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_with(request_hash: Vec<u8>, response: Option<JsonValue>) -> IdempotencyKey {
+        IdempotencyKey {
+            tenant_id: Uuid::new_v4(),
+            key: "deploy-42".to_string(),
+            request_hash,
+            task_id: response.as_ref().map(|_| Uuid::new_v4()),
+            response,
+            created_at: Utc::now(),
+            expires_at: Utc::now() + Duration::hours(DEFAULT_RETENTION_HOURS),
+        }
+    }
+
+    #[test]
+    fn test_hash_request_ignores_field_order() {
+        let a: JsonValue = serde_json::from_str(r#"{"name":"x","adapter":"shell"}"#).unwrap();
+        let b: JsonValue = serde_json::from_str(r#"{"adapter":"shell","name":"x"}"#).unwrap();
+        let c: JsonValue = serde_json::from_str(r#"{"adapter":"shell","name":"y"}"#).unwrap();
+
+        assert_eq!(IdempotencyKey::hash_request(&a), IdempotencyKey::hash_request(&b));
+        assert_ne!(IdempotencyKey::hash_request(&a), IdempotencyKey::hash_request(&c));
+        assert_eq!(IdempotencyKey::hash_request(&a).len(), 32);
+    }
+
+    #[test]
+    fn test_matches_and_completed() {
+        let hash = IdempotencyKey::hash_request(&serde_json::json!({"name": "x"}));
+
+        let pending = key_with(hash.clone(), None);
+        assert!(pending.matches(&hash));
+        assert!(!pending.matches(&[0u8; 32]));
+        assert!(!pending.is_completed());
+
+        let completed = key_with(hash, Some(serde_json::json!({"task_id": "..."})));
+        assert!(completed.is_completed());
+    }
+
+    #[test]
+    fn test_request_hash_not_serialized() {
+        let key = key_with(vec![1, 2, 3], None);
+        let json = serde_json::to_string(&key).unwrap();
+        assert!(!json.contains("request_hash"));
+    }
+}
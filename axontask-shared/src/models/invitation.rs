@@ -0,0 +1,376 @@
+/// Invitation model and database operations
+///
+/// This module stores invitations to join a tenant. An admin invites an email
+/// address with a role; the invitee gets a single-use token by email and
+/// accepting it creates the membership.
+///
+/// # Security
+///
+/// - Tokens are stored as SHA-256 hashes (never plaintext)
+/// - Only a user whose email matches the invitation can accept it
+/// - Inviting the same email again replaces the pending invitation
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE invitations (
+///     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+///     tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+///     email VARCHAR(255) NOT NULL,
+///     role membership_role NOT NULL DEFAULT 'member',
+///     token_hash VARCHAR(64) NOT NULL UNIQUE,
+///     invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     expires_at TIMESTAMPTZ NOT NULL,
+///     accepted_at TIMESTAMPTZ,
+///     accepted_by UUID REFERENCES users(id) ON DELETE SET NULL
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::invitation::{CreateInvitation, Invitation};
+/// use axontask_shared::models::membership::MembershipRole;
+/// use chrono::{Duration, Utc};
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, tenant_id: Uuid, admin_id: Uuid) -> Result<(), sqlx::Error> {
+/// let token = Invitation::generate_token();
+/// let invitation = Invitation::create(&pool, CreateInvitation {
+///     tenant_id,
+///     email: "new.hire@example.com".to_string(),
+///     role: MembershipRole::Member,
+///     invited_by: Some(admin_id),
+///     token: token.clone(),
+///     expires_at: Utc::now() + Duration::days(7),
+/// }).await?;
+///
+/// // Send `token` to the invitee; it is not stored
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::membership::{Membership, MembershipRole};
+
+/// Invitation token prefix
+pub const TOKEN_PREFIX: &str = "inv_";
+
+/// Length of the random part of an invitation token (characters)
+const TOKEN_RANDOM_LENGTH: usize = 32;
+
+/// Invitation model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Invitation {
+    /// Unique invitation ID
+    pub id: Uuid,
+
+    /// Tenant the invitee joins
+    pub tenant_id: Uuid,
+
+    /// Invited email address
+    pub email: String,
+
+    /// Role granted on acceptance
+    pub role: MembershipRole,
+
+    /// SHA-256 hash of the token (never store plaintext!)
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+
+    /// User who sent the invitation
+    pub invited_by: Option<Uuid>,
+
+    /// When the invitation was sent
+    pub created_at: DateTime<Utc>,
+
+    /// When the invitation expires
+    pub expires_at: DateTime<Utc>,
+
+    /// When the invitation was accepted
+    pub accepted_at: Option<DateTime<Utc>>,
+
+    /// User who accepted the invitation
+    pub accepted_by: Option<Uuid>,
+}
+
+/// Input for creating an invitation
+#[derive(Debug, Clone)]
+pub struct CreateInvitation {
+    /// Tenant ID
+    pub tenant_id: Uuid,
+
+    /// Invited email address
+    pub email: String,
+
+    /// Role granted on acceptance
+    pub role: MembershipRole,
+
+    /// User sending the invitation
+    pub invited_by: Option<Uuid>,
+
+    /// Plaintext token (only its hash is stored)
+    pub token: String,
+
+    /// When the invitation expires
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Invitation {
+    /// Generates a random invitation token
+    ///
+    /// Format: `inv_{32_random_chars}`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axontask_shared::models::invitation::Invitation;
+    ///
+    /// let token = Invitation::generate_token();
+    /// assert!(token.starts_with("inv_"));
+    /// assert_eq!(token.len(), 36);
+    /// ```
+    pub fn generate_token() -> String {
+        const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        let mut rng = rand::thread_rng();
+
+        let random: String = (0..TOKEN_RANDOM_LENGTH)
+            .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+            .collect();
+
+        format!("{}{}", TOKEN_PREFIX, random)
+    }
+
+    /// Hashes an invitation token with SHA-256
+    pub fn hash_token(token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
+
+    /// Checks if the invitation can still be accepted
+    pub fn is_pending(&self) -> bool {
+        self.accepted_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    /// Checks if the invitation was sent to an email address (case-insensitive)
+    pub fn is_for_email(&self, email: &str) -> bool {
+        self.email.eq_ignore_ascii_case(email.trim())
+    }
+
+    /// Stores a new invitation, replacing any pending one for the same email
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn create(pool: &PgPool, data: CreateInvitation) -> Result<Self, sqlx::Error> {
+        let invitation = sqlx::query_as::<_, Invitation>(
+            r#"
+            WITH replaced AS (
+                DELETE FROM invitations
+                WHERE tenant_id = $1 AND LOWER(email) = LOWER($2) AND accepted_at IS NULL
+            )
+            INSERT INTO invitations (tenant_id, email, role, token_hash, invited_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, tenant_id, email, role, token_hash, invited_by, created_at,
+                      expires_at, accepted_at, accepted_by
+            "#,
+        )
+        .bind(data.tenant_id)
+        .bind(data.email.trim())
+        .bind(data.role)
+        .bind(Invitation::hash_token(&data.token))
+        .bind(data.invited_by)
+        .bind(data.expires_at)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(invitation)
+    }
+
+    /// Finds an invitation by its plaintext token
+    ///
+    /// Returns accepted and expired invitations too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn find_by_token(pool: &PgPool, token: &str) -> Result<Option<Self>, sqlx::Error> {
+        let invitation = sqlx::query_as::<_, Invitation>(
+            r#"
+            SELECT id, tenant_id, email, role, token_hash, invited_by, created_at,
+                   expires_at, accepted_at, accepted_by
+            FROM invitations
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(Invitation::hash_token(token))
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(invitation)
+    }
+
+    /// Lists a tenant's pending (unaccepted, unexpired) invitations, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn list_pending(pool: &PgPool, tenant_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        let invitations = sqlx::query_as::<_, Invitation>(
+            r#"
+            SELECT id, tenant_id, email, role, token_hash, invited_by, created_at,
+                   expires_at, accepted_at, accepted_by
+            FROM invitations
+            WHERE tenant_id = $1 AND accepted_at IS NULL AND expires_at > NOW()
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(tenant_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(invitations)
+    }
+
+    /// Accepts an invitation, creating the membership
+    ///
+    /// Marking the invitation accepted and creating the membership happen in
+    /// one statement, so a token can only be accepted once.
+    ///
+    /// # Returns
+    ///
+    /// The new membership, or None if the invitation is no longer pending
+    /// or the user is already a member
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn accept(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Membership>, sqlx::Error> {
+        let membership = sqlx::query_as::<_, Membership>(
+            r#"
+            WITH accepted AS (
+                UPDATE invitations
+                SET accepted_at = NOW(), accepted_by = $2
+                WHERE id = $1 AND accepted_at IS NULL AND expires_at > NOW()
+                  AND NOT EXISTS (
+                      SELECT 1 FROM memberships m
+                      WHERE m.tenant_id = invitations.tenant_id AND m.user_id = $2
+                  )
+                RETURNING tenant_id, role
+            )
+            INSERT INTO memberships (tenant_id, user_id, role)
+            SELECT tenant_id, $2, role FROM accepted
+            RETURNING tenant_id, user_id, role, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(membership)
+    }
+
+    /// Revokes (deletes) a pending invitation
+    ///
+    /// # Returns
+    ///
+    /// True if a pending invitation was deleted; false if it does not exist,
+    /// belongs to another tenant or was already accepted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn revoke(pool: &PgPool, tenant_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM invitations WHERE id = $1 AND tenant_id = $2 AND accepted_at IS NULL",
+        )
+        .bind(id)
+        .bind(tenant_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Deletes expired invitations that were never accepted
+    ///
+    /// # Returns
+    ///
+    /// Number of invitations deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn delete_expired(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM invitations WHERE expires_at <= NOW() AND accepted_at IS NULL",
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn invitation(accepted: bool, expires_in: Duration) -> Invitation {
+        let now = Utc::now();
+        Invitation {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            email: "New.Hire@example.com".to_string(),
+            role: MembershipRole::Member,
+            token_hash: Invitation::hash_token("inv_token"),
+            invited_by: None,
+            created_at: now,
+            expires_at: now + expires_in,
+            accepted_at: accepted.then_some(now),
+            accepted_by: None,
+        }
+    }
+
+    #[test]
+    fn test_generate_token() {
+        let token = Invitation::generate_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + TOKEN_RANDOM_LENGTH);
+        assert_ne!(token, Invitation::generate_token());
+    }
+
+    #[test]
+    fn test_is_pending() {
+        assert!(invitation(false, Duration::days(1)).is_pending());
+        assert!(!invitation(true, Duration::days(1)).is_pending());
+        assert!(!invitation(false, Duration::seconds(-1)).is_pending());
+    }
+
+    #[test]
+    fn test_is_for_email() {
+        let invitation = invitation(false, Duration::days(1));
+        assert!(invitation.is_for_email("new.hire@example.com"));
+        assert!(invitation.is_for_email(" NEW.HIRE@example.com "));
+        assert!(!invitation.is_for_email("someone@example.com"));
+    }
+
+    #[test]
+    fn test_token_hash_not_serialized() {
+        let json = serde_json::to_string(&invitation(false, Duration::days(1))).unwrap();
+        assert!(!json.contains("token_hash"));
+    }
+}
@@ -157,6 +157,64 @@ pub struct Membership {
     pub created_at: DateTime<Utc>,
 }
 
+/// A tenant a user belongs to, with the user's role in it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserTenant {
+    /// Tenant ID
+    pub tenant_id: Uuid,
+
+    /// Tenant name
+    pub name: String,
+
+    /// Tenant plan
+    pub plan: String,
+
+    /// User's role within the tenant
+    pub role: MembershipRole,
+
+    /// When the user joined the tenant
+    pub joined_at: DateTime<Utc>,
+
+    /// Whether the tenant requires two-factor authentication
+    pub require_two_factor: bool,
+}
+
+/// A member of a tenant, with the user's email and name
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TenantMember {
+    /// User ID
+    pub user_id: Uuid,
+
+    /// User email
+    pub email: String,
+
+    /// User display name
+    pub name: Option<String>,
+
+    /// Role within the tenant
+    pub role: MembershipRole,
+
+    /// When the user joined the tenant
+    pub joined_at: DateTime<Utc>,
+
+    /// Whether the user has two-factor authentication enabled
+    pub two_factor_enabled: bool,
+}
+
+/// Outcome of a membership change that must keep at least one owner
+#[derive(Debug, Clone)]
+pub enum OwnerGuarded {
+    /// The change was made; holds the membership after an update, or the
+    /// removed membership after a delete
+    Changed(Membership),
+
+    /// The membership does not exist
+    NotFound,
+
+    /// The change would leave the tenant without an owner
+    LastOwner,
+}
+
 /// Input for creating a new membership
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateMembership {
@@ -466,6 +524,167 @@ impl Membership {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Updates a user's role, keeping at least one owner
+    ///
+    /// The tenant's owner memberships are locked while the change is made, so
+    /// concurrent demotions cannot remove the last owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `user_id` - User ID
+    /// * `role` - New role
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use axontask_shared::models::membership::{Membership, MembershipRole, OwnerGuarded};
+    /// # use sqlx::PgPool;
+    /// # use uuid::Uuid;
+    /// # async fn example(pool: PgPool, tenant_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+    /// match Membership::update_role_keeping_owner(&pool, tenant_id, user_id, MembershipRole::Admin).await? {
+    ///     OwnerGuarded::Changed(membership) => println!("Now {}", membership.role.as_str()),
+    ///     OwnerGuarded::NotFound => println!("Not a member"),
+    ///     OwnerGuarded::LastOwner => println!("Promote another owner first"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_role_keeping_owner(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        role: MembershipRole,
+    ) -> Result<OwnerGuarded, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        match Self::lock_for_change(&mut tx, tenant_id, user_id, role).await? {
+            OwnerGuarded::Changed(_) => {}
+            refused => return Ok(refused),
+        }
+
+        let membership = sqlx::query_as::<_, Membership>(
+            r#"
+            UPDATE memberships
+            SET role = $3
+            WHERE tenant_id = $1 AND user_id = $2
+            RETURNING tenant_id, user_id, role, created_at
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(user_id)
+        .bind(role)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(OwnerGuarded::Changed(membership))
+    }
+
+    /// Removes a user from a tenant, keeping at least one owner
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `user_id` - User ID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use axontask_shared::models::membership::{Membership, OwnerGuarded};
+    /// # use sqlx::PgPool;
+    /// # use uuid::Uuid;
+    /// # async fn example(pool: PgPool, tenant_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+    /// if let OwnerGuarded::LastOwner = Membership::delete_keeping_owner(&pool, tenant_id, user_id).await? {
+    ///     println!("The last owner cannot leave");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_keeping_owner(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<OwnerGuarded, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        // A removed member keeps no role, which counts like a demotion
+        let membership =
+            match Self::lock_for_change(&mut tx, tenant_id, user_id, MembershipRole::Viewer).await? {
+                OwnerGuarded::Changed(membership) => membership,
+                refused => return Ok(refused),
+            };
+
+        sqlx::query("DELETE FROM memberships WHERE tenant_id = $1 AND user_id = $2")
+            .bind(tenant_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(OwnerGuarded::Changed(membership))
+    }
+
+    /// Locks a tenant's owners and the membership to change
+    ///
+    /// Returns `Changed` with the current membership if it may be given
+    /// `new_role`, `LastOwner` if that would remove the last owner.
+    async fn lock_for_change(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        new_role: MembershipRole,
+    ) -> Result<OwnerGuarded, sqlx::Error> {
+        let owners: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM memberships
+            WHERE tenant_id = $1 AND role = 'owner'
+            ORDER BY user_id
+            FOR UPDATE
+            "#,
+        )
+        .bind(tenant_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let membership = sqlx::query_as::<_, Membership>(
+            r#"
+            SELECT tenant_id, user_id, role, created_at
+            FROM memberships
+            WHERE tenant_id = $1 AND user_id = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(user_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(match membership {
+            None => OwnerGuarded::NotFound,
+            Some(membership)
+                if membership.role == MembershipRole::Owner
+                    && new_role != MembershipRole::Owner
+                    && owners.len() <= 1 =>
+            {
+                OwnerGuarded::LastOwner
+            }
+            Some(membership) => OwnerGuarded::Changed(membership),
+        })
+    }
+
     /// Lists all members of a tenant
     ///
     /// # Arguments
@@ -509,6 +728,51 @@ impl Membership {
         Ok(memberships)
     }
 
+    /// Lists a tenant's members with their emails and names
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database connection fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use axontask_shared::models::membership::Membership;
+    /// # use sqlx::PgPool;
+    /// # use uuid::Uuid;
+    /// # async fn example(pool: PgPool, tenant_id: Uuid) -> Result<(), sqlx::Error> {
+    /// for member in Membership::list_members(&pool, tenant_id).await? {
+    ///     println!("{} ({})", member.email, member.role.as_str());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_members(pool: &PgPool, tenant_id: Uuid) -> Result<Vec<TenantMember>, sqlx::Error> {
+        let members = sqlx::query_as::<_, TenantMember>(
+            r#"
+            SELECT m.user_id, u.email, u.name, m.role, m.created_at AS joined_at,
+                   EXISTS(
+                       SELECT 1 FROM user_two_factor t
+                       WHERE t.user_id = m.user_id AND t.enabled_at IS NOT NULL
+                   ) AS two_factor_enabled
+            FROM memberships m
+            JOIN users u ON u.id = m.user_id
+            WHERE m.tenant_id = $1
+            ORDER BY m.created_at ASC
+            "#,
+        )
+        .bind(tenant_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(members)
+    }
+
     /// Lists all tenants a user belongs to
     ///
     /// # Arguments
@@ -552,6 +816,52 @@ impl Membership {
         Ok(memberships)
     }
 
+    /// Lists the tenants a user belongs to, with names and roles
+    ///
+    /// Ordered by join date, so the first entry is the user's original
+    /// (usually personal) tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `user_id` - User ID
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database connection fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use axontask_shared::models::membership::Membership;
+    /// # use sqlx::PgPool;
+    /// # use uuid::Uuid;
+    /// # async fn example(pool: PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+    /// for tenant in Membership::list_tenants(&pool, user_id).await? {
+    ///     println!("{} ({})", tenant.name, tenant.role.as_str());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_tenants(pool: &PgPool, user_id: Uuid) -> Result<Vec<UserTenant>, sqlx::Error> {
+        let tenants = sqlx::query_as::<_, UserTenant>(
+            r#"
+            SELECT m.tenant_id, t.name, t.plan, m.role, m.created_at AS joined_at,
+                   COALESCE(t.settings->'require_two_factor' NOT IN ('false'::JSONB, 'null'::JSONB), FALSE)
+                       AS require_two_factor
+            FROM memberships m
+            JOIN tenants t ON t.id = m.tenant_id
+            WHERE m.user_id = $1
+            ORDER BY m.created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tenants)
+    }
+
     /// Lists members by role within a tenant
     ///
     /// # Arguments
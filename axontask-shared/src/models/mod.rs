@@ -11,7 +11,16 @@
 /// - `task`: Background tasks (Task 1.7)
 /// - `task_event`: Append-only event log with hash chaining (Task 1.8)
 /// - `webhook`: Webhook configurations (Task 1.9)
+/// - `webhook_delivery`: Webhook delivery queue and log
 /// - `usage`: Usage tracking for billing and quotas (Task 1.10)
+/// - `idempotency_key`: Idempotency keys for safe start_task retries
+/// - `quota_alert`: Soft quota thresholds reached per period
+/// - `refresh_token`: Refresh token families (login sessions)
+/// - `invitation`: Invitations to join a tenant
+/// - `user_token`: Email verification and password reset tokens
+/// - `audit_log`: Security-relevant account events
+/// - `two_factor`: TOTP two-factor enrollments
+/// - `recovery_code`: Two-factor recovery codes
 ///
 /// # Example
 ///
@@ -41,4 +50,13 @@ pub mod api_key; // Phase 1, Task 1.6
 pub mod task; // Phase 1, Task 1.7
 pub mod task_event; // Phase 1, Task 1.8
 pub mod webhook; // Phase 1, Task 1.9
+pub mod webhook_delivery;
 pub mod usage; // Phase 1, Task 1.10 ✅ PHASE 1 COMPLETE!
+pub mod idempotency_key;
+pub mod quota_alert;
+pub mod refresh_token;
+pub mod invitation;
+pub mod user_token;
+pub mod audit_log;
+pub mod two_factor;
+pub mod recovery_code;
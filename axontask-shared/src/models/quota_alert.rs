@@ -0,0 +1,229 @@
+/// Quota alert model and database operations
+///
+/// This module records soft quota thresholds (e.g. 80% of `daily_tasks`)
+/// reached by a tenant, so each threshold is notified once per quota period.
+/// See `quota::QuotaEnforcer::check_thresholds`.
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE quota_alerts (
+///     tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+///     quota VARCHAR(32) NOT NULL,
+///     period_start DATE NOT NULL,
+///     threshold SMALLINT NOT NULL,
+///     current_usage BIGINT NOT NULL,
+///     quota_limit BIGINT NOT NULL,
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     PRIMARY KEY (tenant_id, quota, period_start, threshold)
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::quota_alert::QuotaAlert;
+/// use chrono::Utc;
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, tenant_id: Uuid) -> Result<(), sqlx::Error> {
+/// let today = Utc::now().date_naive();
+///
+/// if let Some(alert) = QuotaAlert::record(&pool, tenant_id, "daily_tasks", today, 80, 80, 100).await? {
+///     println!("First time at {}% today", alert.threshold);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Quota alert model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct QuotaAlert {
+    /// Tenant that reached the threshold
+    pub tenant_id: Uuid,
+
+    /// Quota name (`daily_tasks` or `task_minutes`)
+    pub quota: String,
+
+    /// First day of the quota period
+    pub period_start: NaiveDate,
+
+    /// Threshold reached (percent of the limit)
+    pub threshold: i16,
+
+    /// Usage when the threshold was reached
+    pub current_usage: i64,
+
+    /// Limit when the threshold was reached
+    pub quota_limit: i64,
+
+    /// When the threshold was reached
+    pub created_at: DateTime<Utc>,
+}
+
+impl QuotaAlert {
+    /// Records a reached threshold
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `quota` - Quota name
+    /// * `period_start` - First day of the quota period
+    /// * `threshold` - Threshold reached (percent)
+    /// * `current_usage` - Current usage
+    /// * `quota_limit` - Current limit
+    ///
+    /// # Returns
+    ///
+    /// The new alert, or None if the threshold was already recorded for this
+    /// period (and must not be notified again)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn record(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        quota: &str,
+        period_start: NaiveDate,
+        threshold: u8,
+        current_usage: u32,
+        quota_limit: u32,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let alert = sqlx::query_as::<_, QuotaAlert>(
+            r#"
+            INSERT INTO quota_alerts (tenant_id, quota, period_start, threshold, current_usage, quota_limit)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tenant_id, quota, period_start, threshold) DO NOTHING
+            RETURNING tenant_id, quota, period_start, threshold, current_usage, quota_limit, created_at
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(quota)
+        .bind(period_start)
+        .bind(threshold as i16)
+        .bind(current_usage as i64)
+        .bind(quota_limit as i64)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(alert)
+    }
+
+    /// Records several reached thresholds in one statement
+    ///
+    /// Same as `record` for each threshold.
+    ///
+    /// # Returns
+    ///
+    /// The new alerts; thresholds already recorded for this period are left
+    /// out (and must not be notified again)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn record_all(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        quota: &str,
+        period_start: NaiveDate,
+        thresholds: &[u8],
+        current_usage: u32,
+        quota_limit: u32,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let thresholds: Vec<i16> = thresholds.iter().map(|&threshold| threshold as i16).collect();
+
+        let alerts = sqlx::query_as::<_, QuotaAlert>(
+            r#"
+            INSERT INTO quota_alerts (tenant_id, quota, period_start, threshold, current_usage, quota_limit)
+            SELECT $1, $2, $3, UNNEST($4::SMALLINT[]), $5, $6
+            ON CONFLICT (tenant_id, quota, period_start, threshold) DO NOTHING
+            RETURNING tenant_id, quota, period_start, threshold, current_usage, quota_limit, created_at
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(quota)
+        .bind(period_start)
+        .bind(&thresholds)
+        .bind(current_usage as i64)
+        .bind(quota_limit as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    /// Finds the highest threshold recorded for a tenant's quota period
+    pub async fn find_highest(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        quota: &str,
+        period_start: NaiveDate,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let alert = sqlx::query_as::<_, QuotaAlert>(
+            r#"
+            SELECT tenant_id, quota, period_start, threshold, current_usage, quota_limit, created_at
+            FROM quota_alerts
+            WHERE tenant_id = $1 AND quota = $2 AND period_start = $3
+            ORDER BY threshold DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(quota)
+        .bind(period_start)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(alert)
+    }
+
+    /// Lists alerts for a tenant's quota period
+    ///
+    /// # Returns
+    ///
+    /// Alerts ordered by threshold ascending
+    pub async fn list_for_period(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        quota: &str,
+        period_start: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let alerts = sqlx::query_as::<_, QuotaAlert>(
+            r#"
+            SELECT tenant_id, quota, period_start, threshold, current_usage, quota_limit, created_at
+            FROM quota_alerts
+            WHERE tenant_id = $1 AND quota = $2 AND period_start = $3
+            ORDER BY threshold ASC
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(quota)
+        .bind(period_start)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    /// Deletes alerts for periods that started before a date
+    ///
+    /// # Returns
+    ///
+    /// Number of alerts deleted
+    pub async fn delete_before(pool: &PgPool, before: NaiveDate) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM quota_alerts WHERE period_start < $1")
+            .bind(before)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,219 @@
+/// Recovery code model and database operations
+///
+/// This module stores two-factor recovery codes: single-use codes a user
+/// keeps offline to log in when their authenticator app is lost.
+///
+/// # Security
+///
+/// - Codes are stored as SHA-256 hashes (never plaintext)
+/// - A code can be used once
+/// - Generating codes replaces all previous ones
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE user_recovery_codes (
+///     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+///     user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+///     code_hash VARCHAR(64) NOT NULL,
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     used_at TIMESTAMPTZ,
+///     UNIQUE (user_id, code_hash)
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::recovery_code::RecoveryCode;
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+/// let codes = RecoveryCode::generate_codes();
+/// RecoveryCode::replace_all(&pool, user_id, &codes).await?;
+///
+/// // Later, when the user has lost their authenticator app
+/// if RecoveryCode::consume(&pool, user_id, &codes[0]).await? {
+///     println!("Logged in with a recovery code");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Number of codes generated at a time
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Random characters in a code (50 bits)
+const CODE_RANDOM_LENGTH: usize = 10;
+
+/// Recovery code helpers
+pub struct RecoveryCode;
+
+impl RecoveryCode {
+    /// Generates a set of random codes
+    ///
+    /// Format: `xxxxx-xxxxx` from lowercase letters and digits 2-7, so codes
+    /// are easy to type and have no 0/O or 1/l confusion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axontask_shared::models::recovery_code::{RecoveryCode, RECOVERY_CODE_COUNT};
+    ///
+    /// let codes = RecoveryCode::generate_codes();
+    /// assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+    /// assert_eq!(codes[0].len(), 11);
+    /// ```
+    pub fn generate_codes() -> Vec<String> {
+        const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+        let mut rng = rand::thread_rng();
+
+        (0..RECOVERY_CODE_COUNT)
+            .map(|_| {
+                let random: String = (0..CODE_RANDOM_LENGTH)
+                    .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+                    .collect();
+                format!("{}-{}", &random[..5], &random[5..])
+            })
+            .collect()
+    }
+
+    /// Hashes a code with SHA-256
+    ///
+    /// Case, dashes and whitespace are ignored, so `ABCDE FGHIJ` matches
+    /// `abcde-fghij`.
+    pub fn hash_code(code: &str) -> String {
+        let normalized: String = code
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        format!("{:x}", Sha256::digest(normalized.as_bytes()))
+    }
+
+    /// Replaces a user's codes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn replace_all(
+        pool: &PgPool,
+        user_id: Uuid,
+        codes: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let hashes: Vec<String> = codes.iter().map(|code| Self::hash_code(code)).collect();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_recovery_codes (user_id, code_hash)
+            SELECT $1, UNNEST($2::VARCHAR[])
+            "#,
+        )
+        .bind(user_id)
+        .bind(&hashes)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Uses a code
+    ///
+    /// # Returns
+    ///
+    /// True if the code belongs to the user and was unused
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn consume(pool: &PgPool, user_id: Uuid, code: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(Self::hash_code(code))
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Counts a user's unused codes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn count_unused(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM user_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Deletes all of a user's codes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn delete_all(pool: &PgPool, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_codes() {
+        let codes = RecoveryCode::generate_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+
+        for code in &codes {
+            assert_eq!(code.len(), CODE_RANDOM_LENGTH + 1);
+            assert_eq!(&code[5..6], "-");
+        }
+
+        let mut unique = codes.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), codes.len());
+    }
+
+    #[test]
+    fn test_hash_code_normalizes() {
+        let hash = RecoveryCode::hash_code("abcde-fghij");
+        assert_eq!(RecoveryCode::hash_code("ABCDE FGHIJ"), hash);
+        assert_eq!(RecoveryCode::hash_code("abcdefghij"), hash);
+        assert_ne!(RecoveryCode::hash_code("abcde-fghik"), hash);
+        assert_eq!(hash.len(), 64);
+    }
+}
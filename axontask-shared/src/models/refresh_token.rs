@@ -0,0 +1,417 @@
+/// Refresh token model and database operations
+///
+/// This module stores issued refresh tokens so they can be rotated, revoked
+/// and listed as sessions.
+///
+/// # Token Families
+///
+/// Each login starts a family: the chain of refresh tokens issued for that
+/// session. Refreshing marks the presented token as used and issues the next
+/// token in the same family, so only the newest token of a family is active.
+///
+/// A used token presented again means it was copied (e.g. stolen). Since the
+/// legitimate client and the attacker cannot be told apart, the whole family
+/// is revoked and both have to log in again.
+///
+/// # Security
+///
+/// - Tokens are stored as SHA-256 hashes (never plaintext)
+/// - Revoked and used tokens are kept until they expire, so reuse is detected
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE refresh_tokens (
+///     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+///     family_id UUID NOT NULL,
+///     user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+///     tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+///     token_hash VARCHAR(64) NOT NULL UNIQUE,
+///     user_agent TEXT,
+///     family_created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     expires_at TIMESTAMPTZ NOT NULL,
+///     used_at TIMESTAMPTZ,
+///     revoked_at TIMESTAMPTZ
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::refresh_token::RefreshToken;
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+/// // List the user's sessions
+/// for session in RefreshToken::list_sessions(&pool, user_id).await? {
+///     println!("{} signed in at {}", session.id, session.created_at);
+/// }
+///
+/// // Log out everywhere
+/// RefreshToken::revoke_all_for_user(&pool, user_id).await?;
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Refresh token model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RefreshToken {
+    /// Unique token ID
+    pub id: Uuid,
+
+    /// Family (session) the token belongs to
+    pub family_id: Uuid,
+
+    /// User the token was issued to
+    pub user_id: Uuid,
+
+    /// Tenant context of the token
+    pub tenant_id: Uuid,
+
+    /// SHA-256 hash of the token (never store plaintext!)
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+
+    /// User agent of the client that logged in or last refreshed
+    pub user_agent: Option<String>,
+
+    /// When the family was started (login time)
+    pub family_created_at: DateTime<Utc>,
+
+    /// When the token was issued
+    pub created_at: DateTime<Utc>,
+
+    /// When the token expires
+    pub expires_at: DateTime<Utc>,
+
+    /// When the token was exchanged for a new one
+    pub used_at: Option<DateTime<Utc>>,
+
+    /// When the token was revoked (logout or reuse detected)
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Input for storing a new refresh token
+#[derive(Debug, Clone)]
+pub struct CreateRefreshToken {
+    /// Family (session) the token belongs to
+    pub family_id: Uuid,
+
+    /// User ID
+    pub user_id: Uuid,
+
+    /// Tenant ID
+    pub tenant_id: Uuid,
+
+    /// Plaintext token (only its hash is stored)
+    pub token: String,
+
+    /// Client user agent
+    pub user_agent: Option<String>,
+
+    /// When the family was started
+    pub family_created_at: DateTime<Utc>,
+
+    /// When the token expires
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Active session (the newest token of a family)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Session {
+    /// Session ID (the token family ID)
+    pub id: Uuid,
+
+    /// Tenant context of the session
+    pub tenant_id: Uuid,
+
+    /// User agent of the client that last refreshed
+    pub user_agent: Option<String>,
+
+    /// When the session started (login time)
+    pub created_at: DateTime<Utc>,
+
+    /// When the session's token was last refreshed
+    pub last_refreshed_at: DateTime<Utc>,
+
+    /// When the session ends unless refreshed
+    pub expires_at: DateTime<Utc>,
+}
+
+impl RefreshToken {
+    /// Hashes a refresh token with SHA-256
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axontask_shared::models::refresh_token::RefreshToken;
+    ///
+    /// let hash = RefreshToken::hash_token("eyJhbGciOiJIUzI1NiJ9...");
+    /// assert_eq!(hash.len(), 64);
+    /// ```
+    pub fn hash_token(token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
+
+    /// Checks if the token can still be exchanged
+    pub fn is_active(&self) -> bool {
+        self.used_at.is_none() && self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    /// Stores a newly issued refresh token
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn create(pool: &PgPool, data: CreateRefreshToken) -> Result<Self, sqlx::Error> {
+        let token = sqlx::query_as::<_, RefreshToken>(
+            r#"
+            INSERT INTO refresh_tokens (family_id, user_id, tenant_id, token_hash, user_agent,
+                                        family_created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, family_id, user_id, tenant_id, token_hash, user_agent,
+                      family_created_at, created_at, expires_at, used_at, revoked_at
+            "#,
+        )
+        .bind(data.family_id)
+        .bind(data.user_id)
+        .bind(data.tenant_id)
+        .bind(RefreshToken::hash_token(&data.token))
+        .bind(data.user_agent)
+        .bind(data.family_created_at)
+        .bind(data.expires_at)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Finds a stored token by its plaintext value
+    ///
+    /// Returns used and revoked tokens too, so callers can detect reuse.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn find_by_token(pool: &PgPool, token: &str) -> Result<Option<Self>, sqlx::Error> {
+        let token = sqlx::query_as::<_, RefreshToken>(
+            r#"
+            SELECT id, family_id, user_id, tenant_id, token_hash, user_agent,
+                   family_created_at, created_at, expires_at, used_at, revoked_at
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(RefreshToken::hash_token(token))
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Marks a token as exchanged
+    ///
+    /// # Returns
+    ///
+    /// True if the token was active; false if it was already used or revoked
+    /// (e.g. by a concurrent request)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn mark_used(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET used_at = NOW()
+            WHERE id = $1 AND used_at IS NULL AND revoked_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Revokes every token of a user's family (ends the session)
+    ///
+    /// # Returns
+    ///
+    /// Number of tokens revoked (0 if the session does not exist or belongs
+    /// to another user)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn revoke_family(
+        pool: &PgPool,
+        user_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = $1 AND family_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(family_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Revokes every token of a user (ends all sessions)
+    ///
+    /// # Returns
+    ///
+    /// Number of tokens revoked
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn revoke_all_for_user<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        user_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Revokes every token of a user in one tenant
+    ///
+    /// Used when a user is removed from a tenant, so their sessions there
+    /// cannot be refreshed.
+    ///
+    /// # Returns
+    ///
+    /// Number of tokens revoked
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn revoke_for_tenant(
+        pool: &PgPool,
+        user_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = $1 AND tenant_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(tenant_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Lists a user's active sessions, most recently refreshed first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn list_sessions(pool: &PgPool, user_id: Uuid) -> Result<Vec<Session>, sqlx::Error> {
+        let sessions = sqlx::query_as::<_, Session>(
+            r#"
+            SELECT family_id AS id, tenant_id, user_agent, family_created_at AS created_at,
+                   created_at AS last_refreshed_at, expires_at
+            FROM refresh_tokens
+            WHERE user_id = $1 AND used_at IS NULL AND revoked_at IS NULL AND expires_at > NOW()
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    /// Deletes expired tokens
+    ///
+    /// # Returns
+    ///
+    /// Number of tokens deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn delete_expired(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM refresh_tokens WHERE expires_at <= NOW()")
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn token(used: bool, revoked: bool, expires_in: Duration) -> RefreshToken {
+        let now = Utc::now();
+        RefreshToken {
+            id: Uuid::new_v4(),
+            family_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            token_hash: RefreshToken::hash_token("token"),
+            user_agent: None,
+            family_created_at: now,
+            created_at: now,
+            expires_at: now + expires_in,
+            used_at: used.then_some(now),
+            revoked_at: revoked.then_some(now),
+        }
+    }
+
+    #[test]
+    fn test_hash_token() {
+        let hash = RefreshToken::hash_token("token");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, RefreshToken::hash_token("token"));
+        assert_ne!(hash, RefreshToken::hash_token("other"));
+    }
+
+    #[test]
+    fn test_is_active() {
+        assert!(token(false, false, Duration::days(1)).is_active());
+        assert!(!token(true, false, Duration::days(1)).is_active());
+        assert!(!token(false, true, Duration::days(1)).is_active());
+        assert!(!token(false, false, Duration::seconds(-1)).is_active());
+    }
+
+    #[test]
+    fn test_token_hash_not_serialized() {
+        let json = serde_json::to_string(&token(false, false, Duration::days(1))).unwrap();
+        assert!(!json.contains("token_hash"));
+    }
+}
@@ -11,22 +11,26 @@
 ///                  → timeout
 /// pending → canceled
 /// running → canceled
+/// running ⇄ paused
+/// paused  → succeeded | failed | timeout | canceled
 /// ```
 ///
 /// # Schema
 ///
 /// ```sql
 /// CREATE TYPE task_state AS ENUM (
-///     'pending', 'running', 'succeeded', 'failed', 'canceled', 'timeout'
+///     'pending', 'running', 'paused', 'succeeded', 'failed', 'canceled', 'timeout'
 /// );
 ///
 /// CREATE TABLE tasks (
 ///     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
 ///     tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
 ///     created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+///     api_key_id UUID REFERENCES api_keys(id) ON DELETE SET NULL,
 ///     name VARCHAR(255) NOT NULL,
 ///     adapter VARCHAR(50) NOT NULL,
 ///     args JSONB NOT NULL DEFAULT '{}',
+///     tags TEXT[] NOT NULL DEFAULT '{}',
 ///     state task_state NOT NULL DEFAULT 'pending',
 ///     started_at TIMESTAMPTZ,
 ///     ended_at TIMESTAMPTZ,
@@ -36,6 +40,8 @@
 ///     timeout_seconds INTEGER NOT NULL DEFAULT 3600,
 ///     error_message TEXT,
 ///     exit_code INTEGER,
+///     paused_at TIMESTAMPTZ,
+///     paused_seconds INTEGER NOT NULL DEFAULT 0,
 ///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
 ///     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 /// );
@@ -55,10 +61,12 @@
 /// let task = Task::create(&pool, CreateTask {
 ///     tenant_id: Uuid::new_v4(),
 ///     created_by: Some(Uuid::new_v4()),
+///     api_key_id: None,
 ///     name: "Deploy app".to_string(),
 ///     adapter: "fly".to_string(),
 ///     args: json!({"app": "myapp", "region": "iad"}),
 ///     timeout_seconds: 900,
+///     tags: vec!["pr:42".to_string()],
 /// }).await?;
 ///
 /// // Start the task
@@ -84,6 +92,9 @@ pub enum TaskState {
     /// Task is currently being executed by a worker
     Running,
 
+    /// Task execution is suspended and can be resumed
+    Paused,
+
     /// Task completed successfully
     Succeeded,
 
@@ -103,12 +114,30 @@ impl std::fmt::Display for TaskState {
     }
 }
 
+impl std::str::FromStr for TaskState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(TaskState::Pending),
+            "running" => Ok(TaskState::Running),
+            "paused" => Ok(TaskState::Paused),
+            "succeeded" => Ok(TaskState::Succeeded),
+            "failed" => Ok(TaskState::Failed),
+            "canceled" => Ok(TaskState::Canceled),
+            "timeout" => Ok(TaskState::Timeout),
+            _ => Err(format!("Unknown task state: {}", s)),
+        }
+    }
+}
+
 impl TaskState {
     /// Converts state to string for database storage
     pub fn as_str(&self) -> &'static str {
         match self {
             TaskState::Pending => "pending",
             TaskState::Running => "running",
+            TaskState::Paused => "paused",
             TaskState::Succeeded => "succeeded",
             TaskState::Failed => "failed",
             TaskState::Canceled => "canceled",
@@ -126,7 +155,7 @@ impl TaskState {
 
     /// Checks if state is active (task is in progress)
     pub fn is_active(&self) -> bool {
-        matches!(self, TaskState::Pending | TaskState::Running)
+        matches!(self, TaskState::Pending | TaskState::Running | TaskState::Paused)
     }
 
     /// Checks if transition to target state is valid
@@ -141,6 +170,14 @@ impl TaskState {
             (TaskState::Running, TaskState::Failed) => true,
             (TaskState::Running, TaskState::Timeout) => true,
             (TaskState::Running, TaskState::Canceled) => true,
+            (TaskState::Running, TaskState::Paused) => true,
+
+            // Paused can resume, or finish without resuming
+            (TaskState::Paused, TaskState::Running) => true,
+            (TaskState::Paused, TaskState::Succeeded) => true,
+            (TaskState::Paused, TaskState::Failed) => true,
+            (TaskState::Paused, TaskState::Timeout) => true,
+            (TaskState::Paused, TaskState::Canceled) => true,
 
             // Terminal states cannot transition
             _ => false,
@@ -160,6 +197,9 @@ pub struct Task {
     /// User who created the task (nullable if user deleted)
     pub created_by: Option<Uuid>,
 
+    /// API key that created the task (nullable if created with a JWT or key deleted)
+    pub api_key_id: Option<Uuid>,
+
     /// Human-readable task name
     pub name: String,
 
@@ -169,6 +209,9 @@ pub struct Task {
     /// Adapter-specific arguments (JSON)
     pub args: JsonValue,
 
+    /// Tags for grouping and filtering (e.g. "session:abc123", "pr:42")
+    pub tags: Vec<String>,
+
     /// Current execution state
     pub state: TaskState,
 
@@ -196,6 +239,12 @@ pub struct Task {
     /// Exit code (if applicable)
     pub exit_code: Option<i32>,
 
+    /// When the task was paused (null unless state is Paused)
+    pub paused_at: Option<DateTime<Utc>>,
+
+    /// Total time spent paused in seconds (excluded from timeout and billing)
+    pub paused_seconds: i32,
+
     /// When the task was created
     pub created_at: DateTime<Utc>,
 
@@ -212,6 +261,10 @@ pub struct CreateTask {
     /// User who created the task
     pub created_by: Option<Uuid>,
 
+    /// API key that created the task
+    #[serde(default)]
+    pub api_key_id: Option<Uuid>,
+
     /// Task name
     pub name: String,
 
@@ -224,12 +277,77 @@ pub struct CreateTask {
     /// Timeout in seconds (default 3600)
     #[serde(default = "default_timeout")]
     pub timeout_seconds: i32,
+
+    /// Tags for grouping and filtering
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 fn default_timeout() -> i32 {
     3600 // 1 hour
 }
 
+/// Maximum number of tags per task
+pub const MAX_TAGS: usize = 20;
+
+/// Maximum length of a single tag
+pub const MAX_TAG_LENGTH: usize = 64;
+
+/// Checks whether a task reads input sent to it
+///
+/// Only interactive shell tasks (`"interactive": true`) do. Mirrors
+/// `Adapter::accepts_input` in the worker, so the API can reject input up
+/// front.
+pub fn accepts_input(adapter: &str, args: &JsonValue) -> bool {
+    adapter == "shell"
+        && args
+            .get("interactive")
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(false)
+}
+
+/// Validates and normalizes task tags
+///
+/// Trims whitespace and removes duplicates (keeping the first occurrence).
+/// Tags may contain letters, digits and `-_.:/=@`, which allows
+/// "key:value" labels such as "session:abc123" or "pr:42".
+///
+/// # Errors
+///
+/// Returns a description of the first invalid tag, or of the tag count
+/// exceeding `MAX_TAGS`
+pub fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+
+    for tag in tags {
+        let tag = tag.trim().to_string();
+
+        if tag.is_empty() || tag.len() > MAX_TAG_LENGTH {
+            return Err(format!("Tags must be 1-{} characters", MAX_TAG_LENGTH));
+        }
+
+        if !tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.:/=@".contains(c))
+        {
+            return Err(format!(
+                "Invalid tag '{}': only letters, digits and -_.:/=@ are allowed",
+                tag
+            ));
+        }
+
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > MAX_TAGS {
+        return Err(format!("At most {} tags are allowed", MAX_TAGS));
+    }
+
+    Ok(normalized)
+}
+
 /// Input for updating a task
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateTask {
@@ -249,11 +367,97 @@ pub struct UpdateTask {
     pub exit_code: Option<i32>,
 }
 
+/// Sort order for task listings (by creation time)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Oldest first
+    Asc,
+
+    /// Newest first
+    #[default]
+    Desc,
+}
+
+/// Filters for listing tasks
+///
+/// All set filters must match. Empty lists and `None` match everything.
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    /// Task is in any of these states
+    pub states: Vec<TaskState>,
+
+    /// Task uses this adapter
+    pub adapter: Option<String>,
+
+    /// Task has all of these tags
+    pub tags: Vec<String>,
+
+    /// Task was created by this user
+    pub created_by: Option<Uuid>,
+
+    /// Task was created with this API key
+    pub api_key_id: Option<Uuid>,
+
+    /// Task was created at or after this time
+    pub created_after: Option<DateTime<Utc>>,
+
+    /// Task was created before this time
+    pub created_before: Option<DateTime<Utc>>,
+
+    /// Task ended at or after this time
+    pub ended_after: Option<DateTime<Utc>>,
+
+    /// Task ended before this time
+    pub ended_before: Option<DateTime<Utc>>,
+}
+
+/// Position in a task listing, for keyset pagination
+///
+/// Listings are ordered by `(created_at, id)`, so a cursor identifies the
+/// last task of a page and stays valid while new tasks are created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskCursor {
+    /// Creation time of the last task seen
+    pub created_at: DateTime<Utc>,
+
+    /// ID of the last task seen
+    pub id: Uuid,
+}
+
+impl TaskCursor {
+    /// Creates a cursor pointing at a task
+    pub fn from_task(task: &Task) -> Self {
+        TaskCursor {
+            created_at: task.created_at,
+            id: task.id,
+        }
+    }
+
+    /// Encodes the cursor as an opaque string for clients
+    pub fn encode(&self) -> String {
+        hex::encode(format!("{}:{}", self.created_at.timestamp_micros(), self.id))
+    }
+
+    /// Decodes a cursor produced by `encode`
+    ///
+    /// Returns None if the string is not a valid cursor.
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let decoded = String::from_utf8(hex::decode(encoded).ok()?).ok()?;
+        let (micros, id) = decoded.split_once(':')?;
+
+        Some(TaskCursor {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: Uuid::parse_str(id).ok()?,
+        })
+    }
+}
+
 impl Task {
     /// Creates a new task in pending state
     ///
     /// # Arguments
-    ///
+    /// * `executor` - Database connection pool or transaction
     /// * `pool` - Database connection pool
     /// * `data` - Task creation data
     ///
@@ -276,31 +480,39 @@ impl Task {
     /// let task = Task::create(&pool, CreateTask {
     ///     tenant_id: Uuid::new_v4(),
     ///     created_by: Some(Uuid::new_v4()),
+    ///     api_key_id: None,
     ///     name: "Deploy app".to_string(),
     ///     adapter: "fly".to_string(),
     ///     args: json!({"app": "myapp"}),
     ///     timeout_seconds: 900,
+    ///     tags: vec![],
     /// }).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn create(pool: &PgPool, data: CreateTask) -> Result<Self, sqlx::Error> {
+    pub async fn create<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        data: CreateTask,
+    ) -> Result<Self, sqlx::Error> {
         let task = sqlx::query_as::<_, Task>(
             r#"
-            INSERT INTO tasks (tenant_id, created_by, name, adapter, args, timeout_seconds)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, tenant_id, created_by, name, adapter, args, state,
+            INSERT INTO tasks (tenant_id, created_by, api_key_id, name, adapter, args, timeout_seconds, tags)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                       started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                      timeout_seconds, error_message, exit_code, created_at, updated_at
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
             "#,
         )
         .bind(data.tenant_id)
         .bind(data.created_by)
+        .bind(data.api_key_id)
         .bind(data.name)
         .bind(data.adapter)
         .bind(data.args)
         .bind(data.timeout_seconds)
-        .fetch_one(pool)
+        .bind(data.tags)
+        .fetch_one(executor)
         .await?;
 
         Ok(task)
@@ -310,9 +522,10 @@ impl Task {
     pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         let task = sqlx::query_as::<_, Task>(
             r#"
-            SELECT id, tenant_id, created_by, name, adapter, args, state,
+            SELECT id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                    started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                   timeout_seconds, error_message, exit_code, created_at, updated_at
+                   timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                   created_at, updated_at
             FROM tasks
             WHERE id = $1
             "#,
@@ -334,9 +547,10 @@ impl Task {
     ) -> Result<Option<Self>, sqlx::Error> {
         let task = sqlx::query_as::<_, Task>(
             r#"
-            SELECT id, tenant_id, created_by, name, adapter, args, state,
+            SELECT id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                    started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                   timeout_seconds, error_message, exit_code, created_at, updated_at
+                   timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                   created_at, updated_at
             FROM tasks
             WHERE id = $1 AND tenant_id = $2
             "#,
@@ -360,9 +574,10 @@ impl Task {
                 started_at = NOW(),
                 updated_at = NOW()
             WHERE id = $1 AND state = 'pending'
-            RETURNING id, tenant_id, created_by, name, adapter, args, state,
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                       started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                      timeout_seconds, error_message, exit_code, created_at, updated_at
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
             "#,
         )
         .bind(id)
@@ -387,10 +602,11 @@ impl Task {
                 ended_at = NOW(),
                 exit_code = $2,
                 updated_at = NOW()
-            WHERE id = $1 AND state = 'running'
-            RETURNING id, tenant_id, created_by, name, adapter, args, state,
+            WHERE id = $1 AND state IN ('running', 'paused')
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                       started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                      timeout_seconds, error_message, exit_code, created_at, updated_at
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
             "#,
         )
         .bind(id)
@@ -418,10 +634,11 @@ impl Task {
                 error_message = $2,
                 exit_code = $3,
                 updated_at = NOW()
-            WHERE id = $1 AND state = 'running'
-            RETURNING id, tenant_id, created_by, name, adapter, args, state,
+            WHERE id = $1 AND state IN ('running', 'paused')
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                       started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                      timeout_seconds, error_message, exit_code, created_at, updated_at
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
             "#,
         )
         .bind(id)
@@ -435,18 +652,73 @@ impl Task {
 
     /// Transitions task to canceled state
     ///
-    /// Can be called from pending or running state.
+    /// Can be called from pending, running or paused state.
     pub async fn transition_to_canceled(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         let task = sqlx::query_as::<_, Task>(
             r#"
             UPDATE tasks
             SET state = 'canceled',
                 ended_at = NOW(),
+                paused_seconds = paused_seconds
+                    + COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0)::INTEGER,
+                paused_at = NULL,
+                updated_at = NOW()
+            WHERE id = $1 AND state IN ('pending', 'running', 'paused')
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
+                      started_at, ended_at, cursor, bytes_streamed, minutes_used,
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(task)
+    }
+
+    /// Transitions task to paused state
+    ///
+    /// Only running tasks can be paused. Sets paused_at so the paused
+    /// interval can be accounted for when the task resumes.
+    pub async fn transition_to_paused(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        let task = sqlx::query_as::<_, Task>(
+            r#"
+            UPDATE tasks
+            SET state = 'paused',
+                paused_at = NOW(),
                 updated_at = NOW()
-            WHERE id = $1 AND state IN ('pending', 'running')
-            RETURNING id, tenant_id, created_by, name, adapter, args, state,
+            WHERE id = $1 AND state = 'running'
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                       started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                      timeout_seconds, error_message, exit_code, created_at, updated_at
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(task)
+    }
+
+    /// Transitions a paused task back to running state
+    ///
+    /// Adds the paused interval to paused_seconds and clears paused_at.
+    pub async fn transition_to_resumed(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        let task = sqlx::query_as::<_, Task>(
+            r#"
+            UPDATE tasks
+            SET state = 'running',
+                paused_seconds = paused_seconds
+                    + COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0)::INTEGER,
+                paused_at = NULL,
+                updated_at = NOW()
+            WHERE id = $1 AND state = 'paused'
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
+                      started_at, ended_at, cursor, bytes_streamed, minutes_used,
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
             "#,
         )
         .bind(id)
@@ -465,10 +737,11 @@ impl Task {
                 ended_at = NOW(),
                 error_message = 'Task exceeded timeout limit',
                 updated_at = NOW()
-            WHERE id = $1 AND state = 'running'
-            RETURNING id, tenant_id, created_by, name, adapter, args, state,
+            WHERE id = $1 AND state IN ('running', 'paused')
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                       started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                      timeout_seconds, error_message, exit_code, created_at, updated_at
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
             "#,
         )
         .bind(id)
@@ -500,7 +773,7 @@ impl Task {
             query.push_str(&format!(", minutes_used = ${}", bind_count));
         }
 
-        query.push_str(" WHERE id = $1 RETURNING id, tenant_id, created_by, name, adapter, args, state, started_at, ended_at, cursor, bytes_streamed, minutes_used, timeout_seconds, error_message, exit_code, created_at, updated_at");
+        query.push_str(" WHERE id = $1 RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state, started_at, ended_at, cursor, bytes_streamed, minutes_used, timeout_seconds, error_message, exit_code, paused_at, paused_seconds, created_at, updated_at");
 
         let mut q = sqlx::query_as::<_, Task>(&query).bind(id);
 
@@ -528,9 +801,10 @@ impl Task {
     ) -> Result<Vec<Self>, sqlx::Error> {
         let tasks = sqlx::query_as::<_, Task>(
             r#"
-            SELECT id, tenant_id, created_by, name, adapter, args, state,
+            SELECT id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                    started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                   timeout_seconds, error_message, exit_code, created_at, updated_at
+                   timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                   created_at, updated_at
             FROM tasks
             WHERE tenant_id = $1
             ORDER BY created_at DESC
@@ -546,6 +820,157 @@ impl Task {
         Ok(tasks)
     }
 
+    /// Lists tasks matching a filter, using keyset pagination
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `filter` - Filters to apply
+    /// * `order` - Sort order by `(created_at, id)`
+    /// * `after` - Cursor of the last task of the previous page (None for the first page)
+    /// * `limit` - Maximum number of tasks to return
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` tasks following the cursor
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use axontask_shared::models::task::{SortOrder, Task, TaskCursor, TaskFilter};
+    /// use sqlx::PgPool;
+    /// use uuid::Uuid;
+    ///
+    /// # async fn example(pool: PgPool, tenant_id: Uuid) -> Result<(), sqlx::Error> {
+    /// let filter = TaskFilter {
+    ///     tags: vec!["session:abc123".to_string()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let page = Task::list(&pool, tenant_id, &filter, SortOrder::Desc, None, 20).await?;
+    /// if let Some(last) = page.last() {
+    ///     let next = TaskCursor::from_task(last);
+    ///     let page2 = Task::list(&pool, tenant_id, &filter, SortOrder::Desc, Some(next), 20).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        filter: &TaskFilter,
+        order: SortOrder,
+        after: Option<TaskCursor>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut query = String::from(
+            "SELECT id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state, started_at, ended_at, cursor, bytes_streamed, minutes_used, timeout_seconds, error_message, exit_code, paused_at, paused_seconds, created_at, updated_at FROM tasks WHERE tenant_id = $1",
+        );
+        let mut bind_count = 1;
+
+        if !filter.states.is_empty() {
+            bind_count += 1;
+            query.push_str(&format!(" AND state::text = ANY(${})", bind_count));
+        }
+        if filter.adapter.is_some() {
+            bind_count += 1;
+            query.push_str(&format!(" AND adapter = ${}", bind_count));
+        }
+        if !filter.tags.is_empty() {
+            bind_count += 1;
+            query.push_str(&format!(" AND tags @> ${}", bind_count));
+        }
+        if filter.created_by.is_some() {
+            bind_count += 1;
+            query.push_str(&format!(" AND created_by = ${}", bind_count));
+        }
+        if filter.api_key_id.is_some() {
+            bind_count += 1;
+            query.push_str(&format!(" AND api_key_id = ${}", bind_count));
+        }
+        if filter.created_after.is_some() {
+            bind_count += 1;
+            query.push_str(&format!(" AND created_at >= ${}", bind_count));
+        }
+        if filter.created_before.is_some() {
+            bind_count += 1;
+            query.push_str(&format!(" AND created_at < ${}", bind_count));
+        }
+        if filter.ended_after.is_some() {
+            bind_count += 1;
+            query.push_str(&format!(" AND ended_at >= ${}", bind_count));
+        }
+        if filter.ended_before.is_some() {
+            bind_count += 1;
+            query.push_str(&format!(" AND ended_at < ${}", bind_count));
+        }
+
+        let (comparison, direction) = match order {
+            SortOrder::Asc => (">", "ASC"),
+            SortOrder::Desc => ("<", "DESC"),
+        };
+
+        if after.is_some() {
+            query.push_str(&format!(
+                " AND (created_at, id) {} (${}, ${})",
+                comparison,
+                bind_count + 1,
+                bind_count + 2
+            ));
+            bind_count += 2;
+        }
+
+        query.push_str(&format!(
+            " ORDER BY created_at {}, id {} LIMIT ${}",
+            direction,
+            direction,
+            bind_count + 1
+        ));
+
+        let mut q = sqlx::query_as::<_, Task>(&query).bind(tenant_id);
+
+        if !filter.states.is_empty() {
+            let states: Vec<&str> = filter.states.iter().map(|s| s.as_str()).collect();
+            q = q.bind(states);
+        }
+        if let Some(adapter) = &filter.adapter {
+            q = q.bind(adapter);
+        }
+        if !filter.tags.is_empty() {
+            q = q.bind(&filter.tags);
+        }
+        if let Some(created_by) = filter.created_by {
+            q = q.bind(created_by);
+        }
+        if let Some(api_key_id) = filter.api_key_id {
+            q = q.bind(api_key_id);
+        }
+        if let Some(created_after) = filter.created_after {
+            q = q.bind(created_after);
+        }
+        if let Some(created_before) = filter.created_before {
+            q = q.bind(created_before);
+        }
+        if let Some(ended_after) = filter.ended_after {
+            q = q.bind(ended_after);
+        }
+        if let Some(ended_before) = filter.ended_before {
+            q = q.bind(ended_before);
+        }
+        if let Some(cursor) = after {
+            q = q.bind(cursor.created_at).bind(cursor.id);
+        }
+
+        let tasks = q.bind(limit).fetch_all(pool).await?;
+
+        Ok(tasks)
+    }
+
     /// Lists tasks by state
     pub async fn list_by_state(
         pool: &PgPool,
@@ -556,9 +981,10 @@ impl Task {
     ) -> Result<Vec<Self>, sqlx::Error> {
         let tasks = sqlx::query_as::<_, Task>(
             r#"
-            SELECT id, tenant_id, created_by, name, adapter, args, state,
+            SELECT id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                    started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                   timeout_seconds, error_message, exit_code, created_at, updated_at
+                   timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                   created_at, updated_at
             FROM tasks
             WHERE tenant_id = $1 AND state = $2
             ORDER BY created_at DESC
@@ -575,15 +1001,94 @@ impl Task {
         Ok(tasks)
     }
 
+    /// Lists tasks having all of the given tags
+    pub async fn list_by_tags(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        tags: &[String],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let tasks = sqlx::query_as::<_, Task>(
+            r#"
+            SELECT id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
+                   started_at, ended_at, cursor, bytes_streamed, minutes_used,
+                   timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                   created_at, updated_at
+            FROM tasks
+            WHERE tenant_id = $1 AND tags @> $2
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(tags)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tasks)
+    }
+
+    /// Cancels all active tasks having all of the given tags
+    ///
+    /// Pending, running and paused tasks are moved to canceled in one
+    /// statement. Tasks already in a terminal state are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `tags` - Tags every canceled task must have
+    /// * `created_by` - Only cancel tasks created by this user (None = any creator)
+    ///
+    /// # Returns
+    ///
+    /// The canceled tasks
+    pub async fn cancel_by_tags(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        tags: &[String],
+        created_by: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let tasks = sqlx::query_as::<_, Task>(
+            r#"
+            UPDATE tasks
+            SET state = 'canceled',
+                ended_at = NOW(),
+                paused_seconds = paused_seconds
+                    + COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0)::INTEGER,
+                paused_at = NULL,
+                updated_at = NOW()
+            WHERE tenant_id = $1 AND tags @> $2
+              AND ($3::uuid IS NULL OR created_by = $3)
+              AND state IN ('pending', 'running', 'paused')
+            RETURNING id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
+                      started_at, ended_at, cursor, bytes_streamed, minutes_used,
+                      timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(tags)
+        .bind(created_by)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tasks)
+    }
+
     /// Gets pending tasks for worker to process
     ///
     /// Returns tasks in pending state ordered by creation (FIFO).
     pub async fn get_pending_tasks(pool: &PgPool, limit: i64) -> Result<Vec<Self>, sqlx::Error> {
         let tasks = sqlx::query_as::<_, Task>(
             r#"
-            SELECT id, tenant_id, created_by, name, adapter, args, state,
+            SELECT id, tenant_id, created_by, api_key_id, name, adapter, args, tags, state,
                    started_at, ended_at, cursor, bytes_streamed, minutes_used,
-                   timeout_seconds, error_message, exit_code, created_at, updated_at
+                   timeout_seconds, error_message, exit_code, paused_at, paused_seconds,
+                   created_at, updated_at
             FROM tasks
             WHERE state = 'pending'
             ORDER BY created_at ASC
@@ -647,6 +1152,7 @@ mod tests {
     fn test_task_state_as_str() {
         assert_eq!(TaskState::Pending.as_str(), "pending");
         assert_eq!(TaskState::Running.as_str(), "running");
+        assert_eq!(TaskState::Paused.as_str(), "paused");
         assert_eq!(TaskState::Succeeded.as_str(), "succeeded");
         assert_eq!(TaskState::Failed.as_str(), "failed");
         assert_eq!(TaskState::Canceled.as_str(), "canceled");
@@ -657,6 +1163,7 @@ mod tests {
     fn test_task_state_is_terminal() {
         assert!(!TaskState::Pending.is_terminal());
         assert!(!TaskState::Running.is_terminal());
+        assert!(!TaskState::Paused.is_terminal());
         assert!(TaskState::Succeeded.is_terminal());
         assert!(TaskState::Failed.is_terminal());
         assert!(TaskState::Canceled.is_terminal());
@@ -667,6 +1174,7 @@ mod tests {
     fn test_task_state_is_active() {
         assert!(TaskState::Pending.is_active());
         assert!(TaskState::Running.is_active());
+        assert!(TaskState::Paused.is_active());
         assert!(!TaskState::Succeeded.is_active());
         assert!(!TaskState::Failed.is_active());
         assert!(!TaskState::Canceled.is_active());
@@ -685,15 +1193,81 @@ mod tests {
         assert!(TaskState::Running.can_transition_to(TaskState::Failed));
         assert!(TaskState::Running.can_transition_to(TaskState::Timeout));
         assert!(TaskState::Running.can_transition_to(TaskState::Canceled));
+        assert!(TaskState::Running.can_transition_to(TaskState::Paused));
+
+        // Paused transitions
+        assert!(TaskState::Paused.can_transition_to(TaskState::Running));
+        assert!(TaskState::Paused.can_transition_to(TaskState::Canceled));
+        assert!(TaskState::Paused.can_transition_to(TaskState::Timeout));
+        assert!(!TaskState::Pending.can_transition_to(TaskState::Paused));
+        assert!(!TaskState::Paused.can_transition_to(TaskState::Pending));
 
         // Terminal states cannot transition
         assert!(!TaskState::Succeeded.can_transition_to(TaskState::Running));
         assert!(!TaskState::Failed.can_transition_to(TaskState::Running));
         assert!(!TaskState::Canceled.can_transition_to(TaskState::Running));
+        assert!(!TaskState::Succeeded.can_transition_to(TaskState::Paused));
+    }
+
+    #[test]
+    fn test_accepts_input() {
+        assert!(accepts_input("shell", &serde_json::json!({"command": "cat", "interactive": true})));
+        assert!(!accepts_input("shell", &serde_json::json!({"command": "cat"})));
+        assert!(!accepts_input("mock", &serde_json::json!({"interactive": true})));
     }
 
     #[test]
     fn test_default_timeout() {
         assert_eq!(default_timeout(), 3600);
     }
+
+    #[test]
+    fn test_task_state_from_str() {
+        assert_eq!("paused".parse::<TaskState>(), Ok(TaskState::Paused));
+        assert_eq!("timeout".parse::<TaskState>(), Ok(TaskState::Timeout));
+        assert!("Running".parse::<TaskState>().is_err());
+    }
+
+    #[test]
+    fn test_task_cursor_roundtrip() {
+        let cursor = TaskCursor {
+            created_at: DateTime::from_timestamp_micros(1_736_000_000_123_456).unwrap(),
+            id: Uuid::new_v4(),
+        };
+
+        let encoded = cursor.encode();
+        assert_eq!(TaskCursor::decode(&encoded), Some(cursor));
+
+        assert_eq!(TaskCursor::decode("not-a-cursor"), None);
+        assert_eq!(TaskCursor::decode(&hex::encode("123:not-a-uuid")), None);
+    }
+
+    #[test]
+    fn test_sort_order_default() {
+        assert_eq!(SortOrder::default(), SortOrder::Desc);
+        assert_eq!(serde_json::from_str::<SortOrder>("\"asc\"").unwrap(), SortOrder::Asc);
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = normalize_tags(vec![
+            " pr:42 ".to_string(),
+            "session:abc123".to_string(),
+            "pr:42".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(tags, vec!["pr:42", "session:abc123"]);
+
+        assert!(normalize_tags(vec![]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_normalize_tags_invalid() {
+        assert!(normalize_tags(vec!["".to_string()]).is_err());
+        assert!(normalize_tags(vec!["has space".to_string()]).is_err());
+        assert!(normalize_tags(vec!["x".repeat(MAX_TAG_LENGTH + 1)]).is_err());
+
+        let too_many = (0..=MAX_TAGS).map(|i| format!("t{}", i)).collect();
+        assert!(normalize_tags(too_many).is_err());
+    }
 }
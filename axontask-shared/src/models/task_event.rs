@@ -88,6 +88,19 @@ pub enum EventKind {
 
     /// Digest/checkpoint event (for compaction)
     Digest,
+
+    /// Task execution paused
+    Paused,
+
+    /// Task execution resumed after a pause
+    Resumed,
+
+    /// Input delivered to the task
+    Input,
+
+    /// Tenant's task minute quota exhausted; task will be canceled after a grace period
+    #[serde(rename = "quota_warning")]
+    QuotaWarning,
 }
 
 impl EventKind {
@@ -103,6 +116,10 @@ impl EventKind {
             EventKind::Canceled => "canceled",
             EventKind::Timeout => "timeout",
             EventKind::Digest => "digest",
+            EventKind::Paused => "paused",
+            EventKind::Resumed => "resumed",
+            EventKind::Input => "input",
+            EventKind::QuotaWarning => "quota_warning",
         }
     }
 
@@ -118,6 +135,10 @@ impl EventKind {
             "canceled" => Some(EventKind::Canceled),
             "timeout" => Some(EventKind::Timeout),
             "digest" => Some(EventKind::Digest),
+            "paused" => Some(EventKind::Paused),
+            "resumed" => Some(EventKind::Resumed),
+            "input" => Some(EventKind::Input),
+            "quota_warning" => Some(EventKind::QuotaWarning),
             _ => None,
         }
     }
@@ -458,12 +479,22 @@ mod tests {
         assert_eq!(EventKind::Canceled.as_str(), "canceled");
         assert_eq!(EventKind::Timeout.as_str(), "timeout");
         assert_eq!(EventKind::Digest.as_str(), "digest");
+        assert_eq!(EventKind::Paused.as_str(), "paused");
+        assert_eq!(EventKind::Resumed.as_str(), "resumed");
+        assert_eq!(EventKind::Input.as_str(), "input");
+        assert_eq!(EventKind::QuotaWarning.as_str(), "quota_warning");
     }
 
     #[test]
     fn test_event_kind_from_str() {
         assert_eq!(EventKind::from_str("started"), Some(EventKind::Started));
         assert_eq!(EventKind::from_str("progress"), Some(EventKind::Progress));
+        assert_eq!(EventKind::from_str("input"), Some(EventKind::Input));
+        assert_eq!(EventKind::from_str("quota_warning"), Some(EventKind::QuotaWarning));
+        assert_eq!(
+            serde_json::to_value(EventKind::QuotaWarning).unwrap(),
+            serde_json::json!("quota_warning")
+        );
         assert_eq!(EventKind::from_str("invalid"), None);
     }
 
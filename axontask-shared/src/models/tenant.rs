@@ -47,9 +47,10 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serde_json::Value as JsonValue;
+use serde_json::{Map as JsonMap, Value as JsonValue};
 use sqlx::PgPool;
 use uuid::Uuid;
+use validator::Validate;
 
 /// Billing plan types
 ///
@@ -123,6 +124,8 @@ pub struct Tenant {
     /// Tenant-specific configuration (JSONB)
     ///
     /// Example: {"quotas": {"concurrent_tasks": 100}, "retention_days": 30}
+    ///
+    /// Use `settings()` for the typed view.
     pub settings: JsonValue,
 
     /// When the tenant was created
@@ -137,6 +140,114 @@ impl Tenant {
     pub fn get_plan(&self) -> Option<TenantPlan> {
         TenantPlan::from_str(&self.plan)
     }
+
+    /// Gets the typed tenant settings
+    ///
+    /// Settings that fail to parse are logged and treated as empty, so a bad
+    /// row falls back to plan defaults instead of locking the tenant out.
+    /// The two-factor requirement is kept either way (see
+    /// `requires_two_factor`).
+    pub fn settings(&self) -> TenantSettings {
+        serde_json::from_value(self.settings.clone()).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, tenant_id = %self.id, "Invalid tenant settings, using defaults");
+            TenantSettings {
+                require_two_factor: self.requires_two_factor(),
+                ..Default::default()
+            }
+        })
+    }
+
+    /// Whether members must use two-factor authentication
+    ///
+    /// Read from `settings.require_two_factor` alone, so invalid quota
+    /// overrides can't turn it off. Fails closed: any value other than
+    /// `false` or `null` requires it (matching `Membership::list_tenants`).
+    pub fn requires_two_factor(&self) -> bool {
+        match self.settings.get("require_two_factor") {
+            None | Some(JsonValue::Null) => false,
+            Some(JsonValue::Bool(required)) => *required,
+            Some(value) => {
+                tracing::warn!(
+                    tenant_id = %self.id,
+                    value = %value,
+                    "Invalid require_two_factor setting, requiring two-factor authentication"
+                );
+                true
+            }
+        }
+    }
+}
+
+/// Typed view of `tenants.settings`
+///
+/// Unknown keys are kept in `other` so that writing the settings back does
+/// not drop them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TenantSettings {
+    /// Per-tenant quota and rate limit overrides
+    #[serde(default)]
+    pub quotas: QuotaOverrides,
+
+    /// Whether members must use two-factor authentication to log in
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_two_factor: bool,
+
+    /// Other settings (e.g. `retention_days`)
+    #[serde(flatten)]
+    pub other: JsonMap<String, JsonValue>,
+}
+
+/// Per-tenant overrides of plan limits
+///
+/// Each field replaces the plan default when set; unset fields keep the plan
+/// default. Stored under `settings.quotas`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaOverrides {
+    /// Maximum concurrent running tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10_000_000, message = "Must be between 1 and 10000000"))]
+    pub concurrent_tasks: Option<u32>,
+
+    /// Maximum tasks created per 24 hours
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10_000_000, message = "Must be between 1 and 10000000"))]
+    pub daily_tasks: Option<u32>,
+
+    /// Maximum open stream connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10_000_000, message = "Must be between 1 and 10000000"))]
+    pub stream_connections: Option<u32>,
+
+    /// Maximum task minutes per calendar month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 100_000_000, message = "Must be between 1 and 100000000"))]
+    pub task_minutes: Option<u32>,
+
+    /// Rate limit refill (requests per minute)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10_000_000, message = "Must be between 1 and 10000000"))]
+    pub requests_per_minute: Option<u32>,
+
+    /// Rate limit bucket capacity (defaults to `requests_per_minute`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10_000_000, message = "Must be between 1 and 10000000"))]
+    pub burst: Option<u32>,
+
+    /// Additional rate limit per API key (requests per minute)
+    ///
+    /// Requests with an API key always count against the tenant's limit as
+    /// well; this only keeps one key from using all of it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10_000_000, message = "Must be between 1 and 10000000"))]
+    pub api_key_requests_per_minute: Option<u32>,
+}
+
+impl QuotaOverrides {
+    /// Whether no limit is overridden
+    pub fn is_empty(&self) -> bool {
+        *self == QuotaOverrides::default()
+    }
 }
 
 /// Input for creating a new tenant
@@ -405,6 +516,107 @@ impl Tenant {
         Ok(tenant)
     }
 
+    /// Replaces a tenant's quota overrides
+    ///
+    /// Only `settings.quotas` is written; other settings are left untouched.
+    /// Empty overrides remove the key, restoring the plan defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - ID of tenant to update
+    /// * `overrides` - New overrides (validate before calling)
+    ///
+    /// # Returns
+    ///
+    /// The updated tenant if found, None if tenant doesn't exist
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database connection fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use axontask_shared::models::tenant::{QuotaOverrides, Tenant};
+    /// # use sqlx::PgPool;
+    /// # use uuid::Uuid;
+    /// # async fn example(pool: PgPool, tenant_id: Uuid) -> Result<(), sqlx::Error> {
+    /// let overrides = QuotaOverrides {
+    ///     concurrent_tasks: Some(1_000),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// Tenant::update_quota_overrides(&pool, tenant_id, &overrides).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_quota_overrides(
+        pool: &PgPool,
+        id: Uuid,
+        overrides: &QuotaOverrides,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let quotas = serde_json::json!(overrides);
+
+        let tenant = sqlx::query_as::<_, Tenant>(
+            r#"
+            UPDATE tenants
+            SET settings = CASE
+                    WHEN $2 = '{}'::jsonb THEN settings - 'quotas'
+                    ELSE jsonb_set(settings, '{quotas}', $2)
+                END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, name, plan, stripe_customer_id, stripe_subscription_id,
+                      settings, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(quotas)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(tenant)
+    }
+
+    /// Sets whether members must use two-factor authentication
+    ///
+    /// Only `settings.require_two_factor` is written; other settings are left
+    /// untouched. Turning it off removes the key.
+    ///
+    /// # Returns
+    ///
+    /// The updated tenant if found, None if tenant doesn't exist
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database connection fails
+    pub async fn set_require_two_factor(
+        pool: &PgPool,
+        id: Uuid,
+        required: bool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let tenant = sqlx::query_as::<_, Tenant>(
+            r#"
+            UPDATE tenants
+            SET settings = CASE
+                    WHEN $2 THEN jsonb_set(settings, '{require_two_factor}', 'true')
+                    ELSE settings - 'require_two_factor'
+                END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, name, plan, stripe_customer_id, stripe_subscription_id,
+                      settings, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(required)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(tenant)
+    }
+
     /// Updates a tenant's plan
     ///
     /// This is a convenience method for the common operation of upgrading/downgrading plans.
@@ -670,5 +882,88 @@ mod tests {
         assert!(update.settings.is_none());
     }
 
+    fn tenant_with_settings(settings: JsonValue) -> Tenant {
+        Tenant {
+            id: Uuid::new_v4(),
+            name: "Test Corp".to_string(),
+            plan: "pro".to_string(),
+            stripe_customer_id: None,
+            stripe_subscription_id: None,
+            settings,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_settings_parse() {
+        let tenant = tenant_with_settings(serde_json::json!({
+            "quotas": {"concurrent_tasks": 1000, "burst": 50},
+            "retention_days": 30
+        }));
+
+        let settings = tenant.settings();
+        assert_eq!(settings.quotas.concurrent_tasks, Some(1000));
+        assert_eq!(settings.quotas.burst, Some(50));
+        assert_eq!(settings.quotas.daily_tasks, None);
+        assert_eq!(settings.other["retention_days"], 30);
+
+        assert!(!settings.require_two_factor);
+
+        // Unknown keys survive a round trip
+        let value = serde_json::to_value(&settings).unwrap();
+        assert_eq!(value["retention_days"], 30);
+        assert!(value.get("require_two_factor").is_none());
+        assert_eq!(value["quotas"], serde_json::json!({"concurrent_tasks": 1000, "burst": 50}));
+    }
+
+    #[test]
+    fn test_settings_invalid_falls_back_to_defaults() {
+        let empty = tenant_with_settings(serde_json::json!({}));
+        assert!(empty.settings().quotas.is_empty());
+
+        let invalid = tenant_with_settings(serde_json::json!({"quotas": {"concurrent_tasks": -1}}));
+        assert_eq!(invalid.settings(), TenantSettings::default());
+    }
+
+    #[test]
+    fn test_two_factor_requirement_fails_closed() {
+        assert!(!tenant_with_settings(serde_json::json!({})).requires_two_factor());
+        assert!(!tenant_with_settings(serde_json::json!({"require_two_factor": null})).requires_two_factor());
+        assert!(!tenant_with_settings(serde_json::json!({"require_two_factor": false})).requires_two_factor());
+
+        // Survives settings that don't parse
+        let invalid_quotas = tenant_with_settings(serde_json::json!({
+            "require_two_factor": true,
+            "quotas": {"unknown_quota": 1}
+        }));
+        assert!(invalid_quotas.requires_two_factor());
+        assert!(invalid_quotas.settings().require_two_factor);
+
+        let invalid_value = tenant_with_settings(serde_json::json!({"require_two_factor": "yes"}));
+        assert!(invalid_value.requires_two_factor());
+        assert!(invalid_value.settings().require_two_factor);
+    }
+
+    #[test]
+    fn test_quota_overrides_validation() {
+        let valid = QuotaOverrides {
+            concurrent_tasks: Some(1_000),
+            requests_per_minute: Some(5_000),
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+
+        let zero = QuotaOverrides {
+            stream_connections: Some(0),
+            ..Default::default()
+        };
+        assert!(zero.validate().is_err());
+
+        let unknown: Result<QuotaOverrides, _> =
+            serde_json::from_value(serde_json::json!({"concurent_tasks": 10}));
+        assert!(unknown.is_err());
+    }
+
     // Integration tests for database operations are in tests/models/tenant_tests.rs
 }
@@ -0,0 +1,244 @@
+/// Two-factor authentication model and database operations
+///
+/// This module stores users' TOTP secrets (see `auth::totp`). A row exists
+/// from the start of enrollment; two-factor authentication is on once
+/// `enabled_at` is set.
+///
+/// # Security
+///
+/// - The secret must be readable to check codes, so unlike tokens it is not
+///   hashed; it is never serialized
+/// - `last_used_step` only moves forward, so each code is accepted once
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE user_two_factor (
+///     user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+///     secret BYTEA NOT NULL,
+///     enabled_at TIMESTAMPTZ,
+///     last_used_step BIGINT,
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::auth::totp;
+/// use axontask_shared::models::two_factor::UserTwoFactor;
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+/// // Start enrollment; the user scans the secret
+/// UserTwoFactor::begin(&pool, user_id, &totp::generate_secret()).await?;
+///
+/// // The user confirms a code from the app (step returned by totp::verify)
+/// let step = totp::step_at(chrono::Utc::now().timestamp());
+/// UserTwoFactor::enable(&pool, user_id, step).await?;
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// A user's TOTP enrollment
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct UserTwoFactor {
+    /// User the secret belongs to
+    pub user_id: Uuid,
+
+    /// TOTP secret (never serialized)
+    #[serde(skip_serializing)]
+    pub secret: Vec<u8>,
+
+    /// When enrollment was confirmed (None while pending)
+    pub enabled_at: Option<DateTime<Utc>>,
+
+    /// Time step of the last accepted code
+    pub last_used_step: Option<i64>,
+
+    /// When enrollment started
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserTwoFactor {
+    /// Checks if enrollment was confirmed
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+
+    /// Starts (or restarts) enrollment with a new secret
+    ///
+    /// # Returns
+    ///
+    /// The pending enrollment, or None if two-factor authentication is
+    /// already enabled (the secret is left unchanged)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn begin(
+        pool: &PgPool,
+        user_id: Uuid,
+        secret: &[u8],
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let enrollment = sqlx::query_as::<_, UserTwoFactor>(
+            r#"
+            INSERT INTO user_two_factor (user_id, secret)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret = EXCLUDED.secret, last_used_step = NULL, created_at = NOW()
+            WHERE user_two_factor.enabled_at IS NULL
+            RETURNING user_id, secret, enabled_at, last_used_step, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(secret)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(enrollment)
+    }
+
+    /// Finds a user's enrollment, pending or enabled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn find(pool: &PgPool, user_id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        let enrollment = sqlx::query_as::<_, UserTwoFactor>(
+            r#"
+            SELECT user_id, secret, enabled_at, last_used_step, created_at
+            FROM user_two_factor
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(enrollment)
+    }
+
+    /// Checks if a user has two-factor authentication enabled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn is_enabled_for(pool: &PgPool, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let enabled = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM user_two_factor
+                WHERE user_id = $1 AND enabled_at IS NOT NULL
+            )
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(enabled)
+    }
+
+    /// Confirms a pending enrollment
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - Time step of the code that confirmed it
+    ///
+    /// # Returns
+    ///
+    /// True if the enrollment was pending and is now enabled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn enable(pool: &PgPool, user_id: Uuid, step: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_two_factor
+            SET enabled_at = NOW(), last_used_step = $2
+            WHERE user_id = $1 AND enabled_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Records the step of an accepted code
+    ///
+    /// A single conditional update, so the same code can't be accepted twice
+    /// under concurrent requests.
+    ///
+    /// # Returns
+    ///
+    /// True if the step is newer than the last used one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn use_step(pool: &PgPool, user_id: Uuid, step: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_two_factor
+            SET last_used_step = $2
+            WHERE user_id = $1 AND enabled_at IS NOT NULL
+              AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes a user's enrollment
+    ///
+    /// # Returns
+    ///
+    /// True if there was one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn delete(pool: &PgPool, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM user_two_factor WHERE user_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_not_serialized() {
+        let enrollment = UserTwoFactor {
+            user_id: Uuid::new_v4(),
+            secret: vec![1, 2, 3],
+            enabled_at: Some(Utc::now()),
+            last_used_step: Some(1),
+            created_at: Utc::now(),
+        };
+
+        assert!(enrollment.is_enabled());
+        let json = serde_json::to_string(&enrollment).unwrap();
+        assert!(!json.contains("secret"));
+    }
+}
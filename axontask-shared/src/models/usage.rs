@@ -10,6 +10,20 @@
 /// - **bytes**: Total bytes streamed via SSE
 /// - **tasks_created**: Total tasks created
 ///
+/// # Idempotency
+///
+/// The `record_*` methods add to the counters through the `usage_records`
+/// ledger. Each increment has a unique key (e.g. `task_minutes:{task_id}`)
+/// and is skipped if the key was already recorded, so retried metering calls
+/// never double-count. The `increment_*` methods are not idempotent.
+///
+/// # Billing Rule
+///
+/// Task minutes are the time from `started_at` to `ended_at` minus the time
+/// the task spent paused, rounded up to whole minutes (see
+/// `billable_minutes`). This matches the worker's `tasks.minutes_used` and the
+/// running minutes counted by quota enforcement.
+///
 /// # Schema
 ///
 /// ```sql
@@ -46,11 +60,70 @@
 /// # }
 /// ```
 
+use crate::models::task::Task;
 use chrono::{Date, DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+/// Metered quantity (a `usage_counters` column)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageMetric {
+    /// Task execution minutes
+    TaskMinutes,
+
+    /// SSE stream connections
+    Streams,
+
+    /// Bytes streamed via SSE
+    Bytes,
+
+    /// Tasks created
+    TasksCreated,
+}
+
+impl UsageMetric {
+    /// Column name (also stored in `usage_records.metric`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageMetric::TaskMinutes => "task_minutes",
+            UsageMetric::Streams => "streams",
+            UsageMetric::Bytes => "bytes",
+            UsageMetric::TasksCreated => "tasks_created",
+        }
+    }
+
+    /// Update applied to the related task, if any
+    fn task_update(&self) -> Option<&'static str> {
+        match self {
+            UsageMetric::TaskMinutes => Some("minutes_used = recorded.amount"),
+            UsageMetric::Bytes => Some("bytes_streamed = tasks.bytes_streamed + recorded.amount"),
+            UsageMetric::Streams | UsageMetric::TasksCreated => None,
+        }
+    }
+}
+
+/// Billable minutes for a task run ending at `ended_at`
+///
+/// Time spent paused is not billed: `paused_seconds`, plus the current pause
+/// if the task is still paused. Any started minute counts as a full minute;
+/// a run that never started (or has no duration) is free.
+pub fn billable_minutes(task: &Task, ended_at: DateTime<Utc>) -> i32 {
+    let Some(started_at) = task.started_at else {
+        return 0;
+    };
+
+    let current_pause = task
+        .paused_at
+        .map(|paused_at| ended_at.signed_duration_since(paused_at).num_milliseconds().max(0))
+        .unwrap_or(0);
+    let millis = ended_at.signed_duration_since(started_at).num_milliseconds()
+        - i64::from(task.paused_seconds) * 1000
+        - current_pause;
+
+    (millis.max(0) as u64).div_ceil(60_000).min(i32::MAX as u64) as i32
+}
+
 /// Usage counter model tracking tenant usage per day
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct UsageCounter {
@@ -271,6 +344,178 @@ impl UsageCounter {
         Ok(usage)
     }
 
+    /// Records usage once per key
+    ///
+    /// Adds `amount` to the tenant's `metric` counter for `period` unless
+    /// `key` was already recorded. When `task_id` is given, the task's own
+    /// usage column (`minutes_used` or `bytes_streamed`) is updated in the
+    /// same statement.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `key` - Idempotency key (e.g. `bytes:{connection_id}`)
+    /// * `tenant_id` - Tenant ID
+    /// * `period` - Day the usage belongs to
+    /// * `metric` - Counter to increment
+    /// * `amount` - Amount to add
+    /// * `task_id` - Related task, if any
+    ///
+    /// # Returns
+    ///
+    /// True if recorded, false if the key was already recorded
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use axontask_shared::models::usage::{UsageCounter, UsageMetric};
+    /// # use chrono::Utc;
+    /// # use sqlx::PgPool;
+    /// # use uuid::Uuid;
+    /// # async fn example(pool: PgPool, tenant_id: Uuid, connection_id: Uuid) -> Result<(), sqlx::Error> {
+    /// let key = format!("streams:{}", connection_id);
+    /// let today = Utc::now().date_naive();
+    ///
+    /// UsageCounter::record(&pool, &key, tenant_id, today, UsageMetric::Streams, 1, None).await?;
+    ///
+    /// // A retry is a no-op
+    /// let again = UsageCounter::record(&pool, &key, tenant_id, today, UsageMetric::Streams, 1, None).await?;
+    /// assert!(!again);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn record(
+        pool: &PgPool,
+        key: &str,
+        tenant_id: Uuid,
+        period: NaiveDate,
+        metric: UsageMetric,
+        amount: i64,
+        task_id: Option<Uuid>,
+    ) -> Result<bool, sqlx::Error> {
+        let column = metric.as_str();
+        let task_cte = match (metric.task_update(), task_id) {
+            (Some(update), Some(_)) => format!(
+                ", task AS (UPDATE tasks SET {} FROM recorded WHERE tasks.id = $6 RETURNING tasks.id)",
+                update
+            ),
+            _ => String::new(),
+        };
+
+        let query = format!(
+            r#"
+            WITH recorded AS (
+                INSERT INTO usage_records (key, tenant_id, period, metric, amount)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (key) DO NOTHING
+                RETURNING tenant_id, period, amount
+            ){task_cte}
+            INSERT INTO usage_counters (tenant_id, period, {column})
+            SELECT tenant_id, period, amount FROM recorded
+            ON CONFLICT (tenant_id, period)
+            DO UPDATE SET {column} = usage_counters.{column} + EXCLUDED.{column}
+            "#,
+        );
+
+        let mut q = sqlx::query(&query)
+            .bind(key)
+            .bind(tenant_id)
+            .bind(period)
+            .bind(column)
+            .bind(amount);
+        if !task_cte.is_empty() {
+            q = q.bind(task_id);
+        }
+
+        let result = q.execute(pool).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Records a task creation (once per task)
+    ///
+    /// Counted on the day the task was created.
+    pub async fn record_task_created(pool: &PgPool, task: &Task) -> Result<bool, sqlx::Error> {
+        Self::record(
+            pool,
+            &format!("tasks_created:{}", task.id),
+            task.tenant_id,
+            task.created_at.date_naive(),
+            UsageMetric::TasksCreated,
+            1,
+            None,
+        )
+        .await
+    }
+
+    /// Records a finished task's execution minutes (once per task)
+    ///
+    /// Minutes are computed with `billable_minutes` (using the current time if
+    /// `ended_at` is not set), counted on the day the task ended and stored in
+    /// `tasks.minutes_used`.
+    ///
+    /// # Returns
+    ///
+    /// True if recorded, false if already recorded or the task never started
+    pub async fn record_task_minutes(pool: &PgPool, task: &Task) -> Result<bool, sqlx::Error> {
+        if task.started_at.is_none() {
+            return Ok(false);
+        }
+
+        let ended_at = task.ended_at.unwrap_or_else(Utc::now);
+
+        Self::record(
+            pool,
+            &format!("task_minutes:{}", task.id),
+            task.tenant_id,
+            ended_at.date_naive(),
+            UsageMetric::TaskMinutes,
+            billable_minutes(task, ended_at) as i64,
+            Some(task.id),
+        )
+        .await
+    }
+
+    /// Records an SSE stream connection (once per connection)
+    pub async fn record_stream(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        Self::record(
+            pool,
+            &format!("streams:{}", connection_id),
+            tenant_id,
+            Self::today(),
+            UsageMetric::Streams,
+            1,
+            None,
+        )
+        .await
+    }
+
+    /// Records the bytes sent on an SSE connection (once per connection)
+    ///
+    /// Also adds them to `tasks.bytes_streamed`.
+    pub async fn record_stream_bytes(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        task_id: Uuid,
+        connection_id: Uuid,
+        bytes: i64,
+    ) -> Result<bool, sqlx::Error> {
+        Self::record(
+            pool,
+            &format!("bytes:{}", connection_id),
+            tenant_id,
+            Self::today(),
+            UsageMetric::Bytes,
+            bytes,
+            Some(task_id),
+        )
+        .await
+    }
+
     /// Gets usage history for a tenant (last N days)
     ///
     /// # Arguments
@@ -377,6 +622,90 @@ impl UsageCounter {
         Ok(aggregate)
     }
 
+    /// Gets daily usage for a date range
+    ///
+    /// Days without usage have no row and are not returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `start_date` - Start date (inclusive)
+    /// * `end_date` - End date (inclusive)
+    ///
+    /// # Returns
+    ///
+    /// Vector of usage counters ordered by period ascending
+    pub async fn get_range(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let usage = sqlx::query_as::<_, UsageCounter>(
+            r#"
+            SELECT tenant_id, period, task_minutes, streams, bytes, tasks_created
+            FROM usage_counters
+            WHERE tenant_id = $1
+              AND period >= $2
+              AND period <= $3
+            ORDER BY period ASC
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(usage)
+    }
+
+    /// Gets usage per adapter for a date range
+    ///
+    /// Computed from the tasks created in the range (UTC days), so a task
+    /// that runs past midnight is attributed to the day it was created.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `tenant_id` - Tenant ID
+    /// * `start_date` - Start date (inclusive)
+    /// * `end_date` - End date (inclusive)
+    ///
+    /// # Returns
+    ///
+    /// Usage per adapter, most task minutes first
+    pub async fn get_adapter_breakdown(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<AdapterUsage>, sqlx::Error> {
+        let breakdown = sqlx::query_as::<_, AdapterUsage>(
+            r#"
+            SELECT
+                adapter,
+                COUNT(*) as tasks,
+                COALESCE(SUM(minutes_used), 0) as task_minutes,
+                COALESCE(SUM(bytes_streamed), 0)::BIGINT as bytes
+            FROM tasks
+            WHERE tenant_id = $1
+              AND created_at >= $2
+              AND created_at < $3
+            GROUP BY adapter
+            ORDER BY task_minutes DESC, adapter ASC
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(start_of_day(start_date))
+        .bind(start_of_day(end_date + chrono::Duration::days(1)))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(breakdown)
+    }
+
     /// Resets usage counters for a specific period
     ///
     /// ⚠️  Use with caution! This is primarily for testing or correcting errors.
@@ -398,6 +727,8 @@ impl UsageCounter {
 
     /// Deletes old usage records (for data retention policies)
     ///
+    /// Removes both the daily counters and the metering ledger entries.
+    ///
     /// # Arguments
     ///
     /// * `pool` - Database connection pool
@@ -407,6 +738,11 @@ impl UsageCounter {
     ///
     /// Number of records deleted
     pub async fn delete_before(pool: &PgPool, before_date: NaiveDate) -> Result<u64, sqlx::Error> {
+        sqlx::query("DELETE FROM usage_records WHERE period < $1")
+            .bind(before_date)
+            .execute(pool)
+            .await?;
+
         let result = sqlx::query("DELETE FROM usage_counters WHERE period < $1")
             .bind(before_date)
             .execute(pool)
@@ -432,6 +768,27 @@ pub struct UsageAggregate {
     pub total_tasks_created: i64,
 }
 
+/// Midnight UTC at the start of a day
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
+/// Usage of one adapter
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AdapterUsage {
+    /// Adapter name
+    pub adapter: String,
+
+    /// Tasks created
+    pub tasks: i64,
+
+    /// Task minutes
+    pub task_minutes: i64,
+
+    /// Bytes streamed
+    pub bytes: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,5 +800,82 @@ mod tests {
         assert_eq!(today, expected);
     }
 
+    /// A task started at `started_at`
+    fn task(started_at: Option<DateTime<Utc>>) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            created_by: None,
+            api_key_id: None,
+            name: "Test".to_string(),
+            adapter: "mock".to_string(),
+            args: serde_json::json!({}),
+            tags: vec![],
+            state: crate::models::task::TaskState::Succeeded,
+            started_at,
+            ended_at: None,
+            cursor: 0,
+            bytes_streamed: 0,
+            minutes_used: 0,
+            timeout_seconds: 3600,
+            error_message: None,
+            exit_code: None,
+            paused_at: None,
+            paused_seconds: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_billable_minutes_rounds_up() {
+        let start = Utc::now();
+        let at = |secs| start + chrono::Duration::seconds(secs);
+        let run = task(Some(start));
+
+        assert_eq!(billable_minutes(&run, at(0)), 0);
+        assert_eq!(billable_minutes(&run, at(1)), 1);
+        assert_eq!(billable_minutes(&run, at(60)), 1);
+        assert_eq!(billable_minutes(&run, at(61)), 2);
+        assert_eq!(billable_minutes(&run, at(-30)), 0);
+        assert_eq!(billable_minutes(&task(None), at(600)), 0);
+    }
+
+    #[test]
+    fn test_billable_minutes_excludes_paused_time() {
+        let start = Utc::now();
+        let at = |secs| start + chrono::Duration::seconds(secs);
+
+        // 10 minutes wall-clock, 8 of them paused
+        let paused = Task {
+            paused_seconds: 480,
+            ..task(Some(start))
+        };
+        assert_eq!(billable_minutes(&paused, at(600)), 2);
+        assert_eq!(billable_minutes(&paused, at(601)), 3);
+
+        // Still paused since minute 3: the current pause is not billed either
+        let still_paused = Task {
+            paused_at: Some(at(180)),
+            ..task(Some(start))
+        };
+        assert_eq!(billable_minutes(&still_paused, at(3_600)), 3);
+
+        // Never negative, even with inconsistent pause accounting
+        let over = Task {
+            paused_seconds: 900,
+            ..task(Some(start))
+        };
+        assert_eq!(billable_minutes(&over, at(600)), 0);
+    }
+
+    #[test]
+    fn test_usage_metric_columns() {
+        assert_eq!(UsageMetric::TaskMinutes.as_str(), "task_minutes");
+        assert_eq!(UsageMetric::Bytes.as_str(), "bytes");
+        assert!(UsageMetric::TaskMinutes.task_update().is_some());
+        assert!(UsageMetric::TasksCreated.task_update().is_none());
+    }
+
     // Integration tests for database operations are in tests/models/usage_tests.rs
 }
@@ -455,6 +455,86 @@ impl User {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Marks a user's email address as verified
+    ///
+    /// Only applies while the user still has the verified address, so a
+    /// verification link sent before an email change does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - User ID
+    /// * `email` - Address that was verified (compared case-insensitively)
+    ///
+    /// # Returns
+    ///
+    /// True if the user was found with that address and updated
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database connection fails
+    pub async fn mark_email_verified(
+        pool: &PgPool,
+        id: Uuid,
+        email: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email_verified = TRUE, updated_at = NOW()
+            WHERE id = $1 AND email = $2
+            "#,
+        )
+        .bind(id)
+        .bind(email)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Sets a new password from a password reset sent to `email`
+    ///
+    /// Only applies while the user still has that address, so a reset link
+    /// sent before an email change does nothing. Receiving the email proves
+    /// ownership, so the address is marked verified too.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - Database connection or transaction
+    /// * `id` - User ID
+    /// * `email` - Address the reset was sent to (compared case-insensitively)
+    /// * `password_hash` - Hash of the new password
+    ///
+    /// # Returns
+    ///
+    /// True if the user was found with that address and updated
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database connection fails
+    pub async fn reset_password<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        id: Uuid,
+        email: &str,
+        password_hash: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET password_hash = $3, email_verified = TRUE, updated_at = NOW()
+            WHERE id = $1 AND email = $2
+            "#,
+        )
+        .bind(id)
+        .bind(email)
+        .bind(password_hash)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Lists all users with pagination
     ///
     /// # Arguments
@@ -0,0 +1,365 @@
+/// User token model and database operations
+///
+/// This module stores the single-use tokens sent in account emails: email
+/// verification links and password reset links.
+///
+/// # Security
+///
+/// - Tokens are stored as SHA-256 hashes (never plaintext)
+/// - A token can be used once, before it expires
+/// - Issuing a token replaces the user's unused tokens of the same purpose
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE user_tokens (
+///     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+///     user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+///     purpose user_token_purpose NOT NULL,
+///     email CITEXT NOT NULL,
+///     token_hash VARCHAR(64) NOT NULL UNIQUE,
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     expires_at TIMESTAMPTZ NOT NULL,
+///     used_at TIMESTAMPTZ
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::user_token::{CreateUserToken, TokenPurpose, UserToken};
+/// use chrono::{Duration, Utc};
+/// use sqlx::PgPool;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+/// let token = UserToken::generate_token(TokenPurpose::PasswordReset);
+/// UserToken::issue(&pool, CreateUserToken {
+///     user_id,
+///     purpose: TokenPurpose::PasswordReset,
+///     email: "user@example.com".to_string(),
+///     token: token.clone(),
+///     expires_at: Utc::now() + Duration::hours(1),
+/// }).await?;
+///
+/// // Later, when the user follows the link
+/// if let Some(used) = UserToken::consume(&pool, &token, TokenPurpose::PasswordReset).await? {
+///     println!("Reset password of {}", used.user_id);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Length of the random part of a token (characters)
+const TOKEN_RANDOM_LENGTH: usize = 32;
+
+/// What a token can be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "user_token_purpose", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TokenPurpose {
+    /// Confirms the user owns their email address
+    EmailVerification,
+
+    /// Sets a new password without the old one
+    PasswordReset,
+}
+
+impl TokenPurpose {
+    /// Converts the purpose to its database name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenPurpose::EmailVerification => "email_verification",
+            TokenPurpose::PasswordReset => "password_reset",
+        }
+    }
+
+    /// Token prefix, so tokens of different purposes are told apart at a glance
+    pub fn token_prefix(&self) -> &'static str {
+        match self {
+            TokenPurpose::EmailVerification => "vfy_",
+            TokenPurpose::PasswordReset => "rst_",
+        }
+    }
+}
+
+/// User token model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserToken {
+    /// Unique token ID
+    pub id: Uuid,
+
+    /// User the token belongs to
+    pub user_id: Uuid,
+
+    /// What the token can be used for
+    pub purpose: TokenPurpose,
+
+    /// Address the token was sent to
+    pub email: String,
+
+    /// SHA-256 hash of the token (never store plaintext!)
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+
+    /// When the token was issued
+    pub created_at: DateTime<Utc>,
+
+    /// When the token expires
+    pub expires_at: DateTime<Utc>,
+
+    /// When the token was used (None if unused)
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+/// Input for issuing a token
+#[derive(Debug, Clone)]
+pub struct CreateUserToken {
+    /// User the token belongs to
+    pub user_id: Uuid,
+
+    /// What the token can be used for
+    pub purpose: TokenPurpose,
+
+    /// Address the token is sent to
+    pub email: String,
+
+    /// Plaintext token (only its hash is stored)
+    pub token: String,
+
+    /// When the token expires
+    pub expires_at: DateTime<Utc>,
+}
+
+impl UserToken {
+    /// Generates a random token for a purpose
+    ///
+    /// Format: `{prefix}{32_random_chars}`, with prefix `vfy_` or `rst_`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axontask_shared::models::user_token::{TokenPurpose, UserToken};
+    ///
+    /// let token = UserToken::generate_token(TokenPurpose::EmailVerification);
+    /// assert!(token.starts_with("vfy_"));
+    /// assert_eq!(token.len(), 36);
+    /// ```
+    pub fn generate_token(purpose: TokenPurpose) -> String {
+        const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        let mut rng = rand::thread_rng();
+
+        let random: String = (0..TOKEN_RANDOM_LENGTH)
+            .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+            .collect();
+
+        format!("{}{}", purpose.token_prefix(), random)
+    }
+
+    /// Hashes a token with SHA-256
+    pub fn hash_token(token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
+
+    /// Checks if the token can still be used
+    pub fn is_usable(&self) -> bool {
+        self.used_at.is_none() && self.expires_at > Utc::now()
+    }
+
+    /// Stores a new token, replacing the user's unused tokens of the same purpose
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn issue(pool: &PgPool, data: CreateUserToken) -> Result<Self, sqlx::Error> {
+        let token = sqlx::query_as::<_, UserToken>(
+            r#"
+            WITH replaced AS (
+                DELETE FROM user_tokens
+                WHERE user_id = $1 AND purpose = $2 AND used_at IS NULL
+            )
+            INSERT INTO user_tokens (user_id, purpose, email, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, user_id, purpose, email::TEXT AS email, token_hash, created_at,
+                      expires_at, used_at
+            "#,
+        )
+        .bind(data.user_id)
+        .bind(data.purpose)
+        .bind(&data.email)
+        .bind(UserToken::hash_token(&data.token))
+        .bind(data.expires_at)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Returns when the user was last issued a token of a purpose
+    ///
+    /// Used to throttle repeated requests for the same email.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn last_issued_at(
+        pool: &PgPool,
+        user_id: Uuid,
+        purpose: TokenPurpose,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        let issued_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            r#"
+            SELECT created_at FROM user_tokens
+            WHERE user_id = $1 AND purpose = $2
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(purpose)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(issued_at)
+    }
+
+    /// Uses a token
+    ///
+    /// Marking the token used is a single conditional update, so a token can
+    /// only be used once even under concurrent requests.
+    ///
+    /// # Returns
+    ///
+    /// The used token, or None if it is unknown, has another purpose, expired
+    /// or was already used
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn consume<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        token: &str,
+        purpose: TokenPurpose,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let token = sqlx::query_as::<_, UserToken>(
+            r#"
+            UPDATE user_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND purpose = $2 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING id, user_id, purpose, email::TEXT AS email, token_hash, created_at,
+                      expires_at, used_at
+            "#,
+        )
+        .bind(UserToken::hash_token(token))
+        .bind(purpose)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Marks all of a user's unused tokens of a purpose used
+    ///
+    /// # Returns
+    ///
+    /// Number of tokens invalidated
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn invalidate_all<'e>(
+        executor: impl sqlx::PgExecutor<'e>,
+        user_id: Uuid,
+        purpose: TokenPurpose,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND purpose = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(purpose)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Deletes expired tokens, used or not
+    ///
+    /// # Returns
+    ///
+    /// Number of tokens deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn delete_expired(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM user_tokens WHERE expires_at <= NOW()")
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn token(used: bool, expires_in: Duration) -> UserToken {
+        let now = Utc::now();
+        UserToken {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            purpose: TokenPurpose::PasswordReset,
+            email: "user@example.com".to_string(),
+            token_hash: UserToken::hash_token("rst_token"),
+            created_at: now,
+            expires_at: now + expires_in,
+            used_at: used.then_some(now),
+        }
+    }
+
+    #[test]
+    fn test_generate_token() {
+        for purpose in [TokenPurpose::EmailVerification, TokenPurpose::PasswordReset] {
+            let token = UserToken::generate_token(purpose);
+            assert!(token.starts_with(purpose.token_prefix()));
+            assert_eq!(token.len(), purpose.token_prefix().len() + TOKEN_RANDOM_LENGTH);
+            assert_ne!(token, UserToken::generate_token(purpose));
+        }
+    }
+
+    #[test]
+    fn test_is_usable() {
+        assert!(token(false, Duration::hours(1)).is_usable());
+        assert!(!token(true, Duration::hours(1)).is_usable());
+        assert!(!token(false, Duration::seconds(-1)).is_usable());
+    }
+
+    #[test]
+    fn test_purpose_serialization() {
+        assert_eq!(
+            serde_json::to_string(&TokenPurpose::EmailVerification).unwrap(),
+            "\"email_verification\""
+        );
+        assert_eq!(TokenPurpose::PasswordReset.as_str(), "password_reset");
+    }
+
+    #[test]
+    fn test_token_hash_not_serialized() {
+        let json = serde_json::to_string(&token(false, Duration::hours(1))).unwrap();
+        assert!(!json.contains("token_hash"));
+    }
+}
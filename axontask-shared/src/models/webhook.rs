@@ -9,6 +9,8 @@
 /// - Each delivery includes an HMAC-SHA256 signature
 /// - Signatures are sent in the X-AxonTask-Signature header
 /// - Recipients should verify signatures to ensure authenticity
+/// - Secrets can be rotated; the previous secret keeps signing deliveries
+///   during an overlap window so receivers can switch over
 ///
 /// # Schema
 ///
@@ -18,6 +20,8 @@
 ///     tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
 ///     url VARCHAR(2048) NOT NULL,
 ///     secret BYTEA NOT NULL,
+///     previous_secret BYTEA,
+///     previous_secret_expires_at TIMESTAMPTZ,
 ///     active BOOLEAN NOT NULL DEFAULT TRUE,
 ///     events TEXT[] NOT NULL DEFAULT ARRAY['task.succeeded', 'task.failed'],
 ///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
@@ -47,13 +51,29 @@
 /// # }
 /// ```
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+/// Event types a webhook can subscribe to
+pub const EVENT_TYPES: &[&str] = &[
+    "task.started",
+    "task.succeeded",
+    "task.failed",
+    "task.canceled",
+    "task.timeout",
+    "quota.threshold_reached",
+];
+
+/// Prefix of secrets shown to clients
+pub const SECRET_PREFIX: &str = "whsec_";
+
+/// Default overlap window for secret rotation (hours)
+pub const DEFAULT_SECRET_OVERLAP_HOURS: i64 = 24;
+
 /// Webhook model representing an HTTP callback endpoint
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Webhook {
@@ -70,6 +90,13 @@ pub struct Webhook {
     #[serde(skip_serializing)] // Never expose secret in API responses
     pub secret: Vec<u8>,
 
+    /// Secret replaced by the last rotation (None if not rotated)
+    #[serde(skip_serializing)]
+    pub previous_secret: Option<Vec<u8>>,
+
+    /// When the previous secret stops being used for signing
+    pub previous_secret_expires_at: Option<DateTime<Utc>>,
+
     /// Whether webhook is active
     pub active: bool,
 
@@ -99,10 +126,33 @@ pub struct CreateWebhook {
     pub events: Vec<String>,
 }
 
-fn default_events() -> Vec<String> {
+/// Default event subscriptions for new webhooks
+pub fn default_events() -> Vec<String> {
     vec!["task.succeeded".to_string(), "task.failed".to_string()]
 }
 
+/// Validates a list of event types
+///
+/// # Errors
+///
+/// Returns a description of the problem if the list is empty or contains an
+/// event type not in `EVENT_TYPES`
+pub fn validate_events(events: &[String]) -> Result<(), String> {
+    if events.is_empty() {
+        return Err("At least one event type is required".to_string());
+    }
+
+    if let Some(unknown) = events.iter().find(|e| !EVENT_TYPES.contains(&e.as_str())) {
+        return Err(format!(
+            "Unknown event type '{}' (expected one of: {})",
+            unknown,
+            EVENT_TYPES.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
 /// Input for updating a webhook
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateWebhook {
@@ -151,6 +201,8 @@ impl Webhook {
     ///     tenant_id: Uuid::new_v4(),
     ///     url: "https://example.com/webhook".to_string(),
     ///     secret: vec![1, 2, 3, 4],
+    ///     previous_secret: None,
+    ///     previous_secret_expires_at: None,
     ///     active: true,
     ///     events: vec!["task.succeeded".to_string()],
     ///     created_at: Utc::now(),
@@ -169,6 +221,34 @@ impl Webhook {
         format!("{:x}", mac.finalize().into_bytes())
     }
 
+    /// Generates signatures with every secret currently valid for signing
+    ///
+    /// Returns the signature with the current secret first, followed by the
+    /// signature with the previous secret while its overlap window lasts.
+    pub fn signatures(&self, payload: &[u8]) -> Vec<String> {
+        let mut signatures = vec![self.generate_signature(payload)];
+
+        if let (Some(previous), Some(expires_at)) =
+            (&self.previous_secret, self.previous_secret_expires_at)
+        {
+            if expires_at > Utc::now() {
+                let mut mac = Hmac::<Sha256>::new_from_slice(previous)
+                    .expect("HMAC can take key of any size");
+                mac.update(payload);
+                signatures.push(format!("{:x}", mac.finalize().into_bytes()));
+            }
+        }
+
+        signatures
+    }
+
+    /// Formats the secret for display to the client
+    ///
+    /// The HMAC key is the hex-decoded part after `SECRET_PREFIX`.
+    pub fn secret_string(&self) -> String {
+        format!("{}{}", SECRET_PREFIX, hex::encode(&self.secret))
+    }
+
     /// Creates a new webhook
     ///
     /// Automatically generates a secure random secret.
@@ -210,7 +290,7 @@ impl Webhook {
             r#"
             INSERT INTO webhooks (tenant_id, url, secret, events)
             VALUES ($1, $2, $3, $4)
-            RETURNING id, tenant_id, url, secret, active, events, created_at, updated_at
+            RETURNING id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at
             "#,
         )
         .bind(data.tenant_id)
@@ -227,7 +307,7 @@ impl Webhook {
     pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         let webhook = sqlx::query_as::<_, Webhook>(
             r#"
-            SELECT id, tenant_id, url, secret, active, events, created_at, updated_at
+            SELECT id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at
             FROM webhooks
             WHERE id = $1
             "#,
@@ -247,7 +327,7 @@ impl Webhook {
     ) -> Result<Option<Self>, sqlx::Error> {
         let webhook = sqlx::query_as::<_, Webhook>(
             r#"
-            SELECT id, tenant_id, url, secret, active, events, created_at, updated_at
+            SELECT id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at
             FROM webhooks
             WHERE id = $1 AND tenant_id = $2
             "#,
@@ -286,7 +366,7 @@ impl Webhook {
             query.push_str(&format!(", active = ${}", bind_count));
         }
 
-        query.push_str(" WHERE id = $1 RETURNING id, tenant_id, url, secret, active, events, created_at, updated_at");
+        query.push_str(" WHERE id = $1 RETURNING id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at");
 
         let mut q = sqlx::query_as::<_, Webhook>(&query).bind(id);
 
@@ -308,6 +388,53 @@ impl Webhook {
         Ok(webhook)
     }
 
+    /// Rotates the webhook secret
+    ///
+    /// Generates a new secret and keeps the current one as the previous
+    /// secret until `overlap` has elapsed. Deliveries are signed with both
+    /// during the overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - Webhook ID
+    /// * `tenant_id` - Tenant ID (for isolation)
+    /// * `overlap` - How long the previous secret stays valid
+    ///
+    /// # Returns
+    ///
+    /// The updated webhook, or None if not found
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn rotate_secret(
+        pool: &PgPool,
+        id: Uuid,
+        tenant_id: Uuid,
+        overlap: Duration,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            UPDATE webhooks
+            SET previous_secret = secret,
+                previous_secret_expires_at = $3,
+                secret = $4,
+                updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $2
+            RETURNING id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(tenant_id)
+        .bind(Utc::now() + overlap)
+        .bind(Self::generate_secret())
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
     /// Toggles webhook active status
     pub async fn toggle_active(pool: &PgPool, id: Uuid, active: bool) -> Result<bool, sqlx::Error> {
         let result = sqlx::query(
@@ -331,11 +458,79 @@ impl Webhook {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Records a successful delivery
+    ///
+    /// Resets the consecutive failure count.
+    pub async fn record_delivery_success(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE webhooks SET consecutive_failures = 0 WHERE id = $1 AND consecutive_failures > 0"
+        )
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a dead-lettered delivery
+    ///
+    /// Increments the consecutive failure count and disables the webhook once
+    /// it reaches `disable_threshold`.
+    ///
+    /// # Returns
+    ///
+    /// True if the webhook was disabled by this call
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn record_delivery_failure(
+        pool: &PgPool,
+        id: Uuid,
+        disable_threshold: i32,
+    ) -> Result<bool, sqlx::Error> {
+        let disabled: Option<(bool,)> = sqlx::query_as(
+            r#"
+            WITH previous AS (
+                SELECT id, active FROM webhooks WHERE id = $1 FOR UPDATE
+            )
+            UPDATE webhooks w
+            SET consecutive_failures = w.consecutive_failures + 1,
+                active = w.active AND w.consecutive_failures + 1 < $2,
+                updated_at = NOW()
+            FROM previous
+            WHERE w.id = previous.id
+            RETURNING previous.active AND NOT w.active
+            "#,
+        )
+        .bind(id)
+        .bind(disable_threshold)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(disabled.map(|(d,)| d).unwrap_or(false))
+    }
+
+    /// Deletes a webhook with tenant isolation
+    pub async fn delete_with_tenant(
+        pool: &PgPool,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = $1 AND tenant_id = $2")
+            .bind(id)
+            .bind(tenant_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Lists all webhooks for a tenant
     pub async fn list_by_tenant(pool: &PgPool, tenant_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
         let webhooks = sqlx::query_as::<_, Webhook>(
             r#"
-            SELECT id, tenant_id, url, secret, active, events, created_at, updated_at
+            SELECT id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at
             FROM webhooks
             WHERE tenant_id = $1
             ORDER BY created_at DESC
@@ -352,7 +547,7 @@ impl Webhook {
     pub async fn list_active_by_tenant(pool: &PgPool, tenant_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
         let webhooks = sqlx::query_as::<_, Webhook>(
             r#"
-            SELECT id, tenant_id, url, secret, active, events, created_at, updated_at
+            SELECT id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at
             FROM webhooks
             WHERE tenant_id = $1 AND active = TRUE
             ORDER BY created_at DESC
@@ -373,7 +568,7 @@ impl Webhook {
     ) -> Result<Vec<Self>, sqlx::Error> {
         let webhooks = sqlx::query_as::<_, Webhook>(
             r#"
-            SELECT id, tenant_id, url, secret, active, events, created_at, updated_at
+            SELECT id, tenant_id, url, secret, previous_secret, previous_secret_expires_at, active, events, created_at, updated_at
             FROM webhooks
             WHERE tenant_id = $1
               AND active = TRUE
@@ -416,6 +611,21 @@ mod tests {
         assert_ne!(secret1, secret2); // Should be random
     }
 
+    fn webhook_with_secret(secret: Vec<u8>) -> Webhook {
+        Webhook {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            url: "https://example.com".to_string(),
+            secret,
+            previous_secret: None,
+            previous_secret_expires_at: None,
+            active: true,
+            events: vec!["task.succeeded".to_string()],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
     #[test]
     fn test_generate_signature() {
         let webhook = Webhook {
@@ -423,6 +633,8 @@ mod tests {
             tenant_id: Uuid::new_v4(),
             url: "https://example.com".to_string(),
             secret: vec![1, 2, 3, 4, 5],
+            previous_secret: None,
+            previous_secret_expires_at: None,
             active: true,
             events: vec!["task.succeeded".to_string()],
             created_at: Utc::now(),
@@ -440,6 +652,42 @@ mod tests {
         assert_ne!(sig1, sig3);
     }
 
+    #[test]
+    fn test_signatures_during_overlap() {
+        let mut webhook = webhook_with_secret(vec![1, 2, 3]);
+        assert_eq!(webhook.signatures(b"payload").len(), 1);
+
+        let old = webhook_with_secret(vec![9, 9, 9]);
+        webhook.previous_secret = Some(old.secret.clone());
+        webhook.previous_secret_expires_at = Some(Utc::now() + Duration::hours(1));
+
+        let signatures = webhook.signatures(b"payload");
+        assert_eq!(
+            signatures,
+            vec![
+                webhook.generate_signature(b"payload"),
+                old.generate_signature(b"payload")
+            ]
+        );
+
+        // Expired previous secret is no longer used
+        webhook.previous_secret_expires_at = Some(Utc::now() - Duration::seconds(1));
+        assert_eq!(webhook.signatures(b"payload").len(), 1);
+    }
+
+    #[test]
+    fn test_secret_string() {
+        let webhook = webhook_with_secret(vec![0xab, 0xcd]);
+        assert_eq!(webhook.secret_string(), "whsec_abcd");
+    }
+
+    #[test]
+    fn test_validate_events() {
+        assert!(validate_events(&default_events()).is_ok());
+        assert!(validate_events(&[]).is_err());
+        assert!(validate_events(&["task.exploded".to_string()]).is_err());
+    }
+
     #[test]
     fn test_default_events() {
         let events = default_events();
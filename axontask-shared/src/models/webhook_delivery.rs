@@ -0,0 +1,796 @@
+/// Webhook delivery model and database operations
+///
+/// This module provides the delivery queue for webhook notifications. Every
+/// task event sent to a webhook is one delivery row, which doubles as the
+/// delivery log.
+///
+/// # Lifecycle
+///
+/// 1. **Enqueue**: When a task starts or finishes (or a tenant reaches a soft
+///    quota threshold), a delivery is created for each active webhook
+///    subscribed to the event
+/// 2. **Claim**: The worker's dispatcher claims due deliveries, leasing them
+///    while the HTTP request is in flight
+/// 3. **Record**: A 2xx response marks the delivery `succeeded`; otherwise it
+///    is retried with exponential backoff
+/// 4. **Dead-letter**: A delivery that exhausts its attempts is marked `dead`
+///
+/// # Schema
+///
+/// ```sql
+/// CREATE TABLE webhook_deliveries (
+///     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+///     webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+///     tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+///     task_id UUID REFERENCES tasks(id) ON DELETE SET NULL,
+///     event VARCHAR(64) NOT NULL,
+///     payload JSONB NOT NULL,
+///     status VARCHAR(16) NOT NULL DEFAULT 'pending',
+///     attempts INTEGER NOT NULL DEFAULT 0,
+///     max_attempts INTEGER NOT NULL DEFAULT 6,
+///     next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     last_attempt_at TIMESTAMPTZ,
+///     response_status INTEGER,
+///     response_body TEXT,
+///     latency_ms INTEGER,
+///     error TEXT,
+///     created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+///     updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+/// );
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::task::Task;
+/// use axontask_shared::models::webhook_delivery::WebhookDelivery;
+/// use sqlx::PgPool;
+///
+/// # async fn example(pool: PgPool, task: Task) -> Result<(), sqlx::Error> {
+/// // Queue notifications for the task's current state
+/// let deliveries = WebhookDelivery::enqueue_for_task(&pool, &task).await?;
+/// println!("Queued {} deliveries", deliveries.len());
+/// # Ok(())
+/// # }
+/// ```
+
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::quota_alert::QuotaAlert;
+use super::task::{Task, TaskState};
+use super::webhook::Webhook;
+
+/// Default number of attempts per delivery (first attempt + 5 retries)
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 6;
+
+/// Delay before each retry (seconds), before jitter
+pub const RETRY_BACKOFF_SECS: [i64; 5] = [1, 2, 4, 8, 16];
+
+/// Maximum stored response body size (bytes)
+pub const MAX_RESPONSE_BODY_BYTES: usize = 4096;
+
+/// Event type of test deliveries
+pub const TEST_EVENT: &str = "webhook.test";
+
+/// Event type of soft quota threshold notifications
+pub const QUOTA_THRESHOLD_EVENT: &str = "quota.threshold_reached";
+
+/// Delivery status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    /// Queued or waiting for a retry
+    Pending,
+
+    /// Delivered (2xx response)
+    Succeeded,
+
+    /// All attempts failed (dead-letter queue)
+    Dead,
+}
+
+impl DeliveryStatus {
+    /// Converts status to string for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Succeeded => "succeeded",
+            DeliveryStatus::Dead => "dead",
+        }
+    }
+}
+
+impl std::str::FromStr for DeliveryStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "succeeded" => Ok(DeliveryStatus::Succeeded),
+            "dead" => Ok(DeliveryStatus::Dead),
+            _ => Err(format!("Invalid delivery status: {}", s)),
+        }
+    }
+}
+
+/// Webhook delivery model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    /// Unique delivery ID (sent in the X-AxonTask-Delivery header)
+    pub id: Uuid,
+
+    /// Target webhook
+    pub webhook_id: Uuid,
+
+    /// Tenant the webhook belongs to
+    pub tenant_id: Uuid,
+
+    /// Task that triggered the event (None for test deliveries)
+    pub task_id: Option<Uuid>,
+
+    /// Event type (e.g. "task.succeeded")
+    pub event: String,
+
+    /// Request body
+    pub payload: JsonValue,
+
+    /// Delivery status (pending, succeeded, dead)
+    pub status: String,
+
+    /// Attempts made so far
+    pub attempts: i32,
+
+    /// Maximum number of attempts
+    pub max_attempts: i32,
+
+    /// When the next attempt is due
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// When the last attempt was made
+    pub last_attempt_at: Option<DateTime<Utc>>,
+
+    /// HTTP status of the last attempt
+    pub response_status: Option<i32>,
+
+    /// Response body of the last attempt (truncated)
+    pub response_body: Option<String>,
+
+    /// Duration of the last attempt in milliseconds
+    pub latency_ms: Option<i32>,
+
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+
+    /// When the delivery was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the delivery was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Result of a single delivery attempt
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryAttempt {
+    /// HTTP status (None if no response was received)
+    pub response_status: Option<i32>,
+
+    /// Response body (truncated)
+    pub response_body: Option<String>,
+
+    /// Attempt duration in milliseconds
+    pub latency_ms: i32,
+
+    /// Error description (None on success)
+    pub error: Option<String>,
+}
+
+impl DeliveryAttempt {
+    /// Checks if the attempt succeeded (2xx response)
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && matches!(self.response_status, Some(200..=299))
+    }
+}
+
+/// Maps a task state to the webhook event it triggers
+///
+/// Pending and paused tasks trigger no event.
+pub fn task_event_type(state: TaskState) -> Option<&'static str> {
+    match state {
+        TaskState::Running => Some("task.started"),
+        TaskState::Succeeded => Some("task.succeeded"),
+        TaskState::Failed => Some("task.failed"),
+        TaskState::Canceled => Some("task.canceled"),
+        TaskState::Timeout => Some("task.timeout"),
+        TaskState::Pending | TaskState::Paused => None,
+    }
+}
+
+/// Builds the request body for a task event
+pub fn task_payload(event: &str, task: &Task) -> JsonValue {
+    json!({
+        "event": event,
+        "timestamp": Utc::now(),
+        "task": {
+            "id": task.id,
+            "tenant_id": task.tenant_id,
+            "name": task.name,
+            "adapter": task.adapter,
+            "state": task.state.as_str(),
+            "tags": task.tags,
+            "started_at": task.started_at,
+            "ended_at": task.ended_at,
+            "minutes_used": task.minutes_used,
+            "exit_code": task.exit_code,
+            "error_message": task.error_message,
+        }
+    })
+}
+
+/// Builds the request body for a soft quota threshold notification
+pub fn quota_threshold_payload(alert: &QuotaAlert) -> JsonValue {
+    json!({
+        "event": QUOTA_THRESHOLD_EVENT,
+        "timestamp": Utc::now(),
+        "quota": {
+            "tenant_id": alert.tenant_id,
+            "name": alert.quota,
+            "threshold": alert.threshold,
+            "current": alert.current_usage,
+            "limit": alert.quota_limit,
+            "period_start": alert.period_start,
+        }
+    })
+}
+
+/// Builds the request body for a test delivery
+pub fn test_payload(webhook: &Webhook) -> JsonValue {
+    json!({
+        "event": TEST_EVENT,
+        "timestamp": Utc::now(),
+        "webhook": {
+            "id": webhook.id,
+            "tenant_id": webhook.tenant_id,
+            "url": webhook.url,
+        }
+    })
+}
+
+/// Computes the delay before the next attempt
+///
+/// # Arguments
+///
+/// * `attempts` - Attempts made so far
+/// * `max_attempts` - Maximum number of attempts
+///
+/// # Returns
+///
+/// The backoff from `RETRY_BACKOFF_SECS` plus up to 25% jitter, or None if
+/// no attempts are left
+pub fn retry_delay(attempts: i32, max_attempts: i32) -> Option<Duration> {
+    if attempts >= max_attempts {
+        return None;
+    }
+
+    let index = (attempts.max(1) as usize - 1).min(RETRY_BACKOFF_SECS.len() - 1);
+    let base_ms = RETRY_BACKOFF_SECS[index] * 1000;
+    let jitter_ms = rand::thread_rng().gen_range(0..=base_ms / 4);
+
+    Some(Duration::milliseconds(base_ms + jitter_ms))
+}
+
+/// Truncates a response body for storage
+pub fn truncate_body(mut body: String) -> String {
+    if body.len() > MAX_RESPONSE_BODY_BYTES {
+        let mut end = MAX_RESPONSE_BODY_BYTES;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+    }
+    body
+}
+
+impl WebhookDelivery {
+    /// Queues a delivery
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `webhook` - Target webhook
+    /// * `task_id` - Task that triggered the event (if any)
+    /// * `event` - Event type
+    /// * `payload` - Request body
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn enqueue(
+        pool: &PgPool,
+        webhook: &Webhook,
+        task_id: Option<Uuid>,
+        event: &str,
+        payload: JsonValue,
+    ) -> Result<Self, sqlx::Error> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            INSERT INTO webhook_deliveries (webhook_id, tenant_id, task_id, event, payload, max_attempts)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, webhook_id, tenant_id, task_id, event, payload, status, attempts,
+                      max_attempts, next_attempt_at, last_attempt_at, response_status,
+                      response_body, latency_ms, error, created_at, updated_at
+            "#,
+        )
+        .bind(webhook.id)
+        .bind(webhook.tenant_id)
+        .bind(task_id)
+        .bind(event)
+        .bind(payload)
+        .bind(DEFAULT_MAX_ATTEMPTS)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// Queues deliveries for a task's current state
+    ///
+    /// Creates one delivery per active webhook subscribed to the event
+    /// matching the task state (see `task_event_type`).
+    ///
+    /// # Returns
+    ///
+    /// The queued deliveries (empty if the state triggers no event or no
+    /// webhook is subscribed)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn enqueue_for_task(pool: &PgPool, task: &Task) -> Result<Vec<Self>, sqlx::Error> {
+        let Some(event) = task_event_type(task.state) else {
+            return Ok(Vec::new());
+        };
+
+        Self::enqueue_for_event(pool, task.tenant_id, Some(task.id), event, task_payload(event, task))
+            .await
+    }
+
+    /// Queues deliveries for a soft quota threshold
+    ///
+    /// Creates one delivery per active webhook subscribed to
+    /// `quota.threshold_reached`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn enqueue_for_quota_alert(
+        pool: &PgPool,
+        alert: &QuotaAlert,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        Self::enqueue_for_event(
+            pool,
+            alert.tenant_id,
+            None,
+            QUOTA_THRESHOLD_EVENT,
+            quota_threshold_payload(alert),
+        )
+        .await
+    }
+
+    /// Queues one delivery per active webhook of a tenant subscribed to `event`
+    async fn enqueue_for_event(
+        pool: &PgPool,
+        tenant_id: Uuid,
+        task_id: Option<Uuid>,
+        event: &str,
+        payload: JsonValue,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let webhooks = Webhook::find_by_event_type(pool, tenant_id, event).await?;
+
+        let mut deliveries = Vec::with_capacity(webhooks.len());
+        for webhook in &webhooks {
+            deliveries.push(Self::enqueue(pool, webhook, task_id, event, payload.clone()).await?);
+        }
+
+        Ok(deliveries)
+    }
+
+    /// Claims deliveries that are due
+    ///
+    /// Increments the attempt count and pushes `next_attempt_at` forward by
+    /// `lease`, so a delivery whose dispatcher dies mid-attempt is retried
+    /// after the lease expires. Safe to call from several workers.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `limit` - Maximum number of deliveries to claim
+    /// * `lease` - How long the claim is held
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn claim_due(
+        pool: &PgPool,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            WITH due AS (
+                SELECT id
+                FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at ASC
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE webhook_deliveries d
+            SET attempts = d.attempts + 1,
+                last_attempt_at = NOW(),
+                next_attempt_at = NOW() + $2,
+                updated_at = NOW()
+            FROM due
+            WHERE d.id = due.id
+            RETURNING d.id, d.webhook_id, d.tenant_id, d.task_id, d.event, d.payload, d.status,
+                      d.attempts, d.max_attempts, d.next_attempt_at, d.last_attempt_at,
+                      d.response_status, d.response_body, d.latency_ms, d.error,
+                      d.created_at, d.updated_at
+            "#,
+        )
+        .bind(limit)
+        .bind(lease)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Records a successful attempt
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn record_success(
+        pool: &PgPool,
+        id: Uuid,
+        attempt: &DeliveryAttempt,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'succeeded',
+                response_status = $2,
+                response_body = $3,
+                latency_ms = $4,
+                error = NULL,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(attempt.response_status)
+        .bind(&attempt.response_body)
+        .bind(attempt.latency_ms)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a failed attempt
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - Delivery ID
+    /// * `attempt` - Attempt result
+    /// * `retry_at` - When to retry, or None to dead-letter the delivery
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn record_failure(
+        pool: &PgPool,
+        id: Uuid,
+        attempt: &DeliveryAttempt,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        let status = if retry_at.is_some() {
+            DeliveryStatus::Pending
+        } else {
+            DeliveryStatus::Dead
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2,
+                next_attempt_at = COALESCE($3, next_attempt_at),
+                response_status = $4,
+                response_body = $5,
+                latency_ms = $6,
+                error = $7,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(status.as_str())
+        .bind(retry_at)
+        .bind(attempt.response_status)
+        .bind(&attempt.response_body)
+        .bind(attempt.latency_ms)
+        .bind(&attempt.error)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Finds a delivery of a webhook
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn find_by_id_and_webhook(
+        pool: &PgPool,
+        id: Uuid,
+        webhook_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, webhook_id, tenant_id, task_id, event, payload, status, attempts,
+                   max_attempts, next_attempt_at, last_attempt_at, response_status,
+                   response_body, latency_ms, error, created_at, updated_at
+            FROM webhook_deliveries
+            WHERE id = $1 AND webhook_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(webhook_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// Lists deliveries of a webhook, newest first
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `webhook_id` - Webhook ID
+    /// * `status` - Only deliveries with this status
+    /// * `before` - Only deliveries older than this delivery (cursor)
+    /// * `limit` - Maximum number of deliveries
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub async fn list_by_webhook(
+        pool: &PgPool,
+        webhook_id: Uuid,
+        status: Option<DeliveryStatus>,
+        before: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, webhook_id, tenant_id, task_id, event, payload, status, attempts,
+                   max_attempts, next_attempt_at, last_attempt_at, response_status,
+                   response_body, latency_ms, error, created_at, updated_at
+            FROM webhook_deliveries
+            WHERE webhook_id = $1
+              AND ($2::varchar IS NULL OR status = $2)
+              AND ($3::uuid IS NULL OR (created_at, id) < (
+                  SELECT created_at, id FROM webhook_deliveries WHERE id = $3 AND webhook_id = $1
+              ))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(status.map(|s| s.as_str()))
+        .bind(before)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Queues a new delivery with the same event and payload
+    ///
+    /// The copy gets a new ID and a full set of attempts; the original
+    /// delivery is left unchanged in the log.
+    ///
+    /// # Returns
+    ///
+    /// The new delivery, or None if the original was not found
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn redeliver(
+        pool: &PgPool,
+        id: Uuid,
+        webhook_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            INSERT INTO webhook_deliveries (webhook_id, tenant_id, task_id, event, payload, max_attempts)
+            SELECT webhook_id, tenant_id, task_id, event, payload, $3
+            FROM webhook_deliveries
+            WHERE id = $1 AND webhook_id = $2
+            RETURNING id, webhook_id, tenant_id, task_id, event, payload, status, attempts,
+                      max_attempts, next_attempt_at, last_attempt_at, response_status,
+                      response_body, latency_ms, error, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(webhook_id)
+        .bind(DEFAULT_MAX_ATTEMPTS)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// Records a delivery that was sent immediately (e.g. a test delivery)
+    ///
+    /// The delivery is logged with a single attempt and is never retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `id` - Delivery ID that was sent in the request
+    /// * `webhook` - Target webhook
+    /// * `event` - Event type
+    /// * `payload` - Request body
+    /// * `attempt` - Attempt result
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails
+    pub async fn record_sent(
+        pool: &PgPool,
+        id: Uuid,
+        webhook: &Webhook,
+        event: &str,
+        payload: JsonValue,
+        attempt: &DeliveryAttempt,
+    ) -> Result<Self, sqlx::Error> {
+        let status = if attempt.is_success() {
+            DeliveryStatus::Succeeded
+        } else {
+            DeliveryStatus::Dead
+        };
+
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            INSERT INTO webhook_deliveries (
+                id, webhook_id, tenant_id, event, payload, status, attempts, max_attempts,
+                last_attempt_at, response_status, response_body, latency_ms, error
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, 1, 1, NOW(), $7, $8, $9, $10)
+            RETURNING id, webhook_id, tenant_id, task_id, event, payload, status, attempts,
+                      max_attempts, next_attempt_at, last_attempt_at, response_status,
+                      response_body, latency_ms, error, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(webhook.id)
+        .bind(webhook.tenant_id)
+        .bind(event)
+        .bind(payload)
+        .bind(status.as_str())
+        .bind(attempt.response_status)
+        .bind(&attempt.response_body)
+        .bind(attempt.latency_ms)
+        .bind(&attempt.error)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(delivery)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_event_type() {
+        assert_eq!(task_event_type(TaskState::Running), Some("task.started"));
+        assert_eq!(task_event_type(TaskState::Succeeded), Some("task.succeeded"));
+        assert_eq!(task_event_type(TaskState::Timeout), Some("task.timeout"));
+        assert_eq!(task_event_type(TaskState::Pending), None);
+        assert_eq!(task_event_type(TaskState::Paused), None);
+
+        // Every triggered event is one webhooks can subscribe to
+        for state in [
+            TaskState::Running,
+            TaskState::Succeeded,
+            TaskState::Failed,
+            TaskState::Canceled,
+            TaskState::Timeout,
+        ] {
+            let event = task_event_type(state).unwrap();
+            assert!(super::super::webhook::EVENT_TYPES.contains(&event));
+        }
+    }
+
+    #[test]
+    fn test_quota_threshold_payload() {
+        let alert = QuotaAlert {
+            tenant_id: Uuid::nil(),
+            quota: "daily_tasks".to_string(),
+            period_start: chrono::NaiveDate::from_ymd_opt(2025, 1, 18).unwrap(),
+            threshold: 80,
+            current_usage: 80,
+            quota_limit: 100,
+            created_at: Utc::now(),
+        };
+
+        let payload = quota_threshold_payload(&alert);
+        assert_eq!(payload["event"], QUOTA_THRESHOLD_EVENT);
+        assert_eq!(payload["quota"]["name"], "daily_tasks");
+        assert_eq!(payload["quota"]["threshold"], 80);
+        assert_eq!(payload["quota"]["period_start"], "2025-01-18");
+        assert!(super::super::webhook::EVENT_TYPES.contains(&QUOTA_THRESHOLD_EVENT));
+    }
+
+    #[test]
+    fn test_retry_delay_schedule() {
+        for (attempts, base) in [(1, 1000), (2, 2000), (3, 4000), (4, 8000), (5, 16000)] {
+            let delay = retry_delay(attempts, DEFAULT_MAX_ATTEMPTS).unwrap().num_milliseconds();
+            assert!(delay >= base && delay <= base + base / 4, "attempt {}: {}", attempts, delay);
+        }
+
+        assert!(retry_delay(DEFAULT_MAX_ATTEMPTS, DEFAULT_MAX_ATTEMPTS).is_none());
+    }
+
+    #[test]
+    fn test_delivery_attempt_success() {
+        let ok = DeliveryAttempt {
+            response_status: Some(204),
+            ..Default::default()
+        };
+        assert!(ok.is_success());
+
+        let server_error = DeliveryAttempt {
+            response_status: Some(500),
+            ..Default::default()
+        };
+        assert!(!server_error.is_success());
+
+        let connection_error = DeliveryAttempt {
+            error: Some("connection refused".to_string()),
+            ..Default::default()
+        };
+        assert!(!connection_error.is_success());
+    }
+
+    #[test]
+    fn test_delivery_status_from_str() {
+        for status in [DeliveryStatus::Pending, DeliveryStatus::Succeeded, DeliveryStatus::Dead] {
+            assert_eq!(status.as_str().parse::<DeliveryStatus>(), Ok(status));
+        }
+        assert!("failed".parse::<DeliveryStatus>().is_err());
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body("ok".to_string()), "ok");
+
+        let long = "é".repeat(MAX_RESPONSE_BODY_BYTES);
+        let truncated = truncate_body(long);
+        assert!(truncated.len() <= MAX_RESPONSE_BODY_BYTES);
+        assert!(truncated.chars().all(|c| c == 'é'));
+    }
+}
@@ -4,7 +4,9 @@
 /// Quotas are enforced on:
 /// - Concurrent running tasks
 /// - Daily task creation
-/// - Active stream connections
+/// - Active stream connections (tracked in Redis, see
+///   `redis::stream_connections`)
+/// - Task minutes per calendar month (UTC)
 ///
 /// # Quota Limits by Plan
 ///
@@ -12,21 +14,39 @@
 /// - Concurrent tasks: 5
 /// - Daily tasks: 100
 /// - Stream connections: 2
+/// - Task minutes: 1,000 per month
 ///
 /// **Entry Plan:**
 /// - Concurrent tasks: 25
 /// - Daily tasks: 1,000
 /// - Stream connections: 5
+/// - Task minutes: 10,000 per month
 ///
 /// **Pro Plan:**
 /// - Concurrent tasks: 100
 /// - Daily tasks: 10,000
 /// - Stream connections: 20
+/// - Task minutes: 100,000 per month
 ///
 /// **Enterprise Plan:**
 /// - Concurrent tasks: 500
 /// - Daily tasks: 100,000
 /// - Stream connections: 100
+/// - Task minutes: 1,000,000 per month
+///
+/// # Per-Tenant Overrides
+///
+/// Any limit can be overridden per tenant in `tenants.settings.quotas`
+/// (see `QuotaOverrides`). Overrides replace the plan default; limits that
+/// are not overridden keep it.
+///
+/// # Soft Thresholds
+///
+/// Before a periodic quota (`DailyTasks`, `TaskMinutes`) is enforced, tenants
+/// are warned when usage reaches a soft threshold (by default 80% and 100%
+/// of the limit, see `QuotaEnforcer::check_thresholds`). Each threshold
+/// queues a `quota.threshold_reached` webhook once per quota period (UTC day
+/// for daily tasks, calendar month for task minutes).
 ///
 /// # Example
 ///
@@ -50,9 +70,13 @@
 /// # }
 /// ```
 
-use crate::models::tenant::{Tenant, TenantPlan};
+use crate::models::quota_alert::QuotaAlert;
+use crate::models::tenant::{QuotaOverrides, Tenant, TenantPlan};
 use crate::models::task::{Task, TaskState};
-use chrono::{DateTime, Duration, Utc};
+use crate::models::webhook_delivery::WebhookDelivery;
+use crate::redis::client::RedisClient;
+use crate::redis::stream_connections::{StreamConnectionError, StreamConnectionTracker};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use sqlx::PgPool;
 use std::fmt;
 use uuid::Uuid;
@@ -72,6 +96,9 @@ pub enum QuotaError {
 
     /// Tenant not found
     TenantNotFound(Uuid),
+
+    /// Redis error (stream connection tracking)
+    RedisError(StreamConnectionError),
 }
 
 impl fmt::Display for QuotaError {
@@ -90,6 +117,7 @@ impl fmt::Display for QuotaError {
             ),
             QuotaError::DatabaseError(err) => write!(f, "Database error: {}", err),
             QuotaError::TenantNotFound(id) => write!(f, "Tenant not found: {}", id),
+            QuotaError::RedisError(err) => write!(f, "Redis error: {}", err),
         }
     }
 }
@@ -102,6 +130,12 @@ impl From<sqlx::Error> for QuotaError {
     }
 }
 
+impl From<StreamConnectionError> for QuotaError {
+    fn from(err: StreamConnectionError) -> Self {
+        QuotaError::RedisError(err)
+    }
+}
+
 /// Type of quota to check
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum QuotaType {
@@ -113,6 +147,9 @@ pub enum QuotaType {
 
     /// Maximum active stream connections
     StreamConnections,
+
+    /// Maximum task minutes per calendar month
+    TaskMinutes,
 }
 
 impl QuotaType {
@@ -122,10 +159,104 @@ impl QuotaType {
             QuotaType::ConcurrentTasks => "Concurrent tasks",
             QuotaType::DailyTasks => "Daily tasks",
             QuotaType::StreamConnections => "Stream connections",
+            QuotaType::TaskMinutes => "Task minutes",
+        }
+    }
+
+    /// Machine-readable name (as in `QuotaOverrides`)
+    pub fn key(&self) -> &'static str {
+        match self {
+            QuotaType::ConcurrentTasks => "concurrent_tasks",
+            QuotaType::DailyTasks => "daily_tasks",
+            QuotaType::StreamConnections => "stream_connections",
+            QuotaType::TaskMinutes => "task_minutes",
+        }
+    }
+
+    /// First day of the quota period containing `today`
+    ///
+    /// Returns None for quotas on current usage (concurrent tasks, stream
+    /// connections), which have no period and no soft thresholds.
+    pub fn period_start(&self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            QuotaType::DailyTasks => Some(today),
+            QuotaType::TaskMinutes => today.with_day(1),
+            QuotaType::ConcurrentTasks | QuotaType::StreamConnections => None,
         }
     }
 }
 
+/// Default soft thresholds (percent of the limit)
+pub const DEFAULT_SOFT_THRESHOLDS: &[u8] = &[80, 100];
+
+/// Parses a comma-separated list of soft thresholds (e.g. `"50,80,100"`)
+///
+/// An empty string disables soft thresholds.
+///
+/// # Errors
+///
+/// Returns a description of the problem if a value is not a percentage
+/// between 1 and 100
+pub fn parse_thresholds(value: &str) -> Result<Vec<u8>, String> {
+    let mut thresholds = value
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.parse::<u8>() {
+            Ok(threshold) if (1..=100).contains(&threshold) => Ok(threshold),
+            _ => Err(format!("Invalid threshold '{}' (expected 1-100)", part)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    Ok(thresholds)
+}
+
+/// Returns the thresholds reached by `current` out of `limit`, ascending
+pub fn reached_thresholds(thresholds: &[u8], current: u32, limit: u32) -> Vec<u8> {
+    if limit == 0 {
+        return thresholds.to_vec();
+    }
+
+    thresholds
+        .iter()
+        .copied()
+        .filter(|threshold| current as u64 * 100 >= *threshold as u64 * limit as u64)
+        .collect()
+}
+
+/// Soft threshold reached by a tenant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaWarning {
+    /// Quota the threshold applies to
+    pub quota_type: QuotaType,
+
+    /// Highest threshold reached (percent)
+    pub threshold: u8,
+
+    /// Current usage
+    pub current: u32,
+
+    /// Limit
+    pub limit: u32,
+}
+
+impl QuotaWarning {
+    /// Formats the warning for the `X-AxonTask-Quota-Warning` header
+    ///
+    /// e.g. `daily_tasks; threshold=80; current=85; limit=100`
+    pub fn header_value(&self) -> String {
+        format!(
+            "{}; threshold={}; current={}; limit={}",
+            self.quota_type.key(),
+            self.threshold,
+            self.current,
+            self.limit
+        )
+    }
+}
+
 /// Quota limits configuration
 #[derive(Debug, Clone, Copy)]
 pub struct QuotaLimits {
@@ -137,6 +268,9 @@ pub struct QuotaLimits {
 
     /// Maximum active stream connections
     pub stream_connections: u32,
+
+    /// Maximum task minutes per calendar month
+    pub task_minutes: u32,
 }
 
 impl QuotaLimits {
@@ -147,31 +281,54 @@ impl QuotaLimits {
                 concurrent_tasks: 5,
                 daily_tasks: 100,
                 stream_connections: 2,
+                task_minutes: 1_000,
             },
             TenantPlan::Entry => QuotaLimits {
                 concurrent_tasks: 25,
                 daily_tasks: 1_000,
                 stream_connections: 5,
+                task_minutes: 10_000,
             },
             TenantPlan::Pro => QuotaLimits {
                 concurrent_tasks: 100,
                 daily_tasks: 10_000,
                 stream_connections: 20,
+                task_minutes: 100_000,
             },
             TenantPlan::Enterprise => QuotaLimits {
                 concurrent_tasks: 500,
                 daily_tasks: 100_000,
                 stream_connections: 100,
+                task_minutes: 1_000_000,
             },
         }
     }
 
+    /// Gets quota limits for a tenant
+    ///
+    /// Plan defaults with the tenant's overrides applied.
+    pub fn for_tenant(tenant: &Tenant) -> Self {
+        let plan = tenant.get_plan().unwrap_or(TenantPlan::Trial);
+        QuotaLimits::for_plan(plan).with_overrides(&tenant.settings().quotas)
+    }
+
+    /// Applies per-tenant overrides
+    pub fn with_overrides(self, overrides: &QuotaOverrides) -> Self {
+        QuotaLimits {
+            concurrent_tasks: overrides.concurrent_tasks.unwrap_or(self.concurrent_tasks),
+            daily_tasks: overrides.daily_tasks.unwrap_or(self.daily_tasks),
+            stream_connections: overrides.stream_connections.unwrap_or(self.stream_connections),
+            task_minutes: overrides.task_minutes.unwrap_or(self.task_minutes),
+        }
+    }
+
     /// Gets limit for a specific quota type
     pub fn get(&self, quota_type: QuotaType) -> u32 {
         match quota_type {
             QuotaType::ConcurrentTasks => self.concurrent_tasks,
             QuotaType::DailyTasks => self.daily_tasks,
             QuotaType::StreamConnections => self.stream_connections,
+            QuotaType::TaskMinutes => self.task_minutes,
         }
     }
 }
@@ -219,12 +376,37 @@ impl QuotaCheckResult {
 /// Checks resource usage against plan-based limits.
 pub struct QuotaEnforcer {
     db: PgPool,
+
+    /// Redis client for stream connection counts (None: counted as 0)
+    redis: Option<RedisClient>,
+
+    /// Soft thresholds (percent of the limit, ascending)
+    thresholds: Vec<u8>,
 }
 
 impl QuotaEnforcer {
     /// Creates a new quota enforcer
     pub fn new(db: PgPool) -> Self {
-        QuotaEnforcer { db }
+        QuotaEnforcer {
+            db,
+            redis: None,
+            thresholds: DEFAULT_SOFT_THRESHOLDS.to_vec(),
+        }
+    }
+
+    /// Attaches a Redis client, enabling stream connection counts
+    pub fn with_redis(mut self, redis: RedisClient) -> Self {
+        self.redis = Some(redis);
+        self
+    }
+
+    /// Sets the soft thresholds (percent of the limit; empty disables them)
+    pub fn with_thresholds(mut self, thresholds: &[u8]) -> Self {
+        let mut thresholds = thresholds.to_vec();
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        self.thresholds = thresholds;
+        self
     }
 
     /// Checks if tenant is within quota for a specific resource
@@ -270,19 +452,21 @@ impl QuotaEnforcer {
             .await?
             .ok_or(QuotaError::TenantNotFound(tenant_id))?;
 
-        let plan = tenant.get_plan().unwrap_or(TenantPlan::Trial);
-        let limits = QuotaLimits::for_plan(plan);
-        let limit = limits.get(quota_type);
+        let limit = QuotaLimits::for_tenant(&tenant).get(quota_type);
 
         // Get current usage
         let current = match quota_type {
             QuotaType::ConcurrentTasks => self.count_concurrent_tasks(tenant_id).await?,
             QuotaType::DailyTasks => self.count_daily_tasks(tenant_id).await?,
-            QuotaType::StreamConnections => {
-                // Stream connections tracking requires Redis/session management
-                // For now, we'll return 0 (not enforced yet)
-                0
-            }
+            QuotaType::TaskMinutes => self.count_task_minutes(tenant_id).await?,
+            QuotaType::StreamConnections => match &self.redis {
+                Some(redis) => {
+                    StreamConnectionTracker::new(redis.clone())
+                        .count(tenant_id)
+                        .await?
+                }
+                None => 0,
+            },
         };
 
         if current >= limit {
@@ -343,6 +527,154 @@ impl QuotaEnforcer {
         Ok(())
     }
 
+    /// Checks soft thresholds for a periodic quota
+    ///
+    /// Counts the current usage, then records thresholds like
+    /// `record_thresholds`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant_id` - Tenant to check
+    /// * `quota_type` - Quota to check (`DailyTasks` or `TaskMinutes`)
+    ///
+    /// # Returns
+    ///
+    /// The highest threshold reached, or None if usage is below every
+    /// threshold or the quota has no period
+    ///
+    /// # Errors
+    ///
+    /// Returns error if database query fails or tenant not found
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use axontask_shared::quota::{QuotaEnforcer, QuotaType};
+    /// # use sqlx::PgPool;
+    /// # use uuid::Uuid;
+    /// # async fn example(pool: PgPool, tenant_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
+    /// let enforcer = QuotaEnforcer::new(pool);
+    ///
+    /// if let Some(warning) = enforcer.check_thresholds(tenant_id, QuotaType::DailyTasks).await? {
+    ///     println!("{}% of daily tasks used", warning.threshold);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn check_thresholds(
+        &self,
+        tenant_id: Uuid,
+        quota_type: QuotaType,
+    ) -> Result<Option<QuotaWarning>, QuotaError> {
+        if quota_type.period_start(Utc::now().date_naive()).is_none() || self.thresholds.is_empty() {
+            return Ok(None);
+        }
+
+        let result = self.check(tenant_id, quota_type).await?;
+        self.record_thresholds(tenant_id, quota_type, result.current, result.limit)
+            .await
+    }
+
+    /// Records the soft thresholds reached by a known usage
+    ///
+    /// For callers that already counted the usage (e.g. while enforcing the
+    /// quota). Each threshold newly reached in the current period is
+    /// recorded and queues a `quota.threshold_reached` webhook; thresholds
+    /// already recorded for the period are not notified again.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant_id` - Tenant to check
+    /// * `quota_type` - Quota (`DailyTasks` or `TaskMinutes`)
+    /// * `current` - Current usage
+    /// * `limit` - Current limit
+    ///
+    /// # Returns
+    ///
+    /// The highest threshold reached, or None if usage is below every
+    /// threshold or the quota has no period
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database operation fails
+    pub async fn record_thresholds(
+        &self,
+        tenant_id: Uuid,
+        quota_type: QuotaType,
+        current: u32,
+        limit: u32,
+    ) -> Result<Option<QuotaWarning>, QuotaError> {
+        let Some(period_start) = quota_type.period_start(Utc::now().date_naive()) else {
+            return Ok(None);
+        };
+
+        let reached = reached_thresholds(&self.thresholds, current, limit);
+        let Some(&highest) = reached.last() else {
+            return Ok(None);
+        };
+
+        let alerts = QuotaAlert::record_all(
+            &self.db,
+            tenant_id,
+            quota_type.key(),
+            period_start,
+            &reached,
+            current,
+            limit,
+        )
+        .await?;
+
+        for alert in alerts {
+            tracing::info!(
+                tenant_id = %tenant_id,
+                quota = quota_type.key(),
+                threshold = alert.threshold,
+                current,
+                limit,
+                "Quota soft threshold reached"
+            );
+
+            // Notification is best effort; the alert is recorded either way
+            if let Err(e) = WebhookDelivery::enqueue_for_quota_alert(&self.db, &alert).await {
+                tracing::error!(error = %e, tenant_id = %tenant_id, "Failed to queue quota threshold webhooks");
+            }
+        }
+
+        Ok(Some(QuotaWarning {
+            quota_type,
+            threshold: highest,
+            current,
+            limit,
+        }))
+    }
+
+    /// Gets the highest threshold already recorded in the current period
+    ///
+    /// Does not count usage, so it is cheap enough for hot paths. The usage
+    /// and limit are those recorded when the threshold was reached.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database query fails
+    pub async fn recorded_threshold(
+        &self,
+        tenant_id: Uuid,
+        quota_type: QuotaType,
+    ) -> Result<Option<QuotaWarning>, QuotaError> {
+        let Some(period_start) = quota_type.period_start(Utc::now().date_naive()) else {
+            return Ok(None);
+        };
+
+        let alert = QuotaAlert::find_highest(&self.db, tenant_id, quota_type.key(), period_start).await?;
+
+        Ok(alert.map(|alert| QuotaWarning {
+            quota_type,
+            threshold: alert.threshold.clamp(0, u8::MAX as i16) as u8,
+            current: alert.current_usage.clamp(0, u32::MAX as i64) as u32,
+            limit: alert.quota_limit.clamp(0, u32::MAX as i64) as u32,
+        }))
+    }
+
     /// Gets quota limits for a tenant
     ///
     /// # Arguments
@@ -351,7 +683,7 @@ impl QuotaEnforcer {
     ///
     /// # Returns
     ///
-    /// Quota limits based on tenant plan
+    /// Quota limits based on tenant plan and overrides
     ///
     /// # Errors
     ///
@@ -361,23 +693,23 @@ impl QuotaEnforcer {
             .await?
             .ok_or(QuotaError::TenantNotFound(tenant_id))?;
 
-        let plan = tenant.get_plan().unwrap_or(TenantPlan::Trial);
-        Ok(QuotaLimits::for_plan(plan))
+        Ok(QuotaLimits::for_tenant(&tenant))
     }
 
     /// Counts concurrent running tasks for a tenant
     ///
-    /// Counts tasks in 'running' state.
+    /// Counts tasks in 'running' or 'paused' state (paused tasks keep their slot).
     async fn count_concurrent_tasks(&self, tenant_id: Uuid) -> Result<u32, sqlx::Error> {
         let count: i64 = sqlx::query_scalar(
             r#"
             SELECT COUNT(*)
             FROM tasks
-            WHERE tenant_id = $1 AND state = $2::task_state
+            WHERE tenant_id = $1 AND state IN ($2::task_state, $3::task_state)
             "#,
         )
         .bind(tenant_id)
         .bind(TaskState::Running.as_str())
+        .bind(TaskState::Paused.as_str())
         .fetch_one(&self.db)
         .await?;
 
@@ -404,6 +736,43 @@ impl QuotaEnforcer {
 
         Ok(count as u32)
     }
+
+    /// Counts task minutes used this calendar month (UTC)
+    ///
+    /// Includes minutes already recorded in `usage_counters` plus the elapsed
+    /// (unpaused) minutes of the tenant's running and paused tasks, so a long
+    /// task is counted while it runs rather than only when it finishes.
+    async fn count_task_minutes(&self, tenant_id: Uuid) -> Result<u32, sqlx::Error> {
+        let today = Utc::now().date_naive();
+        let month_start = QuotaType::TaskMinutes.period_start(today).unwrap_or(today);
+
+        let minutes: i64 = sqlx::query_scalar(
+            r#"
+            SELECT (
+                SELECT COALESCE(SUM(task_minutes), 0)::BIGINT
+                FROM usage_counters
+                WHERE tenant_id = $1 AND period >= $2
+            ) + (
+                SELECT COALESCE(SUM(CEIL(GREATEST(
+                    EXTRACT(EPOCH FROM (NOW() - started_at))
+                        - paused_seconds
+                        - COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0),
+                    0
+                ) / 60.0)), 0)::BIGINT
+                FROM tasks
+                WHERE tenant_id = $1
+                  AND state IN ('running', 'paused')
+                  AND started_at IS NOT NULL
+            )
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(month_start)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(minutes.clamp(0, u32::MAX as i64) as u32)
+    }
 }
 
 #[cfg(test)]
@@ -416,6 +785,7 @@ mod tests {
         assert_eq!(limits.concurrent_tasks, 5);
         assert_eq!(limits.daily_tasks, 100);
         assert_eq!(limits.stream_connections, 2);
+        assert_eq!(limits.task_minutes, 1_000);
     }
 
     #[test]
@@ -424,6 +794,7 @@ mod tests {
         assert_eq!(limits.concurrent_tasks, 25);
         assert_eq!(limits.daily_tasks, 1_000);
         assert_eq!(limits.stream_connections, 5);
+        assert_eq!(limits.task_minutes, 10_000);
     }
 
     #[test]
@@ -432,6 +803,7 @@ mod tests {
         assert_eq!(limits.concurrent_tasks, 100);
         assert_eq!(limits.daily_tasks, 10_000);
         assert_eq!(limits.stream_connections, 20);
+        assert_eq!(limits.task_minutes, 100_000);
     }
 
     #[test]
@@ -440,6 +812,7 @@ mod tests {
         assert_eq!(limits.concurrent_tasks, 500);
         assert_eq!(limits.daily_tasks, 100_000);
         assert_eq!(limits.stream_connections, 100);
+        assert_eq!(limits.task_minutes, 1_000_000);
     }
 
     #[test]
@@ -448,6 +821,41 @@ mod tests {
         assert_eq!(limits.get(QuotaType::ConcurrentTasks), 100);
         assert_eq!(limits.get(QuotaType::DailyTasks), 10_000);
         assert_eq!(limits.get(QuotaType::StreamConnections), 20);
+        assert_eq!(limits.get(QuotaType::TaskMinutes), 100_000);
+    }
+
+    #[test]
+    fn test_quota_limits_with_overrides() {
+        let overrides = QuotaOverrides {
+            concurrent_tasks: Some(1_000),
+            stream_connections: Some(250),
+            task_minutes: Some(5_000_000),
+            ..Default::default()
+        };
+
+        let limits = QuotaLimits::for_plan(TenantPlan::Enterprise).with_overrides(&overrides);
+        assert_eq!(limits.concurrent_tasks, 1_000);
+        assert_eq!(limits.daily_tasks, 100_000); // Plan default kept
+        assert_eq!(limits.stream_connections, 250);
+        assert_eq!(limits.task_minutes, 5_000_000);
+    }
+
+    #[test]
+    fn test_quota_limits_for_tenant() {
+        let tenant = Tenant {
+            id: Uuid::new_v4(),
+            name: "Acme Corp".to_string(),
+            plan: "entry".to_string(),
+            stripe_customer_id: None,
+            stripe_subscription_id: None,
+            settings: serde_json::json!({"quotas": {"daily_tasks": 5000}}),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let limits = QuotaLimits::for_tenant(&tenant);
+        assert_eq!(limits.concurrent_tasks, 25);
+        assert_eq!(limits.daily_tasks, 5_000);
     }
 
     #[test]
@@ -473,6 +881,57 @@ mod tests {
         assert_eq!(QuotaType::ConcurrentTasks.as_str(), "Concurrent tasks");
         assert_eq!(QuotaType::DailyTasks.as_str(), "Daily tasks");
         assert_eq!(QuotaType::StreamConnections.as_str(), "Stream connections");
+        assert_eq!(QuotaType::TaskMinutes.as_str(), "Task minutes");
+        assert_eq!(QuotaType::TaskMinutes.key(), "task_minutes");
+    }
+
+    #[test]
+    fn test_quota_type_period_start() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 18).unwrap();
+
+        assert_eq!(QuotaType::DailyTasks.period_start(today), Some(today));
+        assert_eq!(
+            QuotaType::TaskMinutes.period_start(today),
+            NaiveDate::from_ymd_opt(2025, 1, 1)
+        );
+        assert_eq!(QuotaType::ConcurrentTasks.period_start(today), None);
+        assert_eq!(QuotaType::StreamConnections.period_start(today), None);
+    }
+
+    #[test]
+    fn test_parse_thresholds() {
+        assert_eq!(parse_thresholds("80,100").unwrap(), vec![80, 100]);
+        assert_eq!(parse_thresholds(" 100, 50 ,80,80").unwrap(), vec![50, 80, 100]);
+        assert_eq!(parse_thresholds("").unwrap(), Vec::<u8>::new());
+        assert!(parse_thresholds("0").is_err());
+        assert!(parse_thresholds("120").is_err());
+        assert!(parse_thresholds("eighty").is_err());
+    }
+
+    #[test]
+    fn test_reached_thresholds() {
+        let thresholds = [50, 80, 100];
+
+        assert_eq!(reached_thresholds(&thresholds, 49, 100), Vec::<u8>::new());
+        assert_eq!(reached_thresholds(&thresholds, 80, 100), vec![50, 80]);
+        assert_eq!(reached_thresholds(&thresholds, 150, 100), vec![50, 80, 100]);
+
+        // 79.9% is not 80%
+        assert_eq!(reached_thresholds(&thresholds, 799, 1_000), vec![50]);
+
+        // No overflow on large limits
+        assert_eq!(reached_thresholds(&thresholds, u32::MAX, u32::MAX), vec![50, 80, 100]);
+    }
+
+    #[test]
+    fn test_quota_warning_header_value() {
+        let warning = QuotaWarning {
+            quota_type: QuotaType::DailyTasks,
+            threshold: 80,
+            current: 85,
+            limit: 100,
+        };
+        assert_eq!(warning.header_value(), "daily_tasks; threshold=80; current=85; limit=100");
     }
 
     #[test]
@@ -0,0 +1,246 @@
+/// Task control channel (Redis Pub/Sub)
+///
+/// This module defines the wire format of control messages sent from the API
+/// to the worker executing a task, and a publisher for sending them.
+///
+/// # Channel
+///
+/// Control messages are published on channel: `ctrl:{task_id}`
+///
+/// Message format (JSON):
+/// ```json
+/// {
+///   "command": "pause",
+///   "reason": "Waiting for approval"
+/// }
+/// ```
+///
+/// # Commands
+///
+/// - **cancel**: Cancel the running task
+/// - **pause**: Suspend execution at the next safe point
+/// - **resume**: Continue a paused task
+/// - **input**: Deliver input (e.g. stdin data) to the task; carries `data`
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::redis::client::{RedisClient, RedisConfig};
+/// use axontask_shared::redis::control::{ControlMessage, ControlPublisher};
+/// use uuid::Uuid;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let config = RedisConfig::from_env()?;
+/// let client = RedisClient::new(config).await?;
+/// let publisher = ControlPublisher::new(client);
+///
+/// let receivers = publisher
+///     .publish(Uuid::new_v4(), &ControlMessage::pause(None))
+///     .await?;
+/// println!("Delivered to {} worker(s)", receivers);
+/// # Ok(())
+/// # }
+/// ```
+
+use crate::redis::client::RedisClient;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Control publisher errors
+#[derive(Error, Debug)]
+pub enum ControlPublishError {
+    /// Redis command error
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    /// Message serialization error
+    #[error("Failed to serialize control message: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Control command types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ControlCommand {
+    /// Cancel task execution
+    Cancel,
+
+    /// Pause task execution
+    Pause,
+
+    /// Resume a paused task
+    Resume,
+
+    /// Deliver input to the task
+    Input,
+}
+
+impl ControlCommand {
+    /// Converts command to its wire representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ControlCommand::Cancel => "cancel",
+            ControlCommand::Pause => "pause",
+            ControlCommand::Resume => "resume",
+            ControlCommand::Input => "input",
+        }
+    }
+}
+
+/// Control message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlMessage {
+    /// Command to execute
+    pub command: ControlCommand,
+
+    /// Optional reason/metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Input data (input command only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl ControlMessage {
+    /// Creates a cancel message
+    pub fn cancel(reason: Option<String>) -> Self {
+        ControlMessage {
+            command: ControlCommand::Cancel,
+            reason,
+            data: None,
+        }
+    }
+
+    /// Creates a pause message
+    pub fn pause(reason: Option<String>) -> Self {
+        ControlMessage {
+            command: ControlCommand::Pause,
+            reason,
+            data: None,
+        }
+    }
+
+    /// Creates a resume message
+    pub fn resume(reason: Option<String>) -> Self {
+        ControlMessage {
+            command: ControlCommand::Resume,
+            reason,
+            data: None,
+        }
+    }
+
+    /// Creates an input message
+    pub fn input(data: String) -> Self {
+        ControlMessage {
+            command: ControlCommand::Input,
+            reason: None,
+            data: Some(data),
+        }
+    }
+}
+
+/// Control channel name for a task
+pub fn control_channel(task_id: Uuid) -> String {
+    format!("ctrl:{}", task_id)
+}
+
+/// Publishes control messages to workers
+#[derive(Clone)]
+pub struct ControlPublisher {
+    /// Redis client
+    client: RedisClient,
+}
+
+impl ControlPublisher {
+    /// Creates a new control publisher
+    pub fn new(client: RedisClient) -> Self {
+        ControlPublisher { client }
+    }
+
+    /// Publishes a control message for a task
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - Target task ID
+    /// * `message` - Control message to send
+    ///
+    /// # Returns
+    ///
+    /// Number of subscribers that received the message. Zero means no worker
+    /// is currently listening for this task.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if serialization or the Redis PUBLISH fails
+    pub async fn publish(
+        &self,
+        task_id: Uuid,
+        message: &ControlMessage,
+    ) -> Result<u32, ControlPublishError> {
+        let channel = control_channel(task_id);
+        let payload = serde_json::to_string(message)?;
+
+        let mut conn = self.client.get_connection();
+        let receivers: u32 = conn.publish(&channel, payload).await?;
+
+        tracing::debug!(
+            task_id = %task_id,
+            command = message.command.as_str(),
+            receivers = receivers,
+            "Published control message"
+        );
+
+        Ok(receivers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_message_constructors() {
+        assert_eq!(ControlMessage::cancel(None).command, ControlCommand::Cancel);
+        assert_eq!(ControlMessage::pause(None).command, ControlCommand::Pause);
+        assert_eq!(ControlMessage::resume(None).command, ControlCommand::Resume);
+
+        let input = ControlMessage::input("yes\n".to_string());
+        assert_eq!(input.command, ControlCommand::Input);
+        assert_eq!(input.data, Some("yes\n".to_string()));
+    }
+
+    #[test]
+    fn test_control_message_serialization() {
+        let msg = ControlMessage::pause(Some("Waiting for approval".to_string()));
+        let json = serde_json::to_string(&msg).unwrap();
+
+        assert!(json.contains("\"command\":\"pause\""));
+        assert!(json.contains("\"reason\":\"Waiting for approval\""));
+
+        let json = serde_json::to_string(&ControlMessage::resume(None)).unwrap();
+        assert_eq!(json, "{\"command\":\"resume\"}");
+
+        let json = serde_json::to_string(&ControlMessage::input("y".to_string())).unwrap();
+        assert_eq!(json, "{\"command\":\"input\",\"data\":\"y\"}");
+    }
+
+    #[test]
+    fn test_control_command_as_str() {
+        assert_eq!(ControlCommand::Cancel.as_str(), "cancel");
+        assert_eq!(ControlCommand::Pause.as_str(), "pause");
+        assert_eq!(ControlCommand::Resume.as_str(), "resume");
+        assert_eq!(ControlCommand::Input.as_str(), "input");
+    }
+
+    #[test]
+    fn test_control_channel() {
+        let task_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        assert_eq!(
+            control_channel(task_id),
+            "ctrl:550e8400-e29b-41d4-a716-446655440000"
+        );
+    }
+}
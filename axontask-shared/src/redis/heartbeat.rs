@@ -229,7 +229,7 @@ impl HeartbeatManager {
         let mut conn = self.client.get_connection();
 
         // SETEX key ttl value
-        conn.set_ex(&key, value, self.config.ttl_seconds)
+        conn.set_ex::<_, _, ()>(&key, value, self.config.ttl_seconds)
             .await?;
 
         tracing::trace!(
@@ -274,7 +274,7 @@ impl HeartbeatManager {
         let value = data.to_json()?;
 
         let mut conn = self.client.get_connection();
-        conn.set_ex(&key, value, self.config.ttl_seconds)
+        conn.set_ex::<_, _, ()>(&key, value, self.config.ttl_seconds)
             .await?;
 
         tracing::trace!(
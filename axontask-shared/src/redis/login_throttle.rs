@@ -0,0 +1,547 @@
+/// Login brute-force protection (Redis + Lua)
+///
+/// This module counts failed logins per account and per client IP address,
+/// shared by all API replicas. After a few free attempts, each further
+/// attempt has to wait a delay that doubles with every failure; too many
+/// failures lock the account or IP out for a while.
+///
+/// # Keys
+///
+/// - `login:failures:{scope}`: Hash with `count` (failures in the current
+///   window) and `last` (time of the last failure or throttled attempt, Unix
+///   milliseconds, Redis clock). Expires one window after the last failure.
+/// - `login:lock:{scope}`: Present while the scope is locked out
+///
+/// The scope is `account:{sha256(email)}` or `ip:{address}`; emails are
+/// hashed so Redis doesn't hold a list of addresses people tried.
+///
+/// # Flow
+///
+/// 1. `check` before verifying the password; reject the attempt if it isn't
+///    allowed
+/// 2. `record_failure` after a wrong password or unknown email
+/// 3. `record_success` after a successful login (clears the account's
+///    failures; the IP's stay, so one valid account can't reset them)
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::redis::client::{RedisClient, RedisConfig};
+/// use axontask_shared::redis::login_throttle::{LoginThrottle, LoginThrottleConfig};
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let client = RedisClient::new(RedisConfig::from_env()?).await?;
+/// let throttle = LoginThrottle::new(client, LoginThrottleConfig::default());
+/// let ip = Some("203.0.113.7".parse()?);
+///
+/// let decision = throttle.check("user@example.com", ip).await?;
+/// if !decision.allowed {
+///     println!("Retry after {}s", decision.retry_after_secs());
+/// }
+///
+/// // Wrong password
+/// let outcome = throttle.record_failure("user@example.com", ip).await?;
+/// if outcome.account_locked {
+///     println!("Account locked after {} failures", outcome.account_failures);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use crate::redis::client::RedisClient;
+use redis::Script;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use thiserror::Error;
+
+/// Lua script checking whether an attempt may proceed
+///
+/// KEYS: failures key and lock key of each scope
+/// ARGV: base delay (ms), max delay (ms), then free attempts of each scope
+///
+/// Returns `{allowed, retry_ms, locked}`. An allowed attempt past the free
+/// ones moves `last` to now, so concurrent attempts wait their turn.
+const CHECK_SCRIPT: &str = r#"
+local base = tonumber(ARGV[1])
+local max_delay = tonumber(ARGV[2])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local retry_ms = 0
+local locked = 0
+local delayed = {}
+for i = 1, #KEYS / 2 do
+    local failures_key = KEYS[2 * i - 1]
+    local lock_key = KEYS[2 * i]
+    local free = tonumber(ARGV[2 + i])
+
+    local lock_ms = redis.call('PTTL', lock_key)
+    if lock_ms > 0 then
+        locked = 1
+        retry_ms = math.max(retry_ms, lock_ms)
+    else
+        local state = redis.call('HMGET', failures_key, 'count', 'last')
+        local count = tonumber(state[1]) or 0
+        local last = tonumber(state[2]) or 0
+        if count >= free then
+            local delay = math.min(max_delay, base * 2 ^ (count - free))
+            local wait = last + delay - now
+            if wait > 0 then
+                retry_ms = math.max(retry_ms, wait)
+            else
+                table.insert(delayed, failures_key)
+            end
+        end
+    end
+end
+
+if retry_ms > 0 then
+    return {0, math.ceil(retry_ms), locked}
+end
+
+for _, key in ipairs(delayed) do
+    redis.call('HSET', key, 'last', now)
+end
+return {1, 0, 0}
+"#;
+
+/// Lua script recording a failed attempt
+///
+/// KEYS: failures key and lock key of each scope
+/// ARGV: window (ms), lockout (ms), then max failures of each scope
+///
+/// Returns `{count, newly_locked}` for each scope. Reaching the maximum sets
+/// the lock and starts counting from zero again once it expires.
+const FAILURE_SCRIPT: &str = r#"
+local window = tonumber(ARGV[1])
+local lockout = tonumber(ARGV[2])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local reply = {}
+for i = 1, #KEYS / 2 do
+    local failures_key = KEYS[2 * i - 1]
+    local lock_key = KEYS[2 * i]
+    local max_failures = tonumber(ARGV[2 + i])
+
+    local count = redis.call('HINCRBY', failures_key, 'count', 1)
+    local newly_locked = 0
+    if count >= max_failures then
+        if redis.call('SET', lock_key, 1, 'PX', lockout, 'NX') then
+            newly_locked = 1
+        end
+        redis.call('DEL', failures_key)
+    else
+        redis.call('HSET', failures_key, 'last', now)
+        redis.call('PEXPIRE', failures_key, window)
+    end
+
+    table.insert(reply, count)
+    table.insert(reply, newly_locked)
+end
+return reply
+"#;
+
+/// Login throttle errors
+#[derive(Error, Debug)]
+pub enum LoginThrottleError {
+    /// Redis command error
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    /// Unexpected script reply
+    #[error("Invalid login throttle script reply: {0:?}")]
+    InvalidReply(Vec<i64>),
+}
+
+/// Login throttle configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginThrottleConfig {
+    /// Failures per account before attempts are delayed
+    pub account_free_attempts: u32,
+
+    /// Failures per account that lock it out
+    pub account_max_failures: u32,
+
+    /// Failures per IP address before attempts are delayed
+    pub ip_free_attempts: u32,
+
+    /// Failures per IP address that lock it out
+    pub ip_max_failures: u32,
+
+    /// Delay after the first failure past the free attempts (milliseconds)
+    ///
+    /// Doubles with every further failure.
+    pub base_delay_ms: u64,
+
+    /// Longest delay between attempts (milliseconds)
+    pub max_delay_ms: u64,
+
+    /// Seconds without failures after which the count starts over
+    pub failure_window_secs: u64,
+
+    /// Seconds a lockout lasts
+    pub lockout_secs: u64,
+}
+
+impl Default for LoginThrottleConfig {
+    fn default() -> Self {
+        LoginThrottleConfig {
+            account_free_attempts: 3,
+            account_max_failures: 10,
+            ip_free_attempts: 20,
+            ip_max_failures: 100,
+            base_delay_ms: 1_000,
+            max_delay_ms: 30_000,
+            failure_window_secs: 900,
+            lockout_secs: 900,
+        }
+    }
+}
+
+impl LoginThrottleConfig {
+    /// Delay required before the next attempt after `failures` failures
+    ///
+    /// Mirrors the check script; useful to document and test the schedule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use axontask_shared::redis::login_throttle::LoginThrottleConfig;
+    ///
+    /// let config = LoginThrottleConfig::default();
+    /// assert_eq!(config.delay_ms(2, config.account_free_attempts), 0);
+    /// assert_eq!(config.delay_ms(3, config.account_free_attempts), 1_000);
+    /// assert_eq!(config.delay_ms(5, config.account_free_attempts), 4_000);
+    /// ```
+    pub fn delay_ms(&self, failures: u32, free_attempts: u32) -> u64 {
+        if failures < free_attempts {
+            return 0;
+        }
+
+        let doublings = (failures - free_attempts).min(63);
+        self.base_delay_ms
+            .saturating_mul(1u64 << doublings)
+            .min(self.max_delay_ms)
+    }
+}
+
+/// Outcome of a login throttle check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoginDecision {
+    /// Whether the attempt may proceed
+    pub allowed: bool,
+
+    /// Milliseconds until the next attempt is allowed (0 if allowed)
+    pub retry_after_ms: u64,
+
+    /// Whether the account or IP address is locked out
+    pub locked: bool,
+}
+
+impl LoginDecision {
+    /// Seconds to wait before retrying (at least 1)
+    ///
+    /// Used for the `Retry-After` header.
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after_ms.div_ceil(1000).max(1)
+    }
+}
+
+/// Outcome of recording a failed login
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailureOutcome {
+    /// Failures of the account in the current window, including this one
+    pub account_failures: u32,
+
+    /// Whether this failure locked the account out
+    pub account_locked: bool,
+
+    /// Failures of the IP address in the current window (0 if unknown)
+    pub ip_failures: u32,
+
+    /// Whether this failure locked the IP address out
+    pub ip_locked: bool,
+}
+
+/// Redis-backed login throttle
+#[derive(Clone)]
+pub struct LoginThrottle {
+    /// Redis client
+    client: RedisClient,
+
+    /// Limits and delays
+    config: LoginThrottleConfig,
+
+    /// Check script (loaded once, invoked with EVALSHA)
+    check_script: Script,
+
+    /// Failure script (loaded once, invoked with EVALSHA)
+    failure_script: Script,
+}
+
+impl LoginThrottle {
+    /// Creates a login throttle
+    pub fn new(client: RedisClient, config: LoginThrottleConfig) -> Self {
+        LoginThrottle {
+            client,
+            config,
+            check_script: Script::new(CHECK_SCRIPT),
+            failure_script: Script::new(FAILURE_SCRIPT),
+        }
+    }
+
+    /// Gets the configuration
+    pub fn config(&self) -> &LoginThrottleConfig {
+        &self.config
+    }
+
+    /// Checks whether a login attempt may proceed
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - Email the attempt is for (case and surrounding whitespace
+    ///   are ignored)
+    /// * `ip` - Client IP address, if known
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis script fails
+    pub async fn check(
+        &self,
+        email: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<LoginDecision, LoginThrottleError> {
+        let mut invocation = self.check_script.prepare_invoke();
+        invocation
+            .arg(self.config.base_delay_ms)
+            .arg(self.config.max_delay_ms);
+
+        for (scope, free, _) in self.scopes(email, ip) {
+            invocation
+                .key(failures_key(&scope))
+                .key(lock_key(&scope))
+                .arg(free);
+        }
+
+        let mut conn = self.client.get_connection();
+        let reply: Vec<i64> = invocation.invoke_async(&mut conn).await?;
+
+        match reply[..] {
+            [allowed, retry_ms, locked] => Ok(LoginDecision {
+                allowed: allowed == 1,
+                retry_after_ms: retry_ms.max(0) as u64,
+                locked: locked == 1,
+            }),
+            _ => Err(LoginThrottleError::InvalidReply(reply)),
+        }
+    }
+
+    /// Records a failed login attempt
+    ///
+    /// Call for wrong passwords and unknown emails alike, so attackers can't
+    /// tell them apart by the throttling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis script fails
+    pub async fn record_failure(
+        &self,
+        email: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<FailureOutcome, LoginThrottleError> {
+        let mut invocation = self.failure_script.prepare_invoke();
+        invocation
+            .arg(self.config.failure_window_secs.saturating_mul(1000))
+            .arg(self.config.lockout_secs.saturating_mul(1000));
+
+        for (scope, _, max_failures) in self.scopes(email, ip) {
+            invocation
+                .key(failures_key(&scope))
+                .key(lock_key(&scope))
+                .arg(max_failures);
+        }
+
+        let mut conn = self.client.get_connection();
+        let reply: Vec<i64> = invocation.invoke_async(&mut conn).await?;
+
+        match reply[..] {
+            [account_failures, account_locked] => Ok(FailureOutcome {
+                account_failures: account_failures.max(0) as u32,
+                account_locked: account_locked == 1,
+                ip_failures: 0,
+                ip_locked: false,
+            }),
+            [account_failures, account_locked, ip_failures, ip_locked] => Ok(FailureOutcome {
+                account_failures: account_failures.max(0) as u32,
+                account_locked: account_locked == 1,
+                ip_failures: ip_failures.max(0) as u32,
+                ip_locked: ip_locked == 1,
+            }),
+            _ => Err(LoginThrottleError::InvalidReply(reply)),
+        }
+    }
+
+    /// Clears an account's failures after a successful login
+    ///
+    /// A lockout in place is left to expire.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis command fails
+    pub async fn record_success(&self, email: &str) -> Result<(), LoginThrottleError> {
+        let mut conn = self.client.get_connection();
+        redis::cmd("DEL")
+            .arg(failures_key(&account_scope(email)))
+            .query_async::<_, ()>(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Scopes an attempt counts against, with their free attempts and
+    /// maximum failures (account first)
+    fn scopes(&self, email: &str, ip: Option<IpAddr>) -> Vec<(String, u32, u32)> {
+        let mut scopes = vec![(
+            account_scope(email),
+            self.config.account_free_attempts,
+            self.config.account_max_failures,
+        )];
+        if let Some(ip) = ip {
+            scopes.push((
+                ip_scope(ip),
+                self.config.ip_free_attempts,
+                self.config.ip_max_failures,
+            ));
+        }
+        scopes
+    }
+}
+
+/// Scope of an account, keyed by the hash of its normalized email
+fn account_scope(email: &str) -> String {
+    let normalized = email.trim().to_lowercase();
+    format!("account:{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+/// Scope of a client IP address
+fn ip_scope(ip: IpAddr) -> String {
+    format!("ip:{}", ip)
+}
+
+/// Redis key counting a scope's failures
+fn failures_key(scope: &str) -> String {
+    format!("login:failures:{}", scope)
+}
+
+/// Redis key present while a scope is locked out
+fn lock_key(scope: &str) -> String {
+    format!("login:lock:{}", scope)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_schedule() {
+        let config = LoginThrottleConfig::default();
+        let delays: Vec<u64> = (0..10).map(|failures| config.delay_ms(failures, 3)).collect();
+        assert_eq!(
+            delays,
+            vec![0, 0, 0, 1_000, 2_000, 4_000, 8_000, 16_000, 30_000, 30_000]
+        );
+
+        // Large counts don't overflow
+        assert_eq!(config.delay_ms(u32::MAX, 0), config.max_delay_ms);
+    }
+
+    #[test]
+    fn test_account_scope_normalizes_email() {
+        assert_eq!(
+            account_scope("User@Example.com "),
+            account_scope("user@example.com")
+        );
+        assert_ne!(account_scope("a@example.com"), account_scope("b@example.com"));
+        assert!(!account_scope("user@example.com").contains("example"));
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up() {
+        let decision = LoginDecision {
+            allowed: false,
+            retry_after_ms: 1_001,
+            locked: false,
+        };
+        assert_eq!(decision.retry_after_secs(), 2);
+    }
+
+    fn test_config() -> LoginThrottleConfig {
+        LoginThrottleConfig {
+            account_free_attempts: 2,
+            account_max_failures: 4,
+            base_delay_ms: 200,
+            lockout_secs: 2,
+            ..LoginThrottleConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_progressive_delay() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let throttle = LoginThrottle::new(client, test_config());
+        let email = format!("{}@example.com", uuid::Uuid::new_v4());
+
+        for _ in 0..2 {
+            assert!(throttle.check(&email, None).await.unwrap().allowed);
+            throttle.record_failure(&email, None).await.unwrap();
+        }
+
+        let denied = throttle.check(&email, None).await.unwrap();
+        assert!(!denied.allowed);
+        assert!(!denied.locked);
+        assert!(denied.retry_after_ms <= 200);
+
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        assert!(throttle.check(&email, None).await.unwrap().allowed);
+
+        // A success clears the account's failures
+        throttle.record_success(&email).await.unwrap();
+        assert!(throttle.check(&email, None).await.unwrap().allowed);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_lockout() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let throttle = LoginThrottle::new(client, test_config());
+        let email = format!("{}@example.com", uuid::Uuid::new_v4());
+        let ip = Some("198.51.100.1".parse().unwrap());
+
+        let mut outcome = None;
+        for _ in 0..4 {
+            outcome = Some(throttle.record_failure(&email, ip).await.unwrap());
+        }
+        let outcome = outcome.unwrap();
+        assert_eq!(outcome.account_failures, 4);
+        assert!(outcome.account_locked);
+        assert!(!outcome.ip_locked);
+
+        let denied = throttle.check(&email, ip).await.unwrap();
+        assert!(!denied.allowed);
+        assert!(denied.locked);
+        assert_eq!(denied.retry_after_secs(), 2);
+
+        // Other accounts from the same IP aren't affected
+        let other = format!("{}@example.com", uuid::Uuid::new_v4());
+        assert!(throttle.check(&other, ip).await.unwrap().allowed);
+    }
+}
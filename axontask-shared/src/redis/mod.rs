@@ -6,7 +6,11 @@
 /// - Stream writer for publishing events
 /// - Stream reader for backfill and live tailing
 /// - Heartbeat system for worker liveness
+/// - Control channel for cancel/pause/resume commands
 /// - Gap detection and compaction
+/// - Distributed token bucket rate limiting
+/// - Live stream connection tracking
+/// - Login brute-force protection
 ///
 /// # Architecture
 ///
@@ -44,16 +48,28 @@
 /// ```
 
 pub mod client;
+pub mod control;
 pub mod gap_detection;
 pub mod heartbeat;
+pub mod login_throttle;
 pub mod metrics;
+pub mod rate_limit;
+pub mod stream_connections;
 pub mod stream_reader;
 pub mod stream_writer;
 
 // Re-export common types for convenience
 pub use client::{RedisClient, RedisClientError, RedisConfig, RedisStats};
+pub use control::{ControlCommand, ControlMessage, ControlPublishError, ControlPublisher};
 pub use gap_detection::{GapDetectionError, GapDetector, GapDetectorConfig, GapInfo};
 pub use heartbeat::{HeartbeatConfig, HeartbeatData, HeartbeatError, HeartbeatManager};
+pub use login_throttle::{
+    FailureOutcome, LoginDecision, LoginThrottle, LoginThrottleConfig, LoginThrottleError,
+};
 pub use metrics::{EventRateStats, LagInfo, MetricsError, StreamInfo, StreamMetrics};
+pub use rate_limit::{Bucket, RateLimitDecision, RateLimitError, RateLimiter};
+pub use stream_connections::{
+    Acquire, StreamConnection, StreamConnectionError, StreamConnectionTracker,
+};
 pub use stream_reader::{StreamReader, StreamReaderConfig, StreamReaderError};
 pub use stream_writer::{StreamWriter, StreamWriterConfig, StreamWriterError};
@@ -0,0 +1,404 @@
+/// Distributed token bucket rate limiter (Redis + Lua)
+///
+/// This module implements an atomic token bucket shared by all API replicas.
+/// Each bucket is a Redis hash updated by a single Lua script, so concurrent
+/// requests on different replicas can never over-consume.
+///
+/// # Keys
+///
+/// Buckets are stored at `ratelimit:{scope}:{id}` (e.g.
+/// `ratelimit:tenant:{tenant_id}`) with fields:
+/// - `tokens`: Tokens left after the last request (fractional)
+/// - `ts`: Time of the last request (Unix milliseconds, Redis clock)
+///
+/// Keys expire once the bucket would be full again, since a missing key is
+/// equivalent to a full bucket.
+///
+/// # Clock
+///
+/// The script reads the time with `TIME`, so replicas with skewed clocks
+/// still refill the bucket consistently.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::redis::client::{RedisClient, RedisConfig};
+/// use axontask_shared::redis::rate_limit::{Bucket, RateLimiter};
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let client = RedisClient::new(RedisConfig::from_env()?).await?;
+/// let limiter = RateLimiter::new(client);
+///
+/// // 60 requests/minute, bursts of up to 60
+/// let bucket = Bucket::new("ratelimit:tenant:example", 60, 1.0);
+/// let decision = limiter.check(&[bucket], 1).await?;
+/// if !decision.allowed {
+///     println!("Retry after {}s", decision.retry_after_secs());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use crate::redis::client::RedisClient;
+use redis::Script;
+use thiserror::Error;
+
+/// Lua script consuming `cost` tokens from one or more buckets
+///
+/// The request is allowed only if every bucket has enough tokens, and then
+/// consumes from all of them; a denied request consumes nothing.
+///
+/// KEYS: bucket keys
+/// ARGV: cost, then capacity and refill rate (tokens/second) per key
+///
+/// Returns `{allowed, bucket_index, remaining, refill_ms, retry_ms, now_ms}`
+/// for the most restrictive bucket: the longest wait if denied, the fewest
+/// remaining tokens otherwise.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local cost = tonumber(ARGV[1])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local tokens = {}
+local allowed = 1
+for i, key in ipairs(KEYS) do
+    local capacity = tonumber(ARGV[2 * i])
+    local rate = tonumber(ARGV[2 * i + 1])
+
+    local bucket = redis.call('HMGET', key, 'tokens', 'ts')
+    local left = tonumber(bucket[1])
+    local ts = tonumber(bucket[2])
+    if left == nil or ts == nil then
+        left = capacity
+        ts = now
+    end
+
+    local elapsed = math.max(0, now - ts)
+    tokens[i] = math.min(capacity, left + elapsed * rate / 1000)
+    if tokens[i] < cost then
+        allowed = 0
+    end
+end
+
+local result = nil
+for i, key in ipairs(KEYS) do
+    local capacity = tonumber(ARGV[2 * i])
+    local rate = tonumber(ARGV[2 * i + 1])
+
+    local retry_ms = 0
+    if allowed == 1 then
+        tokens[i] = tokens[i] - cost
+    elseif tokens[i] < cost then
+        retry_ms = math.ceil((cost - tokens[i]) * 1000 / rate)
+    end
+
+    local refill_ms = math.ceil((capacity - tokens[i]) * 1000 / rate)
+    redis.call('HSET', key, 'tokens', tostring(tokens[i]), 'ts', now)
+    redis.call('PEXPIRE', key, refill_ms + 1000)
+
+    local remaining = math.floor(tokens[i])
+    if result == nil
+        or (allowed == 0 and retry_ms > result[5])
+        or (allowed == 1 and remaining < result[3]) then
+        result = {allowed, i - 1, remaining, refill_ms, retry_ms, now}
+    end
+end
+
+return result
+"#;
+
+/// Rate limiter errors
+#[derive(Error, Debug)]
+pub enum RateLimitError {
+    /// Redis command error
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    /// Unexpected script reply
+    #[error("Invalid rate limit script reply: {0:?}")]
+    InvalidReply(Vec<i64>),
+
+    /// Check without any bucket
+    #[error("No rate limit bucket to check")]
+    NoBuckets,
+}
+
+/// A token bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bucket {
+    /// Redis key
+    pub key: String,
+
+    /// Maximum tokens (burst size)
+    pub capacity: u32,
+
+    /// Refill rate in tokens per second
+    pub refill_rate: f64,
+}
+
+impl Bucket {
+    /// Creates a bucket
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Redis key
+    /// * `capacity` - Maximum tokens (burst size)
+    /// * `refill_rate` - Refill rate in tokens per second (must be positive)
+    pub fn new(key: impl Into<String>, capacity: u32, refill_rate: f64) -> Self {
+        Bucket {
+            key: key.into(),
+            capacity,
+            refill_rate,
+        }
+    }
+}
+
+/// Outcome of a rate limit check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    /// Whether the request may proceed
+    pub allowed: bool,
+
+    /// Capacity of the most restrictive bucket
+    pub limit: u32,
+
+    /// Whole tokens left after this request
+    pub remaining: u32,
+
+    /// Milliseconds until the bucket is full again
+    pub reset_after_ms: u64,
+
+    /// Milliseconds until enough tokens are available (0 if allowed)
+    pub retry_after_ms: u64,
+
+    /// Redis time of the check (Unix milliseconds)
+    pub now_ms: u64,
+}
+
+impl RateLimitDecision {
+    /// Parses the script reply
+    fn from_reply(buckets: &[Bucket], reply: Vec<i64>) -> Result<Self, RateLimitError> {
+        match reply[..] {
+            [allowed, index, remaining, reset_ms, retry_ms, now_ms] => Ok(RateLimitDecision {
+                allowed: allowed == 1,
+                limit: usize::try_from(index)
+                    .ok()
+                    .and_then(|index| buckets.get(index))
+                    .ok_or_else(|| RateLimitError::InvalidReply(reply.clone()))?
+                    .capacity,
+                remaining: remaining.max(0) as u32,
+                reset_after_ms: reset_ms.max(0) as u64,
+                retry_after_ms: retry_ms.max(0) as u64,
+                now_ms: now_ms.max(0) as u64,
+            }),
+            _ => Err(RateLimitError::InvalidReply(reply)),
+        }
+    }
+
+    /// Unix timestamp (seconds) when the bucket is full again
+    ///
+    /// Used for the `X-RateLimit-Reset` header.
+    pub fn reset_at(&self) -> u64 {
+        (self.now_ms + self.reset_after_ms).div_ceil(1000)
+    }
+
+    /// Seconds to wait before retrying (at least 1)
+    ///
+    /// Used for the `Retry-After` header.
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after_ms.div_ceil(1000).max(1)
+    }
+}
+
+/// Redis-backed rate limiter
+#[derive(Clone)]
+pub struct RateLimiter {
+    /// Redis client
+    client: RedisClient,
+
+    /// Token bucket script (loaded once, invoked with EVALSHA)
+    script: Script,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter
+    pub fn new(client: RedisClient) -> Self {
+        RateLimiter {
+            client,
+            script: Script::new(TOKEN_BUCKET_SCRIPT),
+        }
+    }
+
+    /// Consumes `cost` tokens from every bucket if all have enough available
+    ///
+    /// The check is atomic over all buckets: either each bucket is charged
+    /// or none is. The decision describes the most restrictive bucket.
+    ///
+    /// A cost above the smallest bucket capacity is capped at that capacity,
+    /// so that expensive requests are still possible with full buckets.
+    ///
+    /// # Arguments
+    ///
+    /// * `buckets` - Buckets to consume from (at least one)
+    /// * `cost` - Tokens this request costs
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no bucket is given or the Redis script fails
+    pub async fn check(
+        &self,
+        buckets: &[Bucket],
+        cost: u32,
+    ) -> Result<RateLimitDecision, RateLimitError> {
+        let capacity = buckets
+            .iter()
+            .map(|bucket| bucket.capacity)
+            .min()
+            .ok_or(RateLimitError::NoBuckets)?;
+        let cost = cost.clamp(1, capacity.max(1));
+
+        let mut invocation = self.script.prepare_invoke();
+        invocation.arg(cost);
+        for bucket in buckets {
+            invocation
+                .key(&bucket.key)
+                .arg(bucket.capacity)
+                .arg(bucket.refill_rate);
+        }
+
+        let mut conn = self.client.get_connection();
+        let reply: Vec<i64> = invocation.invoke_async(&mut conn).await?;
+
+        RateLimitDecision::from_reply(buckets, reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decision_from_reply() {
+        let buckets = [
+            Bucket::new("ratelimit:tenant:a", 60, 1.0),
+            Bucket::new("ratelimit:apikey:b", 10, 1.0),
+        ];
+
+        let decision = RateLimitDecision::from_reply(&buckets, vec![1, 0, 59, 1000, 0, 1_700_000_000_000]).unwrap();
+        assert!(decision.allowed);
+        assert_eq!(decision.limit, 60);
+        assert_eq!(decision.remaining, 59);
+        assert_eq!(decision.reset_at(), 1_700_000_001);
+
+        let decision = RateLimitDecision::from_reply(&buckets, vec![0, 1, 0, 10_000, 1000, 0]).unwrap();
+        assert!(!decision.allowed);
+        assert_eq!(decision.limit, 10);
+
+        assert!(RateLimitDecision::from_reply(&buckets, vec![1, 2]).is_err());
+        assert!(RateLimitDecision::from_reply(&buckets, vec![1, 2, 59, 1000, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up() {
+        let decision = RateLimitDecision {
+            allowed: false,
+            limit: 10,
+            remaining: 0,
+            reset_after_ms: 60_000,
+            retry_after_ms: 5_001,
+            now_ms: 0,
+        };
+        assert_eq!(decision.retry_after_secs(), 6);
+
+        // Never tell clients to retry immediately
+        let almost = RateLimitDecision {
+            retry_after_ms: 1,
+            ..decision
+        };
+        assert_eq!(almost.retry_after_secs(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_bucket_exhaustion() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let limiter = RateLimiter::new(client);
+        let bucket = Bucket::new(
+            format!("ratelimit:test:{}", uuid::Uuid::new_v4()),
+            3,
+            0.5,
+        );
+
+        for remaining in (0..3).rev() {
+            let decision = limiter.check(std::slice::from_ref(&bucket), 1).await.unwrap();
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, remaining);
+        }
+
+        let denied = limiter.check(std::slice::from_ref(&bucket), 1).await.unwrap();
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after_secs(), 2);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_cost() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let limiter = RateLimiter::new(client);
+        let bucket = Bucket::new(
+            format!("ratelimit:test:{}", uuid::Uuid::new_v4()),
+            10,
+            1.0,
+        );
+
+        let decision = limiter.check(std::slice::from_ref(&bucket), 4).await.unwrap();
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 6);
+
+        // Capped at capacity: denied now, but not forever
+        let decision = limiter.check(std::slice::from_ref(&bucket), 50).await.unwrap();
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after_secs(), 4);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_multiple_buckets() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let limiter = RateLimiter::new(client);
+        let id = uuid::Uuid::new_v4();
+        let shared = Bucket::new(format!("ratelimit:test:{}:shared", id), 5, 0.01);
+        let first = Bucket::new(format!("ratelimit:test:{}:first", id), 2, 0.01);
+        let second = Bucket::new(format!("ratelimit:test:{}:second", id), 10, 0.01);
+
+        // The smaller bucket binds and both are charged
+        for remaining in (0..2).rev() {
+            let decision = limiter.check(&[shared.clone(), first.clone()], 1).await.unwrap();
+            assert!(decision.allowed);
+            assert_eq!(decision.limit, 2);
+            assert_eq!(decision.remaining, remaining);
+        }
+
+        // A denied request charges neither bucket
+        let denied = limiter.check(&[shared.clone(), first.clone()], 1).await.unwrap();
+        assert!(!denied.allowed);
+
+        // The shared bucket still limits requests through another bucket
+        for remaining in (0..3).rev() {
+            let decision = limiter.check(&[shared.clone(), second.clone()], 1).await.unwrap();
+            assert!(decision.allowed);
+            assert_eq!(decision.limit, 5);
+            assert_eq!(decision.remaining, remaining);
+        }
+        let denied = limiter.check(&[shared.clone(), second.clone()], 1).await.unwrap();
+        assert!(!denied.allowed);
+        assert_eq!(denied.limit, 5);
+    }
+}
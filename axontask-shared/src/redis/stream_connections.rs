@@ -0,0 +1,424 @@
+/// Live SSE connection tracking (Redis)
+///
+/// This module counts open stream connections per tenant across all API
+/// replicas, so the `StreamConnections` quota can be enforced.
+///
+/// # Storage
+///
+/// Each tenant has a sorted set `streams:{tenant_id}`:
+/// - member: connection ID
+/// - score: lease expiry (Unix milliseconds, Redis clock)
+///
+/// An open connection renews its lease every `lease / 3`. When a replica
+/// dies its connections stop renewing and drop out of the count once their
+/// lease expires. Connections are removed immediately when the stream is
+/// dropped (client disconnect or end of stream).
+///
+/// Renewals only extend a registered lease. A connection whose lease lapsed
+/// and was evicted (e.g. Redis was unreachable for longer than the lease)
+/// has lost its slot: renewal stops and `StreamConnection::lost` resolves,
+/// so the stream can end instead of counting twice.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::redis::client::{RedisClient, RedisConfig};
+/// use axontask_shared::redis::stream_connections::{Acquire, StreamConnectionTracker};
+/// use uuid::Uuid;
+///
+/// # async fn example(tenant_id: Uuid) -> anyhow::Result<()> {
+/// let client = RedisClient::new(RedisConfig::from_env()?).await?;
+/// let tracker = StreamConnectionTracker::new(client);
+///
+/// match tracker.acquire(tenant_id, 5).await? {
+///     Acquire::Acquired(connection) => {
+///         // Keep `connection` alive for as long as the stream is open
+///         drop(connection);
+///     }
+///     Acquire::LimitReached { open } => println!("Limit reached ({} open)", open),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use crate::redis::client::RedisClient;
+use redis::{AsyncCommands, Script};
+use std::future::Future;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Default connection lease
+pub const DEFAULT_LEASE: Duration = Duration::from_secs(60);
+
+/// Lua script registering a connection if the tenant is below its limit
+///
+/// KEYS[1]: tenant set
+/// ARGV: limit, connection ID, lease (ms)
+///
+/// Returns `{acquired, open_connections}`.
+const ACQUIRE_SCRIPT: &str = r#"
+local key = KEYS[1]
+local limit = tonumber(ARGV[1])
+local lease_ms = tonumber(ARGV[3])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+redis.call('ZREMRANGEBYSCORE', key, '-inf', now)
+local count = redis.call('ZCARD', key)
+if count >= limit then
+    return {0, count}
+end
+
+redis.call('ZADD', key, now + lease_ms, ARGV[2])
+redis.call('PEXPIRE', key, lease_ms * 2)
+return {1, count + 1}
+"#;
+
+/// Lua script extending a connection's lease
+///
+/// Never re-registers a released or evicted connection.
+///
+/// KEYS[1]: tenant set
+/// ARGV: connection ID, lease (ms)
+///
+/// Returns 1 if the lease was extended, 0 if the connection holds no slot.
+const RENEW_SCRIPT: &str = r#"
+local key = KEYS[1]
+local lease_ms = tonumber(ARGV[2])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local changed = redis.call('ZADD', key, 'XX', 'CH', now + lease_ms, ARGV[1])
+if changed == 0 and not redis.call('ZSCORE', key, ARGV[1]) then
+    return 0
+end
+
+redis.call('PEXPIRE', key, lease_ms * 2)
+return 1
+"#;
+
+/// Lua script counting connections with a live lease
+///
+/// KEYS[1]: tenant set
+const COUNT_SCRIPT: &str = r#"
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+return redis.call('ZCOUNT', KEYS[1], '(' .. now, '+inf')
+"#;
+
+/// Stream connection tracking errors
+#[derive(Error, Debug)]
+pub enum StreamConnectionError {
+    /// Redis command error
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+/// Returns the Redis key of a tenant's connection set
+pub fn connections_key(tenant_id: Uuid) -> String {
+    format!("streams:{}", tenant_id)
+}
+
+/// Outcome of `StreamConnectionTracker::acquire`
+pub enum Acquire {
+    /// Slot acquired; held until the connection is dropped
+    Acquired(Box<StreamConnection>),
+
+    /// The tenant already has `limit` open connections
+    LimitReached {
+        /// Open connections
+        open: u32,
+    },
+}
+
+/// Tracks open stream connections per tenant
+#[derive(Clone)]
+pub struct StreamConnectionTracker {
+    /// Redis client
+    client: RedisClient,
+
+    /// Connection lease
+    lease: Duration,
+}
+
+impl StreamConnectionTracker {
+    /// Creates a tracker with the default lease
+    pub fn new(client: RedisClient) -> Self {
+        Self::with_lease(client, DEFAULT_LEASE)
+    }
+
+    /// Creates a tracker with a custom lease
+    pub fn with_lease(client: RedisClient, lease: Duration) -> Self {
+        StreamConnectionTracker { client, lease }
+    }
+
+    /// Registers a connection if the tenant has fewer than `limit` open
+    ///
+    /// The limit check and registration are atomic, so concurrent requests
+    /// on different replicas cannot exceed the limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis script fails
+    pub async fn acquire(
+        &self,
+        tenant_id: Uuid,
+        limit: u32,
+    ) -> Result<Acquire, StreamConnectionError> {
+        let key = connections_key(tenant_id);
+        let id = Uuid::new_v4();
+        let mut conn = self.client.get_connection();
+
+        let (acquired, open): (i64, i64) = Script::new(ACQUIRE_SCRIPT)
+            .key(&key)
+            .arg(limit)
+            .arg(id.to_string())
+            .arg(self.lease.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await?;
+
+        if acquired != 1 {
+            return Ok(Acquire::LimitReached {
+                open: open.max(0) as u32,
+            });
+        }
+
+        let (lost_tx, lost) = watch::channel(false);
+        let renewal = tokio::spawn(renew_lease(
+            self.client.clone(),
+            key.clone(),
+            id,
+            self.lease,
+            lost_tx,
+        ));
+
+        Ok(Acquire::Acquired(Box::new(StreamConnection {
+            id,
+            tenant_id,
+            key,
+            client: self.client.clone(),
+            renewal,
+            lost,
+        })))
+    }
+
+    /// Counts a tenant's open connections
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis script fails
+    pub async fn count(&self, tenant_id: Uuid) -> Result<u32, StreamConnectionError> {
+        let mut conn = self.client.get_connection();
+
+        let count: i64 = Script::new(COUNT_SCRIPT)
+            .key(connections_key(tenant_id))
+            .invoke_async(&mut conn)
+            .await?;
+
+        Ok(count.max(0) as u32)
+    }
+}
+
+/// Renews a connection's lease until the task is aborted or the slot is lost
+///
+/// Sends `true` on `lost` when the connection no longer holds a slot.
+async fn renew_lease(
+    client: RedisClient,
+    key: String,
+    id: Uuid,
+    lease: Duration,
+    lost: watch::Sender<bool>,
+) {
+    let mut interval = tokio::time::interval(lease / 3);
+    interval.tick().await; // First tick completes immediately
+
+    loop {
+        interval.tick().await;
+
+        match renew(&client, &key, id, lease).await {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::warn!(connection_id = %id, "Stream connection lease lost, closing stream");
+                let _ = lost.send(true);
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, connection_id = %id, "Failed to renew stream connection lease");
+            }
+        }
+    }
+}
+
+/// Extends a connection's lease; returns false if it holds no slot
+async fn renew(
+    client: &RedisClient,
+    key: &str,
+    id: Uuid,
+    lease: Duration,
+) -> Result<bool, redis::RedisError> {
+    let mut conn = client.get_connection();
+    let renewed: i64 = Script::new(RENEW_SCRIPT)
+        .key(key)
+        .arg(id.to_string())
+        .arg(lease.as_millis() as u64)
+        .invoke_async(&mut conn)
+        .await?;
+
+    Ok(renewed == 1)
+}
+
+/// An open stream connection
+///
+/// Holds a slot in the tenant's connection count until dropped.
+pub struct StreamConnection {
+    /// Connection ID
+    pub id: Uuid,
+
+    /// Tenant the connection counts against
+    pub tenant_id: Uuid,
+
+    /// Tenant set key
+    key: String,
+
+    /// Redis client
+    client: RedisClient,
+
+    /// Lease renewal task
+    renewal: JoinHandle<()>,
+
+    /// Set when the slot is lost
+    lost: watch::Receiver<bool>,
+}
+
+impl StreamConnection {
+    /// Resolves when the connection loses its slot
+    ///
+    /// The slot is lost when the lease lapsed and was evicted; the stream
+    /// should end then. Never resolves while the slot is held.
+    pub fn lost(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut lost = self.lost.clone();
+
+        async move {
+            // An error means renewal stopped without losing the slot
+            if lost.wait_for(|lost| *lost).await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+impl Drop for StreamConnection {
+    fn drop(&mut self) {
+        self.renewal.abort();
+
+        // Release the slot now rather than waiting for the lease to expire
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let mut conn = self.client.get_connection();
+        let key = std::mem::take(&mut self.key);
+        let id = self.id;
+        runtime.spawn(async move {
+            let result: Result<i64, _> = conn.zrem(&key, id.to_string()).await;
+            if let Err(e) = result {
+                tracing::warn!(error = %e, connection_id = %id, "Failed to release stream connection");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Acquire {
+        fn unwrap(self) -> StreamConnection {
+            match self {
+                Acquire::Acquired(connection) => *connection,
+                Acquire::LimitReached { open } => panic!("limit reached ({} open)", open),
+            }
+        }
+    }
+
+    #[test]
+    fn test_connections_key() {
+        let tenant_id = Uuid::nil();
+        assert_eq!(
+            connections_key(tenant_id),
+            "streams:00000000-0000-0000-0000-000000000000"
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_acquire_limit_and_release() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let tracker = StreamConnectionTracker::new(client);
+        let tenant_id = Uuid::new_v4();
+
+        let first = tracker.acquire(tenant_id, 2).await.unwrap().unwrap();
+        let _second = tracker.acquire(tenant_id, 2).await.unwrap().unwrap();
+        assert!(matches!(
+            tracker.acquire(tenant_id, 2).await.unwrap(),
+            Acquire::LimitReached { open: 2 }
+        ));
+        assert_eq!(tracker.count(tenant_id).await.unwrap(), 2);
+
+        // Dropping a connection frees its slot
+        drop(first);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(tracker.count(tenant_id).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_expired_lease_frees_slot() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let tracker = StreamConnectionTracker::with_lease(client, Duration::from_millis(300));
+        let tenant_id = Uuid::new_v4();
+
+        // Simulate a dead replica: the connection is never released or renewed
+        let connection = tracker.acquire(tenant_id, 1).await.unwrap().unwrap();
+        connection.renewal.abort();
+        std::mem::forget(connection);
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(matches!(
+            tracker.acquire(tenant_id, 1).await.unwrap(),
+            Acquire::Acquired(_)
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running Redis instance
+    async fn test_renew_after_release() {
+        use crate::redis::client::RedisConfig;
+
+        let client = RedisClient::new(RedisConfig::default_for_test()).await.unwrap();
+        let tracker = StreamConnectionTracker::new(client.clone());
+        let tenant_id = Uuid::new_v4();
+        let key = connections_key(tenant_id);
+
+        let connection = tracker.acquire(tenant_id, 1).await.unwrap().unwrap();
+        let id = connection.id;
+        drop(connection);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // A late renewal doesn't bring the released connection back
+        assert!(!renew(&client, &key, id, DEFAULT_LEASE).await.unwrap());
+        let mut conn = client.get_connection();
+        let open: i64 = conn.zcard(&key).await.unwrap();
+        assert_eq!(open, 0);
+    }
+}
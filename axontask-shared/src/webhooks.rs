@@ -0,0 +1,626 @@
+/// Webhook HTTP delivery
+///
+/// This module sends signed webhook requests. It is used by the worker's
+/// dispatcher for queued deliveries and by the API for test deliveries.
+///
+/// # Request
+///
+/// Each delivery is a `POST` with the JSON payload and these headers:
+///
+/// - `X-AxonTask-Event`: Event type (e.g. `task.succeeded`)
+/// - `X-AxonTask-Delivery`: Delivery ID (stable across retries)
+/// - `X-AxonTask-Timestamp`: Unix time of the attempt (seconds)
+/// - `X-AxonTask-Signature`: `sha256=<hex>` HMAC of `"{timestamp}.{body}"`
+///
+/// During a secret rotation the signature header carries one comma-separated
+/// signature per valid secret.
+///
+/// # Target Restrictions
+///
+/// Webhooks are only sent to public addresses. URLs whose host is (or
+/// resolves to) a loopback, private, link-local (including cloud metadata
+/// endpoints such as `169.254.169.254`) or otherwise reserved address are
+/// rejected when a webhook is created (see `check_target`) and again on every
+/// request: the sender's DNS resolver refuses such addresses, so a hostname
+/// that is re-pointed after validation (DNS rebinding) is still blocked.
+/// Redirects and proxies are not used.
+///
+/// Only the first `MAX_RESPONSE_BODY_BYTES` of a response body are read.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_shared::models::webhook::Webhook;
+/// use axontask_shared::webhooks::WebhookSender;
+/// use serde_json::json;
+/// use std::time::Duration;
+/// use uuid::Uuid;
+///
+/// # async fn example(webhook: Webhook) {
+/// let sender = WebhookSender::new(Duration::from_secs(10));
+/// let attempt = sender
+///     .send(&webhook, Uuid::new_v4(), "task.succeeded", &json!({}))
+///     .await;
+/// println!("Delivered: {}", attempt.is_success());
+/// # }
+/// ```
+
+use crate::models::webhook::Webhook;
+use crate::models::webhook_delivery::{truncate_body, DeliveryAttempt, MAX_RESPONSE_BODY_BYTES};
+use chrono::Utc;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::Url;
+use serde_json::Value as JsonValue;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Default HTTP request timeout
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends signed webhook requests
+#[derive(Debug, Clone)]
+pub struct WebhookSender {
+    /// HTTP client (redirects are not followed)
+    client: reqwest::Client,
+
+    /// Whether non-public targets are allowed (tests and local development)
+    allow_private: bool,
+}
+
+impl WebhookSender {
+    /// Creates a sender that only reaches public addresses
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - HTTP request timeout
+    pub fn new(timeout: Duration) -> Self {
+        Self::build(timeout, false)
+    }
+
+    /// Creates a sender that also reaches loopback and private addresses
+    ///
+    /// Only for tests and local development: webhook URLs are chosen by
+    /// tenants.
+    pub fn allowing_private_targets(timeout: Duration) -> Self {
+        Self::build(timeout, true)
+    }
+
+    fn build(timeout: Duration, allow_private: bool) -> Self {
+        let mut builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy()
+            .user_agent(concat!("AxonTask-Webhooks/", env!("CARGO_PKG_VERSION")));
+
+        if !allow_private {
+            builder = builder.dns_resolver(Arc::new(PublicResolver));
+        }
+
+        let client = builder.build().expect("Failed to build HTTP client");
+
+        WebhookSender {
+            client,
+            allow_private,
+        }
+    }
+
+    /// Sends one delivery attempt
+    ///
+    /// Never fails: errors are reported in the returned attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `webhook` - Target webhook
+    /// * `delivery_id` - Delivery ID (sent in `X-AxonTask-Delivery`)
+    /// * `event` - Event type
+    /// * `payload` - Request body
+    pub async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery_id: Uuid,
+        event: &str,
+        payload: &JsonValue,
+    ) -> DeliveryAttempt {
+        // Hostnames are checked by the resolver, IP literals here
+        if !self.allow_private {
+            if let Err(e) = check_url(&webhook.url) {
+                return DeliveryAttempt {
+                    error: Some(e),
+                    ..Default::default()
+                };
+            }
+        }
+
+        let body = payload.to_string();
+        let timestamp = Utc::now().timestamp();
+        let started = Instant::now();
+
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-AxonTask-Event", event)
+            .header("X-AxonTask-Delivery", delivery_id.to_string())
+            .header("X-AxonTask-Timestamp", timestamp.to_string())
+            .header("X-AxonTask-Signature", signature_header(webhook, timestamp, &body))
+            .body(body)
+            .send()
+            .await;
+
+        let mut attempt = match response {
+            Ok(response) => {
+                let status = response.status();
+                let body = read_body(response).await;
+
+                DeliveryAttempt {
+                    response_status: Some(status.as_u16() as i32),
+                    response_body: Some(truncate_body(body)),
+                    latency_ms: 0,
+                    error: (!status.is_success()).then(|| format!("HTTP {}", status)),
+                }
+            }
+            Err(e) => DeliveryAttempt {
+                error: Some(e.to_string()),
+                ..Default::default()
+            },
+        };
+
+        attempt.latency_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+        attempt
+    }
+}
+
+impl Default for WebhookSender {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUEST_TIMEOUT)
+    }
+}
+
+/// Reads at most `MAX_RESPONSE_BODY_BYTES` of a response body
+///
+/// Stops reading once the limit is reached, so a large (or endless) body is
+/// never buffered. Read errors end the body early.
+async fn read_body(mut response: reqwest::Response) -> String {
+    let mut body = Vec::new();
+
+    while body.len() < MAX_RESPONSE_BODY_BYTES {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                let take = chunk.len().min(MAX_RESPONSE_BODY_BYTES - body.len());
+                body.extend_from_slice(&chunk[..take]);
+            }
+            _ => break,
+        }
+    }
+
+    truncate_body(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Checks that a webhook URL targets a public address
+///
+/// Validates the URL like the sender does and resolves its hostname; every
+/// resolved address must be public. Call it before storing a webhook URL.
+///
+/// # Errors
+///
+/// Returns a description of why the URL is not allowed
+pub async fn check_target(url: &str) -> Result<(), String> {
+    let Some((host, port)) = check_url(url)? else {
+        return Ok(());
+    };
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|_| format!("Could not resolve {}", host))?
+        .collect();
+
+    check_addrs(&host, &addrs)
+}
+
+/// Validates a webhook URL without resolving it
+///
+/// Requires http or https and checks IP literal hosts.
+///
+/// # Returns
+///
+/// The hostname and port to resolve, or None if the host is an IP address
+fn check_url(url: &str) -> Result<Option<(String, u16)>, String> {
+    let parsed = Url::parse(url).map_err(|_| "URL is not valid".to_string())?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("URL must use http or https".to_string());
+    }
+
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "URL must have a host".to_string())?;
+
+    // IPv6 hosts are bracketed; IPv4 hosts are normalized to dotted form
+    if let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+        return check_ip(ip).map(|_| None);
+    }
+
+    let domain = host.trim_end_matches('.').to_ascii_lowercase();
+    if domain == "localhost" || domain.ends_with(".localhost") {
+        return Err("URL must not point to localhost".to_string());
+    }
+
+    Ok(Some((domain, port)))
+}
+
+/// Checks that every address a host resolved to is public
+fn check_addrs(host: &str, addrs: &[SocketAddr]) -> Result<(), String> {
+    if addrs.is_empty() {
+        return Err(format!("Could not resolve {}", host));
+    }
+
+    for addr in addrs {
+        check_ip(addr.ip()).map_err(|_| format!("{} resolves to a non-public address", host))?;
+    }
+
+    Ok(())
+}
+
+fn check_ip(ip: IpAddr) -> Result<(), String> {
+    if is_public_ip(ip) {
+        Ok(())
+    } else {
+        Err(format!("URL must not point to a non-public address ({})", ip))
+    }
+}
+
+/// Checks whether an address is publicly routable
+///
+/// False for loopback, private, shared (CGNAT), link-local, multicast,
+/// documentation and other reserved ranges, and for IPv6 addresses that
+/// embed such an IPv4 address.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        // Shared address space (CGNAT), 100.64.0.0/10
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments, 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && (18..20).contains(&b))
+        // Reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    // IPv4-mapped (::ffff:a.b.c.d) and NAT64 (64:ff9b::a.b.c.d) addresses
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_ipv4(v4);
+    }
+    let segments = ip.segments();
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [a, b] = segments[6].to_be_bytes();
+        let [c, d] = segments[7].to_be_bytes();
+        return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+    }
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local, fc00::/7
+        || (segments[0] & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (segments[0] & 0xffc0) == 0xfe80
+        // Site-local (deprecated), fec0::/10
+        || (segments[0] & 0xffc0) == 0xfec0
+        // Documentation, 2001:db8::/32
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+}
+
+/// DNS resolver that refuses non-public addresses
+///
+/// Checked on every connection, after the URL was validated, so re-pointing
+/// a hostname to an internal address does not bypass `check_target`.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .collect();
+            check_addrs(&host, &addrs)?;
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Builds the `X-AxonTask-Signature` header value
+///
+/// Signs `"{timestamp}.{body}"` with every secret currently valid for the
+/// webhook (see `Webhook::signatures`).
+pub fn signature_header(webhook: &Webhook, timestamp: i64, body: &str) -> String {
+    let signed = format!("{}.{}", timestamp, body);
+
+    webhook
+        .signatures(signed.as_bytes())
+        .iter()
+        .map(|signature| format!("sha256={}", signature))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn test_webhook(url: String) -> Webhook {
+        Webhook {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            url,
+            secret: vec![7; 32],
+            previous_secret: None,
+            previous_secret_expires_at: None,
+            active: true,
+            events: vec!["task.succeeded".to_string()],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Starts a receiver that answers one request with `status` and returns
+    /// the raw request
+    async fn receiver(status: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+
+            // Read headers, then the body by Content-Length
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let length = text
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                status
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_signature_header() {
+        let mut webhook = test_webhook("https://example.com".to_string());
+        let header = signature_header(&webhook, 1700000000, "{}");
+        assert_eq!(
+            header,
+            format!("sha256={}", webhook.generate_signature(b"1700000000.{}"))
+        );
+
+        // Both secrets sign during a rotation
+        webhook.previous_secret = Some(vec![1; 32]);
+        webhook.previous_secret_expires_at = Some(Utc::now() + chrono::Duration::hours(1));
+        assert_eq!(signature_header(&webhook, 1700000000, "{}").split(',').count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_signed_request() {
+        let (url, handle) = receiver("200 OK").await;
+        let webhook = test_webhook(url);
+        let delivery_id = Uuid::new_v4();
+
+        let attempt = WebhookSender::allowing_private_targets(DEFAULT_REQUEST_TIMEOUT)
+            .send(&webhook, delivery_id, "task.succeeded", &json!({"event": "task.succeeded"}))
+            .await;
+        assert!(attempt.is_success(), "{:?}", attempt);
+        assert_eq!(attempt.response_body.as_deref(), Some("ok"));
+
+        let request = handle.await.unwrap();
+        let header = |name: &str| {
+            request
+                .lines()
+                .find_map(|l| {
+                    let (k, v) = l.split_once(':')?;
+                    k.eq_ignore_ascii_case(name).then(|| v.trim().to_string())
+                })
+                .unwrap()
+        };
+
+        assert_eq!(header("x-axontask-event"), "task.succeeded");
+        assert_eq!(header("x-axontask-delivery"), delivery_id.to_string());
+
+        let body = request.split("\r\n\r\n").nth(1).unwrap();
+        let timestamp: i64 = header("x-axontask-timestamp").parse().unwrap();
+        assert_eq!(
+            header("x-axontask-signature"),
+            signature_header(&webhook, timestamp, body)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_failure_status() {
+        let (url, handle) = receiver("503 Service Unavailable").await;
+        let webhook = test_webhook(url);
+
+        let attempt = WebhookSender::allowing_private_targets(DEFAULT_REQUEST_TIMEOUT)
+            .send(&webhook, Uuid::new_v4(), "task.failed", &json!({}))
+            .await;
+        handle.await.unwrap();
+
+        assert!(!attempt.is_success());
+        assert_eq!(attempt.response_status, Some(503));
+        assert!(attempt.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_send_reads_body_up_to_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let webhook = test_webhook(format!("http://{}/hook", listener.local_addr().unwrap()));
+
+        // Streams a body far larger than the limit, without a length
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n")
+                .await;
+            let chunk = [b'a'; 65536];
+            for _ in 0..1024 {
+                if socket.write_all(&chunk).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let attempt = WebhookSender::allowing_private_targets(DEFAULT_REQUEST_TIMEOUT)
+            .send(&webhook, Uuid::new_v4(), "task.succeeded", &json!({}))
+            .await;
+        handle.abort();
+
+        assert_eq!(attempt.response_status, Some(200));
+        assert_eq!(attempt.response_body.unwrap().len(), MAX_RESPONSE_BODY_BYTES);
+    }
+
+    #[tokio::test]
+    async fn test_send_refuses_private_targets() {
+        let (url, handle) = receiver("200 OK").await;
+        let webhook = test_webhook(url);
+
+        let attempt = WebhookSender::default()
+            .send(&webhook, Uuid::new_v4(), "task.succeeded", &json!({}))
+            .await;
+        handle.abort();
+
+        assert!(!attempt.is_success());
+        assert_eq!(attempt.response_status, None);
+        assert!(attempt.error.unwrap().contains("non-public"));
+
+        // Hostnames are checked when they are resolved for a connection
+        let name: Name = "localhost".parse().unwrap();
+        assert!(PublicResolver.resolve(name).await.is_err());
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.100.100.200",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00:ec2::254",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_check_url() {
+        assert_eq!(
+            check_url("https://example.com/hooks").unwrap(),
+            Some(("example.com".to_string(), 443))
+        );
+        assert_eq!(
+            check_url("HTTP://Example.com:8080").unwrap(),
+            Some(("example.com".to_string(), 8080))
+        );
+        assert_eq!(check_url("https://93.184.216.34/hook").unwrap(), None);
+
+        assert!(check_url("ftp://example.com").is_err());
+        assert!(check_url("not a url").is_err());
+        assert!(check_url("http://localhost:8080/").is_err());
+        assert!(check_url("http://api.localhost/").is_err());
+        assert!(check_url("http://127.0.0.1/").is_err());
+        assert!(check_url("http://[::1]/").is_err());
+        assert!(check_url("http://169.254.169.254/latest/meta-data/").is_err());
+        // Alternative IPv4 notations are normalized by the URL parser
+        assert!(check_url("http://2130706433/").is_err());
+        assert!(check_url("http://0x7f.1/").is_err());
+    }
+
+    #[test]
+    fn test_check_addrs() {
+        let public: SocketAddr = "93.184.216.34:443".parse().unwrap();
+        let private: SocketAddr = "10.0.0.1:443".parse().unwrap();
+
+        assert!(check_addrs("example.com", &[public]).is_ok());
+        assert!(check_addrs("example.com", &[public, private]).is_err());
+        assert!(check_addrs("example.com", &[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_connection_error() {
+        // Bind and drop to get a port with no listener
+        let addr = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let webhook = test_webhook(format!("http://{}/hook", addr));
+
+        let attempt = WebhookSender::allowing_private_targets(DEFAULT_REQUEST_TIMEOUT)
+            .send(&webhook, Uuid::new_v4(), "task.failed", &json!({}))
+            .await;
+        assert!(!attempt.is_success());
+        assert_eq!(attempt.response_status, None);
+        assert!(attempt.error.is_some());
+    }
+}
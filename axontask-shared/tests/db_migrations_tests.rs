@@ -161,6 +161,8 @@ async fn test_migration_creates_all_tables() {
         "webhooks",
         "webhook_deliveries",
         "usage_counters",
+        "usage_records",
+        "quota_alerts",
     ];
 
     for table_name in expected_tables {
@@ -28,6 +28,21 @@
 /// Adapters must check the cancel token regularly and clean up resources
 /// when cancellation is requested.
 ///
+/// # Pausing
+///
+/// Tasks can be paused and resumed through the control channel. Adapters
+/// should call `context.pause_point().await` between units of work; it returns
+/// immediately unless the task is paused. Adapters that cannot stop at safe
+/// points (e.g. external processes) can watch `context.pause_signal()` instead.
+///
+/// # Input
+///
+/// Adapters that read input override `accepts_input`. For those tasks, input
+/// sent to the task (`POST /mcp/tasks/:id/input`) arrives on the receiver
+/// returned by `context.take_input()`; other tasks get no input channel. The
+/// worker records every input it receives as an `input` event, whether or not
+/// it could be forwarded to the adapter.
+///
 /// # Example
 ///
 /// ```no_run
@@ -65,7 +80,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::fmt;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -132,6 +147,18 @@ pub enum AdapterEventKind {
     /// Timeout exceeded
     Timeout,
 
+    /// Task paused
+    Paused,
+
+    /// Task resumed after a pause
+    Resumed,
+
+    /// Input delivered to the task
+    Input,
+
+    /// Task minute quota exhausted; the task will be canceled after a grace period
+    QuotaWarning,
+
     /// Custom adapter-specific event
     Custom,
 }
@@ -147,6 +174,10 @@ impl fmt::Display for AdapterEventKind {
             AdapterEventKind::Failed => write!(f, "failed"),
             AdapterEventKind::Cancelled => write!(f, "cancelled"),
             AdapterEventKind::Timeout => write!(f, "timeout"),
+            AdapterEventKind::Paused => write!(f, "paused"),
+            AdapterEventKind::Resumed => write!(f, "resumed"),
+            AdapterEventKind::Input => write!(f, "input"),
+            AdapterEventKind::QuotaWarning => write!(f, "quota_warning"),
             AdapterEventKind::Custom => write!(f, "custom"),
         }
     }
@@ -218,6 +249,52 @@ impl AdapterEvent {
     pub fn timeout() -> Self {
         AdapterEvent::new(AdapterEventKind::Timeout, serde_json::json!({}))
     }
+
+    /// Creates a paused event
+    pub fn paused() -> Self {
+        AdapterEvent::new(AdapterEventKind::Paused, serde_json::json!({}))
+    }
+
+    /// Creates a resumed event
+    pub fn resumed(paused_ms: u64) -> Self {
+        AdapterEvent::new(
+            AdapterEventKind::Resumed,
+            serde_json::json!({ "paused_ms": paused_ms }),
+        )
+    }
+
+    /// Creates an input event
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Input received by the worker
+    /// * `forwarded` - Whether the input was handed to the adapter
+    pub fn input(data: String, forwarded: bool) -> Self {
+        AdapterEvent::new(
+            AdapterEventKind::Input,
+            serde_json::json!({ "bytes": data.len(), "data": data, "forwarded": forwarded }),
+        )
+    }
+
+    /// Creates a quota warning event
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - Task minutes used this month
+    /// * `limit` - Monthly task minute limit
+    /// * `grace_seconds` - Time left before the task is canceled
+    pub fn quota_warning(current: u32, limit: u32, grace_seconds: u64) -> Self {
+        AdapterEvent::new(
+            AdapterEventKind::QuotaWarning,
+            serde_json::json!({
+                "quota": "task_minutes",
+                "current": current,
+                "limit": limit,
+                "grace_seconds": grace_seconds,
+                "reason": "quota_exceeded",
+            }),
+        )
+    }
 }
 
 /// Adapter execution context
@@ -235,24 +312,56 @@ pub struct AdapterContext {
 
     /// Cancellation token
     pub cancel_token: CancellationToken,
+
+    /// Pause signal (true = paused)
+    pause_signal: watch::Receiver<bool>,
+
+    /// Input receiver (taken by adapters that accept input)
+    input_rx: Option<mpsc::UnboundedReceiver<String>>,
 }
 
 impl AdapterContext {
     /// Creates a new adapter context
+    ///
+    /// The context is never paused unless a pause signal is attached
+    /// with `with_pause_signal`.
     pub fn new(
         task_id: Uuid,
         args: JsonValue,
         event_tx: mpsc::UnboundedSender<AdapterEvent>,
         cancel_token: CancellationToken,
     ) -> Self {
+        let (_pause_tx, pause_signal) = watch::channel(false);
+
         AdapterContext {
             task_id,
             args,
             event_tx,
             cancel_token,
+            pause_signal,
+            input_rx: None,
         }
     }
 
+    /// Attaches a pause signal (see `PauseController::subscribe`)
+    pub fn with_pause_signal(mut self, pause_signal: watch::Receiver<bool>) -> Self {
+        self.pause_signal = pause_signal;
+        self
+    }
+
+    /// Attaches an input receiver
+    pub fn with_input(mut self, input_rx: mpsc::UnboundedReceiver<String>) -> Self {
+        self.input_rx = Some(input_rx);
+        self
+    }
+
+    /// Takes the input receiver
+    ///
+    /// Returns None if no input channel is attached or it was already taken.
+    pub fn take_input(&mut self) -> Option<mpsc::UnboundedReceiver<String>> {
+        self.input_rx.take()
+    }
+
     /// Emits an event
     ///
     /// # Errors
@@ -273,6 +382,29 @@ impl AdapterContext {
     pub async fn cancelled(&self) {
         self.cancel_token.cancelled().await
     }
+
+    /// Checks if the task is currently paused
+    pub fn is_paused(&self) -> bool {
+        *self.pause_signal.borrow()
+    }
+
+    /// Waits at a safe point while the task is paused
+    ///
+    /// Returns immediately if the task is not paused, otherwise waits until
+    /// the task is resumed or cancelled.
+    pub async fn pause_point(&self) {
+        let mut signal = self.pause_signal.clone();
+
+        tokio::select! {
+            _ = signal.wait_for(|paused| !*paused) => {}
+            _ = self.cancel_token.cancelled() => {}
+        }
+    }
+
+    /// Returns a receiver for observing pause state changes
+    pub fn pause_signal(&self) -> watch::Receiver<bool> {
+        self.pause_signal.clone()
+    }
 }
 
 /// Core Adapter trait
@@ -327,6 +459,14 @@ pub trait Adapter: Send + Sync {
         Ok(())
     }
 
+    /// Whether a task with these arguments reads input
+    ///
+    /// The worker only attaches an input channel (see
+    /// `AdapterContext::take_input`) to tasks that do. Default: false.
+    fn accepts_input(&self, _args: &JsonValue) -> bool {
+        false
+    }
+
     /// Returns adapter metadata
     ///
     /// Optional method to provide adapter-specific metadata (version, capabilities, etc.)
@@ -352,6 +492,10 @@ mod tests {
         assert_eq!(AdapterEventKind::Failed.to_string(), "failed");
         assert_eq!(AdapterEventKind::Cancelled.to_string(), "cancelled");
         assert_eq!(AdapterEventKind::Timeout.to_string(), "timeout");
+        assert_eq!(AdapterEventKind::Paused.to_string(), "paused");
+        assert_eq!(AdapterEventKind::Resumed.to_string(), "resumed");
+        assert_eq!(AdapterEventKind::Input.to_string(), "input");
+        assert_eq!(AdapterEventKind::QuotaWarning.to_string(), "quota_warning");
     }
 
     #[test]
@@ -382,6 +526,21 @@ mod tests {
 
         let timeout = AdapterEvent::timeout();
         assert_eq!(timeout.kind, AdapterEventKind::Timeout);
+
+        let resumed = AdapterEvent::resumed(1500);
+        assert_eq!(resumed.kind, AdapterEventKind::Resumed);
+        assert_eq!(resumed.payload["paused_ms"], 1500);
+
+        let input = AdapterEvent::input("yes\n".to_string(), true);
+        assert_eq!(input.kind, AdapterEventKind::Input);
+        assert_eq!(input.payload["bytes"], 4);
+        assert_eq!(input.payload["forwarded"], true);
+
+        let quota_warning = AdapterEvent::quota_warning(1_000, 1_000, 300);
+        assert_eq!(quota_warning.kind, AdapterEventKind::QuotaWarning);
+        assert_eq!(quota_warning.payload["quota"], "task_minutes");
+        assert_eq!(quota_warning.payload["grace_seconds"], 300);
+        assert_eq!(quota_warning.payload["reason"], "quota_exceeded");
     }
 
     #[test]
@@ -425,4 +584,57 @@ mod tests {
         assert_eq!(received.kind, AdapterEventKind::Stdout);
         assert_eq!(received.payload["data"], "test");
     }
+
+    #[tokio::test]
+    async fn test_adapter_context_pause_point() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (pause_tx, pause_rx) = watch::channel(false);
+        let context = AdapterContext::new(
+            Uuid::new_v4(),
+            serde_json::json!({}),
+            tx,
+            CancellationToken::new(),
+        )
+        .with_pause_signal(pause_rx);
+
+        // Not paused: returns immediately
+        context.pause_point().await;
+        assert!(!context.is_paused());
+
+        pause_tx.send_replace(true);
+        assert!(context.is_paused());
+
+        let waiter = tokio::spawn(async move { context.pause_point().await });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        pause_tx.send_replace(false);
+        waiter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_adapter_context_pause_point_cancelled() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (_pause_tx, pause_rx) = watch::channel(true);
+        let cancel_token = CancellationToken::new();
+        let context = AdapterContext::new(Uuid::new_v4(), serde_json::json!({}), tx, cancel_token.clone())
+            .with_pause_signal(pause_rx);
+
+        cancel_token.cancel();
+        context.pause_point().await;
+    }
+
+    #[tokio::test]
+    async fn test_adapter_context_take_input() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (input_tx, input_rx) = mpsc::unbounded_channel();
+        let mut context = AdapterContext::new(Uuid::new_v4(), serde_json::json!({}), tx, CancellationToken::new())
+            .with_input(input_rx);
+
+        let mut input = context.take_input().unwrap();
+        assert!(context.take_input().is_none());
+
+        input_tx.send("yes".to_string()).unwrap();
+        assert_eq!(input.recv().await.unwrap(), "yes");
+    }
 }
@@ -19,6 +19,11 @@
 /// 8. **progress (100%)**: "Done"
 /// 9. **completed**: Task finished
 ///
+/// # Pausing
+///
+/// Each checkpoint is a safe point: a paused task stops before emitting the
+/// next progress event and continues from there once resumed.
+///
 /// # Configuration
 ///
 /// Arguments (JSON):
@@ -177,6 +182,9 @@ impl Adapter for MockAdapter {
         ];
 
         for (i, (percent, progress_msg, stdout_msg)) in checkpoints.iter().enumerate() {
+            // Checkpoints are safe points: wait here while paused
+            context.pause_point().await;
+
             // Check cancellation
             if context.is_cancelled() {
                 tracing::info!(task_id = %context.task_id, "Mock adapter cancelled");
@@ -238,7 +246,7 @@ impl Adapter for MockAdapter {
             "name": "mock",
             "version": "1.0.0",
             "description": "Deterministic mock adapter for testing",
-            "capabilities": ["deterministic", "configurable_duration", "simulated_failure"]
+            "capabilities": ["deterministic", "configurable_duration", "simulated_failure", "pause"]
         })
     }
 }
@@ -429,6 +437,40 @@ mod tests {
         assert!(has_cancelled);
     }
 
+    #[tokio::test]
+    async fn test_execute_pause_resume() {
+        let adapter = MockAdapter::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (pause_tx, pause_rx) = tokio::sync::watch::channel(true);
+
+        let args = serde_json::json!({
+            "duration_ms": 100
+        });
+
+        let context = AdapterContext::new(Uuid::new_v4(), args, tx, CancellationToken::new())
+            .with_pause_signal(pause_rx);
+
+        let handle = tokio::spawn(async move {
+            adapter.execute(context).await
+        });
+
+        // Paused before the first checkpoint: only the started event is emitted
+        sleep(Duration::from_millis(50)).await;
+        let started = rx.recv().await.unwrap();
+        assert_eq!(started.kind, crate::adapters::AdapterEventKind::Started);
+        assert!(rx.try_recv().is_err());
+        assert!(!handle.is_finished());
+
+        pause_tx.send_replace(false);
+        assert!(handle.await.unwrap().is_ok());
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        assert_eq!(events.last().unwrap().kind, crate::adapters::AdapterEventKind::Completed);
+    }
+
     #[test]
     fn test_adapter_metadata() {
         let adapter = MockAdapter::new();
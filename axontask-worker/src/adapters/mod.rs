@@ -14,7 +14,8 @@
 /// # Adapter Types
 ///
 /// - **Mock**: Deterministic fake events for testing/demo
-/// - **Shell**: Execute shell commands (sandboxed)
+/// - **Shell**: Execute shell commands on the worker host (not sandboxed;
+///   only registered when the operator enables it)
 /// - **Docker**: Build and run Docker containers
 /// - **Fly**: Deploy to Fly.io
 ///
@@ -44,10 +45,11 @@ pub mod adapter_trait;
 // pub mod fly;
 pub mod mock;
 // pub mod registry;
-// pub mod shell;
+pub mod shell;
 
 // Re-export main types
 pub use adapter_trait::{
     Adapter, AdapterContext, AdapterError, AdapterEvent, AdapterEventKind, AdapterResult,
 };
 pub use mock::MockAdapter;
+pub use shell::{ShellAdapter, ShellAdapterConfig};
@@ -0,0 +1,731 @@
+/// Shell adapter
+///
+/// This adapter runs a shell command (`sh -c`) and streams its output as
+/// events. Each line written to stdout or stderr becomes a `stdout` or
+/// `stderr` event.
+///
+/// # Security
+///
+/// **This adapter is not sandboxed.** Commands run directly on the worker
+/// host, with the worker's privileges unless a `uid`/`gid` is configured, so
+/// anyone who can start tasks can run arbitrary code there. The worker only
+/// registers it when the operator enables it (`WORKER_SHELL_ENABLED=true`).
+///
+/// What it does confine:
+/// - The worker's environment is not inherited. Commands only see `PATH`,
+///   `HOME` and the task variables named in the allowlist.
+/// - Each task runs in its own directory below the configured working
+///   directory, which is removed when the task ends.
+/// - Commands can run as a dedicated user and group.
+///
+/// # Configuration
+///
+/// Arguments (JSON):
+/// ```json
+/// {
+///   "command": "ls -la",               // Command line (required)
+///   "env": {"LANG": "C.UTF-8"},        // Allowlisted environment variables (optional)
+///   "interactive": false               // Accept input on stdin (default: false)
+/// }
+/// ```
+///
+/// # Input
+///
+/// When `interactive` is true, stdin is a pipe and input sent to the task is
+/// written to it as-is (include a trailing newline to answer a prompt).
+/// Otherwise stdin is `/dev/null` and the task accepts no input.
+///
+/// # Pausing
+///
+/// The command runs in its own process group. Pausing sends `SIGSTOP` to the
+/// group and resuming sends `SIGCONT`, so the whole process tree is suspended,
+/// not just the shell.
+///
+/// # Cancellation
+///
+/// Cancelling kills the process group with `SIGKILL`.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_worker::adapters::{Adapter, AdapterContext, ShellAdapter, ShellAdapterConfig};
+/// use tokio::sync::mpsc;
+/// use tokio_util::sync::CancellationToken;
+/// use uuid::Uuid;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let adapter = ShellAdapter::new(ShellAdapterConfig::default());
+///
+/// let (tx, mut rx) = mpsc::unbounded_channel();
+/// let args = serde_json::json!({"command": "echo hello"});
+///
+/// let context = AdapterContext::new(Uuid::new_v4(), args, tx, CancellationToken::new());
+/// adapter.execute(context).await?;
+///
+/// while let Some(event) = rx.recv().await {
+///     println!("Event: {:?}", event.kind);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+
+use crate::adapters::{Adapter, AdapterContext, AdapterError, AdapterEvent, AdapterResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+
+/// `PATH` commands run with unless configured otherwise
+pub const DEFAULT_SHELL_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+
+/// Operator settings for the shell adapter
+///
+/// Loaded with [`ShellAdapterConfig::from_env`], which returns `None` unless
+/// the operator enabled the adapter.
+#[derive(Debug, Clone)]
+pub struct ShellAdapterConfig {
+    /// Directory that holds the per-task working directories
+    pub working_dir: PathBuf,
+
+    /// Environment variables tasks may set
+    pub env_allowlist: Vec<String>,
+
+    /// `PATH` commands run with
+    pub path: String,
+
+    /// User ID commands run as (default: the worker's)
+    pub uid: Option<u32>,
+
+    /// Group ID commands run as (default: the worker's)
+    pub gid: Option<u32>,
+}
+
+impl Default for ShellAdapterConfig {
+    fn default() -> Self {
+        ShellAdapterConfig {
+            working_dir: env::temp_dir().join("axontask-shell"),
+            env_allowlist: Vec::new(),
+            path: DEFAULT_SHELL_PATH.to_string(),
+            uid: None,
+            gid: None,
+        }
+    }
+}
+
+impl ShellAdapterConfig {
+    /// Loads the shell adapter settings from environment variables
+    ///
+    /// - `WORKER_SHELL_ENABLED`: Register the shell adapter (default: false)
+    /// - `WORKER_SHELL_WORKDIR`: Directory for per-task working directories
+    ///   (default: `$TMPDIR/axontask-shell`)
+    /// - `WORKER_SHELL_ENV_ALLOWLIST`: Comma-separated environment variables
+    ///   tasks may set (default: none)
+    /// - `WORKER_SHELL_PATH`: `PATH` for commands
+    ///   (default: /usr/local/bin:/usr/bin:/bin)
+    /// - `WORKER_SHELL_UID` / `WORKER_SHELL_GID`: User and group commands run
+    ///   as (default: the worker's)
+    ///
+    /// Returns `None` when the adapter is disabled.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let enabled = match env::var("WORKER_SHELL_ENABLED") {
+            Ok(value) => value
+                .parse::<bool>()
+                .map_err(|_| anyhow::anyhow!("WORKER_SHELL_ENABLED must be true or false"))?,
+            Err(_) => false,
+        };
+
+        if !enabled {
+            return Ok(None);
+        }
+
+        let defaults = ShellAdapterConfig::default();
+
+        let working_dir = env::var("WORKER_SHELL_WORKDIR")
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or(defaults.working_dir);
+
+        if !working_dir.is_absolute() {
+            anyhow::bail!("WORKER_SHELL_WORKDIR must be an absolute path");
+        }
+
+        let env_allowlist: Vec<String> = env::var("WORKER_SHELL_ENV_ALLOWLIST")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        if let Some(name) = env_allowlist.iter().find(|name| !valid_env_name(name)) {
+            anyhow::bail!("Invalid variable name in WORKER_SHELL_ENV_ALLOWLIST: {:?}", name);
+        }
+
+        let path = env::var("WORKER_SHELL_PATH")
+            .ok()
+            .filter(|path| !path.is_empty())
+            .unwrap_or(defaults.path);
+
+        Ok(Some(ShellAdapterConfig {
+            working_dir,
+            env_allowlist,
+            path,
+            uid: id_var("WORKER_SHELL_UID")?,
+            gid: id_var("WORKER_SHELL_GID")?,
+        }))
+    }
+}
+
+/// Parses an optional numeric user or group ID variable
+fn id_var(name: &str) -> anyhow::Result<Option<u32>> {
+    match env::var(name) {
+        Ok(value) if !value.is_empty() => value
+            .parse()
+            .map(Some)
+            .map_err(|_| anyhow::anyhow!("{} must be a numeric ID", name)),
+        _ => Ok(None),
+    }
+}
+
+/// Whether a string is a usable environment variable name
+fn valid_env_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains('=') && !name.contains('\0')
+}
+
+/// Shell task arguments
+#[derive(Debug, Clone, Deserialize)]
+struct ShellConfig {
+    /// Command line passed to `sh -c`
+    command: String,
+
+    /// Allowlisted environment variables
+    #[serde(default)]
+    env: HashMap<String, String>,
+
+    /// Whether stdin accepts input sent to the task
+    #[serde(default)]
+    interactive: bool,
+}
+
+/// Sends a signal to a process group
+///
+/// Returns true if the signal was delivered.
+fn signal_group(pgid: u32, signal: libc::c_int) -> bool {
+    // SAFETY: kill(2) has no memory-safety preconditions; a negative pid
+    // addresses the process group created for the child.
+    unsafe { libc::kill(-(pgid as libc::pid_t), signal) == 0 }
+}
+
+/// Receives the next input, if an input channel is attached
+async fn recv_input(input_rx: &mut Option<tokio::sync::mpsc::UnboundedReceiver<String>>) -> Option<String> {
+    match input_rx {
+        Some(rx) => rx.recv().await,
+        None => None,
+    }
+}
+
+/// Shell adapter implementation
+pub struct ShellAdapter {
+    /// Operator settings
+    config: ShellAdapterConfig,
+}
+
+impl ShellAdapter {
+    /// Creates a new shell adapter
+    pub fn new(config: ShellAdapterConfig) -> Self {
+        ShellAdapter { config }
+    }
+
+    /// Creates the task's working directory, owned by the configured user
+    async fn create_task_dir(&self, dir: &Path) -> AdapterResult<()> {
+        tokio::fs::create_dir_all(dir).await.map_err(|e| {
+            AdapterError::Internal(format!("Failed to create working directory: {}", e))
+        })?;
+
+        if self.config.uid.is_some() || self.config.gid.is_some() {
+            std::os::unix::fs::chown(dir, self.config.uid, self.config.gid).map_err(|e| {
+                AdapterError::Internal(format!("Failed to chown working directory: {}", e))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Adapter for ShellAdapter {
+    fn name(&self) -> &str {
+        "shell"
+    }
+
+    fn validate_args(&self, args: &serde_json::Value) -> AdapterResult<()> {
+        let config: ShellConfig = serde_json::from_value(args.clone())
+            .map_err(|e| AdapterError::InvalidArguments(format!("Invalid shell config: {}", e)))?;
+
+        if config.command.trim().is_empty() {
+            return Err(AdapterError::InvalidArguments(
+                "command must not be empty".to_string(),
+            ));
+        }
+
+        if let Some(key) = config.env.keys().find(|k| !valid_env_name(k)) {
+            return Err(AdapterError::InvalidArguments(format!(
+                "Invalid environment variable name: {:?}",
+                key
+            )));
+        }
+
+        if let Some(key) = config
+            .env
+            .keys()
+            .find(|k| !self.config.env_allowlist.contains(k))
+        {
+            return Err(AdapterError::InvalidArguments(format!(
+                "Environment variable not allowed: {:?}",
+                key
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn accepts_input(&self, args: &serde_json::Value) -> bool {
+        serde_json::from_value::<ShellConfig>(args.clone())
+            .map(|config| config.interactive)
+            .unwrap_or(false)
+    }
+
+    async fn execute(&self, context: AdapterContext) -> AdapterResult<()> {
+        tracing::info!(task_id = %context.task_id, "Shell adapter starting");
+
+        let config: ShellConfig = serde_json::from_value(context.args.clone())
+            .map_err(|e| AdapterError::InvalidArguments(format!("Invalid shell config: {}", e)))?;
+
+        let dir = self.config.working_dir.join(context.task_id.to_string());
+        self.create_task_dir(&dir).await?;
+
+        let result = self.run(context, config, &dir).await;
+
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+            tracing::warn!(dir = %dir.display(), error = %e, "Failed to remove working directory");
+        }
+
+        result
+    }
+
+    fn metadata(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": "shell",
+            "version": "1.0.0",
+            "description": "Runs shell commands on the worker host (not sandboxed) and streams their output",
+            "capabilities": ["stdout", "stderr", "exit_code", "pause", "input"]
+        })
+    }
+}
+
+impl ShellAdapter {
+    /// Runs the command in `dir` and streams its output
+    async fn run(
+        &self,
+        mut context: AdapterContext,
+        config: ShellConfig,
+        dir: &Path,
+    ) -> AdapterResult<()> {
+        let started = Instant::now();
+
+        // Input is only accepted by interactive commands
+        let mut input_rx = if config.interactive {
+            context.take_input()
+        } else {
+            None
+        };
+
+        let stdin = if input_rx.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        };
+
+        let mut command = Command::new("/bin/sh");
+        command
+            .arg("-c")
+            .arg(&config.command)
+            .env_clear()
+            .env("PATH", &self.config.path)
+            .env("HOME", dir)
+            .envs(&config.env)
+            .current_dir(dir)
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .kill_on_drop(true);
+
+        if let Some(gid) = self.config.gid {
+            command.gid(gid);
+        }
+        if let Some(uid) = self.config.uid {
+            command.uid(uid);
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| AdapterError::ExecutionFailed(format!("Failed to spawn shell: {}", e)))?;
+
+        let pid = child
+            .id()
+            .ok_or_else(|| AdapterError::Internal("Child process has no PID".to_string()))?;
+
+        context
+            .emit(AdapterEvent::started(serde_json::json!({
+                "adapter": "shell",
+                "command": config.command,
+                "pid": pid,
+            })))
+            .await?;
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| AdapterError::Internal("stdout not captured".to_string()))?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| AdapterError::Internal("stderr not captured".to_string()))?;
+
+        let mut child_stdin = child.stdin.take();
+
+        let mut stdout_lines = BufReader::new(stdout).lines();
+        let mut stderr_lines = BufReader::new(stderr).lines();
+        let mut stdout_open = true;
+        let mut stderr_open = true;
+
+        let mut pause_signal = context.pause_signal();
+        let mut pause_open = true;
+
+        // Stream output until both pipes close
+        while stdout_open || stderr_open {
+            tokio::select! {
+                line = stdout_lines.next_line(), if stdout_open => match line {
+                    Ok(Some(line)) => context.emit(AdapterEvent::stdout(line)).await?,
+                    _ => stdout_open = false,
+                },
+                line = stderr_lines.next_line(), if stderr_open => match line {
+                    Ok(Some(line)) => context.emit(AdapterEvent::stderr(line)).await?,
+                    _ => stderr_open = false,
+                },
+                input = recv_input(&mut input_rx), if input_rx.is_some() => match input {
+                    Some(data) => {
+                        let written = match child_stdin.as_mut() {
+                            Some(stdin) => match stdin.write_all(data.as_bytes()).await {
+                                Ok(()) => stdin.flush().await.is_ok(),
+                                Err(_) => false,
+                            },
+                            None => false,
+                        };
+
+                        if !written {
+                            // Process closed its stdin; stop accepting input
+                            tracing::warn!(task_id = %context.task_id, "Shell stdin closed, dropping input");
+                            child_stdin = None;
+                            input_rx = None;
+                        }
+                    }
+                    None => {
+                        // Input channel closed: signal EOF
+                        child_stdin = None;
+                        input_rx = None;
+                    }
+                },
+                changed = pause_signal.changed(), if pause_open => {
+                    if changed.is_err() {
+                        pause_open = false;
+                        continue;
+                    }
+
+                    let paused = *pause_signal.borrow_and_update();
+                    let signal = if paused { libc::SIGSTOP } else { libc::SIGCONT };
+                    let delivered = signal_group(pid, signal);
+
+                    tracing::info!(
+                        task_id = %context.task_id,
+                        pid = pid,
+                        paused = paused,
+                        delivered = delivered,
+                        "Shell process pause state changed"
+                    );
+                },
+                _ = context.cancelled() => {
+                    tracing::info!(task_id = %context.task_id, "Shell adapter cancelled");
+                    signal_group(pid, libc::SIGKILL);
+                    let _ = child.kill().await;
+                    context.emit(AdapterEvent::cancelled()).await?;
+                    return Ok(());
+                }
+            }
+        }
+
+        let status = tokio::select! {
+            status = child.wait() => status
+                .map_err(|e| AdapterError::Internal(format!("Failed to wait for shell: {}", e)))?,
+            _ = context.cancelled() => {
+                signal_group(pid, libc::SIGKILL);
+                let _ = child.kill().await;
+                context.emit(AdapterEvent::cancelled()).await?;
+                return Ok(());
+            }
+        };
+
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        match status.code() {
+            Some(0) => {
+                context
+                    .emit(AdapterEvent::completed(serde_json::json!({
+                        "exit_code": 0,
+                        "duration_ms": duration_ms,
+                    })))
+                    .await?;
+
+                tracing::info!(task_id = %context.task_id, "Shell adapter completed");
+                Ok(())
+            }
+            Some(code) => {
+                let error = format!("Command exited with status {}", code);
+                context.emit(AdapterEvent::failed(error.clone())).await?;
+                Err(AdapterError::ExecutionFailed(error))
+            }
+            None => {
+                let error = "Command terminated by signal".to_string();
+                context.emit(AdapterEvent::failed(error.clone())).await?;
+                Err(AdapterError::ExecutionFailed(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::AdapterEventKind;
+    use crate::pause::PauseController;
+    use tokio::sync::mpsc;
+    use tokio::time::{sleep, Duration};
+    use tokio_util::sync::CancellationToken;
+    use uuid::Uuid;
+
+    fn adapter() -> ShellAdapter {
+        ShellAdapter::new(ShellAdapterConfig {
+            env_allowlist: vec!["GREETING".to_string()],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_adapter_name() {
+        assert_eq!(adapter().name(), "shell");
+    }
+
+    #[test]
+    fn test_validate_args() {
+        let adapter = adapter();
+
+        assert!(adapter
+            .validate_args(&serde_json::json!({"command": "echo hi"}))
+            .is_ok());
+        assert!(adapter
+            .validate_args(&serde_json::json!({"command": "  "}))
+            .is_err());
+        assert!(adapter.validate_args(&serde_json::json!({})).is_err());
+        assert!(adapter
+            .validate_args(&serde_json::json!({"command": "env", "env": {"A=B": "c"}}))
+            .is_err());
+        assert!(adapter
+            .validate_args(&serde_json::json!({"command": "env", "env": {"GREETING": "hi"}}))
+            .is_ok());
+        assert!(adapter
+            .validate_args(&serde_json::json!({"command": "env", "env": {"LD_PRELOAD": "x.so"}}))
+            .is_err());
+    }
+
+    #[test]
+    fn test_accepts_input() {
+        let adapter = adapter();
+
+        assert!(adapter.accepts_input(&serde_json::json!({"command": "cat", "interactive": true})));
+        assert!(!adapter.accepts_input(&serde_json::json!({"command": "cat"})));
+        assert!(!adapter.accepts_input(&serde_json::json!({})));
+    }
+
+    #[tokio::test]
+    async fn test_execute_confines_environment() {
+        std::env::set_var("AXONTASK_SHELL_TEST_SECRET", "leaked");
+
+        let adapter = adapter();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let task_id = Uuid::new_v4();
+        let task_dir = ShellAdapterConfig::default()
+            .working_dir
+            .join(task_id.to_string());
+
+        let args = serde_json::json!({
+            "command": "echo \"secret=$AXONTASK_SHELL_TEST_SECRET\"; pwd"
+        });
+        let context = AdapterContext::new(task_id, args, tx, CancellationToken::new());
+
+        assert!(adapter.execute(context).await.is_ok());
+
+        let mut stdout = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if event.kind == AdapterEventKind::Stdout {
+                stdout.push(event.payload["data"].as_str().unwrap().to_string());
+            }
+        }
+
+        assert_eq!(stdout[0], "secret=");
+        assert_eq!(stdout[1], task_dir.to_str().unwrap());
+
+        // The working directory is removed afterwards
+        assert!(!task_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_execute_streams_output() {
+        let adapter = adapter();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let args = serde_json::json!({
+            "command": "echo hello; echo oops >&2; echo $GREETING",
+            "env": {"GREETING": "hi"}
+        });
+        let context = AdapterContext::new(Uuid::new_v4(), args, tx, CancellationToken::new());
+
+        let handle = tokio::spawn(async move { adapter.execute(context).await });
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+
+        assert!(handle.await.unwrap().is_ok());
+        assert_eq!(events[0].kind, AdapterEventKind::Started);
+        assert_eq!(events.last().unwrap().kind, AdapterEventKind::Completed);
+
+        let stdout: Vec<_> = events
+            .iter()
+            .filter(|e| e.kind == AdapterEventKind::Stdout)
+            .map(|e| e.payload["data"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(stdout, vec!["hello", "hi"]);
+
+        assert!(events.iter().any(|e| e.kind == AdapterEventKind::Stderr));
+    }
+
+    #[tokio::test]
+    async fn test_execute_non_zero_exit() {
+        let adapter = adapter();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let args = serde_json::json!({"command": "exit 3"});
+        let context = AdapterContext::new(Uuid::new_v4(), args, tx, CancellationToken::new());
+
+        let result = adapter.execute(context).await;
+        assert!(result.unwrap_err().to_string().contains("status 3"));
+
+        let mut kinds = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            kinds.push(event.kind);
+        }
+        assert_eq!(kinds.last(), Some(&AdapterEventKind::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_execute_cancellation() {
+        let adapter = adapter();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let cancel_token = CancellationToken::new();
+
+        let args = serde_json::json!({"command": "sleep 10"});
+        let context = AdapterContext::new(Uuid::new_v4(), args, tx, cancel_token.clone());
+
+        let handle = tokio::spawn(async move { adapter.execute(context).await });
+
+        sleep(Duration::from_millis(100)).await;
+        cancel_token.cancel();
+
+        assert!(handle.await.unwrap().is_ok());
+
+        let mut kinds = Vec::new();
+        while let Some(event) = rx.recv().await {
+            kinds.push(event.kind);
+        }
+        assert_eq!(kinds.last(), Some(&AdapterEventKind::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_execute_interactive_input() {
+        let adapter = adapter();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (input_tx, input_rx) = mpsc::unbounded_channel();
+
+        let args = serde_json::json!({
+            "command": "read answer; echo \"got $answer\"",
+            "interactive": true
+        });
+        let context = AdapterContext::new(Uuid::new_v4(), args, tx, CancellationToken::new())
+            .with_input(input_rx);
+
+        let handle = tokio::spawn(async move { adapter.execute(context).await });
+
+        input_tx.send("yes\n".to_string()).unwrap();
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        assert!(handle.await.unwrap().is_ok());
+
+        let stdout = events
+            .iter()
+            .find(|e| e.kind == AdapterEventKind::Stdout)
+            .unwrap();
+        assert_eq!(stdout.payload["data"], "got yes");
+    }
+
+    #[tokio::test]
+    async fn test_execute_pause_resume() {
+        let adapter = adapter();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let pause = PauseController::new();
+
+        let args = serde_json::json!({"command": "sleep 0.2; echo done"});
+        let context = AdapterContext::new(Uuid::new_v4(), args, tx, CancellationToken::new())
+            .with_pause_signal(pause.subscribe());
+
+        let handle = tokio::spawn(async move { adapter.execute(context).await });
+
+        // Stop the process group right after it starts
+        let started = rx.recv().await.unwrap();
+        assert_eq!(started.kind, AdapterEventKind::Started);
+        pause.pause();
+
+        // Stopped: no output well past the command's own runtime
+        sleep(Duration::from_millis(600)).await;
+        assert!(rx.try_recv().is_err());
+
+        pause.resume();
+
+        let mut kinds = Vec::new();
+        while let Some(event) = rx.recv().await {
+            kinds.push(event.kind);
+        }
+        assert!(handle.await.unwrap().is_ok());
+        assert_eq!(kinds, vec![AdapterEventKind::Stdout, AdapterEventKind::Completed]);
+    }
+}
@@ -1,7 +1,8 @@
 /// Control stream listener
 ///
 /// This module implements listening for control messages from the API server.
-/// Control messages are used to cancel running tasks or send other control signals.
+/// Control messages are used to cancel, pause or resume running tasks, and
+/// to deliver input to them.
 ///
 /// # Control Message Format
 ///
@@ -18,12 +19,21 @@
 /// # Commands
 ///
 /// - **cancel**: Cancel the running task
+/// - **pause**: Suspend the task (adapters wait at their next safe point)
+/// - **resume**: Continue a paused task
+/// - **input**: Forward `data` to the adapter's input channel, if the task
+///   accepts input, and record it as an `input` event
+///
+/// The message types are shared with the API via
+/// `axontask_shared::redis::control`.
 ///
 /// # Example
 ///
 /// ```no_run
 /// use axontask_worker::control::ControlListener;
+/// use axontask_worker::pause::PauseController;
 /// use axontask_shared::redis::{RedisClient, RedisConfig};
+/// use tokio::sync::mpsc;
 /// use tokio_util::sync::CancellationToken;
 /// use uuid::Uuid;
 ///
@@ -34,9 +44,14 @@
 /// let listener = ControlListener::new(redis_client);
 /// let task_id = Uuid::new_v4();
 /// let cancel_token = CancellationToken::new();
+/// let pause = PauseController::new();
+/// let (input_tx, input_rx) = mpsc::unbounded_channel();
+/// let (event_tx, event_rx) = mpsc::unbounded_channel();
 ///
 /// // Start listening (spawns background task)
-/// let handle = listener.listen(task_id, cancel_token.clone()).await?;
+/// let handle = listener
+///     .listen(task_id, cancel_token.clone(), pause.clone(), Some(input_tx), event_tx)
+///     .await?;
 ///
 /// // When task completes, stop listening
 /// handle.abort();
@@ -44,44 +59,20 @@
 /// # }
 /// ```
 
+use crate::adapters::AdapterEvent;
+use crate::pause::PauseController;
+use axontask_shared::redis::control::control_channel;
 use axontask_shared::redis::RedisClient;
 use redis::AsyncCommands;
 use tokio_stream::StreamExt;
-use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-/// Control command types
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum ControlCommand {
-    /// Cancel task execution
-    Cancel,
-}
-
-/// Control message
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ControlMessage {
-    /// Command to execute
-    pub command: ControlCommand,
-
-    /// Optional reason/metadata
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub reason: Option<String>,
-}
-
-impl ControlMessage {
-    /// Creates a cancel message
-    pub fn cancel(reason: Option<String>) -> Self {
-        ControlMessage {
-            command: ControlCommand::Cancel,
-            reason,
-        }
-    }
-}
+pub use axontask_shared::redis::control::{ControlCommand, ControlMessage};
 
 /// Control listener error
 #[derive(Debug, thiserror::Error)]
@@ -116,13 +107,18 @@ impl ControlListener {
     /// Starts listening for control messages
     ///
     /// Spawns a background task that subscribes to the control channel
-    /// for the given task and triggers the cancel token when a cancel
-    /// command is received.
+    /// for the given task. Cancel commands trigger the cancel token; pause
+    /// and resume commands toggle the pause controller; input commands are
+    /// forwarded to `input_tx` and recorded as `input` events on `event_tx`.
     ///
     /// # Arguments
     ///
     /// * `task_id` - Task ID to listen for
-    /// * `cancel_token` - Token to cancel when control message received
+    /// * `cancel_token` - Token to cancel when a cancel command is received
+    /// * `pause` - Pause controller toggled by pause/resume commands
+    /// * `input_tx` - Sender for input data (see `AdapterContext::with_input`);
+    ///   None if the task accepts no input
+    /// * `event_tx` - Sender for task events
     ///
     /// # Returns
     ///
@@ -136,13 +132,18 @@ impl ControlListener {
     ///
     /// ```no_run
     /// # use axontask_worker::control::ControlListener;
+    /// # use axontask_worker::pause::PauseController;
     /// # use axontask_shared::redis::RedisClient;
+    /// # use tokio::sync::mpsc;
     /// # use tokio_util::sync::CancellationToken;
     /// # use uuid::Uuid;
     /// # async fn example(listener: ControlListener, task_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
     /// let cancel_token = CancellationToken::new();
+    /// let (event_tx, _event_rx) = mpsc::unbounded_channel();
     ///
-    /// let handle = listener.listen(task_id, cancel_token.clone()).await?;
+    /// let handle = listener
+    ///     .listen(task_id, cancel_token.clone(), PauseController::new(), None, event_tx)
+    ///     .await?;
     ///
     /// // Task runs...
     ///
@@ -155,12 +156,17 @@ impl ControlListener {
         &self,
         task_id: Uuid,
         cancel_token: CancellationToken,
+        pause: PauseController,
+        input_tx: Option<mpsc::UnboundedSender<String>>,
+        event_tx: mpsc::UnboundedSender<AdapterEvent>,
     ) -> Result<JoinHandle<()>, ControlError> {
         let redis = self.redis.clone();
         let channel = control_channel(task_id);
 
         let handle = tokio::spawn(async move {
-            if let Err(e) = listen_loop(redis, channel, task_id, cancel_token).await {
+            if let Err(e) =
+                listen_loop(redis, channel, task_id, cancel_token, pause, input_tx, event_tx).await
+            {
                 tracing::error!(
                     task_id = %task_id,
                     error = %e,
@@ -214,17 +220,15 @@ impl ControlListener {
     }
 }
 
-/// Control channel name for a task
-fn control_channel(task_id: Uuid) -> String {
-    format!("ctrl:{}", task_id)
-}
-
 /// Background listen loop
 async fn listen_loop(
     redis: RedisClient,
     channel: String,
     task_id: Uuid,
     cancel_token: CancellationToken,
+    pause: PauseController,
+    input_tx: Option<mpsc::UnboundedSender<String>>,
+    event_tx: mpsc::UnboundedSender<AdapterEvent>,
 ) -> Result<(), ControlError> {
     tracing::debug!(
         task_id = %task_id,
@@ -289,6 +293,32 @@ async fn listen_loop(
                                 cancel_token.cancel();
                                 break;
                             }
+                            ControlCommand::Pause => {
+                                let changed = pause.pause();
+                                tracing::info!(
+                                    task_id = %task_id,
+                                    reason = ?control_msg.reason,
+                                    changed,
+                                    "Received pause command"
+                                );
+                            }
+                            ControlCommand::Resume => {
+                                let changed = pause.resume();
+                                tracing::info!(
+                                    task_id = %task_id,
+                                    reason = ?control_msg.reason,
+                                    changed,
+                                    "Received resume command"
+                                );
+                            }
+                            ControlCommand::Input => {
+                                let Some(data) = control_msg.data else {
+                                    tracing::warn!(task_id = %task_id, "Input command without data");
+                                    continue;
+                                };
+
+                                handle_input(task_id, data, input_tx.as_ref(), &event_tx);
+                            }
                         }
                     }
                     None => {
@@ -308,6 +338,33 @@ async fn listen_loop(
     Ok(())
 }
 
+/// Forwards input to the adapter and records it as an `input` event
+///
+/// Returns whether the input was forwarded. It is not when the task accepts
+/// no input or the adapter stopped reading it.
+fn handle_input(
+    task_id: Uuid,
+    data: String,
+    input_tx: Option<&mpsc::UnboundedSender<String>>,
+    event_tx: &mpsc::UnboundedSender<AdapterEvent>,
+) -> bool {
+    let bytes = data.len();
+    let forwarded = input_tx.is_some_and(|tx| tx.send(data.clone()).is_ok());
+
+    tracing::info!(
+        task_id = %task_id,
+        bytes,
+        forwarded,
+        "Received input command"
+    );
+
+    if event_tx.send(AdapterEvent::input(data, forwarded)).is_err() {
+        tracing::warn!(task_id = %task_id, "Event channel closed, input not recorded");
+    }
+
+    forwarded
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,6 +389,49 @@ mod tests {
         assert_eq!(deserialized.reason, Some("User requested".to_string()));
     }
 
+    #[test]
+    fn test_control_message_pause_resume_parse() {
+        let pause: ControlMessage = serde_json::from_str(r#"{"command":"pause"}"#).unwrap();
+        assert_eq!(pause.command, ControlCommand::Pause);
+        assert_eq!(pause.reason, None);
+
+        let resume: ControlMessage =
+            serde_json::from_str(r#"{"command":"resume","reason":"approved"}"#).unwrap();
+        assert_eq!(resume.command, ControlCommand::Resume);
+        assert_eq!(resume.reason, Some("approved".to_string()));
+    }
+
+    #[test]
+    fn test_control_message_input_parse() {
+        let input: ControlMessage =
+            serde_json::from_str(r#"{"command":"input","data":"yes\n"}"#).unwrap();
+        assert_eq!(input.command, ControlCommand::Input);
+        assert_eq!(input.data, Some("yes\n".to_string()));
+    }
+
+    #[test]
+    fn test_handle_input_records_event() {
+        let task_id = Uuid::new_v4();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let (input_tx, mut input_rx) = mpsc::unbounded_channel();
+
+        assert!(handle_input(task_id, "yes\n".to_string(), Some(&input_tx), &event_tx));
+        assert_eq!(input_rx.try_recv().unwrap(), "yes\n");
+
+        let event = event_rx.try_recv().unwrap();
+        assert_eq!(event.payload["data"], "yes\n");
+        assert_eq!(event.payload["forwarded"], true);
+
+        // Adapter stopped reading input
+        drop(input_rx);
+        assert!(!handle_input(task_id, "no\n".to_string(), Some(&input_tx), &event_tx));
+        assert_eq!(event_rx.try_recv().unwrap().payload["forwarded"], false);
+
+        // Task accepts no input
+        assert!(!handle_input(task_id, "no\n".to_string(), None, &event_tx));
+        assert_eq!(event_rx.try_recv().unwrap().payload["forwarded"], false);
+    }
+
     #[test]
     fn test_control_channel() {
         let task_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
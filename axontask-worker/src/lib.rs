@@ -9,6 +9,9 @@
 //! - `orchestrator`: Worker orchestration and task dispatch
 //! - `queue`: Task queue reader
 //! - `events`: Event emission to Redis Streams
+//! - `pause`: Pause/resume state for running tasks
+//! - `quota`: Task minute quota enforcement during execution
+//! - `webhooks`: Webhook delivery dispatcher
 //!
 //! ## Example
 //!
@@ -26,6 +29,9 @@ pub mod control;
 pub mod events;
 // pub mod metrics;
 pub mod orchestrator;
+pub mod pause;
 pub mod queue;
+pub mod quota;
 // pub mod shutdown;
 pub mod timeout;
+pub mod webhooks;
@@ -12,12 +12,25 @@
 //! - Sends heartbeats every 30 seconds
 //! - Handles task cancellation and cleanup
 //!
+//! ## Configuration
+//!
+//! Read from environment variables (and `.env`):
+//! - `DATABASE_URL`: PostgreSQL connection URL (required)
+//! - `REDIS_URL`: Redis connection URL
+//! - `WORKER_QUOTA_CHECK_INTERVAL_SECS`, `WORKER_QUOTA_GRACE_SECS`,
+//!   `QUOTA_SOFT_THRESHOLDS`, `WORKER_SHELL_*`: see
+//!   `OrchestratorConfig::from_env`
+//!
 //! ## Usage
 //!
 //! ```bash
 //! cargo run -p axontask-worker
 //! ```
 
+use anyhow::Context;
+use axontask_shared::db::pool::{create_pool, DatabaseConfig};
+use axontask_shared::redis::{RedisClient, RedisConfig};
+use axontask_worker::orchestrator::{OrchestratorConfig, WorkerOrchestrator};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -36,20 +49,45 @@ async fn main() -> anyhow::Result<()> {
         env!("CARGO_PKG_VERSION")
     );
 
-    // TODO: Load configuration
-    // TODO: Initialize database pool
-    // TODO: Initialize Redis client
-    // TODO: Initialize adapter registry
-    // TODO: Start worker loop
-    // TODO: Start heartbeat system
-    // TODO: Start watchdog
+    // Load configuration
+    dotenvy::dotenv().ok();
+    let config = OrchestratorConfig::from_env()?;
+    tracing::info!(
+        quota_check_interval_secs = config.quota_check_interval_secs,
+        quota_grace_secs = config.quota_grace_secs,
+        shell_enabled = config.shell.is_some(),
+        "Configuration loaded successfully"
+    );
 
-    tracing::info!("Worker ready and listening for tasks");
-    tracing::info!("This is a Phase 0 placeholder. Worker functionality will be implemented in Phase 6.");
+    // Initialize database pool
+    let pool = create_pool(DatabaseConfig {
+        url: std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?,
+        ..Default::default()
+    })
+    .await?;
+    tracing::info!("Database connection pool initialized");
 
-    // Keep the worker running (placeholder)
-    tokio::signal::ctrl_c().await?;
-    tracing::info!("Shutdown signal received, exiting...");
+    // Initialize Redis client
+    let redis = RedisClient::new(RedisConfig::from_env()?).await?;
+    tracing::info!("Redis client initialized");
+
+    let orchestrator = WorkerOrchestrator::with_config(pool, redis, config);
+
+    // Stop taking tasks on Ctrl+C; running tasks are canceled
+    let shutdown = orchestrator.shutdown_token();
+    tokio::spawn(async move {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => {
+                tracing::info!("Shutdown signal received, stopping worker...");
+                shutdown.cancel();
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to listen for shutdown signal"),
+        }
+    });
+
+    tracing::info!("Worker ready and listening for tasks");
+    orchestrator.run().await?;
 
+    tracing::info!("Worker stopped");
     Ok(())
 }
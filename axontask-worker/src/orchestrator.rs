@@ -39,15 +39,23 @@
 /// # }
 /// ```
 
-use crate::adapters::{Adapter, AdapterContext, AdapterEvent, MockAdapter};
+use crate::adapters::{
+    Adapter, AdapterContext, AdapterEvent, MockAdapter, ShellAdapter, ShellAdapterConfig,
+};
 use crate::control::ControlListener;
 use crate::events::EventEmitter;
+use crate::pause::PauseController;
 use crate::queue::TaskQueue;
+use crate::quota::{TaskMinuteEnforcer, QUOTA_EXCEEDED_REASON};
 use crate::timeout::TimeoutEnforcer;
+use crate::webhooks::{self, WebhookDispatcher};
 use axontask_shared::models::task::Task;
+use axontask_shared::models::usage::UsageCounter;
+use axontask_shared::quota::{parse_thresholds, QuotaEnforcer, QuotaType, DEFAULT_SOFT_THRESHOLDS};
 use axontask_shared::redis::RedisClient;
 use sqlx::PgPool;
 use std::collections::HashMap;
+use std::env;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::time::{sleep, Duration};
@@ -65,6 +73,18 @@ pub struct OrchestratorConfig {
 
     /// Task claim batch size
     pub batch_size: usize,
+
+    /// Soft task minute thresholds (percent of the monthly limit)
+    pub quota_thresholds: Vec<u8>,
+
+    /// Interval between task minute quota checks while a task runs, in seconds
+    pub quota_check_interval_secs: u64,
+
+    /// Grace period between the quota warning and cancellation, in seconds
+    pub quota_grace_secs: u64,
+
+    /// Shell adapter settings; the adapter is only registered when set
+    pub shell: Option<ShellAdapterConfig>,
 }
 
 impl Default for OrchestratorConfig {
@@ -73,10 +93,79 @@ impl Default for OrchestratorConfig {
             poll_interval_secs: 1,
             max_concurrent_tasks: 10,
             batch_size: 5,
+            quota_thresholds: DEFAULT_SOFT_THRESHOLDS.to_vec(),
+            quota_check_interval_secs: 60,
+            quota_grace_secs: 300,
+            shell: None,
         }
     }
 }
 
+impl OrchestratorConfig {
+    /// Loads the configuration from environment variables
+    ///
+    /// - `WORKER_QUOTA_CHECK_INTERVAL_SECS`: Seconds between task minute quota
+    ///   checks while a task runs (default: 60, must be positive)
+    /// - `WORKER_QUOTA_GRACE_SECS`: Seconds between the quota warning and
+    ///   cancellation (default: 300, 0 cancels right away)
+    /// - `QUOTA_SOFT_THRESHOLDS`: Comma-separated quota warning thresholds in
+    ///   percent (default: 80,100; empty disables them)
+    /// - `WORKER_SHELL_*`: Shell adapter settings (see
+    ///   `ShellAdapterConfig::from_env`)
+    ///
+    /// Other settings keep their defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable is set to an invalid value
+    pub fn from_env() -> anyhow::Result<Self> {
+        let defaults = OrchestratorConfig::default();
+
+        let quota_thresholds = match env::var("QUOTA_SOFT_THRESHOLDS") {
+            Ok(value) => parse_thresholds(&value)
+                .map_err(|e| anyhow::anyhow!("QUOTA_SOFT_THRESHOLDS: {}", e))?,
+            Err(_) => defaults.quota_thresholds,
+        };
+
+        Ok(OrchestratorConfig {
+            quota_thresholds,
+            quota_check_interval_secs: parse_secs(
+                "WORKER_QUOTA_CHECK_INTERVAL_SECS",
+                env::var("WORKER_QUOTA_CHECK_INTERVAL_SECS").ok().as_deref(),
+                defaults.quota_check_interval_secs,
+                1,
+            )?,
+            quota_grace_secs: parse_secs(
+                "WORKER_QUOTA_GRACE_SECS",
+                env::var("WORKER_QUOTA_GRACE_SECS").ok().as_deref(),
+                defaults.quota_grace_secs,
+                0,
+            )?,
+            shell: ShellAdapterConfig::from_env()?,
+            ..defaults
+        })
+    }
+}
+
+/// Parses a duration in seconds from an optional variable value
+///
+/// Unset or empty values use `default`; values below `min` are rejected.
+fn parse_secs(name: &str, value: Option<&str>, default: u64, min: u64) -> anyhow::Result<u64> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(default);
+    };
+
+    let secs: i64 = value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{} must be a whole number of seconds", name))?;
+
+    if secs < min as i64 {
+        anyhow::bail!("{} must be at least {}", name, min);
+    }
+
+    Ok(secs as u64)
+}
+
 /// Worker orchestrator
 ///
 /// Coordinates task execution by polling the queue, dispatching to adapters,
@@ -113,9 +202,7 @@ impl WorkerOrchestrator {
         let emitter = Arc::new(EventEmitter::new(redis.clone()));
         let config = OrchestratorConfig::default();
 
-        // Initialize adapter registry
-        let mut adapters: HashMap<String, Arc<dyn Adapter>> = HashMap::new();
-        adapters.insert("mock".to_string(), Arc::new(MockAdapter::new()));
+        let adapters = default_adapters(&config);
 
         WorkerOrchestrator {
             queue,
@@ -138,9 +225,7 @@ impl WorkerOrchestrator {
         let queue = TaskQueue::with_batch_size(db, config.batch_size);
         let emitter = Arc::new(EventEmitter::new(redis.clone()));
 
-        // Initialize adapter registry
-        let mut adapters: HashMap<String, Arc<dyn Adapter>> = HashMap::new();
-        adapters.insert("mock".to_string(), Arc::new(MockAdapter::new()));
+        let adapters = default_adapters(&config);
 
         WorkerOrchestrator {
             queue,
@@ -192,6 +277,13 @@ impl WorkerOrchestrator {
     pub async fn run(&self) -> anyhow::Result<()> {
         tracing::info!("Worker orchestrator starting");
 
+        // Deliver webhooks alongside task execution
+        let dispatcher = WebhookDispatcher::new(self.queue.db.clone());
+        let dispatcher_handle = tokio::spawn({
+            let shutdown = self.shutdown_token.child_token();
+            async move { dispatcher.run(shutdown).await }
+        });
+
         // Track active tasks
         let mut active_tasks: HashMap<Uuid, CancellationToken> = HashMap::new();
 
@@ -220,6 +312,8 @@ impl WorkerOrchestrator {
                     tracing::warn!(count = active_tasks.len(), "Force shutdown with tasks still running");
                 }
 
+                let _ = dispatcher_handle.await;
+
                 tracing::info!("Worker orchestrator shut down");
                 break;
             }
@@ -283,6 +377,7 @@ impl WorkerOrchestrator {
                 {
                     tracing::error!(error = %e, "Failed to mark task as failed");
                 }
+                webhooks::notify_task(&self.queue.db, task.id).await;
                 return;
             }
         };
@@ -290,16 +385,43 @@ impl WorkerOrchestrator {
         let emitter = self.emitter.clone();
         let queue = self.queue.clone();
         let redis = self.redis.clone();
+        let quota_enforcer = TaskMinuteEnforcer::with_config(
+            self.queue.db.clone(),
+            Duration::from_secs(self.config.quota_check_interval_secs),
+            Duration::from_secs(self.config.quota_grace_secs),
+        )
+        .with_thresholds(&self.config.quota_thresholds);
 
         // Spawn task execution
         tokio::spawn(async move {
-            if let Err(e) = execute_task(task, adapter, emitter, queue, redis, cancel_token).await {
+            if let Err(e) =
+                execute_task(task, adapter, emitter, queue, redis, cancel_token, quota_enforcer).await
+            {
                 tracing::error!(error = %e, "Task execution failed");
             }
         });
     }
 }
 
+/// Builds the adapter registry
+///
+/// The shell adapter runs commands on the worker host without a sandbox, so
+/// it is only registered when the operator configured it.
+fn default_adapters(config: &OrchestratorConfig) -> HashMap<String, Arc<dyn Adapter>> {
+    let mut adapters: HashMap<String, Arc<dyn Adapter>> = HashMap::new();
+    adapters.insert("mock".to_string(), Arc::new(MockAdapter::new()));
+
+    if let Some(shell) = &config.shell {
+        tracing::warn!(
+            working_dir = %shell.working_dir.display(),
+            "Shell adapter enabled: task commands run on this host without a sandbox"
+        );
+        adapters.insert("shell".to_string(), Arc::new(ShellAdapter::new(shell.clone())));
+    }
+
+    adapters
+}
+
 // Clone impl for TaskQueue (needed for spawning tasks)
 impl Clone for TaskQueue {
     fn clone(&self) -> Self {
@@ -315,11 +437,13 @@ impl Clone for TaskQueue {
 /// This function runs in its own Tokio task and handles the full lifecycle:
 /// 1. Validate adapter arguments
 /// 2. Create event channel
-/// 3. Start timeout enforcer
-/// 4. Start control listener
-/// 5. Execute adapter
-/// 6. Emit events to Redis
-/// 7. Update task status
+/// 3. Start timeout enforcer (paused time does not count)
+/// 4. Start control listener (cancel, pause, resume, input)
+/// 5. Start task minute quota enforcer (warning, grace period, cancellation)
+/// 6. Execute adapter
+/// 7. Emit events to Redis
+/// 8. Update task status
+/// 9. Meter execution minutes, check task minute thresholds and queue webhooks
 async fn execute_task(
     task: Task,
     adapter: Arc<dyn Adapter>,
@@ -327,6 +451,7 @@ async fn execute_task(
     queue: TaskQueue,
     redis: RedisClient,
     cancel_token: CancellationToken,
+    quota_enforcer: TaskMinuteEnforcer,
 ) -> anyhow::Result<()> {
     let task_id = task.id;
     let adapter_name = adapter.name();
@@ -337,28 +462,85 @@ async fn execute_task(
         "Executing task"
     );
 
+    // Claimed tasks are already running
+    webhooks::notify(&queue.db, &task).await;
+
     // Validate adapter arguments
     if let Err(e) = adapter.validate_args(&task.args) {
         tracing::error!(task_id = %task_id, error = %e, "Invalid adapter arguments");
         queue
             .mark_failed(task_id, format!("Invalid arguments: {}", e))
             .await?;
+        webhooks::notify_task(&queue.db, task_id).await;
         return Ok(());
     }
 
+    // Pause state shared by the control listener, timeout enforcer and adapter
+    let pause = PauseController::new();
+
     // Start timeout enforcer
     let timeout_enforcer = TimeoutEnforcer::from_task_timeout(Some(task.timeout_seconds));
-    let timeout_handle = timeout_enforcer.enforce(task_id, cancel_token.clone());
-
-    // Start control listener for cancellation
-    let control_listener = ControlListener::new(redis);
-    let control_handle = control_listener.listen(task_id, cancel_token.clone()).await?;
+    let timeout_handle =
+        timeout_enforcer.enforce_with_pause(task_id, cancel_token.clone(), pause.clone());
 
     // Create event channel
     let (event_tx, mut event_rx) = mpsc::unbounded_channel();
 
+    // Only tasks that read input get an input channel
+    let (input_tx, input_rx) = if adapter.accepts_input(&task.args) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Some(tx), Some(rx))
+    } else {
+        (None, None)
+    };
+
+    // Start control listener for cancellation, pause/resume and input
+    let control_listener = ControlListener::new(redis);
+    let control_handle = control_listener
+        .listen(task_id, cancel_token.clone(), pause.clone(), input_tx, event_tx.clone())
+        .await?;
+
+    // Emit paused/resumed events as the pause state changes
+    let pause_events_handle = {
+        let event_tx = event_tx.clone();
+        let pause = pause.clone();
+        let mut signal = pause.subscribe();
+
+        tokio::spawn(async move {
+            let mut paused_before = pause.paused_duration();
+            while signal.changed().await.is_ok() {
+                let paused = *signal.borrow_and_update();
+                let event = if paused {
+                    paused_before = pause.paused_duration();
+                    AdapterEvent::paused()
+                } else {
+                    let paused_for = pause.paused_duration().saturating_sub(paused_before);
+                    AdapterEvent::resumed(paused_for.as_millis() as u64)
+                };
+
+                if event_tx.send(event).is_err() {
+                    break;
+                }
+            }
+        })
+    };
+
+    // Cancel the task if the tenant's task minute budget runs out
+    let quota_exceeded = CancellationToken::new();
+    let quota_handle = quota_enforcer.enforce(
+        task.tenant_id,
+        task_id,
+        cancel_token.clone(),
+        quota_exceeded.clone(),
+        event_tx.clone(),
+    );
+
     // Create adapter context
-    let context = AdapterContext::new(task_id, task.args.clone(), event_tx, cancel_token.clone());
+    let mut context = AdapterContext::new(task_id, task.args.clone(), event_tx, cancel_token.clone())
+        .with_pause_signal(pause.subscribe());
+    if let Some(input_rx) = input_rx {
+        context = context.with_input(input_rx);
+    }
 
     // Spawn adapter execution
     let adapter_handle = tokio::spawn(async move {
@@ -390,9 +572,11 @@ async fn execute_task(
     // Wait for adapter to complete
     let adapter_result = adapter_handle.await?;
 
-    // Stop timeout and control listeners
+    // Stop timeout, quota and control listeners
     timeout_handle.abort();
+    quota_handle.abort();
     control_handle.abort();
+    pause_events_handle.abort();
 
     // Wait for all events to be emitted
     drop(event_handle); // Close event channel
@@ -401,14 +585,24 @@ async fn execute_task(
     // Update task status based on result
     match adapter_result {
         Ok(()) => {
-            // Check if cancelled or timed out
-            if cancel_token.is_cancelled() {
+            // Check if cancelled, out of quota or timed out
+            if quota_exceeded.is_cancelled() {
+                tracing::warn!(task_id = %task_id, "Task cancelled: task minute quota exceeded");
+                queue
+                    .mark_canceled(
+                        task_id,
+                        format!("{}: Monthly task minute quota exhausted", QUOTA_EXCEEDED_REASON),
+                    )
+                    .await?;
+            } else if cancel_token.is_cancelled() {
                 // Check if task exceeded timeout by looking at task duration
                 if let Ok(Some(current_task)) = Task::find_by_id(&queue.db, task_id).await {
                     if let Some(started_at) = current_task.started_at {
+                        // Paused time does not count toward the timeout
                         let elapsed = chrono::Utc::now()
                             .signed_duration_since(started_at)
-                            .num_seconds();
+                            .num_seconds()
+                            - pause.paused_duration().as_secs() as i64;
 
                         // If elapsed time exceeds timeout, mark as timeout
                         if elapsed >= task.timeout_seconds as i64 {
@@ -439,9 +633,57 @@ async fn execute_task(
         }
     }
 
+    record_task_minutes(&queue.db, task_id, quota_enforcer.thresholds()).await;
+
+    if quota_exceeded.is_cancelled() {
+        // The worker cancelled this task, so nothing has queued `task.canceled` yet
+        match Task::find_by_id(&queue.db, task_id).await {
+            Ok(Some(task)) => webhooks::notify(&queue.db, &task).await,
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!(error = %e, task_id = %task_id, "Failed to load task for webhooks");
+            }
+        }
+    } else {
+        webhooks::notify_task(&queue.db, task_id).await;
+    }
+
     Ok(())
 }
 
+/// Meters a finished task's execution minutes
+///
+/// Then checks the tenant's soft task minute thresholds, which queues a
+/// `quota.threshold_reached` webhook the first time one is reached in a month.
+///
+/// Best effort: failures are logged and never affect task execution.
+/// Recording is idempotent, so a task finished twice is billed once.
+async fn record_task_minutes(db: &PgPool, task_id: Uuid, quota_thresholds: &[u8]) {
+    let task = match Task::find_by_id(db, task_id).await {
+        Ok(Some(task)) => task,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to load task for usage metering");
+            return;
+        }
+    };
+
+    match UsageCounter::record_task_minutes(db, &task).await {
+        Ok(true) => {
+            tracing::debug!(task_id = %task_id, "Recorded task minutes");
+
+            let enforcer = QuotaEnforcer::new(db.clone()).with_thresholds(quota_thresholds);
+            if let Err(e) = enforcer.check_thresholds(task.tenant_id, QuotaType::TaskMinutes).await {
+                tracing::warn!(error = %e, tenant_id = %task.tenant_id, "Failed to check task minute thresholds");
+            }
+        }
+        Ok(false) => {}
+        Err(e) => {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to record task minutes");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -452,6 +694,39 @@ mod tests {
         assert_eq!(config.poll_interval_secs, 1);
         assert_eq!(config.max_concurrent_tasks, 10);
         assert_eq!(config.batch_size, 5);
+        assert_eq!(config.quota_thresholds, vec![80, 100]);
+        assert_eq!(config.quota_check_interval_secs, 60);
+        assert_eq!(config.quota_grace_secs, 300);
+        assert!(config.shell.is_none());
+    }
+
+    #[test]
+    fn test_shell_adapter_requires_opt_in() {
+        let adapters = default_adapters(&OrchestratorConfig::default());
+        assert!(adapters.contains_key("mock"));
+        assert!(!adapters.contains_key("shell"));
+
+        let adapters = default_adapters(&OrchestratorConfig {
+            shell: Some(ShellAdapterConfig::default()),
+            ..Default::default()
+        });
+        assert!(adapters.contains_key("shell"));
+    }
+
+    #[test]
+    fn test_parse_secs() {
+        let name = "WORKER_QUOTA_CHECK_INTERVAL_SECS";
+        assert_eq!(parse_secs(name, None, 60, 1).unwrap(), 60);
+        assert_eq!(parse_secs(name, Some(""), 60, 1).unwrap(), 60);
+        assert_eq!(parse_secs(name, Some(" 15 "), 60, 1).unwrap(), 15);
+
+        // Interval must be positive, grace may be zero
+        assert!(parse_secs(name, Some("0"), 60, 1).is_err());
+        assert_eq!(parse_secs("WORKER_QUOTA_GRACE_SECS", Some("0"), 300, 0).unwrap(), 0);
+
+        let err = parse_secs("WORKER_QUOTA_GRACE_SECS", Some("-5"), 300, 0).unwrap_err();
+        assert!(err.to_string().contains("WORKER_QUOTA_GRACE_SECS"));
+        assert!(parse_secs(name, Some("1m"), 60, 1).is_err());
     }
 
     // Integration tests with actual database and Redis are in tests/orchestrator_tests.rs
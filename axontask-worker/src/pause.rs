@@ -0,0 +1,164 @@
+/// Pause state for running tasks
+///
+/// This module tracks whether a task is paused and how long it has spent
+/// paused. The control listener flips the state when it receives `pause` or
+/// `resume` commands; adapters observe it through their `AdapterContext`, and
+/// the timeout enforcer uses the accumulated paused time to extend deadlines.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_worker::pause::PauseController;
+///
+/// # async fn example() {
+/// let pause = PauseController::new();
+/// let mut signal = pause.subscribe();
+///
+/// pause.pause();
+/// assert!(*signal.borrow_and_update());
+///
+/// pause.resume();
+/// println!("Paused for {:?}", pause.paused_duration());
+/// # }
+/// ```
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Accumulated paused time
+#[derive(Debug, Default)]
+struct PauseTimes {
+    /// When the current pause began (None if not paused)
+    since: Option<Instant>,
+
+    /// Total duration of completed pauses
+    total: Duration,
+}
+
+/// Pause controller for a single task execution
+///
+/// Cheap to clone; all clones share the same state.
+#[derive(Debug, Clone)]
+pub struct PauseController {
+    /// Pause signal (true = paused)
+    tx: Arc<watch::Sender<bool>>,
+
+    /// Paused time accounting
+    times: Arc<Mutex<PauseTimes>>,
+}
+
+impl PauseController {
+    /// Creates a new controller in the running (not paused) state
+    pub fn new() -> Self {
+        let (tx, _rx) = watch::channel(false);
+
+        PauseController {
+            tx: Arc::new(tx),
+            times: Arc::new(Mutex::new(PauseTimes::default())),
+        }
+    }
+
+    /// Pauses the task
+    ///
+    /// Returns true if the state changed (task was running).
+    pub fn pause(&self) -> bool {
+        let mut times = self.times.lock().unwrap();
+        if times.since.is_some() {
+            return false;
+        }
+
+        times.since = Some(Instant::now());
+        self.tx.send_replace(true);
+        true
+    }
+
+    /// Resumes the task
+    ///
+    /// Returns true if the state changed (task was paused).
+    pub fn resume(&self) -> bool {
+        let mut times = self.times.lock().unwrap();
+        let Some(since) = times.since.take() else {
+            return false;
+        };
+
+        times.total += since.elapsed();
+        self.tx.send_replace(false);
+        true
+    }
+
+    /// Checks if the task is currently paused
+    pub fn is_paused(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Subscribes to pause state changes
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.tx.subscribe()
+    }
+
+    /// Total time spent paused, including an in-progress pause
+    pub fn paused_duration(&self) -> Duration {
+        let times = self.times.lock().unwrap();
+        times.total + times.since.map(|s| s.elapsed()).unwrap_or_default()
+    }
+}
+
+impl Default for PauseController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::sleep;
+
+    #[test]
+    fn test_pause_resume_state() {
+        let pause = PauseController::new();
+        assert!(!pause.is_paused());
+
+        assert!(pause.pause());
+        assert!(pause.is_paused());
+        assert!(!pause.pause()); // Already paused
+
+        assert!(pause.resume());
+        assert!(!pause.is_paused());
+        assert!(!pause.resume()); // Already running
+    }
+
+    #[test]
+    fn test_subscribe_sees_changes() {
+        let pause = PauseController::new();
+        let mut rx = pause.subscribe();
+        assert!(!*rx.borrow_and_update());
+
+        pause.pause();
+        assert!(rx.has_changed().unwrap());
+        assert!(*rx.borrow_and_update());
+    }
+
+    #[tokio::test]
+    async fn test_paused_duration_accumulates() {
+        let pause = PauseController::new();
+        assert_eq!(pause.paused_duration(), Duration::ZERO);
+
+        pause.pause();
+        sleep(Duration::from_millis(50)).await;
+        pause.resume();
+
+        let first = pause.paused_duration();
+        assert!(first >= Duration::from_millis(50));
+
+        // Not paused: duration does not grow
+        sleep(Duration::from_millis(20)).await;
+        assert_eq!(pause.paused_duration(), first);
+
+        // In-progress pause is included
+        pause.pause();
+        sleep(Duration::from_millis(20)).await;
+        assert!(pause.paused_duration() >= first + Duration::from_millis(20));
+    }
+}
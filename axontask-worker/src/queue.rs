@@ -144,9 +144,11 @@ impl TaskQueue {
                 tasks.id,
                 tasks.tenant_id,
                 tasks.created_by,
+                tasks.api_key_id,
                 tasks.name,
                 tasks.adapter,
                 tasks.args,
+                tasks.tags,
                 tasks.state,
                 tasks.started_at,
                 tasks.ended_at,
@@ -156,6 +158,8 @@ impl TaskQueue {
                 tasks.timeout_seconds,
                 tasks.error_message,
                 tasks.exit_code,
+                tasks.paused_at,
+                tasks.paused_seconds,
                 tasks.created_at,
                 tasks.updated_at
             "#,
@@ -227,6 +231,9 @@ impl TaskQueue {
 
     /// Marks a task as succeeded
     ///
+    /// Accepts running or paused tasks. Records billable minutes, excluding
+    /// time spent paused.
+    ///
     /// # Arguments
     ///
     /// * `task_id` - Task ID
@@ -243,14 +250,22 @@ impl TaskQueue {
                 state = $2::task_state,
                 ended_at = NOW(),
                 updated_at = NOW(),
-                exit_code = $3
-            WHERE id = $1 AND state = $4::task_state
+                exit_code = $3,
+                minutes_used = CEIL(GREATEST(
+                    EXTRACT(EPOCH FROM (NOW() - started_at)) - paused_seconds
+                        - COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0),
+                    0
+                ) / 60.0)::INTEGER,
+                paused_seconds = paused_seconds + COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0)::INTEGER,
+                paused_at = NULL
+            WHERE id = $1 AND state IN ($4::task_state, $5::task_state)
             "#,
         )
         .bind(task_id)
         .bind(TaskState::Succeeded.as_str())
         .bind(exit_code)
         .bind(TaskState::Running.as_str())
+        .bind(TaskState::Paused.as_str())
         .execute(&self.db)
         .await?;
 
@@ -264,6 +279,9 @@ impl TaskQueue {
 
     /// Marks a task as failed
     ///
+    /// Accepts running or paused tasks. Records billable minutes, excluding
+    /// time spent paused.
+    ///
     /// # Arguments
     ///
     /// * `task_id` - Task ID
@@ -280,14 +298,22 @@ impl TaskQueue {
                 state = $2::task_state,
                 ended_at = NOW(),
                 updated_at = NOW(),
-                error_message = $3
-            WHERE id = $1 AND state = $4::task_state
+                error_message = $3,
+                minutes_used = CEIL(GREATEST(
+                    EXTRACT(EPOCH FROM (NOW() - started_at)) - paused_seconds
+                        - COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0),
+                    0
+                ) / 60.0)::INTEGER,
+                paused_seconds = paused_seconds + COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0)::INTEGER,
+                paused_at = NULL
+            WHERE id = $1 AND state IN ($4::task_state, $5::task_state)
             "#,
         )
         .bind(task_id)
         .bind(TaskState::Failed.as_str())
         .bind(error)
         .bind(TaskState::Running.as_str())
+        .bind(TaskState::Paused.as_str())
         .execute(&self.db)
         .await?;
 
@@ -301,6 +327,9 @@ impl TaskQueue {
 
     /// Marks a task as timed out
     ///
+    /// Accepts running or paused tasks. Records billable minutes, excluding
+    /// time spent paused.
+    ///
     /// # Arguments
     ///
     /// * `task_id` - Task ID
@@ -316,13 +345,21 @@ impl TaskQueue {
                 state = $2::task_state,
                 ended_at = NOW(),
                 updated_at = NOW(),
-                error_message = 'Task timed out'
-            WHERE id = $1 AND state = $3::task_state
+                error_message = 'Task timed out',
+                minutes_used = CEIL(GREATEST(
+                    EXTRACT(EPOCH FROM (NOW() - started_at)) - paused_seconds
+                        - COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0),
+                    0
+                ) / 60.0)::INTEGER,
+                paused_seconds = paused_seconds + COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0)::INTEGER,
+                paused_at = NULL
+            WHERE id = $1 AND state IN ($3::task_state, $4::task_state)
             "#,
         )
         .bind(task_id)
         .bind(TaskState::Timeout.as_str())
         .bind(TaskState::Running.as_str())
+        .bind(TaskState::Paused.as_str())
         .execute(&self.db)
         .await?;
 
@@ -334,6 +371,55 @@ impl TaskQueue {
         Ok(())
     }
 
+    /// Marks a task as canceled by the worker
+    ///
+    /// Used when the worker itself stops a task (e.g. the tenant's task
+    /// minute quota is exhausted). Accepts running or paused tasks. Records
+    /// billable minutes, excluding time spent paused.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - Task ID
+    /// * `reason` - Cancellation reason, stored as the error message
+    ///
+    /// # Errors
+    ///
+    /// Returns error if task not found or database query fails
+    pub async fn mark_canceled(&self, task_id: Uuid, reason: String) -> Result<(), QueueError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE tasks
+            SET
+                state = $2::task_state,
+                ended_at = NOW(),
+                updated_at = NOW(),
+                error_message = $3,
+                minutes_used = CEIL(GREATEST(
+                    EXTRACT(EPOCH FROM (NOW() - started_at)) - paused_seconds
+                        - COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0),
+                    0
+                ) / 60.0)::INTEGER,
+                paused_seconds = paused_seconds + COALESCE(EXTRACT(EPOCH FROM (NOW() - paused_at)), 0)::INTEGER,
+                paused_at = NULL
+            WHERE id = $1 AND state IN ($4::task_state, $5::task_state)
+            "#,
+        )
+        .bind(task_id)
+        .bind(TaskState::Canceled.as_str())
+        .bind(reason)
+        .bind(TaskState::Running.as_str())
+        .bind(TaskState::Paused.as_str())
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(QueueError::TaskNotFound(task_id));
+        }
+
+        tracing::warn!(task_id = %task_id, "Task marked as canceled");
+        Ok(())
+    }
+
     /// Updates task last sequence number
     ///
     /// Called after each event emission to track progress.
@@ -0,0 +1,269 @@
+/// Task minute quota enforcement during execution
+///
+/// Quotas checked at task creation cannot stop a tenant from starting a few
+/// long-running tasks and blowing through their monthly minute budget. This
+/// module re-checks the tenant's `TaskMinutes` quota while a task runs, counting
+/// the elapsed minutes of in-flight tasks as well as recorded usage.
+///
+/// # Enforcement Behavior
+///
+/// 1. **Periodic check**: Usage is checked when the task starts and then every
+///    check interval (60 seconds by default)
+/// 2. **Warning**: Once the budget is exhausted, a `quota_warning` event is
+///    emitted to the task's stream
+/// 3. **Grace period**: The task keeps running for the grace period (5 minutes
+///    by default), e.g. so an admin can raise the tenant's limit
+/// 4. **Cancellation**: If usage is still at or over the limit, the task is
+///    cancelled with the `quota_exceeded` reason
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_worker::quota::TaskMinuteEnforcer;
+/// use sqlx::PgPool;
+/// use tokio::sync::mpsc;
+/// use tokio_util::sync::CancellationToken;
+/// use uuid::Uuid;
+///
+/// # async fn example(pool: PgPool, tenant_id: Uuid, task_id: Uuid) {
+/// let cancel_token = CancellationToken::new();
+/// let quota_exceeded = CancellationToken::new();
+/// let (event_tx, _event_rx) = mpsc::unbounded_channel();
+///
+/// let enforcer = TaskMinuteEnforcer::new(pool);
+/// let quota_handle = enforcer.enforce(
+///     tenant_id,
+///     task_id,
+///     cancel_token.clone(),
+///     quota_exceeded.clone(),
+///     event_tx,
+/// );
+///
+/// // Do work...
+///
+/// // Stop enforcement once the task completes
+/// quota_handle.abort();
+///
+/// if quota_exceeded.is_cancelled() {
+///     println!("Task was stopped by the task minute quota");
+/// }
+/// # }
+/// ```
+
+use crate::adapters::AdapterEvent;
+use axontask_shared::quota::{QuotaCheckResult, QuotaEnforcer, QuotaType, DEFAULT_SOFT_THRESHOLDS};
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Cancellation reason for tasks stopped by the task minute quota
+pub const QUOTA_EXCEEDED_REASON: &str = "quota_exceeded";
+
+/// Default interval between quota checks (60 seconds)
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default grace period between the warning and cancellation (5 minutes)
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(300);
+
+/// Task minute quota enforcer
+///
+/// Cancels a running task once its tenant's monthly task minute budget is
+/// exhausted, after a warning and a grace period.
+#[derive(Clone)]
+pub struct TaskMinuteEnforcer {
+    /// Database connection pool
+    db: PgPool,
+
+    /// Interval between quota checks
+    check_interval: Duration,
+
+    /// Time between the warning and cancellation
+    grace_period: Duration,
+
+    /// Soft thresholds (percent of the limit) checked alongside the quota
+    thresholds: Vec<u8>,
+}
+
+impl TaskMinuteEnforcer {
+    /// Creates a new enforcer with the default interval and grace period
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Database connection pool
+    pub fn new(db: PgPool) -> Self {
+        TaskMinuteEnforcer::with_config(db, DEFAULT_CHECK_INTERVAL, DEFAULT_GRACE_PERIOD)
+    }
+
+    /// Creates a new enforcer with a custom interval and grace period
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Database connection pool
+    /// * `check_interval` - Interval between quota checks
+    /// * `grace_period` - Time between the warning and cancellation
+    pub fn with_config(db: PgPool, check_interval: Duration, grace_period: Duration) -> Self {
+        TaskMinuteEnforcer {
+            db,
+            check_interval,
+            grace_period,
+            thresholds: DEFAULT_SOFT_THRESHOLDS.to_vec(),
+        }
+    }
+
+    /// Sets the soft thresholds checked while the task runs
+    ///
+    /// Reaching one queues a `quota.threshold_reached` webhook (once per month),
+    /// so tenants hear about long tasks before they finish.
+    pub fn with_thresholds(mut self, thresholds: &[u8]) -> Self {
+        self.thresholds = thresholds.to_vec();
+        self
+    }
+
+    /// Enforces the task minute quota on a task
+    ///
+    /// Spawns a background task that checks the tenant's usage periodically.
+    /// When the budget is exhausted it sends a `quota_warning` event, waits
+    /// the grace period and, if usage is still at or over the limit, cancels
+    /// `quota_exceeded` and then `cancel_token`.
+    ///
+    /// Check failures are logged and never cancel the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant_id` - Tenant that owns the task
+    /// * `task_id` - Task ID for logging
+    /// * `cancel_token` - Cancellation token to trigger when the quota is exceeded
+    /// * `quota_exceeded` - Token cancelled first, so the caller can tell a
+    ///   quota cancellation apart from a user cancellation or timeout
+    /// * `event_tx` - Task event channel for the warning event
+    ///
+    /// # Returns
+    ///
+    /// Join handle for the enforcement task (abort it when the task completes)
+    pub fn enforce(
+        &self,
+        tenant_id: Uuid,
+        task_id: Uuid,
+        cancel_token: CancellationToken,
+        quota_exceeded: CancellationToken,
+        event_tx: UnboundedSender<AdapterEvent>,
+    ) -> JoinHandle<()> {
+        let enforcer = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                // Wait until the budget is exhausted
+                let exceeded = loop {
+                    if let Some(result) = enforcer.check(tenant_id, task_id).await {
+                        break result;
+                    }
+
+                    tokio::select! {
+                        _ = sleep(enforcer.check_interval) => {}
+                        _ = cancel_token.cancelled() => return,
+                    }
+                };
+
+                tracing::warn!(
+                    task_id = %task_id,
+                    tenant_id = %tenant_id,
+                    current = exceeded.current,
+                    limit = exceeded.limit,
+                    grace_secs = enforcer.grace_period.as_secs(),
+                    "Task minute quota exhausted, task will be cancelled after grace period"
+                );
+
+                let _ = event_tx.send(AdapterEvent::quota_warning(
+                    exceeded.current,
+                    exceeded.limit,
+                    enforcer.grace_period.as_secs(),
+                ));
+
+                tokio::select! {
+                    _ = sleep(enforcer.grace_period) => {}
+                    _ = cancel_token.cancelled() => return,
+                }
+
+                // The limit may have been raised during the grace period
+                if let Some(result) = enforcer.check(tenant_id, task_id).await {
+                    tracing::warn!(
+                        task_id = %task_id,
+                        tenant_id = %tenant_id,
+                        current = result.current,
+                        limit = result.limit,
+                        "Task minute quota exceeded, cancelling task"
+                    );
+
+                    quota_exceeded.cancel();
+                    cancel_token.cancel();
+                    return;
+                }
+
+                tokio::select! {
+                    _ = sleep(enforcer.check_interval) => {}
+                    _ = cancel_token.cancelled() => return,
+                }
+            }
+        })
+    }
+
+    /// Checks the tenant's task minute quota
+    ///
+    /// # Returns
+    ///
+    /// The check result if the quota is exhausted, None if within quota or
+    /// the check failed
+    async fn check(&self, tenant_id: Uuid, task_id: Uuid) -> Option<QuotaCheckResult> {
+        let enforcer = QuotaEnforcer::new(self.db.clone()).with_thresholds(&self.thresholds);
+
+        let result = match enforcer.check(tenant_id, QuotaType::TaskMinutes).await {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::warn!(error = %e, task_id = %task_id, "Failed to check task minute quota");
+                return None;
+            }
+        };
+
+        // Soft thresholds reuse the count
+        if let Err(e) = enforcer
+            .record_thresholds(tenant_id, QuotaType::TaskMinutes, result.current, result.limit)
+            .await
+        {
+            tracing::warn!(error = %e, tenant_id = %tenant_id, "Failed to check task minute thresholds");
+        }
+
+        (!result.allowed).then_some(result)
+    }
+
+    /// Gets the interval between quota checks
+    pub fn check_interval(&self) -> Duration {
+        self.check_interval
+    }
+
+    /// Gets the grace period
+    pub fn grace_period(&self) -> Duration {
+        self.grace_period
+    }
+
+    /// Gets the soft thresholds
+    pub fn thresholds(&self) -> &[u8] {
+        &self.thresholds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(QUOTA_EXCEEDED_REASON, "quota_exceeded");
+        assert_eq!(DEFAULT_CHECK_INTERVAL, Duration::from_secs(60));
+        assert_eq!(DEFAULT_GRACE_PERIOD, Duration::from_secs(300));
+        assert!(DEFAULT_GRACE_PERIOD > DEFAULT_CHECK_INTERVAL);
+    }
+}
@@ -10,6 +10,9 @@
 /// 2. **Grace period**: 30 seconds for task to clean up
 /// 3. **Force kill**: Task is forcefully terminated after grace period
 ///
+/// Time spent paused does not count toward the timeout: the deadline is
+/// extended by the paused duration (see `enforce_with_pause`).
+///
 /// # Default Timeouts
 ///
 /// - No timeout specified: 1 hour (3600 seconds)
@@ -37,9 +40,10 @@
 /// # }
 /// ```
 
+use crate::pause::PauseController;
 use std::time::Duration;
 use tokio::task::JoinHandle;
-use tokio::time::sleep;
+use tokio::time::{sleep, Instant};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -155,12 +159,58 @@ impl TimeoutEnforcer {
     /// # }
     /// ```
     pub fn enforce(&self, task_id: Uuid, cancel_token: CancellationToken) -> JoinHandle<()> {
+        self.enforce_with_pause(task_id, cancel_token, PauseController::new())
+    }
+
+    /// Enforces timeout on a task that can be paused
+    ///
+    /// Like `enforce`, but only time spent running counts toward the timeout.
+    /// While the task is paused the clock is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - Task ID for logging
+    /// * `cancel_token` - Cancellation token to trigger on timeout
+    /// * `pause` - Pause controller for the task
+    ///
+    /// # Returns
+    ///
+    /// Join handle for the timeout task (can be aborted if task completes early)
+    pub fn enforce_with_pause(
+        &self,
+        task_id: Uuid,
+        cancel_token: CancellationToken,
+        pause: PauseController,
+    ) -> JoinHandle<()> {
         let timeout = self.timeout;
         let grace_period = self.grace_period;
 
         tokio::spawn(async move {
-            // Wait for timeout
-            sleep(timeout).await;
+            let started = Instant::now();
+            let mut signal = pause.subscribe();
+
+            // Wait until the task has been running (not paused) for `timeout`
+            loop {
+                let active = started.elapsed().saturating_sub(pause.paused_duration());
+                if active >= timeout {
+                    break;
+                }
+
+                signal.borrow_and_update();
+                if pause.is_paused() {
+                    // Clock is stopped until the task resumes
+                    tokio::select! {
+                        _ = signal.changed() => continue,
+                        _ = cancel_token.cancelled() => return,
+                    }
+                }
+
+                tokio::select! {
+                    _ = sleep(timeout - active) => {}
+                    _ = signal.changed() => {}
+                    _ = cancel_token.cancelled() => return,
+                }
+            }
 
             // Check if already cancelled
             if cancel_token.is_cancelled() {
@@ -278,6 +328,32 @@ mod tests {
         assert!(cancel_token.is_cancelled());
     }
 
+    #[tokio::test]
+    async fn test_enforce_with_pause_extends_deadline() {
+        let enforcer = TimeoutEnforcer::new(Duration::from_millis(100));
+        let cancel_token = CancellationToken::new();
+        let pause = PauseController::new();
+
+        let timeout_handle =
+            enforcer.enforce_with_pause(Uuid::new_v4(), cancel_token.clone(), pause.clone());
+
+        // Run 50ms, pause 150ms
+        sleep(Duration::from_millis(50)).await;
+        pause.pause();
+        sleep(Duration::from_millis(150)).await;
+        assert!(!cancel_token.is_cancelled());
+
+        // Only ~50ms of running time left after resuming
+        pause.resume();
+        sleep(Duration::from_millis(20)).await;
+        assert!(!cancel_token.is_cancelled());
+
+        sleep(Duration::from_millis(80)).await;
+        assert!(cancel_token.is_cancelled());
+
+        timeout_handle.abort();
+    }
+
     #[test]
     fn test_constants() {
         assert_eq!(DEFAULT_TIMEOUT, Duration::from_secs(3600));
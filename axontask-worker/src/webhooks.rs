@@ -0,0 +1,293 @@
+/// Webhook delivery dispatcher
+///
+/// This module sends queued webhook deliveries (see
+/// `axontask_shared::models::webhook_delivery`) to their endpoints.
+///
+/// Requests are signed and sent by `axontask_shared::webhooks::WebhookSender`.
+///
+/// # Retries
+///
+/// Non-2xx responses and connection errors are retried after 1s, 2s, 4s, 8s
+/// and 16s (plus jitter). A delivery that fails every attempt is
+/// dead-lettered, and a webhook is disabled after `disable_threshold`
+/// consecutive dead-lettered deliveries.
+///
+/// # Example
+///
+/// ```no_run
+/// use axontask_worker::webhooks::WebhookDispatcher;
+/// use sqlx::PgPool;
+/// use tokio_util::sync::CancellationToken;
+///
+/// # async fn example(pool: PgPool) {
+/// let dispatcher = WebhookDispatcher::new(pool);
+/// dispatcher.run(CancellationToken::new()).await;
+/// # }
+/// ```
+
+use axontask_shared::models::task::{Task, TaskState};
+use axontask_shared::models::webhook::Webhook;
+use axontask_shared::models::webhook_delivery::{retry_delay, DeliveryAttempt, WebhookDelivery};
+use axontask_shared::webhooks::{WebhookSender, DEFAULT_REQUEST_TIMEOUT};
+use chrono::Utc;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Webhook dispatcher configuration
+#[derive(Debug, Clone)]
+pub struct DispatcherConfig {
+    /// Delay between polls when no delivery is due
+    pub poll_interval: Duration,
+
+    /// Maximum deliveries claimed per poll
+    pub batch_size: i64,
+
+    /// HTTP request timeout
+    pub request_timeout: Duration,
+
+    /// How long a claimed delivery is held before another dispatcher may retry it
+    pub lease: Duration,
+
+    /// Consecutive dead-lettered deliveries before a webhook is disabled
+    pub disable_threshold: i32,
+}
+
+impl Default for DispatcherConfig {
+    fn default() -> Self {
+        DispatcherConfig {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 20,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            lease: Duration::from_secs(60),
+            disable_threshold: 10,
+        }
+    }
+}
+
+/// Webhook delivery dispatcher
+///
+/// Polls the delivery queue and sends due deliveries. Several dispatchers
+/// (one per worker) can run against the same database.
+pub struct WebhookDispatcher {
+    /// Database connection pool
+    db: PgPool,
+
+    /// HTTP sender
+    sender: WebhookSender,
+
+    /// Configuration
+    config: DispatcherConfig,
+}
+
+impl WebhookDispatcher {
+    /// Creates a dispatcher with the default configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Database connection pool
+    pub fn new(db: PgPool) -> Self {
+        Self::with_config(db, DispatcherConfig::default())
+    }
+
+    /// Creates a dispatcher with a custom configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Database connection pool
+    /// * `config` - Dispatcher configuration
+    pub fn with_config(db: PgPool, config: DispatcherConfig) -> Self {
+        let sender = WebhookSender::new(config.request_timeout);
+
+        WebhookDispatcher { db, sender, config }
+    }
+
+    /// Runs the dispatch loop until `shutdown` is cancelled
+    pub async fn run(&self, shutdown: CancellationToken) {
+        tracing::info!("Webhook dispatcher starting");
+
+        while !shutdown.is_cancelled() {
+            let dispatched = match self.dispatch_due().await {
+                Ok(count) => count,
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to dispatch webhook deliveries");
+                    0
+                }
+            };
+
+            // Keep draining while there is a backlog
+            if dispatched < self.config.batch_size as usize {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = sleep(self.config.poll_interval) => {}
+                }
+            }
+        }
+
+        tracing::info!("Webhook dispatcher stopped");
+    }
+
+    /// Claims and sends due deliveries
+    ///
+    /// # Returns
+    ///
+    /// Number of deliveries attempted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if claiming deliveries fails
+    pub async fn dispatch_due(&self) -> Result<usize, sqlx::Error> {
+        let lease = chrono::Duration::from_std(self.config.lease)
+            .unwrap_or_else(|_| chrono::Duration::seconds(60));
+        let deliveries =
+            WebhookDelivery::claim_due(&self.db, self.config.batch_size, lease).await?;
+
+        let count = deliveries.len();
+        futures::future::join_all(deliveries.into_iter().map(|d| self.process(d))).await;
+
+        Ok(count)
+    }
+
+    /// Sends one claimed delivery and records the outcome
+    async fn process(&self, delivery: WebhookDelivery) {
+        let result = async {
+            let webhook = Webhook::find_by_id(&self.db, delivery.webhook_id).await?;
+
+            let attempt = match &webhook {
+                Some(webhook) if webhook.active => {
+                    self.sender
+                        .send(webhook, delivery.id, &delivery.event, &delivery.payload)
+                        .await
+                }
+                _ => DeliveryAttempt {
+                    error: Some("Webhook is disabled".to_string()),
+                    ..Default::default()
+                },
+            };
+
+            self.record(&delivery, webhook.as_ref(), &attempt).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::error!(error = %e, delivery_id = %delivery.id, "Failed to process webhook delivery");
+        }
+    }
+
+    /// Records an attempt, scheduling a retry or dead-lettering on failure
+    async fn record(
+        &self,
+        delivery: &WebhookDelivery,
+        webhook: Option<&Webhook>,
+        attempt: &DeliveryAttempt,
+    ) -> Result<(), sqlx::Error> {
+        if attempt.is_success() {
+            WebhookDelivery::record_success(&self.db, delivery.id, attempt).await?;
+            Webhook::record_delivery_success(&self.db, delivery.webhook_id).await?;
+
+            tracing::debug!(
+                delivery_id = %delivery.id,
+                webhook_id = %delivery.webhook_id,
+                event = %delivery.event,
+                "Webhook delivered"
+            );
+            return Ok(());
+        }
+
+        // Disabled webhooks are not retried
+        let retry_at = webhook
+            .filter(|w| w.active)
+            .and_then(|_| retry_delay(delivery.attempts, delivery.max_attempts))
+            .map(|delay| Utc::now() + delay);
+
+        WebhookDelivery::record_failure(&self.db, delivery.id, attempt, retry_at).await?;
+
+        if retry_at.is_some() {
+            tracing::warn!(
+                delivery_id = %delivery.id,
+                webhook_id = %delivery.webhook_id,
+                attempt = delivery.attempts,
+                status = ?attempt.response_status,
+                error = ?attempt.error,
+                "Webhook delivery failed, will retry"
+            );
+            return Ok(());
+        }
+
+        tracing::warn!(
+            delivery_id = %delivery.id,
+            webhook_id = %delivery.webhook_id,
+            attempts = delivery.attempts,
+            "Webhook delivery dead-lettered"
+        );
+
+        if webhook.is_some_and(|w| w.active)
+            && Webhook::record_delivery_failure(
+                &self.db,
+                delivery.webhook_id,
+                self.config.disable_threshold,
+            )
+            .await?
+        {
+            tracing::warn!(
+                webhook_id = %delivery.webhook_id,
+                threshold = self.config.disable_threshold,
+                "Webhook disabled after repeated delivery failures"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Queues webhook notifications for a task's final state
+///
+/// Canceled tasks are skipped: `task.canceled` is queued by the API when the
+/// cancellation is requested, and the worker only observes it afterwards.
+///
+/// Best effort: failures are logged and never affect task execution.
+pub async fn notify_task(db: &PgPool, task_id: Uuid) {
+    match Task::find_by_id(db, task_id).await {
+        Ok(Some(task)) if task.state != TaskState::Canceled => notify(db, &task).await,
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(error = %e, task_id = %task_id, "Failed to load task for webhooks");
+        }
+    }
+}
+
+/// Queues webhook notifications for a task
+///
+/// Best effort: failures are logged and never affect task execution.
+pub async fn notify(db: &PgPool, task: &Task) {
+    match WebhookDelivery::enqueue_for_task(db, task).await {
+        Ok(deliveries) if !deliveries.is_empty() => {
+            tracing::debug!(
+                task_id = %task.id,
+                count = deliveries.len(),
+                "Queued webhook deliveries"
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(error = %e, task_id = %task.id, "Failed to queue webhook deliveries");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatcher_config_default() {
+        let config = DispatcherConfig::default();
+        assert_eq!(config.request_timeout, DEFAULT_REQUEST_TIMEOUT);
+        assert!(config.lease > config.request_timeout);
+        assert!(config.disable_threshold > 0);
+    }
+
+    // Request signing and sending is tested in axontask_shared::webhooks
+}
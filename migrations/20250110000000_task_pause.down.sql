@@ -0,0 +1,24 @@
+-- AxonTask Task Pause/Resume Rollback
+-- Migration: 20250110000000_task_pause (DOWN)
+-- Description: Removes paused-time accounting columns
+-- Author: Tyler Mailman
+-- Date: 2025-01-10
+--
+-- PostgreSQL cannot drop a value from an enum type, so 'paused' remains in
+-- task_state. Paused tasks are moved back to running instead.
+
+UPDATE tasks SET state = 'running' WHERE state = 'paused';
+
+DELETE FROM task_events WHERE kind IN ('paused', 'resumed');
+
+ALTER TABLE task_events DROP CONSTRAINT task_events_kind_check;
+
+ALTER TABLE task_events ADD CONSTRAINT task_events_kind_check CHECK (
+    kind IN ('started', 'progress', 'stdout', 'stderr', 'success', 'error', 'canceled', 'timeout', 'digest')
+);
+
+ALTER TABLE tasks DROP CONSTRAINT IF EXISTS tasks_paused_seconds_non_negative;
+
+ALTER TABLE tasks
+    DROP COLUMN IF EXISTS paused_seconds,
+    DROP COLUMN IF EXISTS paused_at;
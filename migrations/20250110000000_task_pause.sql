@@ -0,0 +1,40 @@
+-- AxonTask Task Pause/Resume
+-- Migration: 20250110000000_task_pause
+-- Description: Adds the paused task state and paused-time accounting columns
+-- Author: Tyler Mailman
+-- Date: 2025-01-10
+--
+-- Running tasks can be paused and resumed through the control channel.
+-- Time spent paused is tracked so it can be excluded from timeouts and
+-- billable minutes.
+
+-- ==============================================================================
+-- ENUMS
+-- ==============================================================================
+
+-- New state: task execution is suspended (running → paused → running)
+ALTER TYPE task_state ADD VALUE IF NOT EXISTS 'paused' AFTER 'running';
+
+-- ==============================================================================
+-- TABLE: tasks
+-- ==============================================================================
+
+ALTER TABLE tasks
+    ADD COLUMN paused_at TIMESTAMPTZ,
+    ADD COLUMN paused_seconds INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE tasks
+    ADD CONSTRAINT tasks_paused_seconds_non_negative CHECK (paused_seconds >= 0);
+
+COMMENT ON COLUMN tasks.paused_at IS 'When the task was paused (NULL unless state is "paused")';
+COMMENT ON COLUMN tasks.paused_seconds IS 'Total time spent paused in seconds (excluded from timeout and billable minutes)';
+
+-- ==============================================================================
+-- TABLE: task_events
+-- ==============================================================================
+
+ALTER TABLE task_events DROP CONSTRAINT task_events_kind_check;
+
+ALTER TABLE task_events ADD CONSTRAINT task_events_kind_check CHECK (
+    kind IN ('started', 'progress', 'stdout', 'stderr', 'success', 'error', 'canceled', 'timeout', 'digest', 'paused', 'resumed')
+);
@@ -0,0 +1,13 @@
+-- AxonTask Task Input Rollback
+-- Migration: 20250111000000_task_input (DOWN)
+-- Description: Removes the 'input' task event kind
+-- Author: Tyler Mailman
+-- Date: 2025-01-11
+
+DELETE FROM task_events WHERE kind = 'input';
+
+ALTER TABLE task_events DROP CONSTRAINT task_events_kind_check;
+
+ALTER TABLE task_events ADD CONSTRAINT task_events_kind_check CHECK (
+    kind IN ('started', 'progress', 'stdout', 'stderr', 'success', 'error', 'canceled', 'timeout', 'digest', 'paused', 'resumed')
+);
@@ -0,0 +1,13 @@
+-- AxonTask Task Input
+-- Migration: 20250111000000_task_input
+-- Description: Allows 'input' task events (input delivered to a running task)
+-- Author: Tyler Mailman
+-- Date: 2025-01-11
+--
+-- Input sent to a running task is recorded as an event for auditability.
+
+ALTER TABLE task_events DROP CONSTRAINT task_events_kind_check;
+
+ALTER TABLE task_events ADD CONSTRAINT task_events_kind_check CHECK (
+    kind IN ('started', 'progress', 'stdout', 'stderr', 'success', 'error', 'canceled', 'timeout', 'digest', 'paused', 'resumed', 'input')
+);
@@ -0,0 +1,7 @@
+-- AxonTask Idempotency Keys Rollback
+-- Migration: 20250112000000_idempotency_keys (DOWN)
+-- Description: Drops the idempotency_keys table
+-- Author: Tyler Mailman
+-- Date: 2025-01-12
+
+DROP TABLE IF EXISTS idempotency_keys;
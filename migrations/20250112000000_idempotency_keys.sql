@@ -0,0 +1,42 @@
+-- AxonTask Idempotency Keys
+-- Migration: 20250112000000_idempotency_keys
+-- Description: Stores Idempotency-Key values for start_task replay protection
+-- Author: Tyler Mailman
+-- Date: 2025-01-12
+--
+-- Clients (agent frameworks in particular) retry start_task after network
+-- errors. A retry carrying the same Idempotency-Key returns the original
+-- response instead of creating a duplicate task.
+
+-- ==============================================================================
+-- TABLE: idempotency_keys
+-- ==============================================================================
+
+CREATE TABLE idempotency_keys (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    key VARCHAR(255) NOT NULL,
+    request_hash BYTEA NOT NULL,
+    task_id UUID REFERENCES tasks(id) ON DELETE CASCADE,
+    response JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (tenant_id, key),
+
+    -- A completed key has both a task and a stored response
+    CONSTRAINT idempotency_keys_completed_check CHECK (
+        (task_id IS NULL) = (response IS NULL)
+    )
+);
+
+COMMENT ON TABLE idempotency_keys IS 'Idempotency keys for start_task (per tenant, expiring)';
+COMMENT ON COLUMN idempotency_keys.tenant_id IS 'Tenant the key belongs to (keys are scoped per tenant)';
+COMMENT ON COLUMN idempotency_keys.key IS 'Client-supplied idempotency key';
+COMMENT ON COLUMN idempotency_keys.request_hash IS 'SHA-256 of the canonical request body (detects key reuse with a different body)';
+COMMENT ON COLUMN idempotency_keys.task_id IS 'Task created by the original request (NULL while in progress)';
+COMMENT ON COLUMN idempotency_keys.response IS 'Original response body, returned on replay (NULL while in progress)';
+COMMENT ON COLUMN idempotency_keys.created_at IS 'When the key was first used';
+COMMENT ON COLUMN idempotency_keys.expires_at IS 'When the key can be reused (end of retention window)';
+
+-- Index for purging expired keys
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
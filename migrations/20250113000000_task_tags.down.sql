@@ -0,0 +1,11 @@
+-- AxonTask Task Tags Rollback
+-- Migration: 20250113000000_task_tags (DOWN)
+-- Description: Removes task tags
+-- Author: Tyler Mailman
+-- Date: 2025-01-13
+
+DROP INDEX IF EXISTS idx_tasks_tags;
+
+ALTER TABLE tasks DROP CONSTRAINT IF EXISTS tasks_tags_limit;
+
+ALTER TABLE tasks DROP COLUMN IF EXISTS tags;
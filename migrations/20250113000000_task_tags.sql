@@ -0,0 +1,23 @@
+-- AxonTask Task Tags
+-- Migration: 20250113000000_task_tags
+-- Description: Adds tags to tasks for grouping and filtering
+-- Author: Tyler Mailman
+-- Date: 2025-01-13
+--
+-- Tags are free-form labels, typically "key:value" pairs such as
+-- "session:abc123" or "pr:42". Filters match tasks having all given tags.
+
+-- ==============================================================================
+-- TABLE: tasks
+-- ==============================================================================
+
+ALTER TABLE tasks
+    ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE tasks
+    ADD CONSTRAINT tasks_tags_limit CHECK (cardinality(tags) <= 20);
+
+COMMENT ON COLUMN tasks.tags IS 'Task tags for grouping and filtering (e.g. "session:abc123", "pr:42"), max 20';
+
+-- Index for tag filters (using GIN for array containment)
+CREATE INDEX idx_tasks_tags ON tasks USING GIN(tags);
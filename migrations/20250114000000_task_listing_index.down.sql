@@ -0,0 +1,9 @@
+-- AxonTask Task Listing Index Rollback
+-- Migration: 20250114000000_task_listing_index (DOWN)
+-- Description: Restores the original tenant task index
+-- Author: Tyler Mailman
+-- Date: 2025-01-14
+
+DROP INDEX IF EXISTS idx_tasks_tenant_created;
+
+CREATE INDEX idx_tasks_tenant_id ON tasks(tenant_id, created_at DESC);
@@ -0,0 +1,16 @@
+-- AxonTask Task Listing Index
+-- Migration: 20250114000000_task_listing_index
+-- Description: Supports keyset pagination of task listings
+-- Author: Tyler Mailman
+-- Date: 2025-01-14
+--
+-- Task listings page on (created_at, id) so that tasks created in the same
+-- microsecond are neither skipped nor repeated between pages.
+
+-- ==============================================================================
+-- TABLE: tasks
+-- ==============================================================================
+
+DROP INDEX IF EXISTS idx_tasks_tenant_id;
+
+CREATE INDEX idx_tasks_tenant_created ON tasks(tenant_id, created_at DESC, id DESC);
@@ -0,0 +1,11 @@
+-- AxonTask Webhook Secret Rotation Rollback
+-- Migration: 20250115000000_webhook_secret_rotation (DOWN)
+-- Description: Removes the previous webhook secret
+-- Author: Tyler Mailman
+-- Date: 2025-01-15
+
+ALTER TABLE webhooks DROP CONSTRAINT IF EXISTS webhooks_previous_secret_check;
+
+ALTER TABLE webhooks
+    DROP COLUMN IF EXISTS previous_secret_expires_at,
+    DROP COLUMN IF EXISTS previous_secret;
@@ -0,0 +1,24 @@
+-- AxonTask Webhook Secret Rotation
+-- Migration: 20250115000000_webhook_secret_rotation
+-- Description: Keeps the previous webhook secret valid during rotation
+-- Author: Tyler Mailman
+-- Date: 2025-01-15
+--
+-- After a rotation, deliveries are signed with both the new and the previous
+-- secret until previous_secret_expires_at, so receivers can switch over
+-- without dropping callbacks.
+
+-- ==============================================================================
+-- TABLE: webhooks
+-- ==============================================================================
+
+ALTER TABLE webhooks
+    ADD COLUMN previous_secret BYTEA,
+    ADD COLUMN previous_secret_expires_at TIMESTAMPTZ;
+
+ALTER TABLE webhooks
+    ADD CONSTRAINT webhooks_previous_secret_check
+    CHECK ((previous_secret IS NULL) = (previous_secret_expires_at IS NULL));
+
+COMMENT ON COLUMN webhooks.previous_secret IS 'Secret replaced by the last rotation, still used for signing until previous_secret_expires_at';
+COMMENT ON COLUMN webhooks.previous_secret_expires_at IS 'When the previous secret stops being used for signing';
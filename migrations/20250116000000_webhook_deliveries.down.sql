@@ -0,0 +1,9 @@
+-- AxonTask Webhook Deliveries Rollback
+-- Migration: 20250116000000_webhook_deliveries (DOWN)
+-- Description: Removes the webhook delivery queue and log
+-- Author: Tyler Mailman
+-- Date: 2025-01-16
+
+ALTER TABLE webhooks DROP COLUMN IF EXISTS consecutive_failures;
+
+DROP TABLE IF EXISTS webhook_deliveries;
@@ -0,0 +1,60 @@
+-- AxonTask Webhook Deliveries
+-- Migration: 20250116000000_webhook_deliveries
+-- Description: Adds the webhook delivery queue and log
+-- Author: Tyler Mailman
+-- Date: 2025-01-16
+--
+-- Each row is one event sent to one webhook. Rows are created when a task
+-- event occurs and picked up by the worker's webhook dispatcher. Failed
+-- attempts are retried with exponential backoff; deliveries that exhaust
+-- their attempts are kept with status 'dead' (dead-letter queue).
+
+-- ==============================================================================
+-- TABLE: webhook_deliveries
+-- ==============================================================================
+
+CREATE TABLE webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    task_id UUID REFERENCES tasks(id) ON DELETE SET NULL,
+    event VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 6,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempt_at TIMESTAMPTZ,
+    response_status INTEGER,
+    response_body TEXT,
+    latency_ms INTEGER,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT webhook_deliveries_status_check CHECK (status IN ('pending', 'succeeded', 'dead')),
+    CONSTRAINT webhook_deliveries_attempts_check CHECK (attempts >= 0 AND max_attempts > 0)
+);
+
+COMMENT ON TABLE webhook_deliveries IS 'Webhook delivery queue and log (one row per event per webhook)';
+COMMENT ON COLUMN webhook_deliveries.status IS 'pending (queued or retrying), succeeded, or dead (attempts exhausted)';
+COMMENT ON COLUMN webhook_deliveries.next_attempt_at IS 'When the next attempt is due (also used as a lease while an attempt is in flight)';
+COMMENT ON COLUMN webhook_deliveries.response_body IS 'Response body of the last attempt (truncated)';
+COMMENT ON COLUMN webhook_deliveries.latency_ms IS 'Duration of the last attempt in milliseconds';
+COMMENT ON COLUMN webhook_deliveries.error IS 'Error of the last failed attempt';
+
+-- Index for the dispatcher polling due deliveries
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at)
+    WHERE status = 'pending';
+
+-- Index for listing deliveries of a webhook
+CREATE INDEX idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at DESC);
+
+-- ==============================================================================
+-- TABLE: webhooks
+-- ==============================================================================
+
+ALTER TABLE webhooks
+    ADD COLUMN consecutive_failures INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN webhooks.consecutive_failures IS 'Dead-lettered deliveries since the last successful delivery; the webhook is disabled when this reaches the threshold';
@@ -0,0 +1,7 @@
+-- AxonTask Usage Records Rollback
+-- Migration: 20250117000000_usage_records (DOWN)
+-- Description: Removes the usage metering ledger
+-- Author: Tyler Mailman
+-- Date: 2025-01-17
+
+DROP TABLE IF EXISTS usage_records;
@@ -0,0 +1,34 @@
+-- AxonTask Usage Records
+-- Migration: 20250117000000_usage_records
+-- Description: Adds the usage metering ledger
+-- Author: Tyler Mailman
+-- Date: 2025-01-17
+--
+-- Every increment of usage_counters is recorded here under a unique key
+-- (e.g. 'task_minutes:<task_id>'). An increment whose key already exists is
+-- skipped, so retried metering calls never double-count.
+
+-- ==============================================================================
+-- TABLE: usage_records
+-- ==============================================================================
+
+CREATE TABLE usage_records (
+    key VARCHAR(128) PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    period DATE NOT NULL,
+    metric VARCHAR(32) NOT NULL,
+    amount BIGINT NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT usage_records_metric_check CHECK (
+        metric IN ('task_minutes', 'streams', 'bytes', 'tasks_created')
+    ),
+    CONSTRAINT usage_records_amount_check CHECK (amount >= 0)
+);
+
+COMMENT ON TABLE usage_records IS 'Usage metering ledger (one row per counted increment, for idempotency and audit)';
+COMMENT ON COLUMN usage_records.key IS 'Idempotency key, e.g. task_minutes:<task_id> or bytes:<connection_id>';
+COMMENT ON COLUMN usage_records.period IS 'usage_counters period the amount was added to';
+
+-- Retention cleanup (UsageCounter::delete_before)
+CREATE INDEX idx_usage_records_period ON usage_records(period);
@@ -0,0 +1,7 @@
+-- AxonTask Quota Alerts Rollback
+-- Migration: 20250118000000_quota_alerts (DOWN)
+-- Description: Removes the soft quota threshold ledger
+-- Author: Tyler Mailman
+-- Date: 2025-01-18
+
+DROP TABLE IF EXISTS quota_alerts;
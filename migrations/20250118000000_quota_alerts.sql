@@ -0,0 +1,37 @@
+-- AxonTask Quota Alerts
+-- Migration: 20250118000000_quota_alerts
+-- Description: Adds the soft quota threshold ledger
+-- Author: Tyler Mailman
+-- Date: 2025-01-18
+--
+-- A row is inserted the first time a tenant reaches a soft threshold (e.g.
+-- 80% of daily_tasks) in a quota period. A threshold whose row already
+-- exists is not notified again until the next period.
+
+-- ==============================================================================
+-- TABLE: quota_alerts
+-- ==============================================================================
+
+CREATE TABLE quota_alerts (
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    quota VARCHAR(32) NOT NULL,
+    period_start DATE NOT NULL,
+    threshold SMALLINT NOT NULL,
+    current_usage BIGINT NOT NULL,
+    quota_limit BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (tenant_id, quota, period_start, threshold),
+
+    CONSTRAINT quota_alerts_quota_check CHECK (
+        quota IN ('daily_tasks', 'task_minutes')
+    ),
+    CONSTRAINT quota_alerts_threshold_check CHECK (threshold BETWEEN 1 AND 100)
+);
+
+COMMENT ON TABLE quota_alerts IS 'Soft quota thresholds reached per tenant and period (each notified once)';
+COMMENT ON COLUMN quota_alerts.period_start IS 'First day of the quota period (UTC day for daily_tasks, month for task_minutes)';
+COMMENT ON COLUMN quota_alerts.threshold IS 'Threshold reached, in percent of the limit';
+
+-- Retention cleanup (QuotaAlert::delete_before)
+CREATE INDEX idx_quota_alerts_period_start ON quota_alerts(period_start);
@@ -0,0 +1,13 @@
+-- AxonTask Task Quota Warning Rollback
+-- Migration: 20250119000000_task_quota_warning (DOWN)
+-- Description: Removes the 'quota_warning' task event kind
+-- Author: Tyler Mailman
+-- Date: 2025-01-19
+
+DELETE FROM task_events WHERE kind = 'quota_warning';
+
+ALTER TABLE task_events DROP CONSTRAINT task_events_kind_check;
+
+ALTER TABLE task_events ADD CONSTRAINT task_events_kind_check CHECK (
+    kind IN ('started', 'progress', 'stdout', 'stderr', 'success', 'error', 'canceled', 'timeout', 'digest', 'paused', 'resumed', 'input')
+);
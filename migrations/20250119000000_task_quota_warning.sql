@@ -0,0 +1,14 @@
+-- AxonTask Task Quota Warning
+-- Migration: 20250119000000_task_quota_warning
+-- Description: Allows 'quota_warning' task events
+-- Author: Tyler Mailman
+-- Date: 2025-01-19
+--
+-- When a tenant's monthly task minute quota is exhausted, the worker emits a
+-- quota_warning event to each running task before canceling it.
+
+ALTER TABLE task_events DROP CONSTRAINT task_events_kind_check;
+
+ALTER TABLE task_events ADD CONSTRAINT task_events_kind_check CHECK (
+    kind IN ('started', 'progress', 'stdout', 'stderr', 'success', 'error', 'canceled', 'timeout', 'digest', 'paused', 'resumed', 'input', 'quota_warning')
+);
@@ -0,0 +1,9 @@
+-- AxonTask Task API Key Attribution Rollback
+-- Migration: 20250120000000_task_api_key (DOWN)
+-- Description: Removes task API key attribution
+-- Author: Tyler Mailman
+-- Date: 2025-01-20
+
+DROP INDEX IF EXISTS idx_tasks_api_key;
+
+ALTER TABLE tasks DROP COLUMN IF EXISTS api_key_id;
@@ -0,0 +1,15 @@
+-- AxonTask Task API Key Attribution
+-- Migration: 20250120000000_task_api_key
+-- Description: Records the API key that created each task
+-- Author: Tyler Mailman
+-- Date: 2025-01-20
+--
+-- Tasks started with an API key have no created_by user. api_key_id makes
+-- them attributable to the key; it is set to NULL if the key is deleted, so
+-- task history is kept.
+
+ALTER TABLE tasks ADD COLUMN api_key_id UUID REFERENCES api_keys(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_api_key ON tasks(api_key_id) WHERE api_key_id IS NOT NULL;
+
+COMMENT ON COLUMN tasks.api_key_id IS 'API key that created the task (NULL for JWT-created tasks)';
@@ -0,0 +1,7 @@
+-- AxonTask Refresh Tokens Rollback
+-- Migration: 20250121000000_refresh_tokens (DOWN)
+-- Description: Removes stored refresh tokens
+-- Author: Tyler Mailman
+-- Date: 2025-01-21
+
+DROP TABLE IF EXISTS refresh_tokens;
@@ -0,0 +1,40 @@
+-- AxonTask Refresh Tokens
+-- Migration: 20250121000000_refresh_tokens
+-- Description: Stores refresh tokens for rotation, reuse detection and logout
+-- Author: Tyler Mailman
+-- Date: 2025-01-21
+--
+-- Each login starts a token family (a session). Every refresh marks the
+-- presented token as used and issues a new token in the same family. A used
+-- token presented again means it was copied, so the whole family is revoked.
+-- Only SHA-256 hashes of tokens are stored.
+
+-- ==============================================================================
+-- TABLE: refresh_tokens
+-- ==============================================================================
+
+CREATE TABLE refresh_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    family_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    user_agent TEXT,
+    family_created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+COMMENT ON TABLE refresh_tokens IS 'Refresh tokens, grouped into families (one family per login session)';
+COMMENT ON COLUMN refresh_tokens.family_id IS 'Session the token belongs to; rotation keeps the family';
+COMMENT ON COLUMN refresh_tokens.token_hash IS 'SHA-256 of the refresh token (never store plaintext)';
+COMMENT ON COLUMN refresh_tokens.family_created_at IS 'When the session started (login time)';
+COMMENT ON COLUMN refresh_tokens.used_at IS 'When the token was exchanged; a second use revokes the family';
+COMMENT ON COLUMN refresh_tokens.revoked_at IS 'When the token was revoked (logout or reuse detected)';
+
+CREATE INDEX idx_refresh_tokens_family ON refresh_tokens(family_id);
+CREATE INDEX idx_refresh_tokens_user_active ON refresh_tokens(user_id)
+    WHERE used_at IS NULL AND revoked_at IS NULL;
+CREATE INDEX idx_refresh_tokens_expires ON refresh_tokens(expires_at);
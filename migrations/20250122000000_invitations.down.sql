@@ -0,0 +1,7 @@
+-- AxonTask Tenant Invitations Rollback
+-- Migration: 20250122000000_invitations (DOWN)
+-- Description: Removes tenant invitations
+-- Author: Tyler Mailman
+-- Date: 2025-01-22
+
+DROP TABLE IF EXISTS invitations;
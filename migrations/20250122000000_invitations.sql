@@ -0,0 +1,37 @@
+-- AxonTask Tenant Invitations
+-- Migration: 20250122000000_invitations
+-- Description: Stores pending and accepted invitations to join a tenant
+-- Author: Tyler Mailman
+-- Date: 2025-01-22
+--
+-- An admin invites an email address with a role. The invitee receives a
+-- single-use token by email; accepting it (while logged in with that email)
+-- creates the membership. Only SHA-256 hashes of tokens are stored.
+
+-- ==============================================================================
+-- TABLE: invitations
+-- ==============================================================================
+
+CREATE TABLE invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    role membership_role NOT NULL DEFAULT 'member',
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_at TIMESTAMPTZ,
+    accepted_by UUID REFERENCES users(id) ON DELETE SET NULL
+);
+
+COMMENT ON TABLE invitations IS 'Invitations to join a tenant with a role';
+COMMENT ON COLUMN invitations.email IS 'Invited email address; only a user with this email can accept';
+COMMENT ON COLUMN invitations.token_hash IS 'SHA-256 of the invitation token (never store plaintext)';
+COMMENT ON COLUMN invitations.invited_by IS 'User who sent the invitation';
+COMMENT ON COLUMN invitations.accepted_at IS 'When the invitation was accepted (NULL = pending)';
+
+CREATE INDEX idx_invitations_tenant_pending ON invitations(tenant_id, created_at DESC)
+    WHERE accepted_at IS NULL;
+CREATE INDEX idx_invitations_email ON invitations(tenant_id, LOWER(email));
+CREATE INDEX idx_invitations_expires ON invitations(expires_at);
@@ -0,0 +1,8 @@
+-- AxonTask User Tokens Rollback
+-- Migration: 20250123000000_user_tokens (DOWN)
+-- Description: Removes email verification and password reset tokens
+-- Author: Tyler Mailman
+-- Date: 2025-01-23
+
+DROP TABLE IF EXISTS user_tokens;
+DROP TYPE IF EXISTS user_token_purpose;
@@ -0,0 +1,41 @@
+-- AxonTask User Tokens
+-- Migration: 20250123000000_user_tokens
+-- Description: Stores email verification and password reset tokens
+-- Author: Tyler Mailman
+-- Date: 2025-01-23
+--
+-- Account emails carry a single-use token. Confirming it marks the token used;
+-- requesting a new one replaces any unused token of the same purpose, so only
+-- the latest link works. Only SHA-256 hashes of tokens are stored.
+
+-- ==============================================================================
+-- ENUMS
+-- ==============================================================================
+
+CREATE TYPE user_token_purpose AS ENUM (
+    'email_verification', -- Confirms the user owns their email address
+    'password_reset'      -- Sets a new password without the old one
+);
+
+-- ==============================================================================
+-- TABLE: user_tokens
+-- ==============================================================================
+
+CREATE TABLE user_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose user_token_purpose NOT NULL,
+    email CITEXT NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ
+);
+
+COMMENT ON TABLE user_tokens IS 'Single-use email verification and password reset tokens';
+COMMENT ON COLUMN user_tokens.email IS 'Address the token was sent to; verification only applies while the user still has it';
+COMMENT ON COLUMN user_tokens.token_hash IS 'SHA-256 of the token (never store plaintext)';
+COMMENT ON COLUMN user_tokens.used_at IS 'When the token was confirmed (NULL = unused)';
+
+CREATE INDEX idx_user_tokens_user_purpose ON user_tokens(user_id, purpose, created_at DESC);
+CREATE INDEX idx_user_tokens_expires ON user_tokens(expires_at);
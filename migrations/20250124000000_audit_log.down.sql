@@ -0,0 +1,7 @@
+-- AxonTask Audit Log Rollback
+-- Migration: 20250124000000_audit_log (DOWN)
+-- Description: Removes the audit log
+-- Author: Tyler Mailman
+-- Date: 2025-01-24
+
+DROP TABLE IF EXISTS audit_log;
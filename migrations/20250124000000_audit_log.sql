@@ -0,0 +1,34 @@
+-- AxonTask Audit Log
+-- Migration: 20250124000000_audit_log
+-- Description: Records security-relevant account events
+-- Author: Tyler Mailman
+-- Date: 2025-01-24
+--
+-- Append-only record of security events such as login lockouts. Entries
+-- outlive the user they mention (user_id is cleared on delete) so incidents
+-- can still be investigated; entries scoped to a tenant go with the tenant.
+
+-- ==============================================================================
+-- TABLE: audit_log
+-- ==============================================================================
+
+CREATE TABLE audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID REFERENCES tenants(id) ON DELETE CASCADE,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(64) NOT NULL,
+    ip_address VARCHAR(45),
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE audit_log IS 'Append-only log of security-relevant events';
+COMMENT ON COLUMN audit_log.tenant_id IS 'Tenant the event belongs to (NULL for account-level events)';
+COMMENT ON COLUMN audit_log.user_id IS 'User the event concerns (NULL if unknown or deleted)';
+COMMENT ON COLUMN audit_log.action IS 'Event name, e.g. auth.login_locked';
+COMMENT ON COLUMN audit_log.ip_address IS 'Client IP address the event came from';
+COMMENT ON COLUMN audit_log.details IS 'Event-specific data';
+
+CREATE INDEX idx_audit_log_user ON audit_log(user_id, created_at DESC);
+CREATE INDEX idx_audit_log_tenant ON audit_log(tenant_id, created_at DESC);
+CREATE INDEX idx_audit_log_created ON audit_log(created_at DESC);
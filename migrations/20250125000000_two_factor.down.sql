@@ -0,0 +1,8 @@
+-- AxonTask Two-Factor Authentication Rollback
+-- Migration: 20250125000000_two_factor (DOWN)
+-- Description: Removes TOTP secrets and recovery codes
+-- Author: Tyler Mailman
+-- Date: 2025-01-25
+
+DROP TABLE IF EXISTS user_recovery_codes;
+DROP TABLE IF EXISTS user_two_factor;
@@ -0,0 +1,45 @@
+-- AxonTask Two-Factor Authentication
+-- Migration: 20250125000000_two_factor
+-- Description: Stores TOTP secrets and recovery codes
+-- Author: Tyler Mailman
+-- Date: 2025-01-25
+--
+-- A user enrolls by scanning a TOTP secret into an authenticator app and
+-- confirming a code; until then the secret is pending (enabled_at NULL).
+-- Recovery codes replace the app when it is lost; each works once and only
+-- SHA-256 hashes are stored. Tenants can require two-factor authentication
+-- for their members with `settings.require_two_factor`.
+
+-- ==============================================================================
+-- TABLE: user_two_factor
+-- ==============================================================================
+
+CREATE TABLE user_two_factor (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret BYTEA NOT NULL,
+    enabled_at TIMESTAMPTZ,
+    last_used_step BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE user_two_factor IS 'TOTP secrets of users enrolled (or enrolling) in two-factor authentication';
+COMMENT ON COLUMN user_two_factor.secret IS 'TOTP secret shared with the authenticator app';
+COMMENT ON COLUMN user_two_factor.enabled_at IS 'When enrollment was confirmed (NULL = pending)';
+COMMENT ON COLUMN user_two_factor.last_used_step IS 'Time step of the last accepted code; older codes are rejected (replay protection)';
+
+-- ==============================================================================
+-- TABLE: user_recovery_codes
+-- ==============================================================================
+
+CREATE TABLE user_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    used_at TIMESTAMPTZ,
+    UNIQUE (user_id, code_hash)
+);
+
+COMMENT ON TABLE user_recovery_codes IS 'Single-use two-factor recovery codes';
+COMMENT ON COLUMN user_recovery_codes.code_hash IS 'SHA-256 of the normalized code (never store plaintext)';
+COMMENT ON COLUMN user_recovery_codes.used_at IS 'When the code was used (NULL = unused)';